@@ -11,9 +11,9 @@ use wyndex::common::{
     claim_ownership, drop_ownership_proposal, propose_new_owner, validate_addresses,
 };
 use wyndex::factory::{
-    ConfigResponse, DistributionFlow, ExecuteMsg, FeeInfoResponse, InstantiateMsg, MigrateMsg,
-    PairConfig, PairType, PairsResponse, PartialDefaultStakeConfig, PartialStakeConfig, QueryMsg,
-    ReceiveMsg, ROUTE,
+    CodeIdsResponse, ConfigResponse, DistributionFlow, ExecuteMsg, FeeInfoResponse, InstantiateMsg,
+    MigrateMsg, PairConfig, PairType, PairsCreatedAfterResponse, PairsResponse,
+    PartialDefaultStakeConfig, PartialStakeConfig, QueryMsg, ReceiveMsg, StatsResponse, ROUTE,
 };
 use wyndex::fee_config::FeeConfig;
 use wyndex::stake::UnbondingPeriod;
@@ -22,9 +22,10 @@ use wyndex_stake::msg::ExecuteMsg as StakeExecuteMsg;
 use crate::error::ContractError;
 use crate::querier::query_pair_info;
 use crate::state::{
-    check_asset_infos, pair_key, read_pairs, Config, TmpPairInfo, CONFIG, OWNERSHIP_PROPOSAL,
-    PAIRS, PAIRS_TO_MIGRATE, PAIR_CONFIGS, PERMISSIONLESS_DEPOSIT, STAKING_ADDRESSES,
-    TMP_PAIR_INFO,
+    check_asset_infos, pair_key, read_pairs, read_pairs_created_after, Config, PairOrigin,
+    PairStats, TmpPairInfo, CONFIG, OWNERSHIP_PROPOSAL, PAIRS, PAIRS_BY_CREATION, PAIRS_TO_MIGRATE,
+    PAIR_CONFIGS, PAIR_CREATED_AT, PAIR_ORIGINS, PAIR_STATS, PERMISSIONLESS_DEPOSIT,
+    STAKING_ADDRESSES, TMP_PAIR_INFO,
 };
 
 use itertools::Itertools;
@@ -96,6 +97,7 @@ pub fn instantiate(
         PAIR_CONFIGS.save(deps.storage, pc.pair_type.to_string(), pc)?;
     }
     CONFIG.save(deps.storage, &config)?;
+    PAIR_STATS.save(deps.storage, &PairStats::default())?;
 
     Ok(Response::new())
 }
@@ -379,6 +381,7 @@ fn execute_create_distribution_flow(
                 manager: env.contract.address.to_string(), // use factory as manager for now
                 asset,
                 rewards,
+                claimant_reward_share: None,
             })?,
             funds: vec![],
         }))),
@@ -513,6 +516,10 @@ pub fn execute_create_pair(
             pair_key,
             asset_infos: asset_infos.clone(),
             distribution_flows,
+            origin: PairOrigin {
+                pair_type,
+                permissionless: deposit_sent,
+            },
         },
     )?;
 
@@ -613,6 +620,15 @@ pub mod reply {
         let pair_contract = deps.api.addr_validate(&res.contract_address)?;
 
         PAIRS.save(deps.storage, &tmp.pair_key, &pair_contract)?;
+        let created_at = env.block.time.seconds();
+        PAIRS_BY_CREATION.save(deps.storage, (created_at, &pair_contract), &())?;
+        PAIR_CREATED_AT.save(deps.storage, &pair_contract, &created_at)?;
+
+        // keep track of the pair counters
+        PAIR_ORIGINS.save(deps.storage, &pair_contract, &tmp.origin)?;
+        let mut stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
+        stats.add(&tmp.origin);
+        PAIR_STATS.save(deps.storage, &stats)?;
 
         for asset_info in &tmp.asset_infos {
             for asset_info_2 in &tmp.asset_infos {
@@ -637,22 +653,30 @@ pub mod reply {
         let pair_info = query_pair_info(&deps.querier, &pair_contract)?;
         STAKING_ADDRESSES.save(deps.storage, &pair_info.staking_addr, &())?;
 
+        // create distribution flows
+        let create_flows = tmp
+            .distribution_flows
+            .into_iter()
+            .map(|flow| {
+                let manager = match flow.manager {
+                    Some(manager) => deps.api.addr_validate(&manager)?,
+                    None => env.contract.address.clone(),
+                };
+                Ok(SubMsg::new(wasm_execute(
+                    &pair_info.staking_addr,
+                    &wyndex_stake::msg::ExecuteMsg::CreateDistributionFlow {
+                        manager: manager.to_string(),
+                        asset: flow.asset,
+                        rewards: flow.rewards,
+                        claimant_reward_share: None,
+                    },
+                    vec![],
+                )?))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
         Ok(Response::new()
-            // create distribution flows
-            .add_submessages(tmp.distribution_flows.into_iter().map(|flow| {
-                SubMsg::new(
-                    wasm_execute(
-                        &pair_info.staking_addr,
-                        &wyndex_stake::msg::ExecuteMsg::CreateDistributionFlow {
-                            manager: env.contract.address.to_string(),
-                            asset: flow.asset,
-                            rewards: flow.rewards,
-                        },
-                        vec![],
-                    )
-                    .unwrap(),
-                )
-            }))
+            .add_submessages(create_flows)
             .add_attributes(vec![
                 attr("action", "register"),
                 attr("pair_contract_addr", pair_contract),
@@ -686,10 +710,26 @@ pub fn deregister_pool_and_staking(
 
     let pair_addr = PAIRS.load(deps.storage, &pair_key(&asset_infos))?;
     PAIRS.remove(deps.storage, &pair_key(&asset_infos));
+    if let Some(created_at) = PAIR_CREATED_AT.may_load(deps.storage, &pair_addr)? {
+        PAIRS_BY_CREATION.remove(deps.storage, (created_at, &pair_addr));
+        PAIR_CREATED_AT.remove(deps.storage, &pair_addr);
+    }
     // keep track of staking address
     let pair_info = query_pair_info(&deps.querier, &pair_addr)?;
     STAKING_ADDRESSES.remove(deps.storage, &pair_info.staking_addr);
 
+    // keep track of the pair counters
+    let origin = PAIR_ORIGINS
+        .may_load(deps.storage, &pair_addr)?
+        .unwrap_or(PairOrigin {
+            pair_type: pair_info.pair_type,
+            permissionless: false,
+        });
+    PAIR_ORIGINS.remove(deps.storage, &pair_addr);
+    let mut stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
+    stats.remove(&origin);
+    PAIR_STATS.save(deps.storage, &stats)?;
+
     for asset_info1 in &asset_infos {
         for asset_info2 in &asset_infos {
             if asset_info1 != asset_info2 {
@@ -730,6 +770,11 @@ pub fn deregister_pool_and_staking(
 /// * **QueryMsg::BlacklistedPairTypes {}** Returns a vector that contains blacklisted pair types (pair types that cannot get ASTRO emissions).
 ///
 /// * **QueryMsg::PairsToMigrate {}** Returns a vector that contains pair addresses that are not migrated.
+///
+/// * **QueryMsg::Stats {}** Returns the number of registered pairs using a [`StatsResponse`] structure.
+///
+/// * **QueryMsg::PairsCreatedAfter { timestamp, start_after, limit }** Returns the pairs created after
+/// the given block time using a [`PairsCreatedAfterResponse`] structure.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -746,6 +791,18 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ValidateStakingAddress { address } => {
             to_binary(&STAKING_ADDRESSES.has(deps.storage, &deps.api.addr_validate(&address)?))
         }
+        QueryMsg::CodeIds {} => to_binary(&query_code_ids(deps)?),
+        QueryMsg::Stats {} => to_binary(&query_stats(deps)?),
+        QueryMsg::PairsCreatedAfter {
+            timestamp,
+            start_after,
+            limit,
+        } => to_binary(&query_pairs_created_after(
+            deps,
+            timestamp,
+            start_after,
+            limit,
+        )?),
     }
 }
 
@@ -785,6 +842,36 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     Ok(resp)
 }
 
+/// Returns the code IDs used to instantiate new contracts using a custom [`CodeIdsResponse`] structure.
+pub fn query_code_ids(deps: Deps) -> StdResult<CodeIdsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let pair_code_ids = PAIR_CONFIGS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (_, pair_config) = item?;
+            Ok((pair_config.pair_type, pair_config.code_id))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(CodeIdsResponse {
+        pair_code_ids,
+        token_code_id: config.token_code_id,
+        staking_code_id: config.default_stake_config.staking_code_id,
+    })
+}
+
+/// Returns the number of registered pairs using a custom [`StatsResponse`] structure.
+pub fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
+    let stats = PAIR_STATS.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(StatsResponse {
+        total_pairs: stats.total(),
+        pairs_by_type: stats.pairs_by_type,
+        permissionless_pairs: stats.permissionless,
+        permissioned_pairs: stats.permissioned,
+    })
+}
+
 /// Returns a pair's data using the assets in `asset_infos` as input (those being the assets that are traded in the pair).
 /// * **asset_infos** is a vector with assets traded in the pair.
 pub fn query_pair(deps: Deps, asset_infos: Vec<AssetInfo>) -> StdResult<PairInfo> {
@@ -814,6 +901,22 @@ pub fn query_pairs(
     Ok(PairsResponse { pairs })
 }
 
+/// Returns the pairs created after `timestamp`, ordered by creation time, using a
+/// [`PairsCreatedAfterResponse`] struct. Querying starts after the pair `start_after` and returns `limit` pairs.
+pub fn query_pairs_created_after(
+    deps: Deps,
+    timestamp: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PairsCreatedAfterResponse> {
+    let pairs = read_pairs_created_after(deps, timestamp, start_after, limit)?
+        .into_iter()
+        .map(|(created_at, pair_addr)| Ok((created_at, query_pair_info(&deps.querier, pair_addr)?)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PairsCreatedAfterResponse { pairs })
+}
+
 /// Returns the fee setup for a specific pair type using a [`FeeInfoResponse`] struct.
 /// * **pair_type** is a struct that represents the fee information (total and protocol fees) for a specific pair type.
 pub fn query_fee_info(deps: Deps, pair_type: PairType) -> StdResult<FeeInfoResponse> {
@@ -858,6 +961,9 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
         }
         MigrateMsg::Update() => {
             ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+            if PAIR_STATS.may_load(deps.storage)?.is_none() {
+                init_pair_stats(deps)?;
+            }
         }
         MigrateMsg::AddPermissionlessPoolDeposit(asset) => {
             PERMISSIONLESS_DEPOSIT.save(deps.storage, &asset)?;
@@ -866,3 +972,23 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
 
     Ok(Response::new())
 }
+
+/// Initializes the pair counters for all pairs registered before they were introduced.
+/// There is no record of how these pairs were created, so they are counted as permissioned.
+fn init_pair_stats(deps: DepsMut) -> StdResult<()> {
+    let pairs = PAIRS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|pair| -> StdResult<Addr> { Ok(pair?.1) })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut stats = PairStats::default();
+    for pair_addr in pairs {
+        let origin = PairOrigin {
+            pair_type: query_pair_info(&deps.querier, &pair_addr)?.pair_type,
+            permissionless: false,
+        };
+        stats.add(&origin);
+        PAIR_ORIGINS.save(deps.storage, &pair_addr, &origin)?;
+    }
+    PAIR_STATS.save(deps.storage, &stats)
+}
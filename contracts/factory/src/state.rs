@@ -6,7 +6,7 @@ use itertools::Itertools;
 use crate::error::ContractError;
 use wyndex::asset::{Asset, AssetInfo, AssetInfoValidated};
 use wyndex::common::OwnershipProposal;
-use wyndex::factory::{DefaultStakeConfig, DistributionFlow, PairConfig};
+use wyndex::factory::{DefaultStakeConfig, DistributionFlow, PairConfig, PairType};
 
 /// This structure holds the main contract parameters.
 #[cw_serde]
@@ -34,6 +34,7 @@ pub struct TmpPairInfo {
     pub pair_key: Vec<u8>,
     pub asset_infos: Vec<AssetInfoValidated>,
     pub distribution_flows: Vec<DistributionFlow>,
+    pub origin: PairOrigin,
 }
 
 /// Saves a pair's key
@@ -48,9 +49,86 @@ pub const PERMISSIONLESS_DEPOSIT: Item<Asset> = Item::new("permissionless_deposi
 /// Saves created pairs (from olders to latest)
 pub const PAIRS: Map<&[u8], Addr> = Map::new("pair_info");
 
+/// Registered pairs by the block time they were created at (from oldest to latest).
+/// Pairs registered before this was introduced are not included.
+pub const PAIRS_BY_CREATION: Map<(u64, &Addr), ()> = Map::new("pairs_by_creation");
+
+/// The block time each pair in [`PAIRS_BY_CREATION`] was created at
+pub const PAIR_CREATED_AT: Map<&Addr, u64> = Map::new("pair_created_at");
+
 /// Set of all staking addresses
 pub const STAKING_ADDRESSES: Map<&Addr, ()> = Map::new("staking_addresses");
 
+/// This structure describes how a pair was created.
+#[cw_serde]
+pub struct PairOrigin {
+    /// The pair type the pair was created with
+    pub pair_type: PairType,
+    /// Whether the pair was created by anyone with a deposit instead of the owner
+    pub permissionless: bool,
+}
+
+/// Saves the origin of each registered pair, so the stats can be updated on deregistration
+pub const PAIR_ORIGINS: Map<&Addr, PairOrigin> = Map::new("pair_origins");
+
+/// This structure holds the counters of registered pairs.
+#[cw_serde]
+#[derive(Default)]
+pub struct PairStats {
+    /// Number of registered pairs for each pair type
+    pub pairs_by_type: Vec<(PairType, u64)>,
+    /// Number of registered pairs created permissionlessly
+    pub permissionless: u64,
+    /// Number of registered pairs created by the owner
+    pub permissioned: u64,
+}
+
+impl PairStats {
+    /// Counts a newly registered pair
+    pub fn add(&mut self, origin: &PairOrigin) {
+        match self
+            .pairs_by_type
+            .iter_mut()
+            .find(|(pair_type, _)| *pair_type == origin.pair_type)
+        {
+            Some((_, count)) => *count += 1,
+            None => self.pairs_by_type.push((origin.pair_type.clone(), 1)),
+        }
+        if origin.permissionless {
+            self.permissionless += 1;
+        } else {
+            self.permissioned += 1;
+        }
+    }
+
+    /// Stops counting a deregistered pair
+    pub fn remove(&mut self, origin: &PairOrigin) {
+        if let Some(idx) = self
+            .pairs_by_type
+            .iter()
+            .position(|(pair_type, _)| *pair_type == origin.pair_type)
+        {
+            self.pairs_by_type[idx].1 -= 1;
+            if self.pairs_by_type[idx].1 == 0 {
+                self.pairs_by_type.remove(idx);
+            }
+        }
+        if origin.permissionless {
+            self.permissionless = self.permissionless.saturating_sub(1);
+        } else {
+            self.permissioned = self.permissioned.saturating_sub(1);
+        }
+    }
+
+    /// Total number of registered pairs
+    pub fn total(&self) -> u64 {
+        self.permissionless + self.permissioned
+    }
+}
+
+/// Saves the counters of registered pairs
+pub const PAIR_STATS: Item<PairStats> = Item::new("pair_stats");
+
 /// Calculates a pair key from the specified parameters in the `asset_infos` variable.
 ///
 /// `asset_infos` is an array with multiple items of type [`AssetInfo`].
@@ -117,6 +195,42 @@ pub fn read_pairs(
     }
 }
 
+/// Reads pairs created after `timestamp` from [`PAIRS_BY_CREATION`], ordered by creation time,
+/// together with the time they were created at.
+///
+/// `start_after` is the address of the pair from which the function starts to fetch results.
+///
+/// `limit` is the number of items to retrieve.
+pub fn read_pairs_created_after(
+    deps: Deps,
+    timestamp: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Addr)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| -> StdResult<_> {
+            let addr = deps.api.addr_validate(&addr)?;
+            let created_at = PAIR_CREATED_AT.load(deps.storage, &addr)?;
+            Ok((created_at, addr))
+        })
+        .transpose()?;
+    // an empty address sorts before all pairs created at the same time
+    let empty_addr = Addr::unchecked("");
+
+    let start = match &start_after {
+        Some((created_at, addr)) if *created_at > timestamp => {
+            Bound::exclusive((*created_at, addr))
+        }
+        _ => Bound::inclusive((timestamp.saturating_add(1), &empty_addr)),
+    };
+
+    PAIRS_BY_CREATION
+        .keys(deps.storage, Some(start), None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
 /// Calculates the key of a pair from which to start reading data.
 ///
 /// `start_after` is an [`Option`] type that accepts [`AssetInfo`] elements.
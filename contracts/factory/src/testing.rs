@@ -1,5 +1,5 @@
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, Decimal, ReplyOn, SubMsg, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Addr, Decimal, Deps, ReplyOn, SubMsg, Uint128, WasmMsg,
 };
 use cw_utils::MsgInstantiateContractResponse;
 use wyndex::fee_config::FeeConfig;
@@ -12,8 +12,8 @@ use crate::{
 };
 use wyndex::asset::AssetInfo;
 use wyndex::factory::{
-    ConfigResponse, DefaultStakeConfig, ExecuteMsg, InstantiateMsg, PairConfig, PairType,
-    PairsResponse, PartialStakeConfig, QueryMsg,
+    CodeIdsResponse, ConfigResponse, DefaultStakeConfig, ExecuteMsg, InstantiateMsg, PairConfig,
+    PairType, PairsResponse, PartialDefaultStakeConfig, PartialStakeConfig, QueryMsg,
 };
 use wyndex::pair::PairInfo;
 
@@ -253,6 +253,106 @@ fn update_config() {
     assert_eq!(res, ContractError::Unauthorized {});
 }
 
+#[test]
+fn query_code_ids() {
+    let mut deps = mock_dependencies(&[]);
+    let owner = "owner0000";
+
+    let msg = InstantiateMsg {
+        pair_configs: vec![
+            PairConfig {
+                code_id: 123u64,
+                pair_type: PairType::Xyk {},
+                fee_config: FeeConfig {
+                    total_fee_bps: 100,
+                    protocol_fee_bps: 10,
+                },
+                is_disabled: false,
+            },
+            PairConfig {
+                code_id: 325u64,
+                pair_type: PairType::Stable {},
+                fee_config: FeeConfig {
+                    total_fee_bps: 100,
+                    protocol_fee_bps: 10,
+                },
+                is_disabled: false,
+            },
+        ],
+        token_code_id: 123u64,
+        fee_address: None,
+        owner: owner.to_string(),
+        max_referral_commission: Decimal::one(),
+        default_stake_config: default_stake_config(),
+        trading_starts: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info(owner, &[]), msg).unwrap();
+
+    let query_code_ids = |deps: Deps| -> CodeIdsResponse {
+        from_binary(&query(deps, mock_env(), QueryMsg::CodeIds {}).unwrap()).unwrap()
+    };
+
+    // code ids match the ones in the config
+    let code_ids = query_code_ids(deps.as_ref());
+    let config_res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(code_ids.token_code_id, config_res.token_code_id);
+    assert_eq!(
+        code_ids.pair_code_ids,
+        config_res
+            .pair_configs
+            .into_iter()
+            .map(|c| (c.pair_type, c.code_id))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(code_ids.staking_code_id, 1234u64);
+
+    // update code ids
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdateConfig {
+            token_code_id: Some(200u64),
+            fee_address: None,
+            only_owner_can_create_pairs: None,
+            default_stake_config: Some(PartialDefaultStakeConfig {
+                staking_code_id: Some(300u64),
+                tokens_per_power: None,
+                min_bond: None,
+                unbonding_periods: None,
+                max_distributions: None,
+            }),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner, &[]),
+        ExecuteMsg::UpdatePairConfig {
+            config: PairConfig {
+                code_id: 400u64,
+                pair_type: PairType::Xyk {},
+                fee_config: FeeConfig {
+                    total_fee_bps: 100,
+                    protocol_fee_bps: 10,
+                },
+                is_disabled: false,
+            },
+        },
+    )
+    .unwrap();
+
+    let code_ids = query_code_ids(deps.as_ref());
+    assert_eq!(code_ids.token_code_id, 200u64);
+    assert_eq!(code_ids.staking_code_id, 300u64);
+    assert!(code_ids.pair_code_ids.contains(&(PairType::Xyk {}, 400u64)));
+    assert!(code_ids
+        .pair_code_ids
+        .contains(&(PairType::Stable {}, 325u64)));
+}
+
 #[test]
 fn update_owner() {
     let mut deps = mock_dependencies(&[]);
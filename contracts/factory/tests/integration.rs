@@ -1,10 +1,12 @@
 mod factory_helper;
 
-use cosmwasm_std::{attr, from_slice, Addr, Decimal, StdError, Uint128};
-use wyndex::asset::AssetInfo;
+use cosmwasm_std::{attr, from_slice, to_binary, Addr, Decimal, StdError, Uint128};
+use cw20::Cw20ExecuteMsg;
+use wyndex::asset::{Asset, AssetInfo};
 use wyndex::factory::{
-    ConfigResponse, DefaultStakeConfig, ExecuteMsg, FeeInfoResponse, InstantiateMsg, MigrateMsg,
-    PairConfig, PairType, PartialDefaultStakeConfig, QueryMsg,
+    CodeIdsResponse, ConfigResponse, DefaultStakeConfig, ExecuteMsg, FeeInfoResponse,
+    InstantiateMsg, MigrateMsg, PairConfig, PairType, PairsCreatedAfterResponse,
+    PartialDefaultStakeConfig, PartialStakeConfig, QueryMsg, ReceiveMsg, StatsResponse,
 };
 use wyndex::fee_config::FeeConfig;
 use wyndex::pair::PairInfo;
@@ -945,3 +947,267 @@ fn can_migrate_the_placeholder_to_a_factory_properly() {
     assert_eq!(pair_configs, direct_factory_config.pair_configs);
     assert_eq!(owner, direct_factory_config.owner);
 }
+
+#[test]
+fn test_pair_stats() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user");
+    let factory_code_id = store_factory_code(&mut app);
+    let mut helper = FactoryHelper::instantiate(&mut app, &owner, Some(factory_code_id));
+
+    let tokens = ["tokenW", "tokenX", "tokenY", "tokenZ"]
+        .into_iter()
+        .map(|name| instantiate_token(&mut app, helper.cw20_token_code_id, &owner, name, None))
+        .collect::<Vec<_>>();
+
+    let stats: StatsResponse = app
+        .wrap()
+        .query_wasm_smart(&helper.factory, &QueryMsg::Stats {})
+        .unwrap();
+    assert_eq!(stats.total_pairs, 0);
+    assert_eq!(stats.pairs_by_type, vec![]);
+
+    // add a second pair type
+    let code_ids: CodeIdsResponse = app
+        .wrap()
+        .query_wasm_smart(&helper.factory, &QueryMsg::CodeIds {})
+        .unwrap();
+    let custom_type = PairType::Custom("concentrated".to_string());
+    app.execute_contract(
+        owner.clone(),
+        helper.factory.clone(),
+        &ExecuteMsg::UpdatePairConfig {
+            config: PairConfig {
+                code_id: code_ids.pair_code_ids[0].1,
+                pair_type: custom_type.clone(),
+                fee_config: FeeConfig {
+                    total_fee_bps: 100,
+                    protocol_fee_bps: 10,
+                },
+                is_disabled: false,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the owner creates pairs of both types
+    for (pair_type, pair_tokens) in [
+        (PairType::Xyk {}, [&tokens[0], &tokens[1]]),
+        (PairType::Xyk {}, [&tokens[0], &tokens[2]]),
+        (custom_type.clone(), [&tokens[1], &tokens[2]]),
+    ] {
+        helper
+            .create_pair(
+                &mut app,
+                &owner,
+                pair_type,
+                [pair_tokens[0].as_str(), pair_tokens[1].as_str()],
+                None,
+                None,
+            )
+            .unwrap();
+    }
+
+    // someone else creates a pair with a deposit
+    helper
+        .update_config(&mut app, &owner, None, None, Some(false), None)
+        .unwrap();
+    let wynd = instantiate_token(&mut app, helper.cw20_token_code_id, &owner, "WYND", None);
+    app.execute_contract(
+        owner.clone(),
+        wynd.clone(),
+        &Cw20ExecuteMsg::Mint {
+            recipient: user.to_string(),
+            amount: Uint128::new(1_000),
+        },
+        &[],
+    )
+    .unwrap();
+    app.migrate_contract(
+        owner.clone(),
+        helper.factory.clone(),
+        &MigrateMsg::AddPermissionlessPoolDeposit(Asset {
+            info: AssetInfo::Token(wynd.to_string()),
+            amount: Uint128::new(1_000),
+        }),
+        factory_code_id,
+    )
+    .unwrap();
+    app.execute_contract(
+        user,
+        wynd,
+        &Cw20ExecuteMsg::Send {
+            contract: helper.factory.to_string(),
+            amount: Uint128::new(1_000),
+            msg: to_binary(&ReceiveMsg::CreatePair {
+                pair_type: PairType::Xyk {},
+                asset_infos: vec![
+                    AssetInfo::Token(tokens[0].to_string()),
+                    AssetInfo::Token(tokens[3].to_string()),
+                ],
+                init_params: None,
+                staking_config: PartialStakeConfig::default(),
+                total_fee_bps: None,
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let stats: StatsResponse = app
+        .wrap()
+        .query_wasm_smart(&helper.factory, &QueryMsg::Stats {})
+        .unwrap();
+    assert_eq!(
+        stats,
+        StatsResponse {
+            total_pairs: 4,
+            pairs_by_type: vec![(PairType::Xyk {}, 3), (custom_type.clone(), 1)],
+            permissionless_pairs: 1,
+            permissioned_pairs: 3,
+        }
+    );
+
+    // deregistering pairs updates the counters
+    helper
+        .deregister_pool_and_staking(
+            &mut app,
+            &owner,
+            vec![
+                AssetInfo::Token(tokens[0].to_string()),
+                AssetInfo::Token(tokens[1].to_string()),
+            ],
+        )
+        .unwrap();
+    let stats: StatsResponse = app
+        .wrap()
+        .query_wasm_smart(&helper.factory, &QueryMsg::Stats {})
+        .unwrap();
+    assert_eq!(
+        stats,
+        StatsResponse {
+            total_pairs: 3,
+            pairs_by_type: vec![(PairType::Xyk {}, 2), (custom_type, 1)],
+            permissionless_pairs: 1,
+            permissioned_pairs: 2,
+        }
+    );
+
+    helper
+        .deregister_pool_and_staking(
+            &mut app,
+            &owner,
+            vec![
+                AssetInfo::Token(tokens[1].to_string()),
+                AssetInfo::Token(tokens[2].to_string()),
+            ],
+        )
+        .unwrap();
+    let stats: StatsResponse = app
+        .wrap()
+        .query_wasm_smart(&helper.factory, &QueryMsg::Stats {})
+        .unwrap();
+    assert_eq!(
+        stats,
+        StatsResponse {
+            total_pairs: 2,
+            pairs_by_type: vec![(PairType::Xyk {}, 2)],
+            permissionless_pairs: 1,
+            permissioned_pairs: 1,
+        }
+    );
+}
+
+#[test]
+fn pairs_created_after() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let mut helper = FactoryHelper::init(&mut app, &owner);
+
+    let tokens = ["tokenW", "tokenX", "tokenY", "tokenZ"]
+        .into_iter()
+        .map(|name| instantiate_token(&mut app, helper.cw20_token_code_id, &owner, name, None))
+        .collect::<Vec<_>>();
+
+    // create one pair every 100 seconds
+    let mut created = vec![];
+    for pair_tokens in [
+        [&tokens[0], &tokens[1]],
+        [&tokens[0], &tokens[2]],
+        [&tokens[1], &tokens[2]],
+        [&tokens[0], &tokens[3]],
+    ] {
+        app.update_block(|block| block.time = block.time.plus_seconds(100));
+        helper
+            .create_pair(
+                &mut app,
+                &owner,
+                PairType::Xyk {},
+                [pair_tokens[0].as_str(), pair_tokens[1].as_str()],
+                None,
+                None,
+            )
+            .unwrap();
+        let pair: PairInfo = app
+            .wrap()
+            .query_wasm_smart(
+                &helper.factory,
+                &QueryMsg::Pair {
+                    asset_infos: vec![
+                        AssetInfo::Token(pair_tokens[0].to_string()),
+                        AssetInfo::Token(pair_tokens[1].to_string()),
+                    ],
+                },
+            )
+            .unwrap();
+        created.push((app.block_info().time.seconds(), pair));
+    }
+
+    let factory = helper.factory.clone();
+    let query = |app: &App, timestamp: u64, start_after: Option<String>, limit: Option<u32>| {
+        app.wrap()
+            .query_wasm_smart::<PairsCreatedAfterResponse>(
+                &factory,
+                &QueryMsg::PairsCreatedAfter {
+                    timestamp,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+            .pairs
+    };
+
+    // only pairs created strictly after the timestamp are returned, oldest first
+    assert_eq!(query(&app, created[1].0, None, None), created[2..].to_vec());
+    assert_eq!(query(&app, 0, None, None), created);
+    assert_eq!(query(&app, created[3].0, None, None), vec![]);
+
+    // paginate
+    let page = query(&app, created[0].0, None, Some(2));
+    assert_eq!(page, created[1..3].to_vec());
+    let start_after = page.last().unwrap().1.contract_addr.to_string();
+    assert_eq!(
+        query(&app, created[0].0, Some(start_after), Some(2)),
+        created[3..].to_vec()
+    );
+
+    // deregistered pairs are not returned anymore
+    helper
+        .deregister_pool_and_staking(
+            &mut app,
+            &owner,
+            vec![
+                AssetInfo::Token(tokens[1].to_string()),
+                AssetInfo::Token(tokens[2].to_string()),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        query(&app, created[0].0, None, None),
+        vec![created[1].clone(), created[3].clone()]
+    );
+}
@@ -14,7 +14,7 @@ use wyndex_stake::msg::ExecuteMsg as StakeExecuteMsg;
 
 use crate::error::ContractError;
 use crate::msg::{AdapterQueryMsg, ExecuteMsg, InstantiateMsg, MigrateMsg};
-use crate::state::{Config, CONFIG};
+use crate::state::{BelowMinTotal, Config, CONFIG};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:gauge-adapter";
@@ -38,6 +38,8 @@ pub fn instantiate(
         owner: deps.api.addr_validate(&msg.owner)?,
         rewards_asset: msg.rewards_asset.validate(deps.api)?,
         distribution_duration: msg.epoch_length,
+        min_total_per_epoch: Uint128::zero(),
+        below_min_total: BelowMinTotal::Skip,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -53,6 +55,9 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::UpdateRewards { amount } => execute::update_rewards(deps, info.sender, amount),
+        ExecuteMsg::UpdateMinTotalPerEpoch { amount, mode } => {
+            execute::update_min_total_per_epoch(deps, info.sender, amount, mode)
+        }
     }
 }
 
@@ -77,6 +82,27 @@ mod execute {
             .add_attribute("asset", config.rewards_asset.info.to_string())
             .add_attribute("amount", new_amount.to_string()))
     }
+
+    pub fn update_min_total_per_epoch(
+        deps: DepsMut,
+        sender: Addr,
+        amount: Uint128,
+        mode: BelowMinTotal,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        if sender != config.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        config.min_total_per_epoch = amount;
+        config.below_min_total = mode.clone();
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("update", "min_total_per_epoch")
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("mode", format!("{:?}", mode)))
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -130,15 +156,32 @@ mod query {
             owner: _,
             rewards_asset,
             distribution_duration,
+            min_total_per_epoch,
+            below_min_total,
         } = CONFIG.load(deps.storage)?;
 
+        // epochs distributing only dust are either skipped or padded to the minimum
+        let total_weight = selected
+            .iter()
+            .fold(Decimal::zero(), |sum, (_, weight)| sum + *weight);
+        let below_min =
+            !total_weight.is_zero() && rewards_asset.amount * total_weight < min_total_per_epoch;
+        if below_min && below_min_total == BelowMinTotal::Skip {
+            return Ok(SampleGaugeMsgsResponse { execute: vec![] });
+        }
+
         Ok(SampleGaugeMsgsResponse {
             execute: selected
                 .into_iter()
                 .flat_map(|(option, weight)| {
+                    let amount = if below_min {
+                        min_total_per_epoch.multiply_ratio(weight.atomics(), total_weight.atomics())
+                    } else {
+                        rewards_asset.amount * weight
+                    };
                     let rewards_asset = AssetValidated {
                         info: rewards_asset.info.clone(),
-                        amount: rewards_asset.amount * weight,
+                        amount,
                     };
                     create_distribute_msgs(&env, rewards_asset, option, distribution_duration)
                         .unwrap()
@@ -171,7 +214,10 @@ fn create_distribute_msgs(
             msg: to_binary(&Cw20ExecuteMsg::Send {
                 contract: staking_contract,
                 amount: asset.amount,
-                msg: to_binary(&StakeReceiveDelegationMsg::Fund { funding_info })?,
+                msg: to_binary(&StakeReceiveDelegationMsg::Fund {
+                    funding_info,
+                    on_behalf_of: None,
+                })?,
             })?,
             funds: vec![],
         }
@@ -180,7 +226,10 @@ fn create_distribute_msgs(
             let funds = coins(asset.amount.u128(), denom);
             Ok(vec![WasmMsg::Execute {
                 contract_addr: staking_contract,
-                msg: to_binary(&StakeExecuteMsg::FundDistribution { funding_info })?,
+                msg: to_binary(&StakeExecuteMsg::FundDistribution {
+                    funding_info,
+                    on_behalf_of: None,
+                })?,
                 funds,
             }
             .into()])
@@ -226,7 +275,7 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, Con
 mod tests {
     use cosmwasm_std::{
         testing::{mock_dependencies, mock_env, mock_info},
-        to_binary, Coin, CosmosMsg, Decimal, Uint128, WasmMsg,
+        to_binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Response, Uint128, WasmMsg,
     };
     use wyndex::stake::FundingInfo;
 
@@ -234,7 +283,7 @@ mod tests {
     use crate::{
         error::ContractError,
         msg::{ExecuteMsg, InstantiateMsg},
-        state::CONFIG,
+        state::{BelowMinTotal, CONFIG},
     };
     use wyndex::asset::{Asset, AssetInfo};
 
@@ -319,7 +368,8 @@ mod tests {
                         start_time: mock_env().block.time.seconds(),
                         distribution_duration: EPOCH_LENGTH,
                         amount: Uint128::from(4160u128)
-                    }
+                    },
+                    on_behalf_of: None,
                 })
                 .unwrap(),
                 funds: vec![Coin {
@@ -337,7 +387,8 @@ mod tests {
                         start_time: mock_env().block.time.seconds(),
                         distribution_duration: EPOCH_LENGTH,
                         amount: Uint128::from(3330u128)
-                    }
+                    },
+                    on_behalf_of: None,
                 })
                 .unwrap(),
                 funds: vec![Coin {
@@ -355,7 +406,8 @@ mod tests {
                         start_time: mock_env().block.time.seconds(),
                         distribution_duration: EPOCH_LENGTH,
                         amount: Uint128::from(2500u128)
-                    }
+                    },
+                    on_behalf_of: None,
                 })
                 .unwrap(),
                 funds: vec![Coin {
@@ -423,4 +475,139 @@ mod tests {
         );
         assert_eq!(config.rewards_asset.amount.u128(), 2000);
     }
+
+    fn setup_min_total(
+        mut deps: DepsMut,
+        rewards: u128,
+        min_total: u128,
+        mode: BelowMinTotal,
+    ) -> Result<Response, ContractError> {
+        instantiate(
+            deps.branch(),
+            mock_env(),
+            mock_info("user", &[]),
+            InstantiateMsg {
+                factory: "factory".to_string(),
+                owner: "owner".to_string(),
+                rewards_asset: Asset {
+                    info: AssetInfo::Native("juno".to_string()),
+                    amount: rewards.into(),
+                },
+                epoch_length: EPOCH_LENGTH,
+            },
+        )
+        .unwrap();
+        execute(
+            deps,
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateMinTotalPerEpoch {
+                amount: min_total.into(),
+                mode,
+            },
+        )
+    }
+
+    fn sampled_amounts(deps: Deps, selected: Vec<(String, Decimal)>) -> Vec<Uint128> {
+        query::sample_gauge_msgs(deps, mock_env(), selected)
+            .unwrap()
+            .execute
+            .into_iter()
+            .map(|msg| match msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => funds[0].amount,
+                _ => panic!("unexpected message"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn update_min_total_per_epoch() {
+        let mut deps = mock_dependencies();
+        setup_min_total(deps.as_mut(), 1000, 0, BelowMinTotal::Skip).unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &[]),
+            ExecuteMsg::UpdateMinTotalPerEpoch {
+                amount: 500u128.into(),
+                mode: BelowMinTotal::Pad,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateMinTotalPerEpoch {
+                amount: 500u128.into(),
+                mode: BelowMinTotal::Pad,
+            },
+        )
+        .unwrap();
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.min_total_per_epoch.u128(), 500);
+        assert_eq!(config.below_min_total, BelowMinTotal::Pad);
+    }
+
+    #[test]
+    fn below_min_total_epoch_is_skipped() {
+        let mut deps = mock_dependencies();
+        setup_min_total(deps.as_mut(), 1000, 1000, BelowMinTotal::Skip).unwrap();
+
+        // 70% of the rewards are below the minimum
+        let selected = vec![
+            ("juno1555".to_string(), Decimal::percent(50)),
+            ("juno1444".to_string(), Decimal::percent(20)),
+        ];
+        assert_eq!(sampled_amounts(deps.as_ref(), selected), vec![]);
+
+        // the full rewards are not
+        let selected = vec![
+            ("juno1555".to_string(), Decimal::percent(50)),
+            ("juno1444".to_string(), Decimal::percent(50)),
+        ];
+        assert_eq!(
+            sampled_amounts(deps.as_ref(), selected),
+            vec![Uint128::new(500), Uint128::new(500)]
+        );
+    }
+
+    #[test]
+    fn below_min_total_epoch_is_padded() {
+        let mut deps = mock_dependencies();
+        setup_min_total(deps.as_mut(), 1000, 1000, BelowMinTotal::Pad).unwrap();
+
+        // 70% of the rewards are padded to the minimum, keeping the weights
+        let selected = vec![
+            ("juno1555".to_string(), Decimal::percent(50)),
+            ("juno1444".to_string(), Decimal::percent(20)),
+        ];
+        assert_eq!(
+            sampled_amounts(deps.as_ref(), selected),
+            vec![Uint128::new(714), Uint128::new(285)]
+        );
+
+        // above the minimum, nothing changes
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateMinTotalPerEpoch {
+                amount: 500u128.into(),
+                mode: BelowMinTotal::Pad,
+            },
+        )
+        .unwrap();
+        let selected = vec![
+            ("juno1555".to_string(), Decimal::percent(50)),
+            ("juno1444".to_string(), Decimal::percent(20)),
+        ];
+        assert_eq!(
+            sampled_amounts(deps.as_ref(), selected),
+            vec![Uint128::new(500), Uint128::new(200)]
+        );
+    }
 }
@@ -2,6 +2,8 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{CosmosMsg, Decimal, Uint128};
 use wyndex::asset::Asset;
 
+use crate::state::BelowMinTotal;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     /// The address of the factory contract
@@ -15,7 +17,15 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    UpdateRewards { amount: Uint128 },
+    UpdateRewards {
+        amount: Uint128,
+    },
+    /// Sets the minimum total amount distributed per epoch and how epochs below it are handled.
+    /// Can only be called by the owner.
+    UpdateMinTotalPerEpoch {
+        amount: Uint128,
+        mode: BelowMinTotal,
+    },
 }
 
 #[cw_serde]
@@ -598,6 +598,8 @@ impl PairContract {
                 assets: assets.to_vec(),
                 slippage_tolerance: None,
                 receiver: None,
+                deadline: None,
+                require_all_assets: None,
             },
             send_funds,
         )
@@ -624,6 +626,8 @@ impl StakingContract {
                 msg: to_binary(&ReceiveMsg::Delegate {
                     unbonding_period,
                     delegate_as: None,
+                    unbond_controller: None,
+                    withdraw_to: None,
                 })
                 .unwrap(),
             },
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::Item;
 use wyndex::asset::AssetValidated;
 
@@ -13,6 +13,22 @@ pub struct Config {
     pub rewards_asset: AssetValidated,
     /// Default duration of distributions in seconds.
     pub distribution_duration: u64,
+    /// Minimum total amount of `rewards_asset` distributed in an epoch. Disabled if zero.
+    #[serde(default)]
+    pub min_total_per_epoch: Uint128,
+    /// What happens to epochs that would distribute less than `min_total_per_epoch`
+    #[serde(default)]
+    pub below_min_total: BelowMinTotal,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum BelowMinTotal {
+    /// No rewards are distributed in the epoch
+    #[default]
+    Skip,
+    /// The rewards are increased to `min_total_per_epoch`, split by the same weights
+    Pad,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -149,7 +149,10 @@ mod execute {
                 msg: to_binary(&Cw20ExecuteMsg::Send {
                     contract: pair_contract_from.into_string(),
                     amount,
-                    msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { assets: vec![] })?,
+                    msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+                        assets: vec![],
+                        skip_frozen: false,
+                    })?,
                 })?,
                 funds: vec![],
             },
@@ -257,6 +260,8 @@ mod reply {
                     assets,
                     slippage_tolerance: Some(Decimal::percent(50)), // this is the max allowed slippage
                     receiver: None, // we receive the LP tokens back, since we are the sender
+                    deadline: None,
+                    require_all_assets: None,
                 })?,
                 funds,
             },
@@ -292,6 +297,8 @@ mod reply {
                 msg: to_binary(&ReceiveMsg::Delegate {
                     unbonding_period: tmp_data.unbonding_period,
                     delegate_as: Some(tmp_data.lp_owner.into_string()), // this avoids another reply
+                    unbond_controller: None,
+                    withdraw_to: None,
                 })?,
             })?,
             funds: vec![],
@@ -441,6 +441,8 @@ impl Suite {
                 msg: to_binary(&ReceiveMsg::Delegate {
                     unbonding_period,
                     delegate_as: None,
+                    unbond_controller: None,
+                    withdraw_to: None,
                 })?,
             },
             &[],
@@ -502,6 +504,8 @@ impl Suite {
                 assets: vec![first_asset, second_asset],
                 slippage_tolerance: None,
                 receiver: None,
+                deadline: None,
+                require_all_assets: None,
             },
             &native_tokens,
         )?;
@@ -225,6 +225,7 @@ mod execute {
                         to: receiver,
                         referral_address,
                         referral_commission,
+                        deadline: None,
                     })?,
                 }))
             }
@@ -241,6 +242,7 @@ mod execute {
                         to: receiver,
                         referral_address,
                         referral_commission,
+                        deadline: None,
                     })?,
                 })?,
             })),
@@ -315,6 +315,8 @@ impl Suite {
                 assets: assets.to_vec(),
                 slippage_tolerance: None,
                 receiver: None,
+                deadline: None,
+                require_all_assets: None,
             },
             send_funds,
         )
@@ -86,6 +86,7 @@ pub fn build_swap_msg(
                 to: None,
                 referral_address: None,
                 referral_commission: None,
+                deadline: None,
             })?,
             funds: vec![Coin {
                 denom: offer_asset.info.to_string(),
@@ -105,6 +106,7 @@ pub fn build_swap_msg(
                     to: None,
                     referral_address: None,
                     referral_commission: None,
+                    deadline: None,
                 })?,
             })?,
             funds: vec![],
@@ -18,10 +18,11 @@ use wyndex::decimal2decimal256;
 use wyndex::factory::{ConfigResponse as FactoryConfig, PairType};
 use wyndex::fee_config::FeeConfig;
 use wyndex::pair::{
-    add_referral, assert_max_spread, check_asset_infos, check_assets, check_cw20_in_pool,
-    create_lp_token, get_share_in_assets, handle_referral, handle_reply, migration_check,
-    mint_token_message, save_tmp_staking_config, take_referral, ConfigResponse, ContractError,
-    Cw20HookMsg, MigrateMsg, DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE,
+    add_referral, assert_all_assets_provided, assert_deadline, assert_max_spread,
+    check_asset_infos, check_assets, check_cw20_in_pool, create_lp_token, get_share_in_assets,
+    handle_referral, handle_reply, migration_check, mint_token_message, save_tmp_staking_config,
+    spread_percent, take_referral, ConfigResponse, ContractError, Cw20HookMsg, MigrateMsg,
+    DEFAULT_SLIPPAGE, MAX_ALLOWED_SLIPPAGE,
 };
 use wyndex::pair::{
     CumulativePricesResponse, ExecuteMsg, InstantiateMsg, PairInfo, PoolResponse, QueryMsg,
@@ -60,6 +61,8 @@ pub fn instantiate(
         msg.token_code_id,
         &asset_infos,
         &factory_addr,
+        None,
+        None,
     )?;
 
     let config = Config {
@@ -152,7 +155,20 @@ pub fn execute(
             assets,
             slippage_tolerance,
             receiver,
-        } => provide_liquidity(deps, env, info, assets, slippage_tolerance, receiver),
+            deadline,
+            require_all_assets,
+        } => {
+            assert_deadline(&env.block, deadline)?;
+            provide_liquidity(
+                deps,
+                env,
+                info,
+                assets,
+                slippage_tolerance,
+                receiver,
+                require_all_assets,
+            )
+        }
         ExecuteMsg::UpdateFees { fee_config } => update_fees(deps, info, fee_config),
         ExecuteMsg::Swap {
             offer_asset,
@@ -161,8 +177,10 @@ pub fn execute(
             to,
             referral_address,
             referral_commission,
+            deadline,
             ..
         } => {
+            assert_deadline(&env.block, deadline)?;
             let offer_asset = offer_asset.validate(deps.api)?;
             if !offer_asset.is_native_token() {
                 return Err(ContractError::Unauthorized {});
@@ -215,8 +233,10 @@ pub fn receive_cw20(
             to,
             referral_address,
             referral_commission,
+            deadline,
             ..
         } => {
+            assert_deadline(&env.block, deadline)?;
             // Only asset contract can execute this message
             check_cw20_in_pool(
                 &CONFIG.load(deps.storage)?.pair_info.asset_infos,
@@ -289,6 +309,7 @@ pub fn provide_liquidity(
     assets: Vec<Asset>,
     slippage_tolerance: Option<Decimal>,
     receiver: Option<String>,
+    require_all_assets: Option<bool>,
 ) -> Result<Response, ContractError> {
     let mut assets = check_assets(deps.api, &assets)?;
     check_if_frozen(&deps)?;
@@ -305,6 +326,7 @@ pub fn provide_liquidity(
     }
 
     let mut config = CONFIG.load(deps.storage)?;
+    assert_all_assets_provided(&config.pair_info.asset_infos, &assets, require_all_assets)?;
     let mut pools = config
         .pair_info
         .query_pools(&deps.querier, &env.contract.address)?;
@@ -992,6 +1014,7 @@ pub fn query_simulation(
         spread_amount,
         commission_amount,
         referral_amount,
+        spread_percent: spread_percent(return_amount, spread_amount, commission_amount),
     })
 }
 
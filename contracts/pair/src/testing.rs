@@ -199,6 +199,8 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env();
@@ -267,6 +269,8 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ],
         slippage_tolerance: Some(Decimal::percent(50)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -293,6 +297,7 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ask_asset_info: None,
         referral_address: None,
         referral_commission: None,
+        deadline: None,
     };
 
     let info = mock_info(
@@ -326,6 +331,7 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
             to: None,
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
     });
@@ -339,7 +345,11 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
     // Withdraw liquidity
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: String::from("addr0000"),
-        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { assets: vec![] }).unwrap(),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            assets: vec![],
+            skip_frozen: false,
+        })
+        .unwrap(),
         amount: Uint128::new(100u128),
     });
 
@@ -401,6 +411,8 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let info = mock_info(
@@ -423,6 +435,7 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
             to: None,
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
     });
@@ -488,6 +501,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env();
@@ -598,6 +613,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(50)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -665,6 +682,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env();
@@ -724,6 +743,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -760,6 +781,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -796,6 +819,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -831,6 +856,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -856,6 +883,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     let info = mock_info(
         "addr0001",
@@ -880,6 +909,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(51)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     let info = mock_info(
         "addr0001",
@@ -950,7 +981,11 @@ fn withdraw_liquidity() {
     // Withdraw liquidity
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: String::from("addr0000"),
-        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { assets: vec![] }).unwrap(),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            assets: vec![],
+            skip_frozen: false,
+        })
+        .unwrap(),
         amount: Uint128::new(100u128),
     });
 
@@ -1078,6 +1113,8 @@ fn query_twap() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     // need to set balance manually to simulate funds being sent
     deps.querier
@@ -1135,6 +1172,7 @@ fn query_twap() {
         ask_asset_info: None,
         referral_address: None,
         referral_commission: None,
+        deadline: None,
     };
     // need to set balance manually to simulate funds being sent
     deps.querier
@@ -1249,6 +1287,7 @@ fn try_native_to_token() {
         to: None,
         referral_address: None,
         referral_commission: None,
+        deadline: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info(
@@ -1467,6 +1506,7 @@ fn try_token_to_native() {
         to: None,
         referral_address: None,
         referral_commission: None,
+        deadline: None,
     };
     let env = mock_env_with_block_time(1000);
     let info = mock_info("addr0000", &[]);
@@ -1484,6 +1524,7 @@ fn try_token_to_native() {
             to: None,
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
     });
@@ -1607,6 +1648,7 @@ fn try_token_to_native() {
             to: None,
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
     });
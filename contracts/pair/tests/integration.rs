@@ -238,6 +238,8 @@ fn provide_liquidity_mixed_msg(
         ],
         slippage_tolerance,
         receiver,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let coins = [Coin {
@@ -419,7 +421,11 @@ fn test_provide_and_withdraw_liquidity() {
     let msg = Cw20ExecuteMsg::Send {
         contract: pair_instance.to_string(),
         amount: Uint128::from(50u8),
-        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { assets: vec![] }).unwrap(),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            assets: vec![],
+            skip_frozen: false,
+        })
+        .unwrap(),
     };
     // Try to send withdraw liquidity with FOO token
     let err = router
@@ -446,6 +452,7 @@ fn test_provide_and_withdraw_liquidity() {
                 to: None,
                 referral_address: None,
                 referral_commission: None,
+                deadline: None,
             },
             &[Coin {
                 denom: "cny".to_string(),
@@ -492,6 +499,8 @@ fn provide_liquidity_msg(
         ],
         slippage_tolerance,
         receiver,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let coins = [
@@ -679,6 +688,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
             to: Some(user.to_string()),
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
         amount: x_offer,
@@ -705,6 +715,8 @@ fn test_compatibility_of_tokens_with_different_precision() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     app.execute_contract(owner.clone(), pair_instance, &msg, &[])
@@ -912,6 +924,8 @@ fn provide_liquidity_with_one_asset() {
         assets: vec![AssetInfo::Native("uusd".to_string()).with_balance(100_000u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     router
         .execute_contract(
@@ -973,6 +987,7 @@ fn provide_liquidity_with_swap() {
         to: None,
         referral_address: None,
         referral_commission: None,
+        deadline: None,
     };
     router
         .execute_contract(
@@ -1059,6 +1074,8 @@ fn provide_liquidity_with_unequal_pool() {
         assets: vec![AssetInfo::Native("uluna".to_string()).with_balance(100_000u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     router
         .execute_contract(
@@ -1105,6 +1122,8 @@ fn provide_liquidity_sad_path() {
         assets: vec![AssetInfo::Native("uusd".to_string()).with_balance(100_000u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     let err = router
         .execute_contract(
@@ -1138,6 +1157,8 @@ fn provide_liquidity_sad_path() {
         assets: vec![AssetInfo::Native("uusd".to_string()).with_balance(0u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     let err = router
         .execute_contract(owner.clone(), pair.clone(), &msg, &[])
@@ -1150,6 +1171,8 @@ fn provide_liquidity_sad_path() {
         assets: vec![],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let err = router
@@ -1232,6 +1255,8 @@ fn provide_liquidity_with_one_cw20_asset() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     router
         .execute_contract(owner.clone(), pair.clone(), &msg, &[])
@@ -1242,6 +1267,8 @@ fn provide_liquidity_with_one_cw20_asset() {
         assets: vec![AssetInfo::Token(token2.to_string()).with_balance(100_000u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     router
         .execute_contract(owner.clone(), pair.clone(), &msg, &[])
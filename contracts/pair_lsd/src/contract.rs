@@ -1,45 +1,56 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::vec;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, ensure, from_binary, to_binary, wasm_execute, Addr, Binary, CosmosMsg, Decimal,
+    attr, coin, ensure, from_binary, to_binary, wasm_execute, Addr, Binary, CosmosMsg, Decimal,
     Decimal256, Deps, DepsMut, Empty, Env, Fraction, MessageInfo, QuerierWrapper, Reply, Response,
-    StdError, StdResult, Uint128, Uint256, WasmMsg,
+    StdError, StdResult, SubMsg, SubMsgResult, Uint128, Uint256, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use itertools::Itertools;
 
 use wyndex::asset::{
-    addr_opt_validate, check_swap_parameters, Asset, AssetInfo, AssetInfoValidated, AssetValidated,
-    Decimal256Ext, DecimalAsset, MINIMUM_LIQUIDITY_AMOUNT,
+    addr_opt_validate, check_swap_parameters, Asset, AssetInfo, AssetInfoExt, AssetInfoValidated,
+    AssetValidated, Decimal256Ext, DecimalAsset, MINIMUM_LIQUIDITY_AMOUNT,
+};
+use wyndex::common::{
+    claim_ownership, drop_ownership_proposal, propose_new_owner, validate_addresses,
 };
-use wyndex::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
 use wyndex::factory::PairType;
 use wyndex::fee_config::FeeConfig;
 use wyndex::pair::{
-    add_referral, assert_max_spread, check_asset_infos, check_assets, check_cw20_in_pool,
-    create_lp_token, get_share_in_assets, handle_referral, handle_reply, migration_check,
-    mint_token_message, save_tmp_staking_config, take_referral, ConfigResponse, Cw20HookMsg,
-    InstantiateMsg, MigrateMsg, SpotPricePredictionResponse, SpotPriceResponse, StablePoolParams,
-    StablePoolUpdateParams,
+    add_referral, assert_all_assets_provided, assert_deadline, assert_max_spread,
+    check_asset_infos, check_assets, check_cw20_in_pool, create_lp_token, get_share_in_assets,
+    handle_referral, handle_reply, migration_check, mint_token_message, save_tmp_staking_config,
+    spread_percent, take_referral, ConfigResponse, Cw20HookMsg, FeeBurnParams, HealthResponse,
+    InstantiateMsg, LsdInfo, MarginalResponse, MigrateMsg, SpotPricePredictionResponse,
+    SpotPriceResponse, StablePoolParams, StablePoolUpdateParams,
 };
 use wyndex::pair::{
-    CumulativePricesResponse, ExecuteMsg, PairInfo, PoolResponse, QueryMsg,
-    ReverseSimulationResponse, SimulationResponse, StablePoolConfig,
+    ArbVsPriceResponse, AssetPrecision, BatchSimulationRequest, BatchSimulationResult,
+    CumulativePricesResponse, DepositShareResponse, ExecuteMsg, LpForWithdrawalResponse, PairInfo,
+    PegStatus, PegStatusResponse, PoolDebugResponse, PoolResponse, ProtocolLpShareResponse,
+    QueryMsg, ReverseSimulationResponse, SimulationResponse, SlippageCurveResponse, SlippagePoint,
+    StablePoolConfig, SwapMeetsMinResponse, ValueVsHoldResponse, WithdrawSimulationResponse,
+    MAX_BATCH_SIMULATIONS, MAX_SLIPPAGE_CURVE_POINTS,
 };
-use wyndex::querier::{query_factory_config, query_fee_info, query_supply};
+use wyndex::querier::{query_factory_config, query_fee_info, query_supply, query_token_balance};
 use wyndex::DecimalCheckedOps;
 
 use crate::math::{
-    calc_y, compute_d, AMP_PRECISION, MAX_AMP, MAX_AMP_CHANGE, MIN_AMP_CHANGING_TIME,
+    calc_y, compute_d, compute_marginal, AMP_PRECISION, MAX_AMP, MAX_AMP_CHANGE, MIN_AMP,
+    MIN_AMP_CHANGING_TIME,
 };
-use crate::msg::{TargetQuery, TargetValueResponse};
+use crate::msg::{AllStakedResponse, StakeQuery, TargetQuery, TargetValueResponse};
 use crate::state::{
-    get_precision, store_precisions, Config, LsdData, CIRCUIT_BREAKER, CONFIG, FROZEN,
-    OWNERSHIP_PROPOSAL,
+    active_lp_locks, get_precision, store_precisions, Config, FeeBurn, InitialBasket, LpLock,
+    LsdData, PendingDeposit, BLOCK_REFERENCE_PRICE, CIRCUIT_BREAKER, CONFIG,
+    FEE_ON_TRANSFER_TOKENS, FROZEN, INITIAL_BASKET, LP_LOCKS, OWNERSHIP_PROPOSAL, PENDING_DEPOSIT,
+    PENDING_PROTOCOL_FEES, PENDING_REFUNDS, REBALANCE_BUDGET, TOTAL_UNSENT_REFUNDS, UNSENT_REFUNDS,
 };
 use crate::utils::{
     accumulate_prices, adjust_precision, calc_new_price_a_per_b, calc_spot_price,
@@ -53,6 +64,15 @@ const CONTRACT_NAME: &str = "wyndex-pair-lsd";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const WEEK: u64 = 7 * 24 * 60 * 60;
+/// The maximum number of decimals of the LP token, as supported by cw20-base.
+const MAX_LP_TOKEN_DECIMALS: u8 = 18;
+/// Number of bisection steps used to find the trade size in [`QueryMsg::ArbVsPrice`]
+const ARB_ITERATIONS: u8 = 50;
+/// Reply id of the first refund sent during a withdrawal with `skip_frozen` set.
+/// The following refunds use the next ids, one per pool asset.
+const WITHDRAW_REFUND_REPLY_ID: u64 = 100;
+/// Reply id of the last transfer pulling in fee-on-transfer deposits
+const FEE_ON_TRANSFER_REPLY_ID: u64 = 10;
 
 /// Creates a new contract with the specified parameters in [`InstantiateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -77,25 +97,43 @@ pub fn instantiate(
 
     let params: StablePoolParams = from_binary(&msg.init_params.unwrap())?;
 
-    if params.amp == 0 || params.amp > MAX_AMP {
-        return Err(ContractError::IncorrectAmp { max_amp: MAX_AMP });
+    validate_amp(params.amp)?;
+    if matches!(params.lp_token_decimals, Some(decimals) if decimals > MAX_LP_TOKEN_DECIMALS) {
+        return Err(ContractError::InvalidLpTokenDecimals {
+            max: MAX_LP_TOKEN_DECIMALS,
+        });
     }
 
-    let lsd_data: Option<LsdData> = if let Some(info) = params.lsd {
+    let validate_lsd_info = |info: LsdInfo| -> Result<LsdData, ContractError> {
         ensure!(
             info.target_rate_epoch <= WEEK,
             ContractError::InvalidTargetRateEpoch {}
         );
-        Some(LsdData {
+        Ok(LsdData {
             asset: info.asset.validate(deps.api)?,
             lsd_hub: deps.api.addr_validate(&info.hub)?,
             target_rate: Decimal::one(),
             target_rate_epoch: info.target_rate_epoch,
             last_target_query: 0,
         })
-    } else {
-        None
     };
+    let lsd_data: Option<LsdData> = params.lsd.map(validate_lsd_info).transpose()?;
+    let rate_providers = params
+        .rate_providers
+        .into_iter()
+        .map(validate_lsd_info)
+        .collect::<Result<Vec<_>, _>>()?;
+    // every asset can only have a single rate, which has to belong to the pool
+    let mut rated_assets: Vec<&AssetInfoValidated> = vec![];
+    for provider in lsd_data.iter().chain(&rate_providers) {
+        if !asset_infos.contains(&provider.asset) {
+            return Err(ContractError::InvalidAsset(provider.asset.to_string()));
+        }
+        if rated_assets.contains(&&provider.asset) {
+            return Err(ContractError::DoublingAssets {});
+        }
+        rated_assets.push(&provider.asset);
+    }
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -119,6 +157,8 @@ pub fn instantiate(
         msg.token_code_id,
         &asset_infos,
         &factory_addr,
+        params.lp_token_symbol,
+        params.lp_token_decimals,
     )?;
 
     let config = Config {
@@ -141,6 +181,15 @@ pub fn instantiate(
         cumulative_prices,
         trading_starts: msg.trading_starts,
         lsd: lsd_data,
+        rate_providers,
+        fee_burn: None,
+        hold_protocol_fees: false,
+        max_block_price_move: None,
+        staker_fee_discounts: vec![],
+        provide_lock: 0,
+        min_reserves: vec![],
+        lp_whitelist: None,
+        rebalance_incentive: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -153,7 +202,14 @@ pub fn instantiate(
 
 /// The entry point to the contract for processing replies from submessages.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id >= WITHDRAW_REFUND_REPLY_ID {
+        return refund_reply(deps, msg);
+    }
+    if msg.id == FEE_ON_TRANSFER_REPLY_ID {
+        return fee_on_transfer_reply(deps, env);
+    }
+
     let mut config = CONFIG.load(deps.storage)?;
     let res = handle_reply(&deps, msg, &config.factory_addr, &mut config.pair_info)?;
     CONFIG.save(deps.storage, &config)?;
@@ -227,7 +283,12 @@ pub fn execute(
             assets,
             receiver,
             slippage_tolerance: _,
-        } => provide_liquidity(deps, env, info, assets, receiver),
+            deadline,
+            require_all_assets,
+        } => {
+            assert_deadline(&env.block, deadline)?;
+            provide_liquidity(deps, env, info, assets, receiver, require_all_assets)
+        }
         ExecuteMsg::UpdateFees { fee_config } => update_fees(deps, info, fee_config),
         ExecuteMsg::Swap {
             offer_asset,
@@ -237,15 +298,34 @@ pub fn execute(
             to,
             referral_address,
             referral_commission,
+            deadline,
         } => {
+            assert_deadline(&env.block, deadline)?;
             let offer_asset = offer_asset.validate(deps.api)?;
-            if !offer_asset.is_native_token() {
-                return Err(ContractError::Unauthorized {});
-            }
-
             let to_addr = addr_opt_validate(deps.api, &to)?;
             let referral_address = addr_opt_validate(deps.api, &referral_address)?;
 
+            if !offer_asset.is_native_token() {
+                // only fee-on-transfer tokens are pulled in, others have to be sent
+                let pending = PendingDeposit::Swap {
+                    sender: info.sender.clone(),
+                    ask_asset_info,
+                    belief_price,
+                    max_spread,
+                    to: to_addr,
+                    referral_address,
+                    referral_commission,
+                };
+                return pull_fee_on_transfer_deposits(
+                    deps,
+                    &env,
+                    &info.sender,
+                    &[offer_asset],
+                    pending,
+                )?
+                .ok_or(ContractError::Unauthorized {});
+            }
+
             swap(
                 deps,
                 env,
@@ -309,6 +389,32 @@ pub fn execute(
             FROZEN.save(deps.storage, &frozen)?;
             Ok(Response::new())
         }
+        ExecuteMsg::StartAmpRamp {
+            next_amp,
+            next_amp_time,
+        } => {
+            assert_owner(deps.as_ref(), &cfg, &info.sender)?;
+            start_changing_amp(cfg, deps, env, next_amp, next_amp_time)?;
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "start_amp_ramp"),
+                attr("next_amp", next_amp.to_string()),
+                attr("next_amp_time", next_amp_time.to_string()),
+            ]))
+        }
+        ExecuteMsg::StopAmpRamp {} => {
+            assert_owner(deps.as_ref(), &cfg, &info.sender)?;
+            stop_changing_amp(cfg, deps, env)?;
+            Ok(Response::new().add_attribute("action", "stop_amp_ramp"))
+        }
+        ExecuteMsg::CollectProtocolFees { recipient } => {
+            collect_protocol_fees(deps, cfg, info, recipient)
+        }
+        ExecuteMsg::ClaimUnsentRefunds {} => claim_unsent_refunds(deps, info),
+        ExecuteMsg::EmergencyWithdraw { amount } => emergency_withdraw(deps, info, amount),
+        ExecuteMsg::SweepDust {} => {
+            assert_owner(deps.as_ref(), &cfg, &info.sender)?;
+            sweep_dust(deps, env, cfg)
+        }
     }
 }
 
@@ -329,12 +435,22 @@ pub fn receive_cw20(
             to,
             referral_address,
             referral_commission,
+            deadline,
         } => {
+            assert_deadline(&env.block, deadline)?;
             // Only asset contract can execute this message
             check_cw20_in_pool(
                 &CONFIG.load(deps.storage)?.pair_info.asset_infos,
                 &info.sender,
             )?;
+            // the amount of a fee-on-transfer token that actually arrived is unknown here
+            if FEE_ON_TRANSFER_TOKENS
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                .contains(&info.sender)
+            {
+                return Err(ContractError::FeeOnTransferSend(info.sender.to_string()));
+            }
 
             let to_addr = addr_opt_validate(deps.api, &to)?;
             let referral_address = addr_opt_validate(deps.api, &referral_address)?;
@@ -357,9 +473,20 @@ pub fn receive_cw20(
                 referral_commission,
             )
         }
-        Cw20HookMsg::WithdrawLiquidity { assets } => {
+        Cw20HookMsg::WithdrawLiquidity {
+            assets,
+            skip_frozen,
+        } => {
             let sender = deps.api.addr_validate(&cw20_msg.sender)?;
-            withdraw_liquidity(deps, env, info, sender, cw20_msg.amount, assets)
+            withdraw_liquidity(
+                deps,
+                env,
+                info,
+                sender,
+                cw20_msg.amount,
+                assets,
+                skip_frozen,
+            )
         }
     }
 }
@@ -394,16 +521,61 @@ pub fn update_fees(
 ///
 /// NOTE - the address that wants to provide liquidity should approve the pair contract to pull its relevant tokens.
 pub fn provide_liquidity(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     assets: Vec<Asset>,
     receiver: Option<String>,
+    require_all_assets: Option<bool>,
 ) -> Result<Response, ContractError> {
     check_if_frozen(&deps)?;
     let assets = check_assets(deps.api, &assets)?;
+    assert_all_assets_provided(
+        &CONFIG.load(deps.storage)?.pair_info.asset_infos,
+        &assets,
+        require_all_assets,
+    )?;
+    for asset in &assets {
+        asset.assert_sent_native_token_balance(&info)?;
+    }
+
+    let pending = PendingDeposit::Provide {
+        sender: info.sender.clone(),
+        assets: assets.clone(),
+        receiver: receiver.clone(),
+    };
+    if let Some(resp) =
+        pull_fee_on_transfer_deposits(deps.branch(), &env, &info.sender, &assets, pending)?
+    {
+        return Ok(resp);
+    }
+
+    provide_received_liquidity(deps, env, info.sender, assets, receiver, &[])
+}
+
+/// Provides liquidity for `sender` after the native deposits were checked.
+/// Deposits of the assets in `received` already arrived in the pair, all other cw20 deposits
+/// are transferred from the sender.
+fn provide_received_liquidity(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    assets: Vec<AssetValidated>,
+    receiver: Option<String>,
+    received: &[AssetInfoValidated],
+) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
+    // in permissioned pools, both the provider and the receiver of the LP tokens need to be whitelisted
+    if let Some(whitelist) = &config.lp_whitelist {
+        let receiver = addr_opt_validate(deps.api, &receiver)?;
+        for address in std::iter::once(&sender).chain(&receiver) {
+            if !whitelist.contains(address) {
+                return Err(ContractError::NotWhitelistedProvider(address.to_string()));
+            }
+        }
+    }
+
     if assets.len() > config.pair_info.asset_infos.len() {
         return Err(ContractError::TooManyAssets {
             max: config.pair_info.asset_infos.len(),
@@ -413,9 +585,7 @@ pub fn provide_liquidity(
 
     let save_config = update_target_rate(deps.querier, &mut config, &env)?;
 
-    let pools: HashMap<_, _> = config
-        .pair_info
-        .query_pools(&deps.querier, &env.contract.address)?
+    let pools: HashMap<_, _> = query_pools(deps.as_ref(), &config)?
         .into_iter()
         .map(|pool| (pool.info, pool.amount))
         .collect();
@@ -426,8 +596,6 @@ pub fn provide_liquidity(
         .clone()
         .into_iter()
         .map(|asset| {
-            asset.assert_sent_native_token_balance(&info)?;
-
             // Check that at least one asset is non-zero
             if !asset.amount.is_zero() {
                 non_zero_flag = true;
@@ -470,20 +638,23 @@ pub fn provide_liquidity(
         // Transfer only non-zero amount
         if !deposit.amount.is_zero() {
             // If the pool is a token contract, then we need to execute a TransferFrom msg to receive funds
-            if let AssetInfoValidated::Token(contract_addr) = &deposit.info {
-                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: contract_addr.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-                        owner: info.sender.to_string(),
-                        recipient: env.contract.address.to_string(),
-                        amount: deposit.amount,
-                    })?,
-                    funds: vec![],
-                }))
-            } else {
-                // If the asset is a native token, the pool balance already increased
-                // To calculate the pool balance properly, we should subtract the user deposit from the recorded pool token amount
-                *pool = pool.checked_sub(deposit.amount)?;
+            match &deposit.info {
+                AssetInfoValidated::Token(contract_addr) if !received.contains(&deposit.info) => {
+                    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                            owner: sender.to_string(),
+                            recipient: env.contract.address.to_string(),
+                            amount: deposit.amount,
+                        })?,
+                        funds: vec![],
+                    }))
+                }
+                _ => {
+                    // If the asset is a native token or was already received, the pool balance already increased
+                    // To calculate the pool balance properly, we should subtract the user deposit from the recorded pool token amount
+                    *pool = pool.checked_sub(deposit.amount)?;
+                }
             }
         }
     }
@@ -500,9 +671,109 @@ pub fn provide_liquidity(
         })
         .collect::<StdResult<Vec<(DecimalAsset, Decimal256)>>>()?;
 
+    let total_share = query_supply(&deps.querier, &config.pair_info.liquidity_token)?;
+    let share = compute_provide_share(
+        deps.as_ref(),
+        &env,
+        &config,
+        &assets_collection,
+        total_share,
+    )?;
+    let share = match config.rebalance_incentive {
+        Some(incentive) if !total_share.is_zero() => {
+            let budget = REBALANCE_BUDGET.may_load(deps.storage)?.unwrap_or_default();
+            let (share, budget) =
+                apply_rebalance_incentive(&config, incentive, &assets_collection, share, budget)?;
+            REBALANCE_BUDGET.save(deps.storage, &budget)?;
+            share
+        }
+        _ => share,
+    };
+    if total_share.is_zero() {
+        messages.extend(mint_token_message(
+            &config.pair_info.liquidity_token,
+            &env.contract.address,
+            MINIMUM_LIQUIDITY_AMOUNT,
+        )?);
+    }
+
+    // Mint LP token for the caller (or for the receiver if it was set)
+    let receiver = addr_opt_validate(deps.api, &receiver)?.unwrap_or_else(|| sender.clone());
+    messages.extend(mint_token_message(
+        &config.pair_info.liquidity_token,
+        &receiver,
+        share,
+    )?);
+    if total_share.is_zero() {
+        INITIAL_BASKET.save(
+            deps.storage,
+            &InitialBasket {
+                assets: assets.clone(),
+                share: share + MINIMUM_LIQUIDITY_AMOUNT,
+            },
+        )?;
+    }
+    if config.provide_lock > 0 {
+        let mut locks = active_lp_locks(deps.storage, &receiver, env.block.time.seconds())?;
+        locks.push(LpLock {
+            amount: share,
+            unlock_at: env.block.time.seconds() + config.provide_lock,
+        });
+        LP_LOCKS.save(deps.storage, &receiver, &locks)?;
+    }
+
+    // using assets_collection, since the deposit amount is already subtracted there
+    let old_pools = assets_collection
+        .iter()
+        .map(|(a, p)| DecimalAsset {
+            info: a.info.clone(),
+            amount: *p,
+        })
+        .collect::<Vec<_>>();
+
+    // calculate pools with deposited balances
+    let new_pools = assets_collection
+        .into_iter()
+        .map(|(mut asset, pool)| {
+            // add deposit amount back to pool amount, so we can calculate the new price
+            asset.amount += pool;
+            asset
+        })
+        .collect::<Vec<_>>();
+    let new_price = calc_new_price_a_per_b(deps.as_ref(), &env, &config, &new_pools)?;
+
+    if total_share.is_zero() {
+        // initialize oracle storage
+        wyndex::oracle::initialize_oracle(deps.storage, &env, new_price)?;
+    } else {
+        wyndex::oracle::store_oracle_price(deps.storage, &env, new_price)?;
+    }
+
+    if accumulate_prices(deps.as_ref(), &env, &mut config, &old_pools)? || save_config {
+        CONFIG.save(deps.storage, &config)?;
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "provide_liquidity"),
+        attr("sender", sender),
+        attr("receiver", receiver),
+        attr("assets", assets.iter().join(", ")),
+        attr("share", share),
+    ]))
+}
+
+/// Returns the LP tokens minted for depositing `assets_collection` (pairs of deposit and pool amount
+/// before the deposit), excluding the minimum liquidity that is locked on the first deposit.
+fn compute_provide_share(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    assets_collection: &[(DecimalAsset, Decimal256)],
+    total_share: Uint128,
+) -> Result<Uint128, ContractError> {
     let n_coins = config.pair_info.asset_infos.len() as u8;
 
-    let amp = compute_current_amp(&config, &env)?;
+    let amp = compute_current_amp(config, env)?;
 
     // Initial invariant (D)
     let old_balances = assets_collection
@@ -518,19 +789,12 @@ pub fn provide_liquidity(
         .collect::<StdResult<Vec<_>>>()?;
     let deposit_d = compute_d(amp, &new_balances, config.greatest_precision)?;
 
-    let total_share = query_supply(&deps.querier, &config.pair_info.liquidity_token)?;
     let share = if total_share.is_zero() {
         let share = deposit_d
             .to_uint128_with_precision(config.greatest_precision)?
             .checked_sub(MINIMUM_LIQUIDITY_AMOUNT)
             .map_err(|_| ContractError::MinimumLiquidityAmountError {})?;
 
-        messages.extend(mint_token_message(
-            &config.pair_info.liquidity_token,
-            &env.contract.address,
-            MINIMUM_LIQUIDITY_AMOUNT,
-        )?);
-
         // share cannot become zero after minimum liquidity subtraction
         if share.is_zero() {
             return Err(ContractError::MinimumLiquidityAmountError {});
@@ -576,52 +840,234 @@ pub fn provide_liquidity(
         share
     };
 
-    // Mint LP token for the caller (or for the receiver if it was set)
-    let receiver = addr_opt_validate(deps.api, &receiver)?.unwrap_or_else(|| info.sender.clone());
-    messages.extend(mint_token_message(
-        &config.pair_info.liquidity_token,
-        &receiver,
-        share,
-    )?);
+    Ok(share)
+}
 
-    // using assets_collection, since the deposit amount is already subtracted there
-    let old_pools = assets_collection
+/// Returns how far the given balances are from being balanced, between 0 (balanced) and 1.
+fn imbalance(balances: &[Decimal256]) -> StdResult<Decimal256> {
+    let sum = balances
         .iter()
-        .map(|(a, p)| DecimalAsset {
-            info: a.info.clone(),
-            amount: *p,
+        .try_fold(Decimal256::zero(), |sum, balance| sum.checked_add(*balance))?;
+    match balances.iter().minmax().into_option() {
+        Some((min, max)) if !sum.is_zero() => Ok((*max - *min) / sum),
+        _ => Ok(Decimal256::zero()),
+    }
+}
+
+/// Adjusts the LP tokens minted for a deposit by the rebalance incentive.
+/// Deposits that make the pool more imbalanced pay a penalty into the rebalance budget,
+/// deposits that restore the balance receive a bonus from it.
+/// Returns the adjusted share and the new budget.
+fn apply_rebalance_incentive(
+    config: &Config,
+    incentive: Decimal,
+    assets_collection: &[(DecimalAsset, Decimal256)],
+    share: Uint128,
+    budget: Uint128,
+) -> Result<(Uint128, Uint128), ContractError> {
+    let old_balances = assets_collection
+        .iter()
+        .map(|(_, pool)| *pool)
+        .collect_vec();
+    let new_balances = assets_collection
+        .iter()
+        .map(|(deposit, pool)| pool.checked_add(deposit.amount))
+        .collect::<Result<Vec<_>, _>>()?;
+    let old_imbalance = imbalance(&old_balances)?;
+    let new_imbalance = imbalance(&new_balances)?;
+
+    let incentive = Decimal256::new(incentive.atomics().into());
+    let rate = if new_imbalance > old_imbalance {
+        new_imbalance - old_imbalance
+    } else {
+        old_imbalance - new_imbalance
+    }
+    .checked_mul(incentive)?;
+    let adjustment = Decimal256::with_precision(share, config.greatest_precision)?
+        .checked_mul(rate)?
+        .to_uint128_with_precision(config.greatest_precision)?;
+
+    if new_imbalance > old_imbalance {
+        Ok((share - adjustment, budget + adjustment))
+    } else {
+        // the bonus is limited to what was withheld before
+        let bonus = adjustment.min(budget);
+        Ok((share + bonus, budget - bonus))
+    }
+}
+
+/// Pulls the given deposits of fee-on-transfer tokens into the pair and saves `pending`,
+/// to be continued with the actually received amounts once the last transfer is done.
+/// Returns `None` if none of the assets is a fee-on-transfer token.
+fn pull_fee_on_transfer_deposits(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    assets: &[AssetValidated],
+    pending: PendingDeposit,
+) -> Result<Option<Response>, ContractError> {
+    let tokens = FEE_ON_TRANSFER_TOKENS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let deposits = assets
+        .iter()
+        .filter_map(|asset| match &asset.info {
+            AssetInfoValidated::Token(addr) if tokens.contains(addr) && !asset.amount.is_zero() => {
+                Some((addr, asset))
+            }
+            _ => None,
         })
-        .collect::<Vec<_>>();
+        .collect_vec();
+    if deposits.is_empty() {
+        return Ok(None);
+    }
 
-    // calculate pools with deposited balances
-    let new_pools = assets_collection
+    let balances_before = deposits
+        .iter()
+        .map(|(_, asset)| {
+            let balance = asset
+                .info
+                .query_balance(&deps.querier, &env.contract.address)?;
+            Ok(asset.info.with_balance(balance))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    PENDING_DEPOSIT.save(deps.storage, &(pending, balances_before))?;
+
+    let last = deposits.len() - 1;
+    let messages = deposits
         .into_iter()
-        .map(|(mut asset, pool)| {
-            // add deposit amount back to pool amount, so we can calculate the new price
-            asset.amount += pool;
-            asset
+        .enumerate()
+        .map(|(i, (addr, asset))| {
+            let msg = wasm_execute(
+                addr,
+                &Cw20ExecuteMsg::TransferFrom {
+                    owner: sender.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount: asset.amount,
+                },
+                vec![],
+            )?;
+            // the operation continues after the last transfer
+            Ok(if i == last {
+                SubMsg::reply_on_success(msg, FEE_ON_TRANSFER_REPLY_ID)
+            } else {
+                SubMsg::new(msg)
+            })
         })
-        .collect::<Vec<_>>();
-    let new_price = calc_new_price_a_per_b(deps.as_ref(), &env, &config, &new_pools)?;
+        .collect::<StdResult<Vec<_>>>()?;
 
-    if total_share.is_zero() {
-        // initialize oracle storage
-        wyndex::oracle::initialize_oracle(deps.storage, &env, new_price)?;
-    } else {
-        wyndex::oracle::store_oracle_price(deps.storage, &env, new_price)?;
-    }
+    Ok(Some(
+        Response::new()
+            .add_submessages(messages)
+            .add_attribute("action", "pull_fee_on_transfer_deposits"),
+    ))
+}
 
-    if accumulate_prices(deps.as_ref(), &env, &mut config, &old_pools)? || save_config {
-        CONFIG.save(deps.storage, &config)?;
+/// Continues the pending provide or swap with the amounts of fee-on-transfer tokens
+/// the pair actually received.
+fn fee_on_transfer_reply(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let (pending, balances_before) = PENDING_DEPOSIT.load(deps.storage)?;
+    PENDING_DEPOSIT.remove(deps.storage);
+
+    let received = balances_before
+        .into_iter()
+        .map(|before| {
+            let after = before
+                .info
+                .query_balance(&deps.querier, &env.contract.address)?;
+            Ok(before.info.with_balance(after.checked_sub(before.amount)?))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    match pending {
+        PendingDeposit::Provide {
+            sender,
+            assets,
+            receiver,
+        } => {
+            let assets = assets
+                .into_iter()
+                .map(|asset| {
+                    received
+                        .iter()
+                        .find(|r| r.info == asset.info)
+                        .cloned()
+                        .unwrap_or(asset)
+                })
+                .collect();
+            let received = received.into_iter().map(|r| r.info).collect_vec();
+            provide_received_liquidity(deps, env, sender, assets, receiver, &received)
+        }
+        PendingDeposit::Swap {
+            sender,
+            ask_asset_info,
+            belief_price,
+            max_spread,
+            to,
+            referral_address,
+            referral_commission,
+        } => {
+            let info = MessageInfo {
+                sender: sender.clone(),
+                funds: vec![],
+            };
+            swap(
+                deps,
+                env,
+                info,
+                sender,
+                received[0].clone(),
+                ask_asset_info,
+                belief_price,
+                max_spread,
+                to,
+                referral_address,
+                referral_commission,
+            )
+        }
     }
+}
 
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "provide_liquidity"),
-        attr("sender", info.sender),
-        attr("receiver", receiver),
-        attr("assets", assets.iter().join(", ")),
-        attr("share", share),
-    ]))
+/// Withdraws the sender's proportional share of the pool while the pair is frozen.
+/// No fees are charged and neither the invariant nor the rate providers are used,
+/// so a broken pool cannot block liquidity providers from exiting.
+///
+/// * **amount** is the amount of LP tokens to burn. The pair needs an allowance for them.
+pub fn emergency_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure!(FROZEN.load(deps.storage)?, ContractError::NotFrozen {});
+    let config = CONFIG.load(deps.storage)?;
+
+    let (pools, total_share) = pool_info(deps.as_ref(), &config)?;
+    let refund_assets = get_share_in_assets(&pools, amount, total_share);
+
+    // burning fails if the sender does not have enough LP tokens or allowance
+    let burn_message = wasm_execute(
+        &config.pair_info.liquidity_token,
+        &Cw20ExecuteMsg::BurnFrom {
+            owner: info.sender.to_string(),
+            amount,
+        },
+        vec![],
+    )?;
+    let refund_messages = refund_assets
+        .iter()
+        .filter(|refund| !refund.amount.is_zero())
+        .map(|refund| refund.clone().into_msg(&info.sender))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_message(burn_message)
+        .add_messages(refund_messages)
+        .add_attributes(vec![
+            attr("action", "emergency_withdraw"),
+            attr("sender", info.sender),
+            attr("withdrawn_share", amount),
+            attr("refund_assets", refund_assets.iter().join(", ")),
+        ]))
 }
 
 /// Withdraw liquidity from the pool.
@@ -637,6 +1083,7 @@ pub fn withdraw_liquidity(
     sender: Addr,
     amount: Uint128,
     assets: Vec<Asset>,
+    skip_frozen: bool,
 ) -> Result<Response, ContractError> {
     let assets = check_assets(deps.api, &assets)?;
     let mut config = CONFIG.load(deps.storage)?;
@@ -645,11 +1092,26 @@ pub fn withdraw_liquidity(
         return Err(ContractError::Unauthorized {});
     }
 
-    let burn_amount;
+    // recently minted LP tokens must stay with the sender until they are unlocked
+    let locks = active_lp_locks(deps.storage, &sender, env.block.time.seconds())?;
+    if let Some(last) = locks.last() {
+        let locked: Uint128 = locks.iter().map(|lock| lock.amount).sum();
+        // the withdrawn tokens were already transferred to the pair at this point
+        let remaining =
+            query_token_balance(&deps.querier, &config.pair_info.liquidity_token, &sender)?;
+        if remaining < locked {
+            return Err(ContractError::LpLocked {
+                locked,
+                unlock_at: last.unlock_at,
+            });
+        }
+    }
+
+    let burn_amount;
     let refund_assets;
-    let mut messages = vec![];
+    let mut messages: Vec<CosmosMsg> = vec![];
 
-    let (pools, total_share) = pool_info(deps.querier, &config)?;
+    let (pools, total_share) = pool_info(deps.as_ref(), &config)?;
     if assets.is_empty() {
         burn_amount = amount;
         refund_assets = get_share_in_assets(&pools, amount, total_share);
@@ -673,23 +1135,46 @@ pub fn withdraw_liquidity(
         refund_assets = assets;
     }
 
-    messages.extend(
-        refund_assets
-            .clone()
-            .into_iter()
-            .map(|asset| asset.into_msg(&sender))
-            .collect::<StdResult<Vec<_>>>()?,
-    );
-    messages.push(
-        wasm_execute(
-            &config.pair_info.liquidity_token,
-            &Cw20ExecuteMsg::Burn {
-                amount: burn_amount,
-            },
-            vec![],
-        )?
-        .into(),
-    );
+    // tiny reserves make the invariant ill-conditioned, so withdrawals cannot go below the minimum
+    for pool in &pools {
+        let refund: Uint128 = refund_assets
+            .iter()
+            .filter(|refund| refund.info == pool.info)
+            .map(|refund| refund.amount)
+            .sum();
+        if let Some(min_reserve) = config
+            .min_reserves
+            .iter()
+            .find(|reserve| reserve.info == pool.info)
+        {
+            if !refund.is_zero() && pool.amount.saturating_sub(refund) < min_reserve.amount {
+                return Err(ContractError::BelowMinReserve(min_reserve.to_string()));
+            }
+        }
+    }
+
+    let mut refund_messages = vec![];
+    for (i, refund) in refund_assets.iter().enumerate() {
+        let msg = refund.clone().into_msg(&sender)?;
+        if skip_frozen && !refund.is_native_token() {
+            if refund.amount.is_zero() {
+                continue;
+            }
+            // a failing cw20 transfer does not revert the withdrawal, see `refund_reply`
+            let reply_id = WITHDRAW_REFUND_REPLY_ID + i as u64;
+            PENDING_REFUNDS.save(deps.storage, reply_id, &(sender.clone(), refund.clone()))?;
+            refund_messages.push(SubMsg::reply_always(msg, reply_id));
+        } else {
+            refund_messages.push(SubMsg::new(msg));
+        }
+    }
+    let burn_message = wasm_execute(
+        &config.pair_info.liquidity_token,
+        &Cw20ExecuteMsg::Burn {
+            amount: burn_amount,
+        },
+        vec![],
+    )?;
 
     let old_pools = pools
         .iter()
@@ -718,11 +1203,108 @@ pub fn withdraw_liquidity(
         CONFIG.save(deps.storage, &config)?;
     }
 
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_submessages(refund_messages)
+        .add_message(burn_message)
+        .add_attributes(vec![
+            attr("action", "withdraw_liquidity"),
+            attr("sender", sender),
+            attr("withdrawn_share", amount),
+            attr("refund_assets", refund_assets.iter().join(", ")),
+        ]))
+}
+
+/// Handles the result of a refund sent during a withdrawal with `skip_frozen` set.
+/// If the transfer failed, the refund is kept for the withdrawer to claim later.
+fn refund_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let (recipient, refund) = PENDING_REFUNDS.load(deps.storage, msg.id)?;
+    PENDING_REFUNDS.remove(deps.storage, msg.id);
+
+    match msg.result {
+        SubMsgResult::Ok(_) => Ok(Response::new()),
+        SubMsgResult::Err(err) => {
+            UNSENT_REFUNDS.update(
+                deps.storage,
+                (&recipient, &refund.info),
+                |amount| -> StdResult<_> {
+                    Ok(amount.unwrap_or_default().checked_add(refund.amount)?)
+                },
+            )?;
+            TOTAL_UNSENT_REFUNDS.update(deps.storage, &refund.info, |amount| -> StdResult<_> {
+                Ok(amount.unwrap_or_default().checked_add(refund.amount)?)
+            })?;
+
+            Ok(Response::new().add_attributes(vec![
+                attr("action", "unsent_refund"),
+                attr("recipient", recipient),
+                attr("refund", refund.to_string()),
+                attr("error", err),
+            ]))
+        }
+    }
+}
+
+/// Sends all of the sender's refunds that could not be transferred during a withdrawal.
+fn claim_unsent_refunds(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let refunds = UNSENT_REFUNDS
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|item| item.map(|(asset, amount)| asset.with_balance(amount)))
+        .collect::<StdResult<Vec<_>>>()?;
+    ensure!(!refunds.is_empty(), ContractError::NoUnsentRefunds {});
+
+    let mut messages = vec![];
+    for refund in &refunds {
+        UNSENT_REFUNDS.remove(deps.storage, (&info.sender, &refund.info));
+        TOTAL_UNSENT_REFUNDS.update(deps.storage, &refund.info, |amount| -> StdResult<_> {
+            Ok(amount.unwrap_or_default().checked_sub(refund.amount)?)
+        })?;
+        messages.push(refund.clone().into_msg(&info.sender)?);
+    }
+
     Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "withdraw_liquidity"),
-        attr("sender", sender),
-        attr("withdrawn_share", amount),
-        attr("refund_assets", refund_assets.iter().join(", ")),
+        attr("action", "claim_unsent_refunds"),
+        attr("recipient", info.sender),
+        attr("refunds", refunds.iter().join(", ")),
+    ]))
+}
+
+/// Releases the assets held back for unsent refunds that are not owed to anyone anymore
+/// (e.g. rounding leftovers) back into the pool, so they are credited to the liquidity providers.
+fn sweep_dust(deps: DepsMut, env: Env, config: Config) -> Result<Response, ContractError> {
+    let virtual_price_before = query_health(deps.as_ref(), env.clone())?.virtual_price;
+
+    let mut swept = vec![];
+    for asset_info in &config.pair_info.asset_infos {
+        let held = TOTAL_UNSENT_REFUNDS
+            .may_load(deps.storage, asset_info)?
+            .unwrap_or_default();
+        if held.is_zero() {
+            continue;
+        }
+        let owed = UNSENT_REFUNDS
+            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .filter_map(|item| match item {
+                Ok(((_, info), amount)) if info == *asset_info => Some(Ok(amount)),
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .sum::<StdResult<Uint128>>()?;
+        let dust = held.saturating_sub(owed);
+        if !dust.is_zero() {
+            TOTAL_UNSENT_REFUNDS.save(deps.storage, asset_info, &owed)?;
+            swept.push(asset_info.with_balance(dust));
+        }
+    }
+
+    let virtual_price_after = query_health(deps.as_ref(), env)?.virtual_price;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "sweep_dust"),
+        attr("swept", swept.iter().join(", ")),
+        attr("virtual_price_before", virtual_price_before.to_string()),
+        attr("virtual_price_after", virtual_price_after.to_string()),
     ]))
 }
 
@@ -738,6 +1320,27 @@ fn imbalanced_withdraw(
     config: &Config,
     provided_amount: Uint128,
     assets: &[AssetValidated],
+) -> Result<Uint128, ContractError> {
+    let burn_amount = calc_imbalanced_withdraw_burn(deps, env, config, assets)?;
+
+    if burn_amount > provided_amount {
+        return Err(StdError::generic_err(format!(
+            "Not enough LP tokens. You need {} LP tokens.",
+            burn_amount
+        ))
+        .into());
+    }
+
+    Ok(burn_amount)
+}
+
+/// Calculates the number of LP tokens that need to be burned to withdraw the given assets,
+/// including the imbalance fee.
+fn calc_imbalanced_withdraw_burn(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    assets: &[AssetValidated],
 ) -> Result<Uint128, ContractError> {
     if assets.len() > config.pair_info.asset_infos.len() {
         return Err(ContractError::TooManyAssets {
@@ -746,9 +1349,7 @@ fn imbalanced_withdraw(
         });
     }
 
-    let pools: HashMap<_, _> = config
-        .pair_info
-        .query_pools(&deps.querier, &env.contract.address)?
+    let pools: HashMap<_, _> = query_pools(deps, config)?
         .into_iter()
         .map(|pool| (pool.info, pool.amount))
         .collect();
@@ -846,17 +1447,35 @@ fn imbalanced_withdraw(
         )?
         .checked_add(Uint256::from(1u8))?; // In case of rounding errors - make it unfavorable for the "attacker"
 
-    let burn_amount = burn_amount.try_into()?;
+    Ok(burn_amount.try_into()?)
+}
 
-    if burn_amount > provided_amount {
-        return Err(StdError::generic_err(format!(
-            "Not enough LP tokens. You need {} LP tokens.",
-            burn_amount
-        ))
-        .into());
+/// Sends all protocol fees held by the pair to the given `recipient`.
+fn collect_protocol_fees(
+    deps: DepsMut,
+    config: Config,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let factory_config = query_factory_config(&deps.querier, &config.factory_addr)?;
+    ensure!(
+        factory_config.fee_address.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized {}
+    );
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    let fees = query_pending_protocol_fees(deps.as_ref())?;
+    let mut messages = vec![];
+    for fee in fees.iter().filter(|fee| !fee.amount.is_zero()) {
+        PENDING_PROTOCOL_FEES.remove(deps.storage, &fee.info);
+        messages.push(fee.clone().into_msg(&recipient)?);
     }
 
-    Ok(burn_amount)
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "collect_protocol_fees"),
+        attr("recipient", recipient),
+        attr("collected", fees.iter().join(", ")),
+    ]))
 }
 
 /// Performs an swap operation with the specified parameters.
@@ -898,9 +1517,7 @@ pub fn swap(
 
     // If the asset balance already increased
     // We should subtract the user deposit from the pool offer asset amount
-    let pools = config
-        .pair_info
-        .query_pools(&deps.querier, &env.contract.address)?
+    let pools = query_pools(deps.as_ref(), &config)?
         .into_iter()
         .map(|mut pool| {
             if pool.info.equal(&offer_asset.info) {
@@ -958,10 +1575,9 @@ pub fn swap(
         &pools,
     )?;
 
-    let commission_amount = config
-        .pair_info
-        .fee_config
-        .total_fee_rate()
+    let fee_discount = staker_fee_discount(&deps.querier, &config, &sender)?;
+    let commission_amount = (config.pair_info.fee_config.total_fee_rate()
+        * (Decimal::one() - fee_discount))
         .checked_mul_uint128(return_amount)?;
     let return_amount = return_amount.saturating_sub(commission_amount);
 
@@ -993,7 +1609,28 @@ pub fn swap(
             config.pair_info.fee_config.protocol_fee_rate(),
         ) {
             protocol_fee_amount = f.amount;
-            messages.push(f.into_msg(fee_address)?);
+            if config.hold_protocol_fees {
+                PENDING_PROTOCOL_FEES.update(deps.storage, &f.info, |pending| -> StdResult<_> {
+                    Ok(pending.unwrap_or_default().checked_add(f.amount)?)
+                })?;
+            } else {
+                messages.push(f.into_msg(fee_address)?);
+            }
+        }
+    }
+
+    // Route part of the LP fee into the buy-and-burn target, if configured
+    let mut fee_burn_amount = Uint128::zero();
+    if let Some(fee_burn) = &config.fee_burn {
+        fee_burn_amount = (commission_amount - protocol_fee_amount) * fee_burn.fraction;
+        if !fee_burn_amount.is_zero() {
+            messages.push(fee_burn_msg(
+                fee_burn,
+                AssetValidated {
+                    info: ask_pool.info.clone(),
+                    amount: fee_burn_amount,
+                },
+            )?);
         }
     }
 
@@ -1012,7 +1649,7 @@ pub fn swap(
                 // subtract fee and return amount from ask pool
                 let ask_precision = get_precision(deps.storage, &ask_pool.info)?;
                 pool.amount = pool.amount.checked_sub(Decimal256::with_precision(
-                    return_amount + protocol_fee_amount,
+                    return_amount + protocol_fee_amount + fee_burn_amount,
                     ask_precision,
                 )?)?;
             }
@@ -1020,6 +1657,29 @@ pub fn swap(
         })
         .collect::<StdResult<Vec<_>>>()?;
     let new_price = calc_new_price_a_per_b(deps.as_ref(), &env, &config, &new_pools)?;
+
+    // Check the single block price move limit (if it is configured)
+    if let Some(max_move) = config.max_block_price_move {
+        let reference_price = match BLOCK_REFERENCE_PRICE.may_load(deps.storage)? {
+            Some((height, price)) if height == env.block.height => price,
+            _ => {
+                // first swap in this block, so the price before the swap is the reference
+                let price = calc_new_price_a_per_b(deps.as_ref(), &env, &config, &pools)?;
+                BLOCK_REFERENCE_PRICE.save(deps.storage, &(env.block.height, price))?;
+                price
+            }
+        };
+        let price_move = if new_price > reference_price {
+            new_price - reference_price
+        } else {
+            reference_price - new_price
+        };
+        ensure!(
+            price_move <= reference_price * max_move,
+            ContractError::BlockPriceMoveTooLarge { max: max_move }
+        );
+    }
+
     wyndex::oracle::store_oracle_price(deps.storage, &env, new_price)?;
 
     if accumulate_prices(deps.as_ref(), &env, &mut config, &pools)? || save_config {
@@ -1043,9 +1703,62 @@ pub fn swap(
             attr("spread_amount", spread_amount),
             attr("commission_amount", commission_amount),
             attr("protocol_fee_amount", protocol_fee_amount),
+            attr("fee_burn_amount", fee_burn_amount),
         ]))
 }
 
+/// Returns the fraction of the swap commission waived for `swapper`,
+/// based on the amount of LP tokens it has bonded in the pair's staking contract.
+fn staker_fee_discount(
+    querier: &QuerierWrapper,
+    config: &Config,
+    swapper: &Addr,
+) -> StdResult<Decimal> {
+    if config.staker_fee_discounts.is_empty() {
+        return Ok(Decimal::zero());
+    }
+
+    let response: AllStakedResponse = querier.query_wasm_smart(
+        &config.pair_info.staking_addr,
+        &StakeQuery::AllStaked {
+            address: swapper.to_string(),
+        },
+    )?;
+    let staked: Uint128 = response.stakes.iter().map(|s| s.stake).sum();
+
+    Ok(config
+        .staker_fee_discounts
+        .iter()
+        .rev()
+        .find(|tier| tier.min_staked <= staked)
+        .map(|tier| tier.discount)
+        .unwrap_or_default())
+}
+
+/// Creates the message sending the given fee to the buy-and-burn target.
+/// If a message is configured, it is attached to the transfer.
+fn fee_burn_msg(fee_burn: &FeeBurn, fee: AssetValidated) -> StdResult<CosmosMsg> {
+    match (&fee_burn.msg, &fee.info) {
+        (None, _) => fee.into_msg(&fee_burn.target),
+        (Some(msg), AssetInfoValidated::Native(denom)) => Ok(WasmMsg::Execute {
+            contract_addr: fee_burn.target.to_string(),
+            msg: msg.clone(),
+            funds: vec![coin(fee.amount.u128(), denom)],
+        }
+        .into()),
+        (Some(msg), AssetInfoValidated::Token(token)) => Ok(wasm_execute(
+            token,
+            &Cw20ExecuteMsg::Send {
+                contract: fee_burn.target.to_string(),
+                amount: fee.amount,
+                msg: msg.clone(),
+            },
+            vec![],
+        )?
+        .into()),
+    }
+}
+
 /// Calculates the amount of fees the protocol gets according to specified pair parameters.
 /// Returns a [`None`] if the protocol fee is zero, otherwise returns a [`Asset`] struct with the specified attributes.
 ///
@@ -1155,6 +1868,50 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             target_price,
             iterations,
         )?),
+        QueryMsg::PendingProtocolFees {} => to_binary(&query_pending_protocol_fees(deps)?),
+        QueryMsg::ProtocolLpShare {} => to_binary(&query_protocol_lp_share(deps)?),
+        QueryMsg::WithdrawSimulation {
+            amount,
+            ask_asset_info,
+        } => to_binary(&query_withdraw_simulation(
+            deps,
+            env,
+            amount,
+            ask_asset_info,
+        )?),
+        QueryMsg::Marginal { offer, ask } => to_binary(&query_marginal(deps, env, offer, ask)?),
+        QueryMsg::SwapMeetsMin {
+            offer,
+            ask,
+            min_receive,
+        } => to_binary(&query_swap_meets_min(deps, env, offer, ask, min_receive)?),
+        QueryMsg::Composition {} => to_binary(&query_composition(deps)?),
+        QueryMsg::Health {} => to_binary(&query_health(deps, env)?),
+        QueryMsg::DepositShare { assets } => to_binary(&query_deposit_share(deps, env, assets)?),
+        QueryMsg::LpForWithdrawal { desired } => {
+            to_binary(&query_lp_for_withdrawal(deps, env, desired)?)
+        }
+        QueryMsg::SlippageCurve { offer, ask, sizes } => {
+            to_binary(&query_slippage_curve(deps, env, offer, ask, sizes)?)
+        }
+        QueryMsg::ArbVsPrice {
+            offer,
+            ask,
+            external_price,
+        } => to_binary(&query_arb_vs_price(deps, env, offer, ask, external_price)?),
+        QueryMsg::SimulateBatch { requests } => {
+            to_binary(&query_simulate_batch(deps, env, requests)?)
+        }
+        QueryMsg::ValueVsHold {
+            lp_amount,
+            external_prices,
+        } => to_binary(&query_value_vs_hold(deps, lp_amount, external_prices)?),
+        QueryMsg::PegStatus { band } => to_binary(&query_peg_status(deps, env, band)?),
+        QueryMsg::PoolDebug {} => to_binary(&query_pool_debug(deps, env)?),
+        QueryMsg::Assets {} => to_binary(&query_assets(deps)?),
+        QueryMsg::PostSwapSpot { offer, ask, amount } => {
+            to_binary(&query_post_swap_spot(deps, env, offer, ask, amount)?)
+        }
     }
 }
 
@@ -1162,7 +1919,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 /// tokens currently minted in an object of type [`PoolResponse`].
 pub fn query_pool(deps: Deps) -> StdResult<PoolResponse> {
     let config = CONFIG.load(deps.storage)?;
-    let (assets, total_share) = pool_info(deps.querier, &config)?;
+    let (assets, total_share) = pool_info(deps, &config)?;
 
     let resp = PoolResponse {
         assets,
@@ -1172,19 +1929,372 @@ pub fn query_pool(deps: Deps) -> StdResult<PoolResponse> {
     Ok(resp)
 }
 
+/// Returns each asset's share of the pool, based on the amounts normalized to the same precision.
+/// The shares sum up to one, apart from rounding. If the pool is empty, all shares are zero.
+pub fn query_composition(deps: Deps) -> Result<Vec<(AssetInfoValidated, Decimal)>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pools = query_pools_decimal(deps, &config)?;
+    let total = pools
+        .iter()
+        .try_fold(Decimal256::zero(), |acc, pool| acc.checked_add(pool.amount))?;
+
+    pools
+        .into_iter()
+        .map(|pool| -> Result<_, ContractError> {
+            let share = if total.is_zero() {
+                Decimal::zero()
+            } else {
+                (pool.amount / total).try_into()?
+            };
+            Ok((pool.info, share))
+        })
+        .collect()
+}
+
+/// Compares the invariant D of the reserves with the outstanding LP supply.
+/// LP tokens are minted one to one with D initially and fees only increase D afterwards,
+/// so the supply exceeding D means some reserves are missing.
+pub fn query_health(deps: Deps, env: Env) -> StdResult<HealthResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let d = query_compute_d(deps, env)?;
+    let total_share = query_supply(&deps.querier, &config.pair_info.liquidity_token)?;
+
+    let virtual_price = if total_share.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::checked_from_ratio(d, total_share)
+            .map_err(|e| StdError::generic_err(e.to_string()))?
+    };
+    let drift = total_share.saturating_sub(d);
+
+    Ok(HealthResponse {
+        d,
+        total_share,
+        virtual_price,
+        drift,
+        healthy: drift.is_zero(),
+    })
+}
+
 /// Returns the amount of assets that could be withdrawn from the pool using a specific amount of LP tokens.
 /// The result is returned in a vector that contains objects of type [`Asset`].
 ///
 /// * **amount** is the amount of LP tokens for which we calculate associated amounts of assets.
 pub fn query_share(deps: Deps, amount: Uint128) -> StdResult<Vec<AssetValidated>> {
     let config = CONFIG.load(deps.storage)?;
-    let (pools, total_share) = pool_info(deps.querier, &config)?;
+    let (pools, total_share) = pool_info(deps, &config)?;
     let refund_assets = get_share_in_assets(&pools, amount, total_share);
 
     Ok(refund_assets)
 }
 
+/// Returns the protocol fees held by the pair for each asset of the pool.
+pub fn query_pending_protocol_fees(deps: Deps) -> StdResult<Vec<AssetValidated>> {
+    let config = CONFIG.load(deps.storage)?;
+    config
+        .pair_info
+        .asset_infos
+        .iter()
+        .map(|info| {
+            let amount = PENDING_PROTOCOL_FEES
+                .may_load(deps.storage, info)?
+                .unwrap_or_default();
+            Ok(info.with_balance(amount))
+        })
+        .collect()
+}
+
+/// Returns the LP tokens held by the factory's fee address in a [`ProtocolLpShareResponse`] object.
+pub fn query_protocol_lp_share(deps: Deps) -> StdResult<ProtocolLpShareResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let fee_address = query_factory_config(&deps.querier, &config.factory_addr)?.fee_address;
+
+    let lp_amount = match &fee_address {
+        Some(fee_address) => query_token_balance(
+            &deps.querier,
+            &config.pair_info.liquidity_token,
+            fee_address,
+        )?,
+        None => Uint128::zero(),
+    };
+    let (pools, total_share) = pool_info(deps, &config)?;
+
+    Ok(ProtocolLpShareResponse {
+        fee_address,
+        lp_amount,
+        underlying: get_share_in_assets(&pools, lp_amount, total_share),
+    })
+}
+
+/// Compares the current value of `lp_amount` LP tokens with holding the basket they were backed by
+/// at the first deposit, in a [`ValueVsHoldResponse`] object.
+///
+/// * **external_prices** the price of each pool asset, per smallest unit.
+pub fn query_value_vs_hold(
+    deps: Deps,
+    lp_amount: Uint128,
+    external_prices: Vec<(AssetInfo, Decimal)>,
+) -> StdResult<ValueVsHoldResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let basket = INITIAL_BASKET
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("No initial deposit recorded for this pool"))?;
+    let external_prices = external_prices
+        .into_iter()
+        .map(|(info, price)| Ok((info.validate(deps.api)?, price)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let value = |assets: &[AssetValidated]| -> StdResult<Uint128> {
+        assets.iter().try_fold(Uint128::zero(), |sum, asset| {
+            let price = external_prices
+                .iter()
+                .find(|(info, _)| info == &asset.info)
+                .map(|(_, price)| *price)
+                .ok_or_else(|| {
+                    StdError::generic_err(format!("Missing external price for {}", asset.info))
+                })?;
+            Ok(sum.checked_add(asset.amount * price)?)
+        })
+    };
+
+    let (pools, total_share) = pool_info(deps, &config)?;
+    let current_assets = get_share_in_assets(&pools, lp_amount, total_share);
+    let hold_assets = basket
+        .assets
+        .iter()
+        .map(|asset| {
+            asset
+                .info
+                .with_balance(asset.amount.multiply_ratio(lp_amount, basket.share))
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ValueVsHoldResponse {
+        current_value: value(&current_assets)?,
+        current_assets,
+        hold_value: value(&hold_assets)?,
+        hold_assets,
+    })
+}
+
+/// Returns the reserves in all the forms the swap math uses in a [`PoolDebugResponse`] object.
+pub fn query_pool_debug(deps: Deps, env: Env) -> StdResult<PoolDebugResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let reserves = query_pools(deps, &config)?;
+    let precisions = reserves
+        .iter()
+        .map(|pool| get_precision(deps.storage, &pool.info))
+        .collect::<StdResult<Vec<_>>>()?;
+    let decimal_reserves = reserves
+        .iter()
+        .zip(&precisions)
+        .map(|(pool, &precision)| pool.to_decimal_asset(precision))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PoolDebugResponse {
+        reserves,
+        decimal_reserves,
+        precisions,
+        greatest_precision: config.greatest_precision,
+        amp: Decimal::from_ratio(compute_current_amp(&config, &env)?, AMP_PRECISION),
+        d: query_compute_d(deps, env)?,
+    })
+}
+
+/// Returns the pool's assets together with their stored precisions
+pub fn query_assets(deps: Deps) -> StdResult<Vec<AssetPrecision>> {
+    let config = CONFIG.load(deps.storage)?;
+    config
+        .pair_info
+        .asset_infos
+        .into_iter()
+        .map(|info| {
+            let precision = get_precision(deps.storage, &info)?;
+            Ok(AssetPrecision { info, precision })
+        })
+        .collect()
+}
+
+/// Checks for every pair of pool assets whether the spot price is within `1 ± band`
+/// in a [`PegStatusResponse`] object.
+/// The spot price is scaled by the rates of both assets, so an LSD token trading at its target rate is on peg.
+pub fn query_peg_status(
+    deps: Deps,
+    env: Env,
+    band: Decimal,
+) -> Result<PegStatusResponse, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    update_target_rate(deps.querier, &mut config, &env)?;
+    let pools = query_pools_decimal(deps, &config)?;
+
+    let lower = Decimal::one().saturating_sub(band);
+    let upper = Decimal::one().saturating_add(band);
+
+    let asset_infos = &config.pair_info.asset_infos;
+    let mut pairs = vec![];
+    for (i, offer) in asset_infos.iter().enumerate() {
+        for ask in &asset_infos[i + 1..] {
+            let price = calc_spot_price(deps, &env, &config, offer, ask, &pools)?
+                * config.rate(ask)
+                / config.rate(offer);
+            pairs.push(PegStatus {
+                offer: offer.clone(),
+                ask: ask.clone(),
+                price,
+                in_band: lower <= price && price <= upper,
+            });
+        }
+    }
+
+    Ok(PegStatusResponse { pairs })
+}
+
+/// Returns the assets received for withdrawing `amount` LP tokens in a [`WithdrawSimulationResponse`] object.
+///
+/// * **ask_asset_info** if set, the amount of this asset received for a single-asset withdraw is also returned.
+pub fn query_withdraw_simulation(
+    deps: Deps,
+    env: Env,
+    amount: Uint128,
+    ask_asset_info: Option<AssetInfo>,
+) -> StdResult<WithdrawSimulationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let (pools, total_share) = pool_info(deps, &config)?;
+    let assets = get_share_in_assets(&pools, amount, total_share);
+
+    let single_asset = ask_asset_info
+        .map(|info| {
+            let info = info.validate(deps.api)?;
+            let pool = pools
+                .iter()
+                .find(|pool| pool.info == info)
+                .ok_or_else(|| StdError::generic_err("Given ask asset doesn't belong to pairs"))?;
+
+            // find the biggest amount that can be withdrawn without burning more than `amount` LP tokens
+            let mut low = Uint128::zero();
+            let mut high = pool.amount;
+            while low < high {
+                let mid = high - (high - low) / Uint128::new(2);
+                match calc_imbalanced_withdraw_burn(deps, &env, &config, &[info.with_balance(mid)])
+                {
+                    Ok(burn_amount) if burn_amount <= amount => low = mid,
+                    _ => high = mid - Uint128::one(),
+                }
+            }
+            StdResult::Ok(info.with_balance(low))
+        })
+        .transpose()?;
+
+    Ok(WithdrawSimulationResponse {
+        assets,
+        single_asset,
+    })
+}
+
+/// Returns the amount of LP tokens burned when withdrawing exactly the `desired` assets
+/// in a [`LpForWithdrawalResponse`] object.
+pub fn query_lp_for_withdrawal(
+    deps: Deps,
+    env: Env,
+    desired: Vec<Asset>,
+) -> StdResult<LpForWithdrawalResponse> {
+    let desired =
+        check_assets(deps.api, &desired).map_err(|e| StdError::generic_err(e.to_string()))?;
+    let config = CONFIG.load(deps.storage)?;
+    let (pools, total_share) = pool_info(deps, &config)?;
+
+    // the pool cannot pay out more than it holds
+    for asset in &desired {
+        if let Some(pool) = pools.iter().find(|pool| pool.info == asset.info) {
+            ensure!(
+                asset.amount < pool.amount,
+                StdError::generic_err(format!("Not enough {} in the pool", asset.info))
+            );
+        }
+    }
+
+    let lp_amount = calc_imbalanced_withdraw_burn(deps, &env, &config, &desired)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    ensure!(
+        lp_amount <= total_share,
+        StdError::generic_err("Not enough liquidity in the pool")
+    );
+
+    Ok(LpForWithdrawalResponse { lp_amount })
+}
+
+/// Returns the LP tokens minted for providing `assets` and their fraction of the LP token supply
+/// after the deposit in a [`DepositShareResponse`] object.
+pub fn query_deposit_share(
+    deps: Deps,
+    env: Env,
+    assets: Vec<Asset>,
+) -> StdResult<DepositShareResponse> {
+    let assets =
+        check_assets(deps.api, &assets).map_err(|e| StdError::generic_err(e.to_string()))?;
+    let mut config = CONFIG.load(deps.storage)?;
+    update_target_rate(deps.querier, &mut config, &env)?;
+    let (pools, total_share) = pool_info(deps, &config)?;
+
+    if let Some(asset) = assets
+        .iter()
+        .find(|asset| !config.pair_info.asset_infos.contains(&asset.info))
+    {
+        return Err(StdError::generic_err(
+            ContractError::InvalidAsset(asset.info.to_string()).to_string(),
+        ));
+    }
+
+    let assets_collection = pools
+        .iter()
+        .map(|pool| {
+            let deposit = assets
+                .iter()
+                .filter(|asset| asset.info == pool.info)
+                .map(|asset| asset.amount)
+                .sum::<Uint128>();
+            let precision = get_precision(deps.storage, &pool.info)?;
+            Ok((
+                pool.info
+                    .with_balance(deposit)
+                    .to_decimal_asset(precision)?,
+                Decimal256::with_precision(pool.amount, precision)?,
+            ))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    ensure!(
+        assets_collection
+            .iter()
+            .any(|(deposit, _)| !deposit.amount.is_zero()),
+        StdError::generic_err(ContractError::InvalidZeroAmount {}.to_string())
+    );
+
+    let lp_amount = compute_provide_share(deps, &env, &config, &assets_collection, total_share)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let lp_amount = match config.rebalance_incentive {
+        Some(incentive) if !total_share.is_zero() => {
+            let budget = REBALANCE_BUDGET.may_load(deps.storage)?.unwrap_or_default();
+            apply_rebalance_incentive(&config, incentive, &assets_collection, lp_amount, budget)
+                .map_err(|e| StdError::generic_err(e.to_string()))?
+                .0
+        }
+        _ => lp_amount,
+    };
+    // the minimum liquidity is minted on the first deposit as well
+    let supply_after = if total_share.is_zero() {
+        lp_amount + MINIMUM_LIQUIDITY_AMOUNT
+    } else {
+        total_share + lp_amount
+    };
+
+    Ok(DepositShareResponse {
+        lp_amount,
+        share_of_pool: Decimal::from_ratio(lp_amount, supply_after),
+    })
+}
+
 /// Returns information about a swap simulation in a [`SimulationResponse`] object.
+/// The swapper is not known here, so the full commission is assumed without any staker discount.
 ///
 /// * **offer_asset** is the asset to swap as well as an amount of the said asset.
 pub fn query_simulation(
@@ -1198,9 +2308,7 @@ pub fn query_simulation(
     let mut offer_asset = offer_asset.validate(deps.api)?;
     let ask_asset_info = ask_asset_info.map(|a| a.validate(deps.api)).transpose()?;
     let mut config = CONFIG.load(deps.storage)?;
-    let pools = config
-        .pair_info
-        .query_pools_decimal(&deps.querier, &config.pair_info.contract_addr)?;
+    let pools = query_pools_decimal(deps, &config)?;
 
     let referral_amount = if referral {
         let factory_config = query_factory_config(&deps.querier, &config.factory_addr)?;
@@ -1232,6 +2340,7 @@ pub fn query_simulation(
             spread_amount: Uint128::zero(),
             commission_amount: Uint128::zero(),
             referral_amount: Uint128::zero(),
+            spread_percent: Decimal::zero(),
         });
     }
 
@@ -1262,10 +2371,107 @@ pub fn query_simulation(
         spread_amount,
         commission_amount,
         referral_amount,
+        spread_percent: spread_percent(return_amount, spread_amount, commission_amount),
     })
 }
 
+/// Returns whether swapping `offer` into `ask` returns at least `min_receive` at the current
+/// pool state in a [`SwapMeetsMinResponse`] object.
+pub fn query_swap_meets_min(
+    deps: Deps,
+    env: Env,
+    offer: Asset,
+    ask: AssetInfo,
+    min_receive: Uint128,
+) -> StdResult<SwapMeetsMinResponse> {
+    let SimulationResponse { return_amount, .. } =
+        query_simulation(deps, env, offer, Some(ask), false, None)?;
+
+    Ok(SwapMeetsMinResponse {
+        meets_min: return_amount >= min_receive,
+        return_amount,
+    })
+}
+
+/// Simulates a swap of each of the given `sizes` of `offer` into `ask`,
+/// returning the results in a [`SlippageCurveResponse`] object.
+pub fn query_slippage_curve(
+    deps: Deps,
+    env: Env,
+    offer: AssetInfo,
+    ask: AssetInfo,
+    sizes: Vec<Uint128>,
+) -> StdResult<SlippageCurveResponse> {
+    if sizes.len() > MAX_SLIPPAGE_CURVE_POINTS {
+        return Err(StdError::generic_err(format!(
+            "Cannot sample more than {MAX_SLIPPAGE_CURVE_POINTS} points"
+        )));
+    }
+
+    let points = sizes
+        .into_iter()
+        .map(|amount| {
+            let SimulationResponse {
+                return_amount,
+                spread_amount,
+                ..
+            } = query_simulation(
+                deps,
+                env.clone(),
+                offer.with_balance(amount),
+                Some(ask.clone()),
+                false,
+                None,
+            )?;
+            Ok(SlippagePoint {
+                offer_amount: amount,
+                return_amount,
+                spread_amount,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SlippageCurveResponse { points })
+}
+
+/// Simulates each of the given swaps against the current pool state,
+/// returning one [`BatchSimulationResult`] per request.
+pub fn query_simulate_batch(
+    deps: Deps,
+    env: Env,
+    requests: Vec<BatchSimulationRequest>,
+) -> StdResult<Vec<BatchSimulationResult>> {
+    if requests.len() > MAX_BATCH_SIMULATIONS {
+        return Err(StdError::generic_err(format!(
+            "Cannot simulate more than {MAX_BATCH_SIMULATIONS} swaps"
+        )));
+    }
+
+    requests
+        .into_iter()
+        .map(|BatchSimulationRequest { offer, ask, amount }| {
+            let SimulationResponse {
+                return_amount,
+                spread_amount,
+                ..
+            } = query_simulation(
+                deps,
+                env.clone(),
+                offer.with_balance(amount),
+                Some(ask),
+                false,
+                None,
+            )?;
+            Ok(BatchSimulationResult {
+                return_amount,
+                spread_amount,
+            })
+        })
+        .collect()
+}
+
 /// Returns information about a reverse swap simulation in a [`ReverseSimulationResponse`] object.
+/// The swapper is not known here, so the full commission is assumed without any staker discount.
 ///
 /// * **ask_asset** is the asset to swap to as well as the desired amount of ask
 /// assets to receive from the swap.
@@ -1284,9 +2490,7 @@ pub fn query_reverse_simulation(
     let offer_asset_info = offer_asset_info.map(|a| a.validate(deps.api)).transpose()?;
 
     let mut config = CONFIG.load(deps.storage)?;
-    let pools = config
-        .pair_info
-        .query_pools_decimal(&deps.querier, &config.pair_info.contract_addr)?;
+    let pools = query_pools_decimal(deps, &config)?;
     let (offer_pool, ask_pool) =
         select_pools(offer_asset_info.as_ref(), Some(&ask_asset.info), &pools)
             .map_err(|err| StdError::generic_err(format!("{err}")))?;
@@ -1372,7 +2576,7 @@ pub fn query_reverse_simulation(
 /// Returns information about cumulative prices for the assets in the pool using a [`CumulativePricesResponse`] object.
 pub fn query_cumulative_prices(deps: Deps, env: Env) -> StdResult<CumulativePricesResponse> {
     let mut config = CONFIG.load(deps.storage)?;
-    let (assets, total_share) = pool_info(deps.querier, &config)?;
+    let (assets, total_share) = pool_info(deps, &config)?;
     let decimal_assets = assets
         .iter()
         .cloned()
@@ -1415,7 +2619,7 @@ pub fn query_spot_price(
     let to = ask.validate(deps.api)?;
 
     let config = CONFIG.load(deps.storage)?;
-    let (assets, _) = pool_info(deps.querier, &config)?;
+    let (assets, _) = pool_info(deps, &config)?;
     let decimal_assets = assets
         .iter()
         .cloned()
@@ -1429,6 +2633,115 @@ pub fn query_spot_price(
     Ok(SpotPriceResponse { price })
 }
 
+/// Returns the spot price of `offer` in terms of `ask` after swapping `amount` of `offer` into `ask`
+/// in a [`SpotPriceResponse`] object.
+pub fn query_post_swap_spot(
+    deps: Deps,
+    env: Env,
+    offer: AssetInfo,
+    ask: AssetInfo,
+    amount: Uint128,
+) -> Result<SpotPriceResponse, ContractError> {
+    let offer = offer.validate(deps.api)?;
+    let ask = ask.validate(deps.api)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let pools = query_pools(deps, &config)?;
+    let decimal_pools = query_pools_decimal(deps, &config)?;
+    let (offer_pool, ask_pool) = select_pools(Some(&offer), Some(&ask), &decimal_pools)?;
+    check_swap_parameters(pools.iter().map(|pool| pool.amount).collect(), amount)?;
+
+    // simulate the swap the same way an actual swap does
+    update_target_rate(deps.querier, &mut config, &env)?;
+    let offer_precision = get_precision(deps.storage, &offer)?;
+    let ask_precision = get_precision(deps.storage, &ask)?;
+    let SwapResult { return_amount, .. } = compute_swap(
+        deps.storage,
+        &env,
+        &config,
+        &offer
+            .with_balance(amount)
+            .to_decimal_asset(offer_precision)?,
+        &offer_pool,
+        &ask_pool,
+        &decimal_pools,
+    )?;
+    let commission_amount = config
+        .pair_info
+        .fee_config
+        .total_fee_rate()
+        .checked_mul_uint128(return_amount)?;
+    let factory_config = query_factory_config(&deps.querier, &config.factory_addr)?;
+    let protocol_fee_amount = factory_config
+        .fee_address
+        .and_then(|_| {
+            calculate_protocol_fee(
+                &ask,
+                commission_amount,
+                config.pair_info.fee_config.protocol_fee_rate(),
+            )
+        })
+        .map(|fee| fee.amount)
+        .unwrap_or_default();
+    let fee_burn_amount = config
+        .fee_burn
+        .as_ref()
+        .map_or_else(Uint128::zero, |fee_burn| {
+            (commission_amount - protocol_fee_amount) * fee_burn.fraction
+        });
+    // the trader gets the return amount without the commission, part of which also leaves the pool
+    let ask_outflow = return_amount - commission_amount + protocol_fee_amount + fee_burn_amount;
+
+    let new_pools = decimal_pools
+        .into_iter()
+        .map(|mut pool| -> StdResult<DecimalAsset> {
+            if pool.info == offer {
+                pool.amount = pool
+                    .amount
+                    .checked_add(Decimal256::with_precision(amount, offer_precision)?)?;
+            } else if pool.info == ask {
+                pool.amount = pool
+                    .amount
+                    .checked_sub(Decimal256::with_precision(ask_outflow, ask_precision)?)?;
+            }
+            Ok(pool)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let price = calc_spot_price(deps, &env, &config, &offer, &ask, &new_pools)?;
+    Ok(SpotPriceResponse { price })
+}
+
+/// Returns the first and second derivatives of the swap curve at the current balances
+/// in a [`MarginalResponse`] object.
+pub fn query_marginal(
+    deps: Deps,
+    env: Env,
+    offer: AssetInfo,
+    ask: AssetInfo,
+) -> Result<MarginalResponse, ContractError> {
+    let from = offer.validate(deps.api)?;
+    let to = ask.validate(deps.api)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let pools = query_pools_decimal(deps, &config)?;
+    let (offer_pool, ask_pool) = select_pools(Some(&from), Some(&to), &pools)?;
+
+    let amp = compute_current_amp(&config, &env)?;
+    let (first_derivative, second_derivative) = compute_marginal(
+        amp,
+        &offer_pool,
+        &ask_pool,
+        config.greatest_precision,
+        &config,
+    )?;
+
+    Ok(MarginalResponse {
+        first_derivative: first_derivative.try_into()?,
+        second_derivative: second_derivative.try_into()?,
+    })
+}
+
 /// Returns information about cumulative prices for the assets in the pool using a [`CumulativePricesResponse`] object.
 pub fn query_spot_price_prediction(
     deps: Deps,
@@ -1456,7 +2769,7 @@ pub fn query_spot_price_prediction(
     );
 
     let config = CONFIG.load(deps.storage)?;
-    let (assets, _) = pool_info(deps.querier, &config)?;
+    let (assets, _) = pool_info(deps, &config)?;
     let decimal_assets = assets
         .iter()
         .cloned()
@@ -1480,19 +2793,112 @@ pub fn query_spot_price_prediction(
     Ok(SpotPricePredictionResponse { trade })
 }
 
+/// Returns the swap that brings the spot price of `offer` in terms of `ask` to `external_price`
+/// in an [`ArbVsPriceResponse`] object.
+pub fn query_arb_vs_price(
+    deps: Deps,
+    env: Env,
+    offer: AssetInfo,
+    ask: AssetInfo,
+    external_price: Decimal,
+) -> Result<ArbVsPriceResponse, ContractError> {
+    let offer = offer.validate(deps.api)?;
+    let ask = ask.validate(deps.api)?;
+    let inverse_price = external_price
+        .inv()
+        .ok_or(ContractError::SpotPriceInvalidTargetPrice {})?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let pools = query_pools(deps, &config)?;
+    let decimal_pools = query_pools_decimal(deps, &config)?;
+
+    let spot_price = calc_spot_price(deps, &env, &config, &offer, &ask, &decimal_pools)?;
+    // the pool pays too much for `offer` -> sell it, too little -> sell `ask` for it
+    let (sell, buy, target_price) = match spot_price.cmp(&external_price) {
+        Ordering::Greater => (offer, ask, external_price),
+        Ordering::Less => (ask, offer, inverse_price),
+        Ordering::Equal => {
+            return Ok(ArbVsPriceResponse {
+                spot_price,
+                trade: None,
+            })
+        }
+    };
+
+    // the pool can never take more than its own balance without moving the price past any target
+    let max_trade = pools
+        .iter()
+        .find(|pool| pool.info == sell)
+        .map(|pool| pool.amount)
+        .ok_or(ContractError::AssetMismatch {})?;
+    let trade = find_spot_price(
+        deps,
+        &env,
+        &config,
+        sell.clone(),
+        buy,
+        decimal_pools,
+        max_trade,
+        target_price,
+        ARB_ITERATIONS,
+    )?;
+
+    Ok(ArbVsPriceResponse {
+        spot_price,
+        trade: trade.map(|amount| sell.with_balance(amount)),
+    })
+}
+
 /// Returns the total amount of assets in the pool as well as the total amount of LP tokens currently minted.
-pub fn pool_info(
-    querier: QuerierWrapper,
-    config: &Config,
-) -> StdResult<(Vec<AssetValidated>, Uint128)> {
-    let pools = config
-        .pair_info
-        .query_pools(&querier, &config.pair_info.contract_addr)?;
-    let total_share = query_supply(&querier, &config.pair_info.liquidity_token)?;
+pub fn pool_info(deps: Deps, config: &Config) -> StdResult<(Vec<AssetValidated>, Uint128)> {
+    let pools = query_pools(deps, config)?;
+    let total_share = query_supply(&deps.querier, &config.pair_info.liquidity_token)?;
 
     Ok((pools, total_share))
 }
 
+/// Returns the amounts of assets in the pool.
+/// Protocol fees held by the pair until they are collected and unsent withdrawal refunds
+/// are not part of the pool.
+fn query_pools(deps: Deps, config: &Config) -> StdResult<Vec<AssetValidated>> {
+    config
+        .pair_info
+        .query_pools(&deps.querier, &config.pair_info.contract_addr)?
+        .into_iter()
+        .map(|mut pool| {
+            let pending_fees = PENDING_PROTOCOL_FEES
+                .may_load(deps.storage, &pool.info)?
+                .unwrap_or_default();
+            let unsent_refunds = TOTAL_UNSENT_REFUNDS
+                .may_load(deps.storage, &pool.info)?
+                .unwrap_or_default();
+            pool.amount = pool.amount.checked_sub(pending_fees + unsent_refunds)?;
+            Ok(pool)
+        })
+        .collect()
+}
+
+/// Same as [`query_pools`], but returns the amounts in decimal.
+fn query_pools_decimal(deps: Deps, config: &Config) -> StdResult<Vec<DecimalAsset>> {
+    query_pools(deps, config)?
+        .into_iter()
+        .map(|pool| {
+            let precision = get_precision(deps.storage, &pool.info)?;
+            pool.to_decimal_asset(precision)
+        })
+        .collect()
+}
+
+/// Checks that the sender is the pair owner, falling back to the factory owner if none is set.
+fn assert_owner(deps: Deps, config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    let owner = match &config.owner {
+        Some(owner) => owner.clone(),
+        None => query_factory_config(&deps.querier, &config.factory_addr)?.owner,
+    };
+    ensure!(*sender == owner, ContractError::Unauthorized {});
+    Ok(())
+}
+
 /// Updates the pool configuration with the specified parameters in the `params` variable.
 ///
 /// * **params** new parameter values.
@@ -1503,17 +2909,7 @@ pub fn update_config(
     params: Binary,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let factory_config = query_factory_config(&deps.querier, &config.factory_addr)?;
-
-    if info.sender
-        != if let Some(ref owner) = config.owner {
-            owner.to_owned()
-        } else {
-            factory_config.owner
-        }
-    {
-        return Err(ContractError::Unauthorized {});
-    }
+    assert_owner(deps.as_ref(), &config, &info.sender)?;
 
     match from_binary::<StablePoolUpdateParams>(&params)? {
         StablePoolUpdateParams::StartChangingAmp {
@@ -1521,11 +2917,130 @@ pub fn update_config(
             next_amp_time,
         } => start_changing_amp(config, deps, env, next_amp, next_amp_time)?,
         StablePoolUpdateParams::StopChangingAmp {} => stop_changing_amp(config, deps, env)?,
+        StablePoolUpdateParams::UpdateFeeBurn { fee_burn } => {
+            update_fee_burn(config, deps, fee_burn)?
+        }
+        StablePoolUpdateParams::UpdateHoldProtocolFees { hold } => CONFIG.save(
+            deps.storage,
+            &Config {
+                hold_protocol_fees: hold,
+                ..config
+            },
+        )?,
+        StablePoolUpdateParams::UpdateFeeOnTransfer {
+            token,
+            fee_on_transfer,
+        } => {
+            let token = deps.api.addr_validate(&token)?;
+            check_cw20_in_pool(&config.pair_info.asset_infos, &token)?;
+            let mut tokens = FEE_ON_TRANSFER_TOKENS
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+            tokens.retain(|t| *t != token);
+            if fee_on_transfer {
+                tokens.push(token);
+            }
+            FEE_ON_TRANSFER_TOKENS.save(deps.storage, &tokens)?
+        }
+        StablePoolUpdateParams::UpdateMaxBlockPriceMove {
+            max_block_price_move,
+        } => {
+            ensure!(
+                max_block_price_move != Some(Decimal::zero()),
+                ContractError::InvalidMaxBlockPriceMove {}
+            );
+            CONFIG.save(
+                deps.storage,
+                &Config {
+                    max_block_price_move,
+                    ..config
+                },
+            )?
+        }
+        StablePoolUpdateParams::UpdateStakerFeeDiscounts { mut tiers } => {
+            tiers.sort_by_key(|tier| tier.min_staked);
+            ensure!(
+                tiers.iter().all(|tier| tier.discount <= Decimal::one())
+                    && tiers
+                        .windows(2)
+                        .all(|pair| pair[0].min_staked != pair[1].min_staked),
+                ContractError::InvalidStakerFeeDiscount {}
+            );
+            CONFIG.save(
+                deps.storage,
+                &Config {
+                    staker_fee_discounts: tiers,
+                    ..config
+                },
+            )?
+        }
+        StablePoolUpdateParams::UpdateProvideLock { provide_lock } => CONFIG.save(
+            deps.storage,
+            &Config {
+                provide_lock,
+                ..config
+            },
+        )?,
+        StablePoolUpdateParams::UpdateMinReserves { min_reserves } => {
+            let min_reserves = check_assets(deps.api, &min_reserves)?;
+            if let Some(reserve) = min_reserves
+                .iter()
+                .find(|reserve| !config.pair_info.asset_infos.contains(&reserve.info))
+            {
+                return Err(ContractError::InvalidAsset(reserve.info.to_string()));
+            }
+            CONFIG.save(
+                deps.storage,
+                &Config {
+                    min_reserves,
+                    ..config
+                },
+            )?
+        }
+        StablePoolUpdateParams::UpdateLpWhitelist { lp_whitelist } => {
+            let lp_whitelist = lp_whitelist
+                .map(|whitelist| validate_addresses(deps.api, &whitelist))
+                .transpose()?;
+            CONFIG.save(
+                deps.storage,
+                &Config {
+                    lp_whitelist,
+                    ..config
+                },
+            )?
+        }
+        StablePoolUpdateParams::UpdateRebalanceIncentive {
+            rebalance_incentive,
+        } => {
+            ensure!(
+                rebalance_incentive.map_or(true, |incentive| !incentive.is_zero()
+                    && incentive <= Decimal::one()),
+                ContractError::InvalidRebalanceIncentive {}
+            );
+            CONFIG.save(
+                deps.storage,
+                &Config {
+                    rebalance_incentive,
+                    ..config
+                },
+            )?
+        }
     }
 
     Ok(Response::default())
 }
 
+/// Checks that `amp` is within `[MIN_AMP, MAX_AMP]`, so `calc_y` converges and the pool is not degenerate.
+fn validate_amp(amp: u64) -> Result<(), ContractError> {
+    if !(MIN_AMP..=MAX_AMP).contains(&amp) {
+        return Err(ContractError::IncorrectAmp {
+            min_amp: MIN_AMP,
+            max_amp: MAX_AMP,
+        });
+    }
+    Ok(())
+}
+
 /// Start changing the AMP value.
 ///
 /// * **next_amp** new value for AMP.
@@ -1538,9 +3053,7 @@ fn start_changing_amp(
     next_amp: u64,
     next_amp_time: u64,
 ) -> Result<(), ContractError> {
-    if next_amp == 0 || next_amp > MAX_AMP {
-        return Err(ContractError::IncorrectAmp { max_amp: MAX_AMP });
-    }
+    validate_amp(next_amp)?;
 
     let current_amp = compute_current_amp(&config, &env)?.u64();
 
@@ -1574,6 +3087,33 @@ fn start_changing_amp(
     Ok(())
 }
 
+/// Sets or removes the fee burn configuration.
+///
+/// * **fee_burn** new fee burn parameters. `None` disables the fee burn.
+fn update_fee_burn(
+    mut config: Config,
+    deps: DepsMut,
+    fee_burn: Option<FeeBurnParams>,
+) -> Result<(), ContractError> {
+    config.fee_burn = fee_burn
+        .map(|params| -> Result<_, ContractError> {
+            ensure!(
+                params.fraction <= Decimal::one(),
+                ContractError::InvalidFeeBurnFraction {}
+            );
+            Ok(FeeBurn {
+                fraction: params.fraction,
+                target: deps.api.addr_validate(&params.target)?,
+                msg: params.msg,
+            })
+        })
+        .transpose()?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(())
+}
+
 /// Stop changing the AMP value.
 fn stop_changing_amp(mut config: Config, deps: DepsMut, env: Env) -> StdResult<()> {
     let current_amp = compute_current_amp(&config, &env)?;
@@ -1595,9 +3135,7 @@ fn query_compute_d(deps: Deps, env: Env) -> StdResult<Uint128> {
     let config = CONFIG.load(deps.storage)?;
 
     let amp = compute_current_amp(&config, &env)?;
-    let pools = config
-        .pair_info
-        .query_pools_decimal(&deps.querier, env.contract.address)?
+    let pools = query_pools_decimal(deps, &config)?
         .into_iter()
         .map(|pool| pool.amount)
         .collect::<Vec<_>>();
@@ -1614,11 +3152,16 @@ fn update_target_rate(
     config: &mut Config,
     env: &Env,
 ) -> StdResult<bool> {
-    if let Some(lsd) = &mut config.lsd {
-        let now = env.block.time.seconds();
+    let now = env.block.time.seconds();
+    let mut updated = false;
+    for lsd in config
+        .lsd
+        .iter_mut()
+        .chain(config.rate_providers.iter_mut())
+    {
         if now < lsd.last_target_query + lsd.target_rate_epoch {
             // target rate is up to date
-            return Ok(false);
+            continue;
         }
 
         let response: TargetValueResponse =
@@ -1626,9 +3169,7 @@ fn update_target_rate(
 
         lsd.target_rate = response.target_value;
         lsd.last_target_query = now;
-
-        Ok(true)
-    } else {
-        Ok(false)
+        updated = true;
     }
+    Ok(updated)
 }
@@ -6,6 +6,7 @@ use wyndex::asset::{AssetInfoValidated, Decimal256Ext, DecimalAsset};
 /// The maximum number of calculation steps for Newton's method.
 const ITERATIONS: u8 = 32;
 
+pub const MIN_AMP: u64 = 1;
 pub const MAX_AMP: u64 = 1_000_000;
 pub const MAX_AMP_CHANGE: u64 = 10;
 pub const MIN_AMP_CHANGING_TIME: u64 = 86400;
@@ -98,9 +99,7 @@ pub(crate) fn calc_y(
         })
         .collect_vec();
 
-    if config.is_lsd(&from_asset.info) {
-        new_amount *= Decimal256::from(config.target_rate());
-    }
+    new_amount *= Decimal256::from(config.rate(&from_asset.info));
 
     let n_coins = Uint64::from(pools.len() as u8);
     let ann = Uint256::from(amp.checked_mul(n_coins)?.u64() / AMP_PRECISION);
@@ -147,36 +146,76 @@ pub(crate) fn calc_y(
     Err(StdError::generic_err("y is not converging"))
 }
 
-/// Applies the target rate to the amount if the asset is the LSD token.
-pub(crate) fn apply_rate(asset: &AssetInfoValidated, amount: Uint128, config: &Config) -> Uint128 {
-    if config.is_lsd(asset) {
-        amount * config.target_rate()
-    } else {
-        amount
+/// Computes the first and second derivatives of the swap curve `y(x)` at the current balances,
+/// where `x` is the `offer` pool and `y` is the `ask` pool.
+/// Returns `-dy/dx` and `d²y/dx²`, both in whole units of the respective assets.
+///
+/// * **Equation**
+///
+/// For two coins the invariant is `F(x, y) = Ann * (x + y) + D - Ann * D - D**3 / (4 * x * y) = 0`,
+/// so implicit differentiation gives `-dy/dx = F_x / F_y` and
+/// `d²y/dx² = -(F_xx * F_y**2 - 2 * F_xy * F_x * F_y + F_yy * F_x**2) / F_y**3`.
+pub(crate) fn compute_marginal(
+    amp: Uint64,
+    offer_pool: &DecimalAsset,
+    ask_pool: &DecimalAsset,
+    greatest_precision: u8,
+    config: &Config,
+) -> StdResult<(Decimal256, Decimal256)> {
+    let x = apply_rate_decimal(&offer_pool.info, offer_pool.amount, config);
+    let y = apply_rate_decimal(&ask_pool.info, ask_pool.amount, config);
+    if x.is_zero() || y.is_zero() {
+        return Err(StdError::generic_err("The pool is empty"));
     }
+
+    let d = compute_d(amp, &[x, y], greatest_precision)?;
+    let ann = Decimal256::from_ratio(amp.checked_mul(Uint64::new(2))?.u64(), AMP_PRECISION);
+    let two = Decimal256::from_integer(2u8);
+    let four = Decimal256::from_integer(4u8);
+
+    // a = D / x, b = D / y keep the intermediate values close to 1
+    let a = d / x;
+    let b = d / y;
+    // F_x = Ann + D**3 / (4 * x**2 * y), F_y = Ann + D**3 / (4 * x * y**2)
+    let f_x = ann + a * a * b / four;
+    let f_y = ann + a * b * b / four;
+    let first = f_x / f_y;
+
+    // with F_xx = -a**3 * b / (2 * D), F_xy = -a**2 * b**2 / (4 * D), F_yy = -a * b**3 / (2 * D)
+    // the second derivative simplifies to
+    // a * b * ((a * F_y)**2 - a * F_y * b * F_x + (b * F_x)**2) / (2 * D * F_y**3)
+    let a_f_y = a * f_y;
+    let b_f_x = b * f_x;
+    let curvature = a_f_y * a_f_y + b_f_x * b_f_x - a_f_y * b_f_x;
+    let second = a * b * curvature / (two * d * f_y * f_y * f_y);
+
+    // convert back from rate-adjusted amounts to actual amounts
+    let offer_rate = apply_rate_decimal(&offer_pool.info, Decimal256::one(), config);
+    let ask_rate = apply_rate_decimal(&ask_pool.info, Decimal256::one(), config);
+    Ok((
+        first * offer_rate / ask_rate,
+        second * offer_rate * offer_rate / ask_rate,
+    ))
+}
+
+/// Applies the asset's target rate to the amount.
+pub(crate) fn apply_rate(asset: &AssetInfoValidated, amount: Uint128, config: &Config) -> Uint128 {
+    amount * config.rate(asset)
 }
 
-/// Applies the target rate to the amount if the asset is the LSD token.
+/// Applies the asset's target rate to the amount.
 pub(crate) fn apply_rate_decimal(
     asset: &AssetInfoValidated,
     amount: Decimal256,
     config: &Config,
 ) -> Decimal256 {
-    if config.is_lsd(asset) {
-        amount * Decimal256::from(config.target_rate())
-    } else {
-        amount
-    }
+    amount * Decimal256::from(config.rate(asset))
 }
 
 fn inverse_rate(to: &AssetInfoValidated, y: Uint128, config: &Config) -> Uint128 {
-    if config.is_lsd(to) {
-        // y / target_rate
-        let t = config.target_rate();
-        y.multiply_ratio(t.denominator(), t.numerator())
-    } else {
-        y
-    }
+    // y / target_rate
+    let t = config.rate(to);
+    y.multiply_ratio(t.denominator(), t.numerator())
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Decimal;
+use cosmwasm_std::{Decimal, Uint128};
 
 /// The target query implemented by the lsd hub contract (or any other oracle contract).
 #[cw_serde]
@@ -9,6 +9,27 @@ pub enum TargetQuery {
     TargetValue {},
 }
 
+/// The query of the staking contract used to look up the LP tokens a swapper has bonded.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum StakeQuery {
+    #[returns(AllStakedResponse)]
+    AllStaked { address: String },
+}
+
+#[cw_serde]
+pub struct AllStakedResponse {
+    pub stakes: Vec<StakedResponse>,
+}
+
+#[cw_serde]
+pub struct StakedResponse {
+    pub stake: Uint128,
+    pub total_locked: Uint128,
+    pub unbonding_period: u64,
+    pub cw20_contract: String,
+}
+
 #[cw_serde]
 pub struct TargetValueResponse {
     /// Current exchange rate between the LSD token and the underlying native token minus liquidity discount
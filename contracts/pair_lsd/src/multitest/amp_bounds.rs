@@ -0,0 +1,69 @@
+use wyndex::{asset::AssetInfo, factory::PairType, pair::StablePoolParams};
+
+use super::suite::SuiteBuilder;
+use crate::math::{MAX_AMP, MIN_AMP};
+
+fn params(amp: u64) -> StablePoolParams {
+    StablePoolParams {
+        amp,
+        owner: None,
+        lsd: None,
+        lp_token_symbol: None,
+        lp_token_decimals: None,
+        rate_providers: vec![],
+    }
+}
+
+fn native(denom: &str) -> AssetInfo {
+    AssetInfo::Native(denom.to_string())
+}
+
+#[test]
+fn amp_at_bounds_is_accepted() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner.clone();
+
+    suite
+        .create_pair(
+            &owner,
+            PairType::Lsd {},
+            Some(params(MIN_AMP)),
+            &[native("juno"), native("uusd")],
+        )
+        .unwrap();
+    suite
+        .create_pair(
+            &owner,
+            PairType::Lsd {},
+            Some(params(MAX_AMP)),
+            &[native("juno"), native("uatom")],
+        )
+        .unwrap();
+}
+
+#[test]
+fn amp_out_of_bounds_is_rejected() {
+    let mut suite = SuiteBuilder::new().build();
+    let owner = suite.owner.clone();
+    let expected = format!("Amp coefficient must be between {MIN_AMP} and {MAX_AMP}");
+
+    let err = suite
+        .create_pair(
+            &owner,
+            PairType::Lsd {},
+            Some(params(MIN_AMP - 1)),
+            &[native("juno"), native("uusd")],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), expected);
+
+    let err = suite
+        .create_pair(
+            &owner,
+            PairType::Lsd {},
+            Some(params(MAX_AMP + 1)),
+            &[native("juno"), native("uusd")],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), expected);
+}
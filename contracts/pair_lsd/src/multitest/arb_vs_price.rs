@@ -0,0 +1,101 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt, AssetInfoValidated},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+const TRADER: &str = "trader";
+
+fn balanced_pair(suite: &mut Suite) -> Addr {
+    suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap()
+}
+
+#[test]
+fn suggested_trade_restores_external_price() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(
+            TRADER,
+            &[
+                coin(300_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .build();
+    let pair = balanced_pair(&mut suite);
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    // push the pool off-price by selling a lot of juno
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(300_000_000_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+    let spot_price = suite
+        .query_spot_price(&pair, &juno_info, &uusd_info)
+        .unwrap();
+    assert!(spot_price < Decimal::one());
+
+    // juno is cheap in the pool, so uusd should be sold to it
+    let res = suite
+        .query_arb_vs_price(&pair, &juno_info, &uusd_info, Decimal::one())
+        .unwrap();
+    assert_eq!(res.spot_price, spot_price);
+    let trade = res.trade.unwrap();
+    assert_eq!(trade.info, AssetInfoValidated::Native("uusd".to_string()));
+
+    // executing the suggested trade brings the spot price close to the external price
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            uusd_info.with_balance(trade.amount),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+    let spot_price = suite
+        .query_spot_price(&pair, &juno_info, &uusd_info)
+        .unwrap();
+    assert!(spot_price > Decimal::permille(995), "{spot_price}");
+    assert!(spot_price < Decimal::permille(1005), "{spot_price}");
+
+    // the other way around, the pool now pays too much for juno
+    let res = suite
+        .query_arb_vs_price(&pair, &juno_info, &uusd_info, Decimal::percent(90))
+        .unwrap();
+    assert_eq!(
+        res.trade.unwrap().info,
+        AssetInfoValidated::Native("juno".to_string())
+    );
+}
@@ -0,0 +1,57 @@
+use cosmwasm_std::coin;
+use wyndex::{
+    asset::{AssetInfo, AssetInfoValidated},
+    factory::PairType,
+    pair::{AssetPrecision, StablePoolParams},
+};
+
+use super::suite::SuiteBuilder;
+
+#[test]
+fn returns_assets_with_their_precisions() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let token = suite.instantiate_token_with_decimals("owner", "wyJUNO", 8);
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000),
+            (AssetInfo::Token(token.to_string()), 100_000_000_000),
+            vec![coin(1_000_000_000, "juno")],
+        )
+        .unwrap();
+
+    let assets = suite.query_assets(&pair).unwrap();
+    assert_eq!(
+        assets,
+        vec![
+            AssetPrecision {
+                info: AssetInfoValidated::Native("juno".to_string()),
+                precision: 6,
+            },
+            AssetPrecision {
+                info: AssetInfoValidated::Token(token),
+                precision: 8,
+            },
+        ]
+    );
+
+    // these are the precisions the swap math works with
+    let debug = suite.query_pool_debug(&pair).unwrap();
+    assert_eq!(
+        assets
+            .iter()
+            .map(|asset| asset.precision)
+            .collect::<Vec<_>>(),
+        debug.precisions
+    );
+    assert_eq!(debug.greatest_precision, 8);
+}
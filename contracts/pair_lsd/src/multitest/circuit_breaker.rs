@@ -0,0 +1,174 @@
+use cosmwasm_std::{coin, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams, StablePoolUpdateParams},
+};
+
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+
+#[test]
+fn single_block_price_move_is_limited() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(TRADER, &[coin(1_000_000_000_000, "juno")])
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: Some("owner".to_string()),
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000_000),
+            (uusd_info, 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    // zero would block all swaps
+    let err = suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateMaxBlockPriceMove {
+                max_block_price_move: Some(Decimal::zero()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMaxBlockPriceMove {},
+        err.downcast().unwrap()
+    );
+
+    // only the owner can set the limit
+    suite
+        .update_pair_config(
+            "random",
+            &pair,
+            StablePoolUpdateParams::UpdateMaxBlockPriceMove {
+                max_block_price_move: Some(Decimal::percent(1)),
+            },
+        )
+        .unwrap_err();
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateMaxBlockPriceMove {
+                max_block_price_move: Some(Decimal::percent(1)),
+            },
+        )
+        .unwrap();
+
+    // a single large swap moves the price by about 2%
+    let err = suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(200_000_000_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BlockPriceMoveTooLarge {
+            max: Decimal::percent(1)
+        },
+        err.downcast().unwrap()
+    );
+
+    // smaller swaps in different blocks are fine
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(50_000_000_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+    suite.next_block();
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(50_000_000_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+
+    // but they add up within the same block
+    let err = suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(100_000_000_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BlockPriceMoveTooLarge {
+            max: Decimal::percent(1)
+        },
+        err.downcast().unwrap()
+    );
+
+    // the reference is reset in the next block
+    suite.next_block();
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(50_000_000_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+
+    // removing the limit allows the large swap again
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateMaxBlockPriceMove {
+                max_block_price_move: None,
+            },
+        )
+        .unwrap();
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(200_000_000_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+}
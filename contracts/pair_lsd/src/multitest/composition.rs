@@ -0,0 +1,78 @@
+use cosmwasm_std::{coin, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoValidated},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::SuiteBuilder;
+
+#[test]
+fn composition_of_unequal_deposit() {
+    let mut suite = SuiteBuilder::new().build();
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000),
+            (AssetInfo::Native("uusd".to_string()), 3_000_000),
+            vec![coin(1_000_000, "juno"), coin(3_000_000, "uusd")],
+        )
+        .unwrap();
+
+    let composition = suite.query_composition(&pair).unwrap();
+    assert_eq!(
+        composition,
+        vec![
+            (
+                AssetInfoValidated::Native("juno".to_string()),
+                Decimal::percent(25)
+            ),
+            (
+                AssetInfoValidated::Native("uusd".to_string()),
+                Decimal::percent(75)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn composition_sums_to_one() {
+    let mut suite = SuiteBuilder::new().build();
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000),
+            (AssetInfo::Native("uusd".to_string()), 2_000_000),
+            vec![coin(1_000_000, "juno"), coin(2_000_000, "uusd")],
+        )
+        .unwrap();
+
+    let composition = suite.query_composition(&pair).unwrap();
+    assert_eq!(composition.len(), 2);
+    // thirds cannot be represented exactly, so the sum may be off by the last digit
+    let sum: Decimal = composition.iter().map(|(_, share)| *share).sum();
+    let diff = Decimal::one() - sum;
+    assert!(diff <= Decimal::raw(1), "sum was {sum}");
+    assert!(composition[0].1 < composition[1].1);
+    assert_eq!(
+        composition[0].1,
+        Decimal::from_ratio(1u128, 3u128),
+        "juno should be a third of the pool"
+    );
+}
@@ -0,0 +1,119 @@
+use cosmwasm_std::coin;
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams},
+};
+
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+
+#[test]
+fn swap_and_provide_respect_deadline() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(
+            TRADER,
+            &[coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000_000),
+            (uusd_info.clone(), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    let deadline = suite.app.block_info().time.seconds() + 100;
+
+    // before the deadline everything works
+    suite
+        .swap_with_deadline(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            uusd_info.clone(),
+            deadline,
+        )
+        .unwrap();
+    suite
+        .provide_liquidity_with_deadline(
+            TRADER,
+            &pair,
+            &[
+                juno_info.with_balance(1_000_000u128),
+                uusd_info.with_balance(1_000_000u128),
+            ],
+            &[coin(1_000_000, "juno"), coin(1_000_000, "uusd")],
+            deadline,
+        )
+        .unwrap();
+
+    // the deadline itself is still accepted
+    suite.wait(100);
+    suite
+        .swap_with_deadline(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            uusd_info.clone(),
+            deadline,
+        )
+        .unwrap();
+
+    // afterwards both are rejected
+    suite.wait(1);
+    let err = suite
+        .swap_with_deadline(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            uusd_info.clone(),
+            deadline,
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Expired { deadline }, err.downcast().unwrap());
+    let err = suite
+        .provide_liquidity_with_deadline(
+            TRADER,
+            &pair,
+            &[
+                juno_info.with_balance(1_000_000u128),
+                uusd_info.with_balance(1_000_000u128),
+            ],
+            &[coin(1_000_000, "juno"), coin(1_000_000, "uusd")],
+            deadline,
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Expired { deadline }, err.downcast().unwrap());
+
+    // without a deadline the pool keeps working
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            uusd_info,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+}
@@ -0,0 +1,76 @@
+use cosmwasm_std::{coin, Decimal, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::SuiteBuilder;
+
+const PROVIDER: &str = "provider";
+
+#[test]
+fn deposit_share_matches_pool_arithmetic() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(
+            PROVIDER,
+            &[coin(100_000_000, "juno"), coin(100_000_000, "uusd")],
+        )
+        .build();
+    let juno = AssetInfo::Native("juno".to_string());
+    let uusd = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno.clone(), 1_000_000_000),
+            (uusd.clone(), 1_000_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .unwrap();
+    let total_share = suite.query_pool(&pair).unwrap().total_share;
+    assert_eq!(total_share, Uint128::new(2_000_000_000));
+
+    // a balanced deposit of 10% of the reserves mints 10% of the supply
+    let assets = vec![
+        juno.with_balance(100_000_000u128),
+        uusd.with_balance(100_000_000u128),
+    ];
+    let res = suite.query_deposit_share(&pair, assets.clone()).unwrap();
+    assert_eq!(res.lp_amount, Uint128::new(200_000_000));
+    assert_eq!(
+        res.share_of_pool,
+        Decimal::from_ratio(res.lp_amount, total_share + res.lp_amount)
+    );
+    assert_eq!(res.share_of_pool, Decimal::from_ratio(1u128, 11u128));
+
+    // the simulation matches the actual deposit
+    suite
+        .provide_liquidity(
+            PROVIDER,
+            &pair,
+            &assets,
+            &[coin(100_000_000, "juno"), coin(100_000_000, "uusd")],
+        )
+        .unwrap();
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    assert_eq!(
+        suite.query_cw20_balance(PROVIDER, &lp_token).unwrap(),
+        res.lp_amount.u128()
+    );
+
+    // imbalanced deposits are charged a fee, so they mint less
+    let res = suite
+        .query_deposit_share(&pair, vec![juno.with_balance(200_000_000u128)])
+        .unwrap();
+    assert!(res.lp_amount < Uint128::new(200_000_000));
+}
@@ -0,0 +1,97 @@
+use cosmwasm_std::{coin, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams},
+};
+
+use super::suite::SuiteBuilder;
+
+const WHALE: &str = "whale";
+const TRADER: &str = "trader";
+const BREAKER: &str = "breaker";
+
+#[test]
+fn emergency_withdraw_while_frozen() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(TRADER, &[coin(1_000, "juno")])
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000),
+            (uusd_info, 3_000_000),
+            vec![coin(1_000_000, "juno"), coin(3_000_000, "uusd")],
+        )
+        .unwrap();
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    let lp_amount = suite.query_cw20_balance(WHALE, &lp_token).unwrap() / 2;
+    suite
+        .increase_allowance(WHALE, &lp_token, pair.as_str(), lp_amount)
+        .unwrap();
+
+    // only possible while frozen
+    let err = suite
+        .emergency_withdraw(WHALE, &pair, lp_amount)
+        .unwrap_err();
+    assert_eq!(ContractError::NotFrozen {}, err.downcast().unwrap());
+
+    suite.migrate_freeze(&pair, false, Some(BREAKER)).unwrap();
+    let err = suite.freeze_pair(WHALE, &pair, true).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite.freeze_pair(BREAKER, &pair, true).unwrap();
+
+    // normal operations are blocked
+    let err = suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::ContractFrozen {}, err.downcast().unwrap());
+
+    // the withdrawal is strictly proportional to the imbalanced pool, without any fees
+    let pool = suite.query_pool(&pair).unwrap();
+    let expected_juno = pool.assets[0].amount.u128() * lp_amount / pool.total_share.u128();
+    let expected_uusd = pool.assets[1].amount.u128() * lp_amount / pool.total_share.u128();
+    suite.emergency_withdraw(WHALE, &pair, lp_amount).unwrap();
+
+    assert_eq!(suite.query_balance(WHALE, "juno").unwrap(), expected_juno);
+    assert_eq!(suite.query_balance(WHALE, "uusd").unwrap(), expected_uusd);
+    assert_eq!(
+        suite.query_cw20_balance(WHALE, &lp_token).unwrap(),
+        lp_amount
+    );
+    let new_pool = suite.query_pool(&pair).unwrap();
+    assert_eq!(
+        new_pool.total_share.u128(),
+        pool.total_share.u128() - lp_amount
+    );
+    assert_eq!(
+        new_pool.assets[0].amount.u128(),
+        pool.assets[0].amount.u128() - expected_juno
+    );
+
+    // the allowance is used up
+    suite
+        .emergency_withdraw(WHALE, &pair, lp_amount)
+        .unwrap_err();
+}
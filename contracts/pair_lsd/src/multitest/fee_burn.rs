@@ -0,0 +1,135 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coin, Decimal, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, FeeBurnParams, StablePoolParams, StablePoolUpdateParams},
+};
+
+use super::simulation::get_attribute;
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+const BURNER: &str = "burner";
+
+#[test]
+fn swap_routes_fee_fraction_to_burn_target() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(100, 0)
+        .with_funds(TRADER, &[coin(1_000_000, "juno")])
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: Some("owner".to_string()),
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000_000),
+            (uusd_info.clone(), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    // fraction cannot be bigger than 1
+    let err = suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateFeeBurn {
+                fee_burn: Some(FeeBurnParams {
+                    fraction: Decimal::percent(101),
+                    target: BURNER.to_string(),
+                    msg: None,
+                }),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidFeeBurnFraction {},
+        err.downcast().unwrap()
+    );
+
+    // disabled by default, so nothing goes to the burner
+    let res = suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(500_000u128),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(get_attribute(&res, "fee_burn_amount"), Some("0"));
+    assert_eq!(suite.query_balance(BURNER, "uusd").unwrap(), 0);
+
+    // only the owner can enable it
+    suite
+        .update_pair_config(
+            "random",
+            &pair,
+            StablePoolUpdateParams::UpdateFeeBurn {
+                fee_burn: Some(FeeBurnParams {
+                    fraction: Decimal::percent(50),
+                    target: BURNER.to_string(),
+                    msg: None,
+                }),
+            },
+        )
+        .unwrap_err();
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateFeeBurn {
+                fee_burn: Some(FeeBurnParams {
+                    fraction: Decimal::percent(50),
+                    target: BURNER.to_string(),
+                    msg: None,
+                }),
+            },
+        )
+        .unwrap();
+
+    let balance_before = suite.query_balance(TRADER, "uusd").unwrap();
+    let res = suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(500_000u128),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    let commission = Uint128::from_str(get_attribute(&res, "commission_amount").unwrap()).unwrap();
+    let return_amount = Uint128::from_str(get_attribute(&res, "return_amount").unwrap()).unwrap();
+    assert!(!commission.is_zero());
+
+    // half of the fee went to the burner, the trader received the full return amount
+    let burned = commission * Decimal::percent(50);
+    assert_eq!(
+        get_attribute(&res, "fee_burn_amount"),
+        Some(burned.to_string().as_str())
+    );
+    assert_eq!(suite.query_balance(BURNER, "uusd").unwrap(), burned.u128());
+    assert_eq!(
+        suite.query_balance(TRADER, "uusd").unwrap(),
+        balance_before + return_amount.u128()
+    );
+}
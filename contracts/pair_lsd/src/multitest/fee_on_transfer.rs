@@ -0,0 +1,157 @@
+use cosmwasm_std::{coin, Addr, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams, StablePoolUpdateParams},
+};
+
+use super::mock_fee_on_transfer_cw20::amount_after_fee;
+use super::suite::{Suite, SuiteBuilder};
+
+const OWNER: &str = "owner";
+const WHALE: &str = "whale";
+const TRADER: &str = "trader";
+
+/// Creates a juno / fee-on-transfer token pair and provides 1_000_000 of each
+fn create_pair_and_provide(suite: &mut Suite, flag: bool) -> (Addr, Addr) {
+    let token = suite.instantiate_fee_on_transfer_token(OWNER, "FOT");
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let token_info = AssetInfo::Token(token.to_string());
+    let pair = suite
+        .create_pair(
+            OWNER,
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            &[juno_info.clone(), token_info.clone()],
+        )
+        .unwrap();
+    if flag {
+        suite
+            .update_pair_config(
+                OWNER,
+                &pair,
+                StablePoolUpdateParams::UpdateFeeOnTransfer {
+                    token: token.to_string(),
+                    fee_on_transfer: true,
+                },
+            )
+            .unwrap();
+    }
+
+    suite.mint_cw20(OWNER, &token, 1_000_000, WHALE).unwrap();
+    suite
+        .increase_allowance(WHALE, &token, pair.as_str(), 1_000_000)
+        .unwrap();
+    suite
+        .provide_liquidity(
+            WHALE,
+            &pair,
+            &[
+                juno_info.with_balance(1_000_000u128),
+                token_info.with_balance(1_000_000u128),
+            ],
+            &[coin(1_000_000, "juno")],
+        )
+        .unwrap();
+
+    (pair, token)
+}
+
+#[test]
+fn unflagged_token_breaks_lp_accounting() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(WHALE, &[coin(1_000_000, "juno")])
+        .build();
+    let (pair, _) = create_pair_and_provide(&mut suite, false);
+
+    // LP tokens were minted for more than the pair received
+    let pool = suite.query_pool(&pair).unwrap();
+    assert_eq!(pool.assets[1].amount.u128(), amount_after_fee(1_000_000));
+    assert!(!suite.query_health(&pair).unwrap().healthy);
+}
+
+#[test]
+fn deposits_use_received_amount() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(WHALE, &[coin(1_000_000, "juno")])
+        .build();
+    let (pair, _) = create_pair_and_provide(&mut suite, true);
+
+    let pool = suite.query_pool(&pair).unwrap();
+    assert_eq!(pool.assets[0].amount.u128(), 1_000_000);
+    assert_eq!(pool.assets[1].amount.u128(), amount_after_fee(1_000_000));
+
+    // LP tokens are minted for the received amount only
+    let health = suite.query_health(&pair).unwrap();
+    assert!(health.healthy);
+    assert_eq!(health.total_share, pool.total_share);
+    assert_eq!(health.d, pool.total_share);
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    assert_eq!(
+        suite.query_cw20_balance(WHALE, &lp_token).unwrap(),
+        pool.total_share.u128() - 1_000
+    );
+}
+
+#[test]
+fn swaps_use_received_amount() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(WHALE, &[coin(1_000_000, "juno")])
+        .build();
+    let (pair, token) = create_pair_and_provide(&mut suite, true);
+    let token_info = AssetInfo::Token(token.to_string());
+    let juno_info = AssetInfo::Native("juno".to_string());
+    suite.mint_cw20(OWNER, &token, 20_000, TRADER).unwrap();
+
+    // sending the token does not tell how much arrived
+    let err = suite
+        .swap(
+            &pair,
+            TRADER,
+            token_info.with_balance(10_000u128),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::FeeOnTransferSend(token.to_string()).to_string(),
+        err.root_cause().to_string()
+    );
+
+    let received = amount_after_fee(10_000);
+    let expected = suite
+        .query_simulation(&pair, token_info.with_balance(received), juno_info.clone())
+        .unwrap()
+        .return_amount;
+    let pool_before = suite.query_pool(&pair).unwrap();
+
+    suite
+        .swap_with_allowance(
+            &pair,
+            TRADER,
+            token_info.with_balance(10_000u128),
+            juno_info,
+        )
+        .unwrap();
+
+    assert_eq!(
+        Uint128::new(suite.query_balance(TRADER, "juno").unwrap()),
+        expected
+    );
+    assert_eq!(suite.query_cw20_balance(TRADER, &token).unwrap(), 10_000);
+    let pool_after = suite.query_pool(&pair).unwrap();
+    assert_eq!(
+        pool_after.assets[1].amount,
+        pool_before.assets[1].amount + Uint128::new(received)
+    );
+    assert!(suite.query_health(&pair).unwrap().healthy);
+}
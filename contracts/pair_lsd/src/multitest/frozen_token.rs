@@ -0,0 +1,111 @@
+use cosmwasm_std::{coin, Addr};
+use wyndex::{
+    asset::AssetInfo,
+    factory::PairType,
+    pair::{ContractError, StablePoolParams},
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+const OWNER: &str = "owner";
+const WHALE: &str = "whale";
+
+fn create_pair_with_freezable_token(suite: &mut Suite) -> (Addr, Addr) {
+    let token = suite.instantiate_freezable_token(OWNER, "FRZ");
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000),
+            (AssetInfo::Token(token.to_string()), 1_000_000),
+            vec![coin(1_000_000, "juno")],
+        )
+        .unwrap();
+    (pair, token)
+}
+
+fn pool_amounts(suite: &Suite, pair: &Addr) -> (u128, u128) {
+    let pool = suite.query_pool(pair).unwrap();
+    (pool.assets[0].amount.u128(), pool.assets[1].amount.u128())
+}
+
+#[test]
+fn partial_withdraw_with_frozen_token() {
+    let mut suite = SuiteBuilder::new().build();
+    let (pair, token) = create_pair_with_freezable_token(&mut suite);
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    let lp_amount = suite.query_cw20_balance(WHALE, &lp_token).unwrap();
+
+    suite.freeze_token(&token, true).unwrap();
+
+    // without the flag, the frozen token makes the whole withdrawal fail
+    suite
+        .withdraw_liquidity(WHALE, &pair, &lp_token, lp_amount / 2, vec![])
+        .unwrap_err();
+
+    let (juno_before, token_before) = pool_amounts(&suite, &pair);
+    suite
+        .withdraw_liquidity_with_skip_frozen(WHALE, &pair, &lp_token, lp_amount / 2, vec![], true)
+        .unwrap();
+    let (juno_after, token_after) = pool_amounts(&suite, &pair);
+    let juno_refund = juno_before - juno_after;
+    let token_refund = token_before - token_after;
+    assert!(juno_refund > 0);
+    assert!(token_refund > 0);
+
+    // the working asset was sent, the frozen one is kept for the withdrawer and not part of the pool anymore
+    assert_eq!(suite.query_balance(WHALE, "juno").unwrap(), juno_refund);
+    assert_eq!(suite.query_cw20_balance(WHALE, &token).unwrap(), 0);
+    assert_eq!(
+        suite.query_cw20_balance(WHALE, &lp_token).unwrap(),
+        lp_amount - lp_amount / 2
+    );
+
+    // claiming fails while the token is still frozen
+    suite.claim_unsent_refunds(WHALE, &pair).unwrap_err();
+
+    suite.freeze_token(&token, false).unwrap();
+    suite.claim_unsent_refunds(WHALE, &pair).unwrap();
+    assert_eq!(
+        suite.query_cw20_balance(WHALE, &token).unwrap(),
+        token_refund
+    );
+    // claiming does not change the pool
+    assert_eq!(pool_amounts(&suite, &pair), (juno_after, token_after));
+
+    // nothing left to claim
+    let err = suite.claim_unsent_refunds(WHALE, &pair).unwrap_err();
+    assert_eq!(ContractError::NoUnsentRefunds {}, err.downcast().unwrap());
+}
+
+#[test]
+fn skip_frozen_without_frozen_token_withdraws_everything() {
+    let mut suite = SuiteBuilder::new().build();
+    let (pair, token) = create_pair_with_freezable_token(&mut suite);
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    let lp_amount = suite.query_cw20_balance(WHALE, &lp_token).unwrap();
+
+    let (juno_before, token_before) = pool_amounts(&suite, &pair);
+    suite
+        .withdraw_liquidity_with_skip_frozen(WHALE, &pair, &lp_token, lp_amount, vec![], true)
+        .unwrap();
+    let (juno_after, token_after) = pool_amounts(&suite, &pair);
+
+    assert_eq!(
+        suite.query_balance(WHALE, "juno").unwrap(),
+        juno_before - juno_after
+    );
+    assert_eq!(
+        suite.query_cw20_balance(WHALE, &token).unwrap(),
+        token_before - token_after
+    );
+    let err = suite.claim_unsent_refunds(WHALE, &pair).unwrap_err();
+    assert_eq!(ContractError::NoUnsentRefunds {}, err.downcast().unwrap());
+}
@@ -0,0 +1,172 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use proptest::prelude::*;
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams},
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+const TRADER: &str = "trader";
+
+fn balanced_pair(suite: &mut Suite) -> Addr {
+    suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .unwrap()
+}
+
+#[test]
+fn healthy_pool_has_no_drift() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(TRADER, &[coin(1_000_000, "juno")])
+        .build();
+    let pair = balanced_pair(&mut suite);
+
+    let health = suite.query_health(&pair).unwrap();
+    assert!(health.healthy);
+    assert_eq!(health.drift, Uint128::zero());
+    assert_eq!(health.total_share, Uint128::new(2_000_000_000));
+    assert_eq!(health.d, health.total_share);
+    assert_eq!(health.virtual_price, Decimal::one());
+
+    // swap fees stay in the pool, so the virtual price grows
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            AssetInfo::Native("juno".to_string()).with_balance(1_000_000u128),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    let health = suite.query_health(&pair).unwrap();
+    assert!(health.healthy);
+    assert_eq!(health.drift, Uint128::zero());
+    assert!(health.d > health.total_share);
+    assert!(health.virtual_price > Decimal::one());
+}
+
+#[test]
+fn rounding_dust_stays_with_liquidity_providers() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(0, 0)
+        .with_funds(
+            TRADER,
+            &[coin(100_000_000, "juno"), coin(100_000_000, "uusd")],
+        )
+        .build();
+    let pair = balanced_pair(&mut suite);
+
+    // odd-sized swaps without fees, so only rounding changes the invariant
+    for i in 0..20u128 {
+        let (offer, ask) = if i % 2 == 0 {
+            ("juno", "uusd")
+        } else {
+            ("uusd", "juno")
+        };
+        suite
+            .swap(
+                &pair,
+                TRADER,
+                AssetInfo::Native(offer.to_string()).with_balance(1_000_003 + 7_919 * i),
+                AssetInfo::Native(ask.to_string()),
+                None,
+                Decimal::percent(50),
+                None,
+            )
+            .unwrap();
+    }
+
+    // the reserves are the pair's balances, so no rounding dust is left outside of them
+    let pool = suite.query_pool(&pair).unwrap();
+    let pool_before_sweep = pool.clone();
+    for asset in pool.assets {
+        assert_eq!(
+            asset.amount.u128(),
+            suite
+                .query_balance(pair.as_str(), &asset.info.to_string())
+                .unwrap()
+        );
+    }
+    // and it is credited to the LPs
+    let health = suite.query_health(&pair).unwrap();
+    assert!(health.healthy);
+    assert!(health.virtual_price >= Decimal::one());
+
+    // only the owner can sweep
+    let err = suite.sweep_dust(TRADER, &pair).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // nothing is held back from the pool, so sweeping neither changes the reserves
+    // nor harms the virtual price
+    suite.sweep_dust("owner", &pair).unwrap();
+    assert_eq!(suite.query_pool(&pair).unwrap(), pool_before_sweep);
+    let swept = suite.query_health(&pair).unwrap();
+    assert!(swept.healthy);
+    assert!(swept.virtual_price >= health.virtual_price);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn swaps_never_decrease_virtual_price(
+        swaps in prop::collection::vec((any::<bool>(), 10_000..10_000_000u128), 1..8)
+    ) {
+        let mut suite = SuiteBuilder::new()
+            .with_fees(30, 0)
+            .with_funds(
+                TRADER,
+                &[coin(100_000_000, "juno"), coin(100_000_000, "uusd")],
+            )
+            .build();
+        let pair = balanced_pair(&mut suite);
+
+        let mut virtual_price = suite.query_health(&pair).unwrap().virtual_price;
+        for (juno_to_uusd, amount) in swaps {
+            let (offer, ask) = if juno_to_uusd {
+                ("juno", "uusd")
+            } else {
+                ("uusd", "juno")
+            };
+            suite
+                .swap(
+                    &pair,
+                    TRADER,
+                    AssetInfo::Native(offer.to_string()).with_balance(amount),
+                    AssetInfo::Native(ask.to_string()),
+                    None,
+                    Decimal::percent(50),
+                    None,
+                )
+                .unwrap();
+
+            let health = suite.query_health(&pair).unwrap();
+            prop_assert!(health.healthy);
+            prop_assert!(
+                health.virtual_price >= virtual_price,
+                "virtual price decreased from {} to {}",
+                virtual_price,
+                health.virtual_price
+            );
+            virtual_price = health.virtual_price;
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{coin, Addr};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams, StablePoolUpdateParams},
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+const PROVIDER: &str = "provider";
+const TRADER: &str = "trader";
+
+fn setup() -> (Suite, Addr) {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(
+            PROVIDER,
+            &[coin(1_000_000, "juno"), coin(1_000_000, "uusd")],
+        )
+        .with_funds(TRADER, &[coin(1_000_000, "juno"), coin(1_000_000, "uusd")])
+        .build();
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: Some("owner".to_string()),
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .unwrap();
+
+    (suite, pair)
+}
+
+fn provide(suite: &mut Suite, pair: &Addr, sender: &str) -> AnyResult<()> {
+    suite.provide_liquidity(
+        sender,
+        pair,
+        &[
+            AssetInfo::Native("juno".to_string()).with_balance(100_000u128),
+            AssetInfo::Native("uusd".to_string()).with_balance(100_000u128),
+        ],
+        &[coin(100_000, "juno"), coin(100_000, "uusd")],
+    )?;
+    Ok(())
+}
+
+#[test]
+fn anyone_can_provide_without_whitelist() {
+    let (mut suite, pair) = setup();
+
+    provide(&mut suite, &pair, PROVIDER).unwrap();
+    provide(&mut suite, &pair, TRADER).unwrap();
+}
+
+#[test]
+fn whitelist_restricts_providing_but_not_swapping() {
+    let (mut suite, pair) = setup();
+
+    // only the owner can set the whitelist
+    let err = suite
+        .update_pair_config(
+            PROVIDER,
+            &pair,
+            StablePoolUpdateParams::UpdateLpWhitelist {
+                lp_whitelist: Some(vec![PROVIDER.to_string()]),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateLpWhitelist {
+                lp_whitelist: Some(vec![PROVIDER.to_string()]),
+            },
+        )
+        .unwrap();
+
+    // whitelisted addresses can provide
+    provide(&mut suite, &pair, PROVIDER).unwrap();
+
+    // others cannot
+    let err = provide(&mut suite, &pair, TRADER).unwrap_err();
+    assert_eq!(
+        ContractError::NotWhitelistedProvider(TRADER.to_string()),
+        err.downcast().unwrap()
+    );
+
+    // but they can still swap
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            AssetInfo::Native("juno".to_string()).with_balance(10_000u128),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+    // removing the whitelist opens providing to everyone again
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateLpWhitelist { lp_whitelist: None },
+        )
+        .unwrap();
+    provide(&mut suite, &pair, TRADER).unwrap();
+}
@@ -0,0 +1,100 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+/// Asserts that the values differ by at most 0.01%
+fn assert_approx_eq(expected: Decimal, actual: Decimal) {
+    let tolerance = Decimal::from_ratio(1u128, 10_000u128);
+    let diff = if expected > actual {
+        expected - actual
+    } else {
+        actual - expected
+    };
+    assert!(
+        diff <= expected * tolerance,
+        "expected {expected}, got {actual}"
+    );
+}
+
+fn imbalanced_pair(suite: &mut Suite) -> Addr {
+    suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 3_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(3_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap()
+}
+
+#[test]
+fn first_derivative_matches_tiny_swap() {
+    let mut suite = SuiteBuilder::new().build();
+    let pair = imbalanced_pair(&mut suite);
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    // swapping one whole token is tiny compared to the pool
+    let marginal = suite.query_marginal(&pair, &juno_info, &uusd_info).unwrap();
+    let spot_price = suite
+        .query_spot_price(&pair, &juno_info, &uusd_info)
+        .unwrap();
+    assert!(marginal.first_derivative > Decimal::one());
+    assert_approx_eq(marginal.first_derivative, spot_price);
+    assert!(!marginal.second_derivative.is_zero());
+
+    // the other direction is the inverse rate
+    let reverse = suite.query_marginal(&pair, &uusd_info, &juno_info).unwrap();
+    let spot_price = suite
+        .query_spot_price(&pair, &uusd_info, &juno_info)
+        .unwrap();
+    assert!(reverse.first_derivative < Decimal::one());
+    assert_approx_eq(reverse.first_derivative, spot_price);
+    assert_approx_eq(
+        Decimal::one(),
+        marginal.first_derivative * reverse.first_derivative,
+    );
+}
+
+#[test]
+fn second_derivative_predicts_price_impact() {
+    let mut suite = SuiteBuilder::new().build();
+    let pair = imbalanced_pair(&mut suite);
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let marginal = suite.query_marginal(&pair, &juno_info, &uusd_info).unwrap();
+
+    // swap 10_000 whole tokens
+    let offer = Decimal::from_ratio(10_000u128, 1u128);
+    let simulation = suite
+        .query_simulation(&pair, juno_info.with_balance(10_000_000_000u128), None)
+        .unwrap();
+    let received = Decimal::from_ratio(simulation.return_amount, 1_000_000u128);
+
+    // the first order approximation is noticeably off...
+    let linear = marginal.first_derivative * offer;
+    assert!(linear - received > received * Decimal::permille(1));
+    // ...while the second order term accounts for the price impact
+    let quadratic =
+        linear - marginal.second_derivative * offer * offer / Decimal::from_ratio(2u128, 1u128);
+    assert_approx_eq(quadratic, received);
+}
@@ -0,0 +1,126 @@
+use cosmwasm_std::{coin, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams, StablePoolUpdateParams},
+};
+
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+const WHALE: &str = "whale";
+
+#[test]
+fn withdrawals_respect_min_reserve() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(TRADER, &[coin(1_000, "juno")])
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: Some("owner".to_string()),
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000),
+            (uusd_info.clone(), 1_000_000),
+            vec![coin(1_000_000, "juno"), coin(1_000_000, "uusd")],
+        )
+        .unwrap();
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+
+    // only assets of the pair can have a minimum reserve
+    let err = suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateMinReserves {
+                min_reserves: vec![AssetInfo::Native("luna".to_string()).with_balance(2_000u128)],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAsset("luna".to_string()),
+        err.downcast().unwrap()
+    );
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateMinReserves {
+                min_reserves: vec![
+                    juno_info.with_balance(2_000u128),
+                    uusd_info.with_balance(2_000u128),
+                ],
+            },
+        )
+        .unwrap();
+
+    // withdraw down toward empty, leaving about 10_000 of each asset
+    let total_share = suite.query_pool(&pair).unwrap().total_share.u128();
+    suite
+        .withdraw_liquidity(
+            WHALE,
+            &pair,
+            &lp_token,
+            total_share - total_share / 100,
+            vec![],
+        )
+        .unwrap();
+
+    // withdrawing the rest would leave less than the minimum reserve
+    let lp_amount = suite.query_cw20_balance(WHALE, &lp_token).unwrap();
+    let err = suite
+        .withdraw_liquidity(WHALE, &pair, &lp_token, lp_amount, vec![])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BelowMinReserve("2000juno".to_string()),
+        err.downcast().unwrap()
+    );
+    // the same goes for imbalanced withdrawals
+    let err = suite
+        .withdraw_liquidity(
+            WHALE,
+            &pair,
+            &lp_token,
+            lp_amount,
+            vec![uusd_info.with_balance(8_500u128)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::BelowMinReserve("2000uusd".to_string()),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .withdraw_liquidity(WHALE, &pair, &lp_token, lp_amount / 2, vec![])
+        .unwrap();
+    let pool = suite.query_pool(&pair).unwrap();
+    assert!(pool
+        .assets
+        .iter()
+        .all(|asset| asset.amount >= Uint128::new(2_000)));
+
+    // small swaps still work on the remaining reserves
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000u128),
+            uusd_info,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(suite.query_balance(TRADER, "uusd").unwrap() > 0);
+}
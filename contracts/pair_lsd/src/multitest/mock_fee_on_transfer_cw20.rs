@@ -0,0 +1,58 @@
+use cosmwasm_std::{
+    Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+};
+use cw20_base::allowances::{execute_burn_from, execute_transfer_from};
+use cw20_base::contract::{execute_burn, execute_transfer};
+use cw20_base::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw20_base::ContractError;
+
+/// The fraction of every transfer that is burned instead of arriving at the recipient
+const TRANSFER_FEE: Decimal = Decimal::percent(1);
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw20_base::contract::instantiate(deps, env, info, msg)
+}
+
+/// Takes the fee on `Transfer` and `TransferFrom`. `Send` and `SendFrom` are not charged.
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Transfer { recipient, amount } => {
+            let fee = amount * TRANSFER_FEE;
+            if !fee.is_zero() {
+                execute_burn(deps.branch(), env.clone(), info.clone(), fee)?;
+            }
+            execute_transfer(deps, env, info, recipient, amount - fee)
+        }
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => {
+            let fee = amount * TRANSFER_FEE;
+            if !fee.is_zero() {
+                execute_burn_from(deps.branch(), env.clone(), info.clone(), owner.clone(), fee)?;
+            }
+            execute_transfer_from(deps, env, info, owner, recipient, amount - fee)
+        }
+        msg => cw20_base::contract::execute(deps, env, info, msg),
+    }
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    cw20_base::contract::query(deps, env, msg)
+}
+
+/// The amount arriving at the recipient when transferring `amount`
+pub fn amount_after_fee(amount: u128) -> u128 {
+    (Uint128::new(amount) - Uint128::new(amount) * TRANSFER_FEE).u128()
+}
@@ -0,0 +1,47 @@
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use cw20_base::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw20_base::ContractError;
+use cw_storage_plus::Item;
+
+/// Whether transfers of the token are currently rejected
+const MOCK_FROZEN: Item<bool> = Item::new("mock_frozen");
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw20_base::contract::instantiate(deps, env, info, msg)
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let moves_tokens = matches!(
+        msg,
+        ExecuteMsg::Transfer { .. }
+            | ExecuteMsg::Send { .. }
+            | ExecuteMsg::TransferFrom { .. }
+            | ExecuteMsg::SendFrom { .. }
+    );
+    if moves_tokens && MOCK_FROZEN.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(StdError::generic_err("token is frozen").into());
+    }
+
+    cw20_base::contract::execute(deps, env, info, msg)
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    cw20_base::contract::query(deps, env, msg)
+}
+
+/// Freezes or unfreezes the token
+pub fn sudo(deps: DepsMut, _env: Env, frozen: bool) -> StdResult<Response> {
+    MOCK_FROZEN.save(deps.storage, &frozen)?;
+
+    Ok(Response::new())
+}
@@ -1,4 +1,38 @@
+mod amp_bounds;
+mod arb_vs_price;
+mod assets;
+mod circuit_breaker;
+mod composition;
+mod deadline;
+mod deposit_share;
+mod emergency_withdraw;
+mod fee_burn;
+mod fee_on_transfer;
+mod frozen_token;
+mod health;
+mod lp_whitelist;
+mod marginal;
+mod min_reserve;
+mod mock_fee_on_transfer_cw20;
+mod mock_frozen_cw20;
 mod mock_hub;
+mod peg_status;
+mod pool_debug;
+mod post_swap_spot;
+mod protocol_fees;
+mod provide_lock;
+mod rate_provider;
+mod rebalance_incentive;
+mod referral;
+mod require_all_assets;
+mod simulate_batch;
 mod simulation;
+mod slippage_curve;
+mod spread;
+mod staker_fee_discount;
 mod suite;
+mod swap_meets_min;
+mod swap_recipient;
 mod target_rate;
+mod value_vs_hold;
+mod withdraw_simulation;
@@ -0,0 +1,66 @@
+use cosmwasm_std::{coin, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoValidated},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::SuiteBuilder;
+
+fn stable_params(amp: u64) -> StablePoolParams {
+    StablePoolParams {
+        amp,
+        owner: None,
+        lsd: None,
+        lp_token_symbol: None,
+        lp_token_decimals: None,
+        rate_providers: vec![],
+    }
+}
+
+#[test]
+fn balanced_pool_is_in_band() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(stable_params(100)),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .unwrap();
+
+    let res = suite.query_peg_status(&pair, Decimal::percent(1)).unwrap();
+    assert_eq!(res.pairs.len(), 1);
+    let status = &res.pairs[0];
+    assert_eq!(status.offer, AssetInfoValidated::Native("juno".to_string()));
+    assert_eq!(status.ask, AssetInfoValidated::Native("uusd".to_string()));
+    assert!(status.in_band);
+}
+
+#[test]
+fn imbalanced_pool_is_out_of_band() {
+    let mut suite = SuiteBuilder::new().build();
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(stable_params(10)),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 100_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(100_000_000, "uusd")],
+        )
+        .unwrap();
+
+    // juno is abundant, so it is cheap in terms of uusd
+    let res = suite.query_peg_status(&pair, Decimal::percent(1)).unwrap();
+    let status = &res.pairs[0];
+    assert!(status.price < Decimal::percent(99));
+    assert!(!status.in_band);
+
+    // a wide enough band still accepts it
+    let res = suite.query_peg_status(&pair, Decimal::one()).unwrap();
+    assert!(res.pairs[0].in_band);
+}
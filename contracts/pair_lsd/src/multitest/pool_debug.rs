@@ -0,0 +1,69 @@
+use cosmwasm_std::{coin, Decimal, Decimal256};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::SuiteBuilder;
+use crate::utils::adjust_precision;
+
+const TRADER: &str = "trader";
+
+#[test]
+fn decimal_reserves_match_raw_reserves() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(TRADER, &[coin(10_000_000, "juno")])
+        .build();
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .unwrap();
+    // make the reserves uneven
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            AssetInfo::Native("juno".to_string()).with_balance(10_000_000u128),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+    let debug = suite.query_pool_debug(&pair).unwrap();
+    assert_eq!(debug.reserves, suite.query_pool(&pair).unwrap().assets);
+    assert_eq!(debug.precisions, vec![6, 6]);
+    assert_eq!(debug.greatest_precision, 6);
+    assert_eq!(debug.amp, Decimal::from_atomics(100u128, 0).unwrap());
+    assert_eq!(debug.d, suite.query_health(&pair).unwrap().d);
+
+    for ((raw, decimal), &precision) in debug
+        .reserves
+        .iter()
+        .zip(&debug.decimal_reserves)
+        .zip(&debug.precisions)
+    {
+        assert_eq!(raw.info, decimal.info);
+        let adjusted = adjust_precision(raw.amount, precision, debug.greatest_precision).unwrap();
+        assert_eq!(
+            decimal.amount,
+            Decimal256::with_precision(adjusted, debug.greatest_precision as u32).unwrap()
+        );
+    }
+}
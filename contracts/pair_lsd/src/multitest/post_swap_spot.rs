@@ -0,0 +1,66 @@
+use cosmwasm_std::{coin, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+
+#[test]
+fn matches_spot_price_after_swap() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 1000)
+        .with_fee_address("fee")
+        .with_funds(TRADER, &[coin(100_000_000_000, "juno")])
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000_000),
+            (uusd_info.clone(), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    let spot_before = suite
+        .query_spot_price(&pair, &juno_info, &uusd_info)
+        .unwrap();
+    let predicted = suite
+        .query_post_swap_spot(&pair, &juno_info, &uusd_info, 100_000_000_000)
+        .unwrap();
+    // selling juno makes it cheaper
+    assert!(predicted < spot_before);
+
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(100_000_000_000u128),
+            uusd_info.clone(),
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+    let spot_after = suite
+        .query_spot_price(&pair, &juno_info, &uusd_info)
+        .unwrap();
+    assert_eq!(predicted, spot_after);
+}
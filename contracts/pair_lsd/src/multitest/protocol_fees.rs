@@ -0,0 +1,198 @@
+use cosmwasm_std::{coin, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt, AssetInfoValidated},
+    factory::PairType,
+    pair::{ContractError, PoolResponse, StablePoolParams, StablePoolUpdateParams},
+};
+
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+const FEE_ADDRESS: &str = "fee_address";
+const RECIPIENT: &str = "recipient";
+
+#[test]
+fn held_protocol_fees_can_be_collected() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(100, 5000)
+        .with_fee_address(FEE_ADDRESS)
+        .with_funds(TRADER, &[coin(2_000_000, "juno")])
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: Some("owner".to_string()),
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000_000),
+            (uusd_info, 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    // by default, protocol fees are sent out directly
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    let directly_paid = suite.query_balance(FEE_ADDRESS, "uusd").unwrap();
+    assert!(directly_paid > 0);
+    let pending = suite.query_pending_protocol_fees(&pair).unwrap();
+    assert!(pending.iter().all(|fee| fee.amount.is_zero()));
+
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateHoldProtocolFees { hold: true },
+        )
+        .unwrap();
+
+    // now they are held by the pair
+    let pool_before = suite.query_pool(&pair).unwrap();
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_balance(FEE_ADDRESS, "uusd").unwrap(),
+        directly_paid
+    );
+    let pending = suite.query_pending_protocol_fees(&pair).unwrap();
+    let uusd = AssetInfoValidated::Native("uusd".to_string());
+    let pending_uusd = pending.iter().find(|fee| fee.info == uusd).unwrap().amount;
+    assert!(!pending_uusd.is_zero());
+
+    // held fees are not part of the pool
+    let pool_after = suite.query_pool(&pair).unwrap();
+    let pool_uusd =
+        |pool: &PoolResponse| pool.assets.iter().find(|a| a.info == uusd).unwrap().amount;
+    let uusd_balance = suite.query_balance(pair.as_str(), "uusd").unwrap();
+    assert_eq!(
+        pool_uusd(&pool_after),
+        Uint128::new(uusd_balance) - pending_uusd
+    );
+    assert!(pool_uusd(&pool_after) < pool_uusd(&pool_before));
+
+    // only the fee address can collect
+    let err = suite
+        .collect_protocol_fees("random", &pair, RECIPIENT)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    suite
+        .collect_protocol_fees(FEE_ADDRESS, &pair, RECIPIENT)
+        .unwrap();
+    assert_eq!(
+        suite.query_balance(RECIPIENT, "uusd").unwrap(),
+        pending_uusd.u128()
+    );
+    let pending = suite.query_pending_protocol_fees(&pair).unwrap();
+    assert!(pending.iter().all(|fee| fee.amount.is_zero()));
+}
+
+#[test]
+fn protocol_lp_share_grows_with_fees() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(100, 5000)
+        .with_fee_address(FEE_ADDRESS)
+        .with_funds(
+            TRADER,
+            &[coin(10_000_000, "juno"), coin(10_000_000, "uusd")],
+        )
+        .with_funds(
+            FEE_ADDRESS,
+            &[coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000),
+            (uusd_info.clone(), 1_000_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .unwrap();
+
+    // the protocol does not hold any LP tokens yet
+    let share = suite.query_protocol_lp_share(&pair).unwrap();
+    assert_eq!(share.fee_address.unwrap().as_str(), FEE_ADDRESS);
+    assert_eq!(share.lp_amount, Uint128::zero());
+
+    // the protocol owns part of the pool
+    suite
+        .provide_liquidity(
+            FEE_ADDRESS,
+            &pair,
+            &[
+                juno_info.with_balance(1_000_000_000u128),
+                uusd_info.with_balance(1_000_000_000u128),
+            ],
+            &[coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .unwrap();
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    let share = suite.query_protocol_lp_share(&pair).unwrap();
+    assert_eq!(
+        share.lp_amount.u128(),
+        suite.query_cw20_balance(FEE_ADDRESS, &lp_token).unwrap()
+    );
+    let value_before: Uint128 = share.underlying.iter().map(|a| a.amount).sum();
+
+    // swap fees accrue to the LPs, including the protocol
+    for (offer, ask) in [(&juno_info, &uusd_info), (&uusd_info, &juno_info)] {
+        suite
+            .swap(
+                &pair,
+                TRADER,
+                offer.with_balance(5_000_000u128),
+                ask.clone(),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+    }
+    let share_after = suite.query_protocol_lp_share(&pair).unwrap();
+    assert_eq!(share_after.lp_amount, share.lp_amount);
+    let value_after: Uint128 = share_after.underlying.iter().map(|a| a.amount).sum();
+    assert!(value_after > value_before);
+}
@@ -0,0 +1,107 @@
+use cosmwasm_std::{coin, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams, StablePoolUpdateParams},
+};
+
+use super::suite::SuiteBuilder;
+
+const PROVIDER: &str = "provider";
+const WHALE: &str = "whale";
+
+#[test]
+fn provided_lp_tokens_are_locked() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(100, 0)
+        .with_funds(
+            PROVIDER,
+            &[coin(1_000_000, "juno"), coin(1_000_000, "uusd")],
+        )
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: Some("owner".to_string()),
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000_000),
+            (uusd_info.clone(), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+
+    // only the owner can set the lock
+    let err = suite
+        .update_pair_config(
+            PROVIDER,
+            &pair,
+            StablePoolUpdateParams::UpdateProvideLock { provide_lock: 100 },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateProvideLock { provide_lock: 100 },
+        )
+        .unwrap();
+
+    suite
+        .provide_liquidity(
+            PROVIDER,
+            &pair,
+            &[
+                juno_info.with_balance(1_000_000u128),
+                uusd_info.with_balance(1_000_000u128),
+            ],
+            &[coin(1_000_000, "juno"), coin(1_000_000, "uusd")],
+        )
+        .unwrap();
+    let lp_amount = suite.query_cw20_balance(PROVIDER, &lp_token).unwrap();
+    let unlock_at = suite.app.block_info().time.seconds() + 100;
+
+    // withdrawing right away is rejected
+    let err = suite
+        .withdraw_liquidity(PROVIDER, &pair, &lp_token, lp_amount, vec![])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::LpLocked {
+            locked: Uint128::new(lp_amount),
+            unlock_at
+        },
+        err.downcast().unwrap()
+    );
+
+    // liquidity provided before the lock was set is not affected
+    suite
+        .withdraw_liquidity(WHALE, &pair, &lp_token, 1_000, vec![])
+        .unwrap();
+
+    // still locked just before the lock expires
+    suite.wait(99);
+    suite
+        .withdraw_liquidity(PROVIDER, &pair, &lp_token, lp_amount, vec![])
+        .unwrap_err();
+
+    suite.wait(1);
+    suite
+        .withdraw_liquidity(PROVIDER, &pair, &lp_token, lp_amount, vec![])
+        .unwrap();
+    assert_eq!(suite.query_cw20_balance(PROVIDER, &lp_token).unwrap(), 0);
+    assert!(suite.query_balance(PROVIDER, "juno").unwrap() > 0);
+}
@@ -0,0 +1,153 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{assert_approx_eq, coin, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, LsdInfo, StablePoolParams},
+};
+
+use super::suite::SuiteBuilder;
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn swaps_price_around_provided_rate() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds("sender", &[coin(1_000_000, "ustjuno")])
+        .build();
+    let rate_provider = suite.instantiate_mock_hub(Decimal::from_str("1.05").unwrap());
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let stjuno_info = AssetInfo::Native("ustjuno".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 45,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![LsdInfo {
+                    asset: stjuno_info.clone(),
+                    hub: rate_provider.to_string(),
+                    target_rate_epoch: DAY,
+                }],
+            }),
+            (juno_info.clone(), 105_000_000_000),
+            (stjuno_info.clone(), 100_000_000_000),
+            vec![
+                coin(105_000_000_000, "juno"),
+                coin(100_000_000_000, "ustjuno"),
+            ],
+        )
+        .unwrap();
+
+    // 1 stJUNO is worth 1.05 JUNO instead of 1 JUNO
+    let spot = suite
+        .query_spot_price(&pair, &stjuno_info, &juno_info)
+        .unwrap();
+    assert_approx_eq!(
+        spot.atomics(),
+        Decimal::from_str("1.05").unwrap().atomics(),
+        "0.000001"
+    );
+
+    let sim = suite
+        .query_simulation(&pair, stjuno_info.with_balance(1_000_000u128), None)
+        .unwrap();
+    assert_approx_eq!(sim.return_amount.u128(), 1_050_000u128, "0.0001");
+
+    suite
+        .swap(
+            &pair,
+            "sender",
+            stjuno_info.with_balance(1_000_000u128),
+            juno_info,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_balance("sender", "juno").unwrap(),
+        sim.return_amount.u128()
+    );
+}
+
+#[test]
+fn rates_of_both_assets_are_applied() {
+    let mut suite = SuiteBuilder::new()
+        .with_initial_target_rate(Decimal::from_str("1.05").unwrap())
+        .build();
+    let rate_provider = suite.instantiate_mock_hub(Decimal::from_str("1.05").unwrap());
+
+    let wyjuno_info = AssetInfo::Native("uwyjuno".to_string());
+    let stjuno_info = AssetInfo::Native("ustjuno".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 45,
+                owner: None,
+                lsd: Some(LsdInfo {
+                    asset: wyjuno_info.clone(),
+                    hub: suite.mock_hub.to_string(),
+                    target_rate_epoch: DAY,
+                }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![LsdInfo {
+                    asset: stjuno_info.clone(),
+                    hub: rate_provider.to_string(),
+                    target_rate_epoch: DAY,
+                }],
+            }),
+            (stjuno_info.clone(), 100_000_000_000),
+            (wyjuno_info.clone(), 100_000_000_000),
+            vec![
+                coin(100_000_000_000, "ustjuno"),
+                coin(100_000_000_000, "uwyjuno"),
+            ],
+        )
+        .unwrap();
+
+    // both are pegged at the same ratio, so they trade 1:1
+    let spot = suite
+        .query_spot_price(&pair, &stjuno_info, &wyjuno_info)
+        .unwrap();
+    assert_approx_eq!(spot.atomics(), Decimal::one().atomics(), "0.000001");
+
+    // a rate can only be provided once per asset
+    let owner = suite.owner.clone();
+    let err = suite
+        .create_pair(
+            &owner,
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 45,
+                owner: None,
+                lsd: Some(LsdInfo {
+                    asset: stjuno_info.clone(),
+                    hub: suite.mock_hub.to_string(),
+                    target_rate_epoch: DAY,
+                }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![LsdInfo {
+                    asset: stjuno_info.clone(),
+                    hub: rate_provider.to_string(),
+                    target_rate_epoch: DAY,
+                }],
+            }),
+            &[stjuno_info, AssetInfo::Native("uatom".to_string())],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DoublingAssets {}.to_string(),
+        err.root_cause().to_string()
+    );
+}
@@ -0,0 +1,123 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams, StablePoolUpdateParams},
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+const OWNER: &str = "owner";
+const PROVIDER: &str = "provider";
+
+fn imbalanced_pool(rebalance_incentive: Option<Decimal>) -> (Suite, Addr, Addr) {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(
+            PROVIDER,
+            &[coin(100_000_000, "juno"), coin(100_000_000, "uusd")],
+        )
+        .build();
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: Some(OWNER.to_string()),
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 3_000_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(3_000_000_000, "uusd")],
+        )
+        .unwrap();
+    if rebalance_incentive.is_some() {
+        suite
+            .update_pair_config(
+                OWNER,
+                &pair,
+                StablePoolUpdateParams::UpdateRebalanceIncentive {
+                    rebalance_incentive,
+                },
+            )
+            .unwrap();
+    }
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    (suite, pair, lp_token)
+}
+
+/// Deposits the given amount of a single asset and returns the LP tokens minted for it
+fn deposit(suite: &mut Suite, pair: &Addr, lp_token: &Addr, denom: &str, amount: u128) -> u128 {
+    let before = suite.query_cw20_balance(PROVIDER, lp_token).unwrap();
+    let asset = AssetInfo::Native(denom.to_string()).with_balance(amount);
+    let simulated = suite
+        .query_deposit_share(pair, vec![asset.clone()])
+        .unwrap()
+        .lp_amount
+        .u128();
+    suite
+        .provide_liquidity(PROVIDER, pair, &[asset], &[coin(amount, denom)])
+        .unwrap();
+    let minted = suite.query_cw20_balance(PROVIDER, lp_token).unwrap() - before;
+    assert_eq!(minted, simulated);
+    minted
+}
+
+#[test]
+fn rebalancing_deposits_receive_withheld_lp_tokens() {
+    let (mut plain, plain_pair, plain_lp) = imbalanced_pool(None);
+    let (mut suite, pair, lp_token) = imbalanced_pool(Some(Decimal::one()));
+
+    // depositing the abundant asset makes the pool more imbalanced
+    let plain_abundant = deposit(&mut plain, &plain_pair, &plain_lp, "uusd", 100_000_000);
+    let abundant = deposit(&mut suite, &pair, &lp_token, "uusd", 100_000_000);
+    assert!(abundant < plain_abundant);
+    let withheld = plain_abundant - abundant;
+
+    // depositing the scarce asset restores the balance and receives a bonus
+    let plain_scarce = deposit(&mut plain, &plain_pair, &plain_lp, "juno", 100_000_000);
+    let scarce = deposit(&mut suite, &pair, &lp_token, "juno", 100_000_000);
+    assert!(scarce > plain_scarce);
+    // but the bonus is limited to what was withheld before
+    assert!(scarce - plain_scarce <= withheld);
+    assert!(
+        plain.query_pool(&plain_pair).unwrap().total_share
+            >= suite.query_pool(&pair).unwrap().total_share
+    );
+}
+
+#[test]
+fn no_bonus_without_budget() {
+    let (mut plain, plain_pair, plain_lp) = imbalanced_pool(None);
+    let (mut suite, pair, lp_token) = imbalanced_pool(Some(Decimal::one()));
+
+    // without anything withheld before, there is no bonus
+    let plain_scarce = deposit(&mut plain, &plain_pair, &plain_lp, "juno", 100_000_000);
+    let scarce = deposit(&mut suite, &pair, &lp_token, "juno", 100_000_000);
+    assert_eq!(scarce, plain_scarce);
+}
+
+#[test]
+fn rebalance_incentive_is_validated() {
+    let (mut suite, pair, _) = imbalanced_pool(None);
+
+    for incentive in [Decimal::zero(), Decimal::percent(101)] {
+        let err = suite
+            .update_pair_config(
+                OWNER,
+                &pair,
+                StablePoolUpdateParams::UpdateRebalanceIncentive {
+                    rebalance_incentive: Some(incentive),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidRebalanceIncentive {},
+            err.downcast().unwrap()
+        );
+    }
+}
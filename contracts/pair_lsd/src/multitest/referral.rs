@@ -0,0 +1,133 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams},
+};
+
+use super::simulation::get_attribute;
+use super::suite::{Suite, SuiteBuilder};
+
+const TRADER: &str = "trader";
+const REFERRAL: &str = "referral";
+
+fn setup() -> (Suite, Addr) {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(100, 0)
+        .with_max_referral_commission(Decimal::percent(5))
+        .with_funds(TRADER, &[coin(3_000_000, "juno")])
+        .build();
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    (suite, pair)
+}
+
+#[test]
+fn swap_pays_referral_commission() {
+    let (mut suite, pair) = setup();
+    let juno_info = AssetInfo::Native("juno".to_string());
+
+    // the referral commission is taken from the offer asset before swapping
+    let expected = suite
+        .query_simulation(&pair, juno_info.with_balance(990_000u128), None)
+        .unwrap();
+
+    let res = suite
+        .swap_with_referral(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            None,
+            None,
+            None,
+            None,
+            REFERRAL,
+            Decimal::percent(1),
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_balance(REFERRAL, "juno").unwrap(), 10_000);
+    assert_eq!(
+        get_attribute(&res, "return_amount"),
+        Some(expected.return_amount.to_string().as_str())
+    );
+    assert_eq!(
+        suite.query_balance(TRADER, "uusd").unwrap(),
+        expected.return_amount.u128()
+    );
+}
+
+#[test]
+fn swap_rejects_referral_commission_above_max() {
+    let (mut suite, pair) = setup();
+    let juno_info = AssetInfo::Native("juno".to_string());
+
+    let err = suite
+        .swap_with_referral(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            None,
+            None,
+            None,
+            None,
+            REFERRAL,
+            Decimal::percent(6),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::ReferralCommissionTooHigh {},
+        err.downcast().unwrap()
+    );
+    assert_eq!(suite.query_balance(TRADER, "juno").unwrap(), 3_000_000);
+}
+
+#[test]
+fn swap_without_referral_is_unchanged() {
+    let (mut suite, pair) = setup();
+    let juno_info = AssetInfo::Native("juno".to_string());
+
+    let expected = suite
+        .query_simulation(&pair, juno_info.with_balance(1_000_000u128), None)
+        .unwrap();
+
+    // a commission without a referral address is ignored
+    suite
+        .swap_with_referral(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            None,
+            None,
+            None,
+            None,
+            None,
+            Decimal::percent(1),
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_balance(REFERRAL, "juno").unwrap(), 0);
+    assert_eq!(
+        suite.query_balance(TRADER, "uusd").unwrap(),
+        expected.return_amount.u128()
+    );
+}
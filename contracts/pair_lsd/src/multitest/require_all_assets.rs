@@ -0,0 +1,86 @@
+use cosmwasm_std::coin;
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams},
+};
+
+use super::suite::SuiteBuilder;
+
+const PROVIDER: &str = "provider";
+
+#[test]
+fn partial_basket_rejected_only_if_all_assets_required() {
+    let mut suite = SuiteBuilder::new()
+        .with_funds(
+            PROVIDER,
+            &[
+                coin(1_000_000_000, "juno"),
+                coin(1_000_000_000, "uluna"),
+                coin(1_000_000_000, "uusd"),
+            ],
+        )
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let luna_info = AssetInfo::Native("uluna".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair(
+            "owner",
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            &[juno_info.clone(), luna_info.clone(), uusd_info.clone()],
+        )
+        .unwrap();
+
+    // a full basket is accepted
+    suite
+        .provide_liquidity_with_require_all(
+            PROVIDER,
+            &pair,
+            &[
+                juno_info.with_balance(100_000_000u128),
+                luna_info.with_balance(100_000_000u128),
+                uusd_info.with_balance(100_000_000u128),
+            ],
+            &[
+                coin(100_000_000, "juno"),
+                coin(100_000_000, "uluna"),
+                coin(100_000_000, "uusd"),
+            ],
+            true,
+        )
+        .unwrap();
+
+    let partial_basket = [
+        juno_info.with_balance(1_000_000u128),
+        uusd_info.with_balance(1_000_000u128),
+    ];
+    let partial_funds = [coin(1_000_000, "juno"), coin(1_000_000, "uusd")];
+
+    // the missing asset is reported
+    let err = suite
+        .provide_liquidity_with_require_all(PROVIDER, &pair, &partial_basket, &partial_funds, true)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::MissingAsset("uluna".to_string()),
+        err.downcast().unwrap()
+    );
+
+    // without the flag, the partial basket is accepted as an imbalanced deposit
+    let pool_before = suite.query_pool(&pair).unwrap();
+    suite
+        .provide_liquidity_with_require_all(PROVIDER, &pair, &partial_basket, &partial_funds, false)
+        .unwrap();
+    let pool_after = suite.query_pool(&pair).unwrap();
+    assert!(pool_after.total_share > pool_before.total_share);
+}
@@ -0,0 +1,93 @@
+use cosmwasm_std::{coin, Addr};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{BatchSimulationRequest, StablePoolParams, MAX_BATCH_SIMULATIONS},
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+fn imbalanced_pair(suite: &mut Suite) -> Addr {
+    suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000),
+            (AssetInfo::Native("uusd".to_string()), 2_000_000),
+            vec![coin(1_000_000, "juno"), coin(2_000_000, "uusd")],
+        )
+        .unwrap()
+}
+
+#[test]
+fn batch_matches_single_simulations() {
+    let mut suite = SuiteBuilder::new().with_fees(30, 0).build();
+    let pair = imbalanced_pair(&mut suite);
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    // both directions, multiple sizes each
+    let requests: Vec<_> = [1_000u128, 10_000, 100_000]
+        .into_iter()
+        .flat_map(|amount| {
+            [
+                BatchSimulationRequest {
+                    offer: juno_info.clone(),
+                    ask: uusd_info.clone(),
+                    amount: amount.into(),
+                },
+                BatchSimulationRequest {
+                    offer: uusd_info.clone(),
+                    ask: juno_info.clone(),
+                    amount: amount.into(),
+                },
+            ]
+        })
+        .collect();
+
+    let results = suite.query_simulate_batch(&pair, requests.clone()).unwrap();
+    assert_eq!(results.len(), requests.len());
+
+    for (request, result) in requests.into_iter().zip(results) {
+        let simulation = suite
+            .query_simulation(
+                &pair,
+                request.offer.with_balance(request.amount),
+                request.ask,
+            )
+            .unwrap();
+        assert_eq!(result.return_amount, simulation.return_amount);
+        assert_eq!(result.spread_amount, simulation.spread_amount);
+    }
+}
+
+#[test]
+fn too_many_simulations() {
+    let mut suite = SuiteBuilder::new().build();
+    let pair = imbalanced_pair(&mut suite);
+
+    let request = BatchSimulationRequest {
+        offer: AssetInfo::Native("juno".to_string()),
+        ask: AssetInfo::Native("uusd".to_string()),
+        amount: 1_000u128.into(),
+    };
+
+    suite
+        .query_simulate_batch(&pair, vec![request.clone(); MAX_BATCH_SIMULATIONS])
+        .unwrap();
+
+    let err = suite
+        .query_simulate_batch(&pair, vec![request; MAX_BATCH_SIMULATIONS + 1])
+        .unwrap_err();
+    assert!(err.to_string().contains(&format!(
+        "Cannot simulate more than {MAX_BATCH_SIMULATIONS} swaps"
+    )));
+}
@@ -59,6 +59,9 @@ fn simulate_changing_rate() {
                         hub: suite.mock_hub.to_string(),
                         target_rate_epoch: DAY,
                     }),
+                    lp_token_symbol: None,
+                    lp_token_decimals: None,
+                    rate_providers: vec![],
                 }),
                 (juno_info.clone(), 150_000_000_000_000_000),
                 (wy_juno_info.clone(), 100_000_000_000_000_000),
@@ -248,6 +251,9 @@ fn compare_to_uniswap(
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             (juno_info.clone(), juno_amount),
             (wy_juno_info.clone(), lsd_amount),
@@ -375,7 +381,7 @@ fn get_spread(swap_response: AppResponse) -> Uint128 {
     Uint128::from_str(get_attribute(&swap_response, "spread_amount").unwrap()).unwrap()
 }
 
-fn get_attribute<'a>(swap_response: &'a AppResponse, key: &str) -> Option<&'a str> {
+pub(super) fn get_attribute<'a>(swap_response: &'a AppResponse, key: &str) -> Option<&'a str> {
     swap_response
         .events
         .iter()
@@ -416,6 +422,9 @@ fn binary_search_lsd_provision(
                             hub: suite.mock_hub.to_string(),
                             target_rate_epoch: DAY,
                         }),
+                        lp_token_symbol: None,
+                        lp_token_decimals: None,
+                        rate_providers: vec![],
                     }),
                     (juno.info.clone(), juno.amount.u128()),
                     (wy_juno_info.clone(), lsd_amount.u128()),
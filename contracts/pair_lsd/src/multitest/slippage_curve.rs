@@ -0,0 +1,79 @@
+use cosmwasm_std::{coin, Addr};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{StablePoolParams, MAX_SLIPPAGE_CURVE_POINTS},
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+fn balanced_pair(suite: &mut Suite) -> Addr {
+    suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000),
+            vec![coin(1_000_000, "juno"), coin(1_000_000, "uusd")],
+        )
+        .unwrap()
+}
+
+#[test]
+fn curve_matches_simulations() {
+    let mut suite = SuiteBuilder::new().with_fees(30, 0).build();
+    let pair = balanced_pair(&mut suite);
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+    let sizes = [1_000, 10_000, 100_000, 500_000];
+
+    let curve = suite
+        .query_slippage_curve(&pair, &juno_info, &uusd_info, &sizes)
+        .unwrap();
+    assert_eq!(curve.points.len(), sizes.len());
+
+    for (point, &size) in curve.points.iter().zip(sizes.iter()) {
+        assert_eq!(point.offer_amount.u128(), size);
+        let simulation = suite
+            .query_simulation(&pair, juno_info.with_balance(size), uusd_info.clone())
+            .unwrap();
+        assert_eq!(point.return_amount, simulation.return_amount);
+        assert_eq!(point.spread_amount, simulation.spread_amount);
+    }
+
+    // bigger swaps move the price more
+    assert!(curve
+        .points
+        .windows(2)
+        .all(|w| w[0].spread_amount < w[1].spread_amount));
+}
+
+#[test]
+fn too_many_points() {
+    let mut suite = SuiteBuilder::new().build();
+    let pair = balanced_pair(&mut suite);
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let sizes = vec![1_000; MAX_SLIPPAGE_CURVE_POINTS];
+    suite
+        .query_slippage_curve(&pair, &juno_info, &uusd_info, &sizes)
+        .unwrap();
+
+    let sizes = vec![1_000; MAX_SLIPPAGE_CURVE_POINTS + 1];
+    let err = suite
+        .query_slippage_curve(&pair, &juno_info, &uusd_info, &sizes)
+        .unwrap_err();
+    assert!(err.to_string().contains(&format!(
+        "Cannot sample more than {MAX_SLIPPAGE_CURVE_POINTS} points"
+    )));
+}
@@ -0,0 +1,80 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{SimulationResponse, StablePoolParams},
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+fn create_pair(suite: &mut Suite, juno_amount: u128, uusd_amount: u128) -> Addr {
+    suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), juno_amount),
+            (AssetInfo::Native("uusd".to_string()), uusd_amount),
+            vec![coin(juno_amount, "juno"), coin(uusd_amount, "uusd")],
+        )
+        .unwrap()
+}
+
+fn assert_spread_consistent(simulation: &SimulationResponse) {
+    let spot_return =
+        simulation.return_amount + simulation.commission_amount + simulation.spread_amount;
+    assert_eq!(
+        simulation.spread_percent,
+        Decimal::from_ratio(simulation.spread_amount, spot_return)
+    );
+}
+
+#[test]
+fn spread_percent_near_zero_when_balanced() {
+    let mut suite = SuiteBuilder::new().with_fees(30, 0).build();
+    let pair = create_pair(&mut suite, 1_000_000_000, 1_000_000_000);
+
+    let simulation = suite
+        .query_simulation(
+            &pair,
+            AssetInfo::Native("juno".to_string()).with_balance(1_000u128),
+            AssetInfo::Native("uusd".to_string()),
+        )
+        .unwrap();
+
+    assert_spread_consistent(&simulation);
+    assert!(
+        simulation.spread_percent < Decimal::permille(1),
+        "spread was {}",
+        simulation.spread_percent
+    );
+}
+
+#[test]
+fn spread_percent_meaningful_when_imbalanced() {
+    let mut suite = SuiteBuilder::new().with_fees(30, 0).build();
+    let pair = create_pair(&mut suite, 1_000_000, 100_000);
+
+    let simulation = suite
+        .query_simulation(
+            &pair,
+            AssetInfo::Native("juno".to_string()).with_balance(100_000u128),
+            AssetInfo::Native("uusd".to_string()),
+        )
+        .unwrap();
+
+    assert!(!simulation.spread_amount.is_zero());
+    assert_spread_consistent(&simulation);
+    assert!(
+        simulation.spread_percent > Decimal::percent(1),
+        "spread was {}",
+        simulation.spread_percent
+    );
+    assert!(simulation.spread_percent < Decimal::one());
+}
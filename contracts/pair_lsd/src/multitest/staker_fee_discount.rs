@@ -0,0 +1,129 @@
+use cosmwasm_std::{coin, Decimal, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::{ContractError, StablePoolParams, StablePoolUpdateParams, StakerFeeDiscount},
+};
+
+use super::simulation::get_attribute;
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+const WHALE: &str = "whale";
+
+#[test]
+fn staked_lp_holder_pays_discounted_fee() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(100, 0)
+        .with_funds(TRADER, &[coin(1_000_000, "juno")])
+        .with_funds(WHALE, &[coin(1_000_000, "juno")])
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: Some("owner".to_string()),
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000_000),
+            (uusd_info.clone(), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    let tiers = vec![
+        StakerFeeDiscount {
+            min_staked: Uint128::new(1_000_000),
+            discount: Decimal::percent(50),
+        },
+        StakerFeeDiscount {
+            min_staked: Uint128::new(1_000),
+            discount: Decimal::percent(20),
+        },
+    ];
+
+    // only the owner can set discounts
+    let err = suite
+        .update_pair_config(
+            TRADER,
+            &pair,
+            StablePoolUpdateParams::UpdateStakerFeeDiscounts {
+                tiers: tiers.clone(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // discount cannot be bigger than 1
+    let err = suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateStakerFeeDiscounts {
+                tiers: vec![StakerFeeDiscount {
+                    min_staked: Uint128::new(1_000),
+                    discount: Decimal::percent(101),
+                }],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidStakerFeeDiscount {},
+        err.downcast().unwrap()
+    );
+
+    suite
+        .update_pair_config(
+            "owner",
+            &pair,
+            StablePoolUpdateParams::UpdateStakerFeeDiscounts { tiers },
+        )
+        .unwrap();
+    suite.stake_lp(WHALE, &pair, 1_000_000).unwrap();
+
+    // unstaked trader pays the full fee
+    let offer = juno_info.with_balance(500_000u128);
+    let simulation = suite.query_simulation(&pair, offer.clone(), None).unwrap();
+    let res = suite
+        .swap(&pair, TRADER, offer.clone(), None, None, None, None)
+        .unwrap();
+    let full_commission = simulation.commission_amount;
+    assert_eq!(
+        get_attribute(&res, "commission_amount"),
+        Some(full_commission.to_string().as_str())
+    );
+
+    // identical trade by the staker only pays half of the fee, according to the highest tier reached
+    let simulation = suite.query_simulation(&pair, offer.clone(), None).unwrap();
+    let res = suite
+        .swap(&pair, WHALE, offer, None, None, None, None)
+        .unwrap();
+    let discounted_commission: Uint128 = get_attribute(&res, "commission_amount")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(
+        discounted_commission,
+        simulation.commission_amount * Decimal::percent(50)
+    );
+    assert!(discounted_commission < full_commission);
+    assert_eq!(
+        get_attribute(&res, "return_amount"),
+        Some(
+            (simulation.return_amount + simulation.commission_amount - discounted_commission)
+                .to_string()
+                .as_str()
+        )
+    );
+}
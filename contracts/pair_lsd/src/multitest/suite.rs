@@ -5,18 +5,23 @@ use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
 use cw20_base::msg::InstantiateMsg as Cw20BaseInstantiateMsg;
 use cw_multi_test::{App, AppResponse, BankSudo, ContractWrapper, Executor, SudoMsg};
 
-use wyndex::asset::{Asset, AssetInfo};
+use wyndex::asset::{Asset, AssetInfo, AssetInfoValidated, AssetValidated};
 use wyndex::factory::{
     DefaultStakeConfig, ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg,
     PairConfig, PairType, QueryMsg as FactoryQueryMsg,
 };
 use wyndex::fee_config::FeeConfig;
 use wyndex::pair::{
-    Cw20HookMsg, ExecuteMsg as PairExecuteMsg, PairInfo, QueryMsg, SimulationResponse,
+    ArbVsPriceResponse, AssetPrecision, BatchSimulationRequest, BatchSimulationResult, Cw20HookMsg,
+    DepositShareResponse, ExecuteMsg as PairExecuteMsg, HealthResponse, LpForWithdrawalResponse,
+    MarginalResponse, MigrateMsg as PairMigrateMsg, PairInfo, PegStatusResponse, PoolDebugResponse,
+    PoolResponse, ProtocolLpShareResponse, QueryMsg, SimulationResponse, SlippageCurveResponse,
     SpotPricePredictionResponse, SpotPriceResponse, StablePoolParams, StablePoolUpdateParams,
+    SwapMeetsMinResponse, ValueVsHoldResponse, WithdrawSimulationResponse,
 };
+use wyndex::stake::ReceiveMsg as StakeReceiveMsg;
 
-use super::mock_hub;
+use super::{mock_fee_on_transfer_cw20, mock_frozen_cw20, mock_hub};
 
 const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
 
@@ -50,7 +55,8 @@ fn store_pair(app: &mut App) -> u64 {
             crate::contract::instantiate,
             crate::contract::query,
         )
-        .with_reply_empty(crate::contract::reply),
+        .with_reply_empty(crate::contract::reply)
+        .with_migrate_empty(crate::contract::migrate),
     );
 
     app.store_code(contract)
@@ -79,6 +85,29 @@ fn store_cw20(app: &mut App) -> u64 {
     app.store_code(contract)
 }
 
+fn store_mock_frozen_cw20(app: &mut App) -> u64 {
+    let contract = Box::new(
+        ContractWrapper::new(
+            mock_frozen_cw20::execute,
+            mock_frozen_cw20::instantiate,
+            mock_frozen_cw20::query,
+        )
+        .with_sudo(mock_frozen_cw20::sudo),
+    );
+
+    app.store_code(contract)
+}
+
+fn store_mock_fee_on_transfer_cw20(app: &mut App) -> u64 {
+    let contract = Box::new(ContractWrapper::new(
+        mock_fee_on_transfer_cw20::execute,
+        mock_fee_on_transfer_cw20::instantiate,
+        mock_fee_on_transfer_cw20::query,
+    ));
+
+    app.store_code(contract)
+}
+
 fn store_staking(app: &mut App) -> u64 {
     let contract = Box::new(ContractWrapper::new(
         wyndex_stake::contract::execute,
@@ -96,6 +125,7 @@ pub struct SuiteBuilder {
     stake_config: DefaultStakeConfig,
     total_fee_bps: u16,
     protocol_fee_bps: u16,
+    fee_address: Option<String>,
     initial_target_rate: Decimal,
 }
 
@@ -107,6 +137,7 @@ impl SuiteBuilder {
             max_referral_commission: Decimal::one(),
             total_fee_bps: 0,
             protocol_fee_bps: 0,
+            fee_address: None,
             stake_config: DefaultStakeConfig {
                 staking_code_id: 0, // will be set in build()
                 tokens_per_power: Uint128::new(1000),
@@ -134,6 +165,11 @@ impl SuiteBuilder {
         self
     }
 
+    pub fn with_fee_address(mut self, fee_address: &str) -> Self {
+        self.fee_address = Some(fee_address.to_owned());
+        self
+    }
+
     pub fn with_max_referral_commission(mut self, max: Decimal) -> Self {
         self.max_referral_commission = max;
         self
@@ -150,6 +186,8 @@ impl SuiteBuilder {
         let owner = Addr::unchecked("owner");
 
         let cw20_code_id = store_cw20(&mut app);
+        let frozen_cw20_code_id = store_mock_frozen_cw20(&mut app);
+        let fee_on_transfer_cw20_code_id = store_mock_fee_on_transfer_cw20(&mut app);
         let stable_pair_code_id = store_pair(&mut app);
         let xyk_pair_code_id = store_xyk_pair(&mut app);
         let factory_code_id = store_factory(&mut app);
@@ -180,7 +218,7 @@ impl SuiteBuilder {
                         },
                     ],
                     token_code_id: cw20_code_id,
-                    fee_address: None,
+                    fee_address: self.fee_address,
                     owner: owner.to_string(),
                     max_referral_commission: self.max_referral_commission,
                     default_stake_config: DefaultStakeConfig {
@@ -221,6 +259,8 @@ impl SuiteBuilder {
             app,
             factory,
             cw20_code_id,
+            frozen_cw20_code_id,
+            fee_on_transfer_cw20_code_id,
             mock_hub,
         }
     }
@@ -232,6 +272,8 @@ pub struct Suite {
     pub factory: Addr,
     pub mock_hub: Addr,
     cw20_code_id: u64,
+    frozen_cw20_code_id: u64,
+    fee_on_transfer_cw20_code_id: u64,
 }
 
 #[allow(dead_code)]
@@ -242,6 +284,29 @@ impl Suite {
             .update_block(|block| block.time = block.time.plus_seconds(seconds));
     }
 
+    // advance to the next block
+    pub fn next_block(&mut self) {
+        self.app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(5);
+        });
+    }
+
+    /// Instantiates another mock hub that returns the given rate as target value
+    pub fn instantiate_mock_hub(&mut self, rate: Decimal) -> Addr {
+        let code_id = store_mock_hub(&mut self.app);
+        self.app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked(&self.owner),
+                &rate,
+                &[],
+                "Mock Hub",
+                None,
+            )
+            .unwrap()
+    }
+
     pub fn create_pair(
         &mut self,
         sender: &str,
@@ -278,6 +343,17 @@ impl Suite {
         pair: &Addr,
         assets: &[Asset],
         send_funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.provide_liquidity_with_deadline(owner, pair, assets, send_funds, None)
+    }
+
+    pub fn provide_liquidity_with_deadline(
+        &mut self,
+        owner: &str,
+        pair: &Addr,
+        assets: &[Asset],
+        send_funds: &[Coin],
+        deadline: impl Into<Option<u64>>,
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(owner),
@@ -286,11 +362,55 @@ impl Suite {
                 assets: assets.to_vec(),
                 slippage_tolerance: None,
                 receiver: None,
+                deadline: deadline.into(),
+                require_all_assets: None,
             },
             send_funds,
         )
     }
 
+    pub fn provide_liquidity_with_require_all(
+        &mut self,
+        owner: &str,
+        pair: &Addr,
+        assets: &[Asset],
+        send_funds: &[Coin],
+        require_all_assets: bool,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(owner),
+            pair.clone(),
+            &PairExecuteMsg::ProvideLiquidity {
+                assets: assets.to_vec(),
+                slippage_tolerance: None,
+                receiver: None,
+                deadline: None,
+                require_all_assets: Some(require_all_assets),
+            },
+            send_funds,
+        )
+    }
+
+    /// Bonds LP tokens of the given pair in its staking contract for the shortest unbonding period
+    pub fn stake_lp(&mut self, sender: &str, pair: &Addr, amount: u128) -> AnyResult<AppResponse> {
+        let pair_info = self.query_pair(pair)?;
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            pair_info.liquidity_token,
+            &Cw20ExecuteMsg::Send {
+                contract: pair_info.staking_addr.to_string(),
+                amount: amount.into(),
+                msg: to_binary(&StakeReceiveMsg::Delegate {
+                    unbonding_period: SECONDS_PER_DAY * 7,
+                    delegate_as: None,
+                    unbond_controller: None,
+                    withdraw_to: None,
+                })?,
+            },
+            &[],
+        )
+    }
+
     pub fn increase_allowance(
         &mut self,
         owner: &str,
@@ -398,14 +518,48 @@ impl Suite {
     }
 
     pub fn instantiate_token(&mut self, owner: &str, token: &str) -> Addr {
+        self.instantiate_token_with_code(self.cw20_code_id, owner, token, 6)
+    }
+
+    pub fn instantiate_token_with_decimals(
+        &mut self,
+        owner: &str,
+        token: &str,
+        decimals: u8,
+    ) -> Addr {
+        self.instantiate_token_with_code(self.cw20_code_id, owner, token, decimals)
+    }
+
+    /// Instantiates a token that can be frozen using [`Suite::freeze_token`]
+    pub fn instantiate_freezable_token(&mut self, owner: &str, token: &str) -> Addr {
+        self.instantiate_token_with_code(self.frozen_cw20_code_id, owner, token, 6)
+    }
+
+    /// Makes all transfers of the given freezable token fail, or allows them again
+    pub fn freeze_token(&mut self, token: &Addr, frozen: bool) -> AnyResult<AppResponse> {
+        self.app.wasm_sudo(token.clone(), &frozen)
+    }
+
+    /// Instantiates a token that burns a fee on every transfer
+    pub fn instantiate_fee_on_transfer_token(&mut self, owner: &str, token: &str) -> Addr {
+        self.instantiate_token_with_code(self.fee_on_transfer_cw20_code_id, owner, token, 6)
+    }
+
+    fn instantiate_token_with_code(
+        &mut self,
+        code_id: u64,
+        owner: &str,
+        token: &str,
+        decimals: u8,
+    ) -> Addr {
         self.app
             .instantiate_contract(
-                self.cw20_code_id,
+                code_id,
                 Addr::unchecked(owner),
                 &Cw20BaseInstantiateMsg {
                     name: token.to_owned(),
                     symbol: token.to_owned(),
-                    decimals: 6,
+                    decimals,
                     initial_balances: vec![],
                     mint: Some(MinterResponse {
                         minter: owner.to_string(),
@@ -473,6 +627,34 @@ impl Suite {
         max_spread: impl Into<Option<Decimal>>,
         to: impl Into<Option<&'a str>>,
     ) -> AnyResult<AppResponse> {
+        self.swap_with_referral(
+            pair,
+            sender,
+            offer_asset,
+            ask_asset_info,
+            belief_price,
+            max_spread,
+            to,
+            None,
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_with_referral<'a>(
+        &mut self,
+        pair: &Addr,
+        sender: &str,
+        offer_asset: Asset,
+        ask_asset_info: impl Into<Option<AssetInfo>>,
+        belief_price: impl Into<Option<Decimal>>,
+        max_spread: impl Into<Option<Decimal>>,
+        to: impl Into<Option<&'a str>>,
+        referral_address: impl Into<Option<&'a str>>,
+        referral_commission: impl Into<Option<Decimal>>,
+    ) -> AnyResult<AppResponse> {
+        let referral_address = referral_address.into().map(|s| s.to_owned());
+        let referral_commission = referral_commission.into();
         match &offer_asset.info {
             AssetInfo::Token(token) => self.app.execute_contract(
                 Addr::unchecked(sender),
@@ -482,11 +664,12 @@ impl Suite {
                     amount: offer_asset.amount,
                     msg: to_binary(&Cw20HookMsg::Swap {
                         ask_asset_info: ask_asset_info.into(),
-                        referral_address: None,
-                        referral_commission: None,
+                        referral_address,
+                        referral_commission,
                         belief_price: belief_price.into(),
                         max_spread: max_spread.into(),
                         to: to.into().map(|s| s.to_owned()),
+                        deadline: None,
                     })?,
                 },
                 &[],
@@ -499,11 +682,12 @@ impl Suite {
                     &PairExecuteMsg::Swap {
                         offer_asset,
                         ask_asset_info: ask_asset_info.into(),
-                        referral_address: None,
-                        referral_commission: None,
+                        referral_address,
+                        referral_commission,
                         belief_price: belief_price.into(),
                         max_spread: max_spread.into(),
                         to: to.into().map(|s| s.to_owned()),
+                        deadline: None,
                     },
                     funds,
                 )
@@ -511,6 +695,87 @@ impl Suite {
         }
     }
 
+    pub fn swap_with_deadline(
+        &mut self,
+        pair: &Addr,
+        sender: &str,
+        offer_asset: Asset,
+        ask_asset_info: impl Into<Option<AssetInfo>>,
+        deadline: u64,
+    ) -> AnyResult<AppResponse> {
+        match &offer_asset.info {
+            AssetInfo::Token(token) => self.app.execute_contract(
+                Addr::unchecked(sender),
+                Addr::unchecked(token),
+                &Cw20ExecuteMsg::Send {
+                    contract: pair.to_string(),
+                    amount: offer_asset.amount,
+                    msg: to_binary(&Cw20HookMsg::Swap {
+                        ask_asset_info: ask_asset_info.into(),
+                        belief_price: None,
+                        max_spread: None,
+                        to: None,
+                        referral_address: None,
+                        referral_commission: None,
+                        deadline: Some(deadline),
+                    })?,
+                },
+                &[],
+            ),
+            AssetInfo::Native(denom) => {
+                let funds = &[coin(offer_asset.amount.u128(), denom)];
+                self.app.execute_contract(
+                    Addr::unchecked(sender),
+                    pair.clone(),
+                    &PairExecuteMsg::Swap {
+                        offer_asset,
+                        ask_asset_info: ask_asset_info.into(),
+                        belief_price: None,
+                        max_spread: None,
+                        to: None,
+                        referral_address: None,
+                        referral_commission: None,
+                        deadline: Some(deadline),
+                    },
+                    funds,
+                )
+            }
+        }
+    }
+
+    /// Swaps a cw20 token by letting the pair pull it using an allowance, instead of sending it
+    pub fn swap_with_allowance(
+        &mut self,
+        pair: &Addr,
+        sender: &str,
+        offer_asset: Asset,
+        ask_asset_info: impl Into<Option<AssetInfo>>,
+    ) -> AnyResult<AppResponse> {
+        if let AssetInfo::Token(token) = &offer_asset.info {
+            self.increase_allowance(
+                sender,
+                &Addr::unchecked(token),
+                pair.as_str(),
+                offer_asset.amount.u128(),
+            )?;
+        }
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            pair.clone(),
+            &PairExecuteMsg::Swap {
+                offer_asset,
+                ask_asset_info: ask_asset_info.into(),
+                belief_price: None,
+                max_spread: None,
+                to: None,
+                referral_address: None,
+                referral_commission: None,
+                deadline: None,
+            },
+            &[],
+        )
+    }
+
     pub fn withdraw_liquidity(
         &mut self,
         sender: &str,
@@ -518,6 +783,25 @@ impl Suite {
         liquidity_token: &Addr,
         amount: u128,
         assets: Vec<Asset>,
+    ) -> AnyResult<AppResponse> {
+        self.withdraw_liquidity_with_skip_frozen(
+            sender,
+            pair,
+            liquidity_token,
+            amount,
+            assets,
+            false,
+        )
+    }
+
+    pub fn withdraw_liquidity_with_skip_frozen(
+        &mut self,
+        sender: &str,
+        pair: &Addr,
+        liquidity_token: &Addr,
+        amount: u128,
+        assets: Vec<Asset>,
+        skip_frozen: bool,
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(sender),
@@ -525,12 +809,82 @@ impl Suite {
             &Cw20ExecuteMsg::Send {
                 contract: pair.to_string(),
                 amount: amount.into(),
-                msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { assets })?,
+                msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+                    assets,
+                    skip_frozen,
+                })?,
+            },
+            &[],
+        )
+    }
+
+    /// Migrates the pair to the same code to set its circuit breaker and frozen state
+    pub fn migrate_freeze(
+        &mut self,
+        pair: &Addr,
+        frozen: bool,
+        circuit_breaker: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        let code_id = self.app.wrap().query_wasm_contract_info(pair)?.code_id;
+        self.app.migrate_contract(
+            Addr::unchecked(&self.owner),
+            pair.clone(),
+            &PairMigrateMsg::UpdateFreeze {
+                frozen,
+                circuit_breaker: circuit_breaker.map(|addr| addr.to_owned()),
+            },
+            code_id,
+        )
+    }
+
+    pub fn freeze_pair(
+        &mut self,
+        sender: &str,
+        pair: &Addr,
+        frozen: bool,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            pair.clone(),
+            &PairExecuteMsg::Freeze { frozen },
+            &[],
+        )
+    }
+
+    pub fn emergency_withdraw(
+        &mut self,
+        sender: &str,
+        pair: &Addr,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            pair.clone(),
+            &PairExecuteMsg::EmergencyWithdraw {
+                amount: amount.into(),
             },
             &[],
         )
     }
 
+    pub fn sweep_dust(&mut self, sender: &str, pair: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            pair.clone(),
+            &PairExecuteMsg::SweepDust {},
+            &[],
+        )
+    }
+
+    pub fn claim_unsent_refunds(&mut self, sender: &str, pair: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            pair.clone(),
+            &PairExecuteMsg::ClaimUnsentRefunds {},
+            &[],
+        )
+    }
+
     pub fn update_config(&mut self, params: StablePoolUpdateParams) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked("sender"),
@@ -542,6 +896,22 @@ impl Suite {
         )
     }
 
+    pub fn update_pair_config(
+        &mut self,
+        sender: &str,
+        pair: &Addr,
+        params: StablePoolUpdateParams,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            pair.clone(),
+            &PairExecuteMsg::UpdateConfig {
+                params: to_binary(&params)?,
+            },
+            &[],
+        )
+    }
+
     pub fn query_simulation(
         &self,
         pair: &Addr,
@@ -560,6 +930,70 @@ impl Suite {
         Ok(res)
     }
 
+    pub fn query_withdraw_simulation(
+        &self,
+        pair: &Addr,
+        amount: u128,
+        ask_asset_info: impl Into<Option<AssetInfo>>,
+    ) -> AnyResult<WithdrawSimulationResponse> {
+        let res: WithdrawSimulationResponse = self.app.wrap().query_wasm_smart(
+            pair.clone(),
+            &QueryMsg::WithdrawSimulation {
+                amount: amount.into(),
+                ask_asset_info: ask_asset_info.into(),
+            },
+        )?;
+        Ok(res)
+    }
+
+    pub fn query_lp_for_withdrawal(&self, pair: &Addr, desired: Vec<Asset>) -> AnyResult<u128> {
+        let res: LpForWithdrawalResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::LpForWithdrawal { desired })?;
+        Ok(res.lp_amount.u128())
+    }
+
+    pub fn collect_protocol_fees(
+        &mut self,
+        sender: &str,
+        pair: &Addr,
+        recipient: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            pair.clone(),
+            &PairExecuteMsg::CollectProtocolFees {
+                recipient: recipient.to_owned(),
+            },
+            &[],
+        )
+    }
+
+    pub fn query_pending_protocol_fees(&self, pair: &Addr) -> AnyResult<Vec<AssetValidated>> {
+        let res: Vec<AssetValidated> = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::PendingProtocolFees {})?;
+        Ok(res)
+    }
+
+    pub fn query_protocol_lp_share(&self, pair: &Addr) -> AnyResult<ProtocolLpShareResponse> {
+        let res: ProtocolLpShareResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::ProtocolLpShare {})?;
+        Ok(res)
+    }
+
+    pub fn query_pool(&self, pair: &Addr) -> AnyResult<PoolResponse> {
+        let res: PoolResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::Pool {})?;
+        Ok(res)
+    }
+
     pub fn query_pair(&self, pair: &Addr) -> AnyResult<PairInfo> {
         let res: PairInfo = self
             .app
@@ -568,6 +1002,24 @@ impl Suite {
         Ok(res)
     }
 
+    pub fn query_post_swap_spot(
+        &self,
+        pair: &Addr,
+        offer: &AssetInfo,
+        ask: &AssetInfo,
+        amount: u128,
+    ) -> AnyResult<Decimal> {
+        let res: SpotPriceResponse = self.app.wrap().query_wasm_smart(
+            pair.clone(),
+            &QueryMsg::PostSwapSpot {
+                offer: offer.clone(),
+                ask: ask.clone(),
+                amount: amount.into(),
+            },
+        )?;
+        Ok(res.price)
+    }
+
     pub fn query_spot_price(
         &self,
         pair: &Addr,
@@ -584,6 +1036,24 @@ impl Suite {
         Ok(res.price)
     }
 
+    pub fn query_arb_vs_price(
+        &self,
+        pair: &Addr,
+        offer: &AssetInfo,
+        ask: &AssetInfo,
+        external_price: Decimal,
+    ) -> AnyResult<ArbVsPriceResponse> {
+        let res: ArbVsPriceResponse = self.app.wrap().query_wasm_smart(
+            pair.clone(),
+            &QueryMsg::ArbVsPrice {
+                offer: offer.clone(),
+                ask: ask.clone(),
+                external_price,
+            },
+        )?;
+        Ok(res)
+    }
+
     pub fn query_predict_spot_price(
         &self,
         pair: &Addr,
@@ -606,6 +1076,138 @@ impl Suite {
         Ok(res.trade)
     }
 
+    pub fn query_marginal(
+        &self,
+        pair: &Addr,
+        offer: &AssetInfo,
+        ask: &AssetInfo,
+    ) -> AnyResult<MarginalResponse> {
+        let res: MarginalResponse = self.app.wrap().query_wasm_smart(
+            pair.clone(),
+            &QueryMsg::Marginal {
+                offer: offer.clone(),
+                ask: ask.clone(),
+            },
+        )?;
+        Ok(res)
+    }
+
+    pub fn query_swap_meets_min(
+        &self,
+        pair: &Addr,
+        offer: Asset,
+        ask: &AssetInfo,
+        min_receive: u128,
+    ) -> AnyResult<SwapMeetsMinResponse> {
+        let res: SwapMeetsMinResponse = self.app.wrap().query_wasm_smart(
+            pair.clone(),
+            &QueryMsg::SwapMeetsMin {
+                offer,
+                ask: ask.clone(),
+                min_receive: min_receive.into(),
+            },
+        )?;
+        Ok(res)
+    }
+
+    pub fn query_slippage_curve(
+        &self,
+        pair: &Addr,
+        offer: &AssetInfo,
+        ask: &AssetInfo,
+        sizes: &[u128],
+    ) -> AnyResult<SlippageCurveResponse> {
+        let res: SlippageCurveResponse = self.app.wrap().query_wasm_smart(
+            pair.clone(),
+            &QueryMsg::SlippageCurve {
+                offer: offer.clone(),
+                ask: ask.clone(),
+                sizes: sizes.iter().map(|&size| size.into()).collect(),
+            },
+        )?;
+        Ok(res)
+    }
+
+    pub fn query_simulate_batch(
+        &self,
+        pair: &Addr,
+        requests: Vec<BatchSimulationRequest>,
+    ) -> AnyResult<Vec<BatchSimulationResult>> {
+        let res: Vec<BatchSimulationResult> = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::SimulateBatch { requests })?;
+        Ok(res)
+    }
+
+    pub fn query_composition(&self, pair: &Addr) -> AnyResult<Vec<(AssetInfoValidated, Decimal)>> {
+        let res: Vec<(AssetInfoValidated, Decimal)> = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::Composition {})?;
+        Ok(res)
+    }
+
+    pub fn query_health(&self, pair: &Addr) -> AnyResult<HealthResponse> {
+        let res: HealthResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::Health {})?;
+        Ok(res)
+    }
+
+    pub fn query_value_vs_hold(
+        &self,
+        pair: &Addr,
+        lp_amount: u128,
+        external_prices: Vec<(AssetInfo, Decimal)>,
+    ) -> AnyResult<ValueVsHoldResponse> {
+        let res: ValueVsHoldResponse = self.app.wrap().query_wasm_smart(
+            pair.clone(),
+            &QueryMsg::ValueVsHold {
+                lp_amount: lp_amount.into(),
+                external_prices,
+            },
+        )?;
+        Ok(res)
+    }
+
+    pub fn query_peg_status(&self, pair: &Addr, band: Decimal) -> AnyResult<PegStatusResponse> {
+        let res: PegStatusResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::PegStatus { band })?;
+        Ok(res)
+    }
+
+    pub fn query_assets(&self, pair: &Addr) -> AnyResult<Vec<AssetPrecision>> {
+        let res: Vec<AssetPrecision> = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::Assets {})?;
+        Ok(res)
+    }
+
+    pub fn query_pool_debug(&self, pair: &Addr) -> AnyResult<PoolDebugResponse> {
+        let res: PoolDebugResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::PoolDebug {})?;
+        Ok(res)
+    }
+
+    pub fn query_deposit_share(
+        &self,
+        pair: &Addr,
+        assets: Vec<Asset>,
+    ) -> AnyResult<DepositShareResponse> {
+        let res: DepositShareResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::DepositShare { assets })?;
+        Ok(res)
+    }
+
     pub fn query_balance(&self, sender: &str, denom: &str) -> AnyResult<u128> {
         let amount = self
             .app
@@ -0,0 +1,102 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+const TRADER: &str = "trader";
+
+fn balanced_pair(suite: &mut Suite) -> Addr {
+    suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 10,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000),
+            vec![coin(1_000_000, "juno"), coin(1_000_000, "uusd")],
+        )
+        .unwrap()
+}
+
+#[test]
+fn min_receive_satisfiable() {
+    let mut suite = SuiteBuilder::new().with_fees(30, 0).build();
+    let pair = balanced_pair(&mut suite);
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+    let offer = juno_info.with_balance(10_000u128);
+
+    let simulation = suite
+        .query_simulation(&pair, offer.clone(), uusd_info.clone())
+        .unwrap();
+    let return_amount = simulation.return_amount.u128();
+    assert!(return_amount > 0);
+
+    // exactly the simulated amount is still fine
+    let res = suite
+        .query_swap_meets_min(&pair, offer.clone(), &uusd_info, return_amount)
+        .unwrap();
+    assert!(res.meets_min);
+    assert_eq!(res.return_amount.u128(), return_amount);
+
+    let res = suite
+        .query_swap_meets_min(&pair, offer, &uusd_info, return_amount - 100)
+        .unwrap();
+    assert!(res.meets_min);
+    assert_eq!(res.return_amount.u128(), return_amount);
+}
+
+#[test]
+fn min_receive_unsatisfiable() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(TRADER, &[coin(500_000, "juno")])
+        .build();
+    let pair = balanced_pair(&mut suite);
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+    let offer = juno_info.with_balance(10_000u128);
+
+    let return_amount = suite
+        .query_swap_meets_min(&pair, offer.clone(), &uusd_info, 0)
+        .unwrap()
+        .return_amount
+        .u128();
+
+    // one more than the swap returns cannot be satisfied
+    let res = suite
+        .query_swap_meets_min(&pair, offer.clone(), &uusd_info, return_amount + 1)
+        .unwrap();
+    assert!(!res.meets_min);
+    assert_eq!(res.return_amount.u128(), return_amount);
+
+    // after a big swap in the same direction, the previous return is not reachable anymore
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(500_000u128),
+            None,
+            None,
+            Decimal::percent(50),
+            None,
+        )
+        .unwrap();
+    let res = suite
+        .query_swap_meets_min(&pair, offer, &uusd_info, return_amount)
+        .unwrap();
+    assert!(!res.meets_min);
+    assert!(res.return_amount.u128() < return_amount);
+}
@@ -0,0 +1,82 @@
+use cosmwasm_std::coin;
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::simulation::get_attribute;
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+const RECIPIENT: &str = "recipient";
+
+#[test]
+fn swap_output_goes_to_recipient() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(100, 0)
+        .with_funds(TRADER, &[coin(2_000_000, "juno")])
+        .build();
+
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno_info.clone(), 1_000_000_000_000),
+            (uusd_info.clone(), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    // the recipient address is validated
+    suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            uusd_info.clone(),
+            None,
+            None,
+            "Recipient",
+        )
+        .unwrap_err();
+
+    let res = suite
+        .swap(
+            &pair,
+            TRADER,
+            juno_info.with_balance(1_000_000u128),
+            uusd_info,
+            None,
+            None,
+            RECIPIENT,
+        )
+        .unwrap();
+    let return_amount: u128 = get_attribute(&res, "return_amount")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(return_amount > 0);
+    assert_eq!(get_attribute(&res, "receiver"), Some(RECIPIENT));
+
+    // the recipient receives the output, the sender only paid the offer asset
+    assert_eq!(
+        suite.query_balance(RECIPIENT, "uusd").unwrap(),
+        return_amount
+    );
+    assert_eq!(suite.query_balance(TRADER, "uusd").unwrap(), 0);
+    assert_eq!(suite.query_balance(TRADER, "juno").unwrap(), 1_000_000);
+}
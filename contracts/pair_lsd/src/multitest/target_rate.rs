@@ -36,6 +36,9 @@ fn basic_provide_and_swap() {
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             (juno_info.clone(), 150_000_000_000_000_000),
             (wy_juno_info.clone(), 100_000_000_000_000_000),
@@ -114,6 +117,9 @@ fn simple_provide_liquidity() {
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             (juno_info, 150_000_000_000_000_000),
             (wy_juno_info, 100_000_000_000_000_000),
@@ -181,6 +187,9 @@ fn provide_liquidity_multiple() {
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             &[juno_info.clone(), wy_juno_info.clone()],
         )
@@ -291,6 +300,9 @@ fn provide_liquidity_changing_rate() {
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             (juno_info.clone(), 150_000_000_000_000_000),
             (wy_juno_info, 100_000_000_000_000_000),
@@ -355,6 +367,9 @@ fn changing_target_rate() {
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             (juno_info.clone(), 150_000_000_000_000_000),
             (wy_juno_info.clone(), 100_000_000_000_000_000),
@@ -458,6 +473,9 @@ fn drastic_rate_change() {
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             (juno_info.clone(), 200_000_000_000_000_000),
             (wy_juno_info.clone(), 100_000_000_000_000_000),
@@ -584,6 +602,9 @@ fn changing_spot_price() {
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             (juno_info.clone(), 150_000_000),
             (wy_juno_info.clone(), 100_000_000),
@@ -667,6 +688,9 @@ fn predict_swap_spot_price() {
                     hub: suite.mock_hub.to_string(),
                     target_rate_epoch: DAY,
                 }),
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             }),
             (juno_info.clone(), 1_500_000_000),
             (wy_juno_info.clone(), 1_000_000_000),
@@ -0,0 +1,99 @@
+use cosmwasm_std::{coin, Decimal, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt, AssetInfoValidated},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::SuiteBuilder;
+
+const TRADER: &str = "trader";
+
+#[test]
+fn on_peg_pool_earns_fees_over_holding() {
+    let mut suite = SuiteBuilder::new()
+        .with_fees(30, 0)
+        .with_funds(
+            TRADER,
+            &[coin(100_000_000, "juno"), coin(100_000_000, "uusd")],
+        )
+        .build();
+    let juno = AssetInfo::Native("juno".to_string());
+    let uusd = AssetInfo::Native("uusd".to_string());
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (juno.clone(), 1_000_000_000),
+            (uusd.clone(), 1_000_000_000),
+            vec![coin(1_000_000_000, "juno"), coin(1_000_000_000, "uusd")],
+        )
+        .unwrap();
+    let prices = vec![
+        (juno.clone(), Decimal::one()),
+        (uusd.clone(), Decimal::one()),
+    ];
+    let lp_amount = 1_000_000_000;
+
+    // right after the first deposit, providing is the same as holding
+    let res = suite
+        .query_value_vs_hold(&pair, lp_amount, prices.clone())
+        .unwrap();
+    assert_eq!(
+        res.hold_assets,
+        vec![
+            AssetInfoValidated::Native("juno".to_string()).with_balance(500_000_000u128),
+            AssetInfoValidated::Native("uusd".to_string()).with_balance(500_000_000u128),
+        ]
+    );
+    assert_eq!(res.hold_value, Uint128::new(1_000_000_000));
+    assert_eq!(res.current_value, res.hold_value);
+
+    // swapping back and forth keeps the pool on peg, but accrues fees
+    for _ in 0..5 {
+        suite
+            .swap(
+                &pair,
+                TRADER,
+                juno.with_balance(10_000_000u128),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        suite
+            .swap(
+                &pair,
+                TRADER,
+                uusd.with_balance(10_000_000u128),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+    }
+
+    let res = suite
+        .query_value_vs_hold(&pair, lp_amount, prices.clone())
+        .unwrap();
+    assert_eq!(res.hold_value, Uint128::new(1_000_000_000));
+    assert!(res.current_value > res.hold_value);
+    // but only slightly
+    assert!(res.current_value < res.hold_value + Uint128::new(1_000_000));
+
+    // every pool asset needs a price
+    let err = suite
+        .query_value_vs_hold(&pair, lp_amount, prices[..1].to_vec())
+        .unwrap_err();
+    assert!(err.to_string().contains("Missing external price for uusd"));
+}
@@ -0,0 +1,183 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use wyndex::{
+    asset::{AssetInfo, AssetInfoExt},
+    factory::PairType,
+    pair::StablePoolParams,
+};
+
+use super::suite::{Suite, SuiteBuilder};
+
+const WHALE: &str = "whale";
+
+fn setup() -> (Suite, Addr) {
+    let mut suite = SuiteBuilder::new().with_fees(100, 0).build();
+
+    let pair = suite
+        .create_pair_and_provide_liquidity(
+            PairType::Lsd {},
+            Some(StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
+            }),
+            (AssetInfo::Native("juno".to_string()), 1_000_000_000_000),
+            (AssetInfo::Native("uusd".to_string()), 1_000_000_000_000),
+            vec![
+                coin(1_000_000_000_000, "juno"),
+                coin(1_000_000_000_000, "uusd"),
+            ],
+        )
+        .unwrap();
+
+    (suite, pair)
+}
+
+#[test]
+fn balanced_withdraw_simulation_is_proportional() {
+    let (mut suite, pair) = setup();
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    let lp_amount = suite.query_cw20_balance(WHALE, &lp_token).unwrap() / 10;
+
+    let pool = suite.query_pool(&pair).unwrap();
+    let simulation = suite
+        .query_withdraw_simulation(&pair, lp_amount, None)
+        .unwrap();
+    assert_eq!(simulation.single_asset, None);
+    assert_eq!(simulation.assets.len(), 2);
+    for (asset, pool_asset) in simulation.assets.iter().zip(pool.assets.iter()) {
+        assert_eq!(asset.info, pool_asset.info);
+        assert_eq!(
+            asset.amount,
+            pool_asset
+                .amount
+                .multiply_ratio(lp_amount, pool.total_share)
+        );
+    }
+
+    // the simulation matches the actual withdraw
+    suite
+        .withdraw_liquidity(WHALE, &pair, &lp_token, lp_amount, vec![])
+        .unwrap();
+    assert_eq!(
+        suite.query_balance(WHALE, "juno").unwrap(),
+        simulation.assets[0].amount.u128()
+    );
+    assert_eq!(
+        suite.query_balance(WHALE, "uusd").unwrap(),
+        simulation.assets[1].amount.u128()
+    );
+}
+
+#[test]
+fn single_asset_withdraw_simulation_includes_imbalance_fee() {
+    let (mut suite, pair) = setup();
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    let lp_amount = suite.query_cw20_balance(WHALE, &lp_token).unwrap() / 10;
+
+    let simulation = suite
+        .query_withdraw_simulation(&pair, lp_amount, juno_info.clone())
+        .unwrap();
+    let balanced_total: Uint128 = simulation.assets.iter().map(|a| a.amount).sum();
+    let single_asset = simulation.single_asset.unwrap();
+    assert_eq!(single_asset.info.to_string(), "juno");
+    // withdrawing a single asset is charged an imbalance fee,
+    // so it yields less than the balanced withdraw of equally priced assets
+    assert!(single_asset.amount < balanced_total);
+    assert!(single_asset.amount > balanced_total * Decimal::percent(98));
+
+    // withdrawing more than simulated is not possible with the given LP tokens
+    let err = suite
+        .withdraw_liquidity(
+            WHALE,
+            &pair,
+            &lp_token,
+            lp_amount,
+            vec![juno_info.with_balance(single_asset.amount + Uint128::new(1_000))],
+        )
+        .unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("Not enough LP tokens."));
+
+    // but withdrawing the simulated amount is
+    suite
+        .withdraw_liquidity(
+            WHALE,
+            &pair,
+            &lp_token,
+            lp_amount,
+            vec![juno_info.with_balance(single_asset.amount)],
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_balance(WHALE, "juno").unwrap(),
+        single_asset.amount.u128()
+    );
+}
+
+#[test]
+fn lp_for_withdrawal_pays_out_desired_assets() {
+    let (mut suite, pair) = setup();
+    let lp_token = suite.query_pair(&pair).unwrap().liquidity_token;
+    let juno_info = AssetInfo::Native("juno".to_string());
+    let uusd_info = AssetInfo::Native("uusd".to_string());
+
+    for desired in [
+        // single asset
+        vec![juno_info.with_balance(1_000_000_000u128)],
+        // proportional
+        vec![
+            juno_info.with_balance(1_000_000_000u128),
+            uusd_info.with_balance(1_000_000_000u128),
+        ],
+    ] {
+        let lp_amount = suite
+            .query_lp_for_withdrawal(&pair, desired.clone())
+            .unwrap();
+        let lp_before = suite.query_cw20_balance(WHALE, &lp_token).unwrap();
+        let juno_before = suite.query_balance(WHALE, "juno").unwrap();
+        let uusd_before = suite.query_balance(WHALE, "uusd").unwrap();
+
+        // sending more LP tokens than needed returns the rest
+        suite
+            .withdraw_liquidity(WHALE, &pair, &lp_token, lp_amount * 2, desired.clone())
+            .unwrap();
+
+        assert_eq!(
+            suite.query_cw20_balance(WHALE, &lp_token).unwrap(),
+            lp_before - lp_amount
+        );
+        let received = [
+            suite.query_balance(WHALE, "juno").unwrap() - juno_before,
+            suite.query_balance(WHALE, "uusd").unwrap() - uusd_before,
+        ];
+        for asset in &desired {
+            let idx = if asset.info == juno_info { 0 } else { 1 };
+            assert!(received[idx] >= asset.amount.u128());
+        }
+    }
+
+    // the proportional withdrawal does not pay an imbalance fee
+    let pool = suite.query_pool(&pair).unwrap();
+    let lp_amount = suite
+        .query_lp_for_withdrawal(
+            &pair,
+            vec![
+                juno_info.with_balance(pool.assets[0].amount / Uint128::new(10)),
+                uusd_info.with_balance(pool.assets[1].amount / Uint128::new(10)),
+            ],
+        )
+        .unwrap();
+    // allow for rounding in the invariant calculation
+    assert!(lp_amount * 10_000 <= pool.total_share.u128() / 10 * 10_001);
+
+    // the pool cannot pay out more than it holds
+    suite
+        .query_lp_for_withdrawal(&pair, vec![juno_info.with_balance(pool.assets[0].amount)])
+        .unwrap_err();
+}
@@ -1,9 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, DepsMut, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, DepsMut, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
-use wyndex::asset::AssetInfoValidated;
+use wyndex::asset::{AssetInfo, AssetInfoValidated, AssetValidated};
 use wyndex::common::OwnershipProposal;
-use wyndex::pair::PairInfo;
+use wyndex::pair::{PairInfo, StakerFeeDiscount};
 
 /// This structure stores the main stableswap pair parameters.
 #[cw_serde]
@@ -32,21 +32,47 @@ pub struct Config {
     pub trading_starts: u64,
 
     pub lsd: Option<LsdData>,
+    /// Exchange rate providers for the other assets that are pegged at a non-1:1 ratio
+    #[serde(default)]
+    pub rate_providers: Vec<LsdData>,
+    /// Routing of part of the swap fees into a buy-and-burn target. Disabled if `None`.
+    pub fee_burn: Option<FeeBurn>,
+    /// If set, protocol fees are held by the pair until they are collected
+    /// instead of being sent to the fee address on every swap.
+    #[serde(default)]
+    pub hold_protocol_fees: bool,
+    /// The maximum relative price move allowed within a single block. Disabled if `None`.
+    #[serde(default)]
+    pub max_block_price_move: Option<Decimal>,
+    /// Swap fee discounts for LP stakers, sorted by ascending `min_staked`. Disabled if empty.
+    /// The simulation queries do not know the swapper, so they never apply the discount.
+    #[serde(default)]
+    pub staker_fee_discounts: Vec<StakerFeeDiscount>,
+    /// Number of seconds LP tokens minted on a provide cannot be withdrawn. Disabled if zero.
+    #[serde(default)]
+    pub provide_lock: u64,
+    /// Minimum reserves withdrawals cannot go below
+    #[serde(default)]
+    pub min_reserves: Vec<AssetValidated>,
+    /// Addresses allowed to provide liquidity. Anyone can provide if `None`.
+    #[serde(default)]
+    pub lp_whitelist: Option<Vec<Addr>>,
+    /// Fraction of a deposit's LP tokens that is withheld or added per unit the deposit moves the
+    /// pool's imbalance, see [`REBALANCE_BUDGET`]. Disabled if `None`.
+    #[serde(default)]
+    pub rebalance_incentive: Option<Decimal>,
 }
 
 impl Config {
-    pub fn target_rate(&self) -> Decimal {
-        match &self.lsd {
-            Some(lsd) => lsd.target_rate,
-            None => Decimal::one(),
-        }
-    }
-
-    pub fn is_lsd(&self, asset: &AssetInfoValidated) -> bool {
+    /// Returns the rate the given asset is pegged at, either from the LSD hub or one of the
+    /// rate providers. Defaults to `1.0`.
+    pub fn rate(&self, asset: &AssetInfoValidated) -> Decimal {
         self.lsd
-            .as_ref()
-            .map(|l| &l.asset == asset)
-            .unwrap_or(false)
+            .iter()
+            .chain(&self.rate_providers)
+            .find(|l| &l.asset == asset)
+            .map(|l| l.target_rate)
+            .unwrap_or_else(Decimal::one)
     }
 }
 
@@ -66,7 +92,106 @@ pub struct LsdData {
     pub last_target_query: u64,
 }
 
+#[cw_serde]
+pub struct FeeBurn {
+    /// The fraction of the LP part of the swap commission that is sent to `target`
+    pub fraction: Decimal,
+    /// Address of the contract buying and burning the LP token or a governance token
+    pub target: Addr,
+    /// Optional message attached when sending the fee to `target`
+    pub msg: Option<Binary>,
+}
+
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// The assets of the first deposit into the pool and the LP tokens minted for them,
+/// including the permanently locked minimum liquidity.
+/// This is the reference basket for [`wyndex::pair::QueryMsg::ValueVsHold`].
+#[cw_serde]
+pub struct InitialBasket {
+    pub assets: Vec<AssetValidated>,
+    pub share: Uint128,
+}
+
+pub const INITIAL_BASKET: Item<InitialBasket> = Item::new("initial_basket");
+
+/// Protocol fees held by the pair until they are collected, see [`Config::hold_protocol_fees`]
+pub const PENDING_PROTOCOL_FEES: Map<&AssetInfoValidated, Uint128> =
+    Map::new("pending_protocol_fees");
+
+/// Withdrawn assets that could not be sent to the withdrawer, because the token contract rejected
+/// the transfer. They are not part of the pool anymore and can be claimed by the withdrawer.
+pub const UNSENT_REFUNDS: Map<(&Addr, &AssetInfoValidated), Uint128> = Map::new("unsent_refunds");
+/// The sum of all [`UNSENT_REFUNDS`] per asset
+pub const TOTAL_UNSENT_REFUNDS: Map<&AssetInfoValidated, Uint128> =
+    Map::new("total_unsent_refunds");
+/// Refunds of the current withdrawal that are sent in sub-messages, by reply id
+pub const PENDING_REFUNDS: Map<u64, (Addr, AssetValidated)> = Map::new("pending_refunds");
+
+/// Cw20 pool assets that take a fee on transfer.
+/// Deposits of these are measured by the balance the pair actually received.
+pub const FEE_ON_TRANSFER_TOKENS: Item<Vec<Addr>> = Item::new("fee_on_transfer_tokens");
+
+/// A provide or swap that is continued once its fee-on-transfer deposits were pulled in
+#[cw_serde]
+pub enum PendingDeposit {
+    Provide {
+        sender: Addr,
+        assets: Vec<AssetValidated>,
+        receiver: Option<String>,
+    },
+    Swap {
+        sender: Addr,
+        ask_asset_info: Option<AssetInfo>,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<Addr>,
+        referral_address: Option<Addr>,
+        referral_commission: Option<Decimal>,
+    },
+}
+
+/// The pending operation and the pair's balances of the pulled tokens before the transfer
+pub const PENDING_DEPOSIT: Item<(PendingDeposit, Vec<AssetValidated>)> =
+    Item::new("pending_deposit");
+
+/// The block height and the price at the start of that block.
+/// This is the reference for [`Config::max_block_price_move`].
+pub const BLOCK_REFERENCE_PRICE: Item<(u64, Decimal)> = Item::new("block_reference_price");
+
+/// LP tokens withheld from deposits that made the pool more imbalanced.
+/// Deposits that restore the balance receive a bonus of at most this amount.
+pub const REBALANCE_BUDGET: Item<Uint128> = Item::new("rebalance_budget");
+
+/// LP tokens minted on a provide while [`Config::provide_lock`] was set
+#[cw_serde]
+pub struct LpLock {
+    pub amount: Uint128,
+    /// The block time at which the tokens can be withdrawn
+    pub unlock_at: u64,
+}
+
+/// Locked LP tokens per receiver, ordered by `unlock_at`
+pub const LP_LOCKS: Map<&Addr, Vec<LpLock>> = Map::new("lp_locks");
+
+/// Returns the LP locks of `owner` that did not expire yet at `now`,
+/// removing the expired ones from storage.
+pub fn active_lp_locks(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    now: u64,
+) -> StdResult<Vec<LpLock>> {
+    let mut locks = LP_LOCKS.may_load(storage, owner)?.unwrap_or_default();
+    let len = locks.len();
+    locks.retain(|lock| lock.unlock_at > now);
+    if locks.is_empty() {
+        LP_LOCKS.remove(storage, owner);
+    } else if locks.len() != len {
+        LP_LOCKS.save(storage, owner, &locks)?;
+    }
+    Ok(locks)
+}
+
 // Address which can trigger a Freeze or Unfreeze via an ExecuteMsg variant
 pub const CIRCUIT_BREAKER: Item<Addr> = Item::new("circuit_breaker");
 // Whether the contract is frozen or not
@@ -78,6 +78,9 @@ fn proper_initialization() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -178,6 +181,9 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -211,6 +217,8 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env();
@@ -274,6 +282,8 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ],
         slippage_tolerance: Some(Decimal::percent(50)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -300,6 +310,7 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ask_asset_info: None,
         referral_address: None,
         referral_commission: None,
+        deadline: None,
     };
 
     let env = mock_env();
@@ -354,6 +365,8 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -387,6 +400,7 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
             to: None,
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
     });
@@ -401,7 +415,11 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
     // Withdraw liquidity
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: String::from("addr0000"),
-        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { assets: vec![] }).unwrap(),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            assets: vec![],
+            skip_frozen: false,
+        })
+        .unwrap(),
         amount: Uint128::new(100u128),
     });
 
@@ -454,6 +472,8 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -489,6 +509,8 @@ fn test_freezing_a_pool_blocking_actions_then_unfreeze() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -532,6 +554,9 @@ fn provide_liquidity() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -566,6 +591,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env();
@@ -678,6 +705,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -744,6 +773,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env();
@@ -821,6 +852,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -856,6 +889,8 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let env = mock_env_with_block_time(env.block.time.seconds() + 1000);
@@ -905,6 +940,9 @@ fn withdraw_liquidity() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -936,7 +974,11 @@ fn withdraw_liquidity() {
     // Withdraw liquidity
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: String::from("addr0000"),
-        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { assets: vec![] }).unwrap(),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+            assets: vec![],
+            skip_frozen: false,
+        })
+        .unwrap(),
         amount: Uint128::new(100000u128) - MINIMUM_LIQUIDITY_AMOUNT,
     });
 
@@ -1044,6 +1086,9 @@ fn query_twap() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -1074,6 +1119,8 @@ fn query_twap() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     // need to set balance manually to simulate funds being sent
     deps.querier.with_balance(&[(
@@ -1133,6 +1180,7 @@ fn query_twap() {
         ask_asset_info: None,
         referral_address: None,
         referral_commission: None,
+        deadline: None,
     };
     // need to set balance manually to simulate funds being sent
     deps.querier.with_balance(&[(
@@ -1223,6 +1271,7 @@ mod disabled {
                 to_binary(&StablePoolParams {
                     amp: 100,
                     owner: None,
+                    rate_providers: vec![],
                 })
                 .unwrap(),
             ),
@@ -1246,6 +1295,7 @@ mod disabled {
             belief_price: None,
             max_spread: Some(Decimal::percent(50)),
             to: None,
+            deadline: None,
         };
         let env = mock_env_with_block_time(1000);
         let info = mock_info(
@@ -1377,6 +1427,7 @@ mod disabled {
                 to_binary(&StablePoolParams {
                     amp: 100,
                     owner: None,
+                    rate_providers: vec![],
                 })
                 .unwrap(),
             ),
@@ -1400,6 +1451,7 @@ mod disabled {
             belief_price: None,
             max_spread: None,
             to: None,
+            deadline: None,
         };
         let env = mock_env_with_block_time(1000);
         let info = mock_info("addr0000", &[]);
@@ -1415,6 +1467,7 @@ mod disabled {
                 belief_price: None,
                 max_spread: None,
                 to: None,
+                deadline: None,
             })
             .unwrap(),
         });
@@ -1514,6 +1567,7 @@ mod disabled {
                 belief_price: None,
                 max_spread: None,
                 to: None,
+                deadline: None,
             })
             .unwrap(),
         });
@@ -1594,6 +1648,7 @@ mod disabled {
                 to_binary(&StablePoolParams {
                     amp: 100,
                     owner: None,
+                    rate_providers: vec![],
                 })
                 .unwrap(),
             ),
@@ -1657,6 +1712,7 @@ mod disabled {
                 to_binary(&StablePoolParams {
                     amp: 100,
                     owner: None,
+                    rate_providers: vec![],
                 })
                 .unwrap(),
             ),
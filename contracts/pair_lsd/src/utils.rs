@@ -368,9 +368,7 @@ fn pools_after_swap(
         .iter()
         .cloned()
         .map(|mut asset| {
-            if config.is_lsd(&asset.info) {
-                amount /= Decimal256::from(config.target_rate());
-            }
+            amount /= Decimal256::from(config.rate(&asset.info));
             if &asset.info == offer {
                 asset.amount += amount;
                 asset
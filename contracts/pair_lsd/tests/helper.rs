@@ -195,6 +195,9 @@ impl Helper {
                     amp,
                     owner: None,
                     lsd: None,
+                    lp_token_symbol: None,
+                    lp_token_decimals: None,
+                    rate_providers: vec![],
                 })
                 .unwrap(),
             ),
@@ -234,6 +237,8 @@ impl Helper {
             assets: assets.iter().cloned().map(Into::into).collect(),
             slippage_tolerance: None,
             receiver: None,
+            deadline: None,
+            require_all_assets: None,
         };
 
         self.app
@@ -249,7 +254,11 @@ impl Helper {
         let msg = Cw20ExecuteMsg::Send {
             contract: self.pair_addr.to_string(),
             amount: Uint128::from(amount),
-            msg: to_binary(&Cw20HookMsg::WithdrawLiquidity { assets }).unwrap(),
+            msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {
+                assets,
+                skip_frozen: false,
+            })
+            .unwrap(),
         };
 
         self.app
@@ -274,6 +283,7 @@ impl Helper {
                         to: None,
                         referral_address: None,
                         referral_commission: None,
+                        deadline: None,
                     })
                     .unwrap(),
                 };
@@ -297,6 +307,7 @@ impl Helper {
                     to: None,
                     referral_address: None,
                     referral_commission: None,
+                    deadline: None,
                 };
 
                 self.app
@@ -13,11 +13,13 @@ use wyndex::pair::{
 use cosmwasm_std::{
     attr, from_binary, to_binary, Addr, Coin, Decimal, QueryRequest, Uint128, WasmQuery,
 };
-use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+use cw20::{
+    BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse, TokenInfoResponse,
+};
 use cw20_base::msg::InstantiateMsg as TokenInstantiateMsg;
 use cw_multi_test::{App, ContractWrapper, Executor};
 use wyndex::querier::query_token_balance;
-use wyndex_pair_lsd::math::{MAX_AMP, MAX_AMP_CHANGE, MIN_AMP_CHANGING_TIME};
+use wyndex_pair_lsd::math::{MAX_AMP, MAX_AMP_CHANGE, MIN_AMP, MIN_AMP_CHANGING_TIME};
 
 const OWNER: &str = "owner";
 
@@ -137,6 +139,9 @@ fn instantiate_pair(router: &mut App, owner: &Addr) -> Addr {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -226,6 +231,9 @@ fn instantiate_mixed_pair(
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -267,6 +275,8 @@ fn provide_liquidity_mixed_msg(
         ],
         slippage_tolerance,
         receiver,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let coins = [Coin {
@@ -429,6 +439,8 @@ fn provide_liquidity_msg(
         ],
         slippage_tolerance: None,
         receiver,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let coins = [
@@ -569,6 +581,9 @@ fn provide_lp_for_single_token() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -625,6 +640,7 @@ fn provide_lp_for_single_token() {
             to: None,
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
         amount: swap_amount,
@@ -651,6 +667,8 @@ fn provide_lp_for_single_token() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let err = app
@@ -674,6 +692,8 @@ fn provide_lp_for_single_token() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     app.execute_contract(owner.clone(), pair_instance.clone(), &msg, &[])
@@ -693,6 +713,8 @@ fn provide_lp_for_single_token() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     app.execute_contract(owner.clone(), pair_instance.clone(), &msg, &[])
@@ -720,6 +742,8 @@ fn provide_lp_for_single_token() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     app.execute_contract(owner.clone(), pair_instance.clone(), &msg, &[])
@@ -735,6 +759,7 @@ fn provide_lp_for_single_token() {
             to: None,
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
         amount: swap_amount,
@@ -753,6 +778,7 @@ fn provide_lp_for_single_token() {
             to: None,
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
         amount: swap_amount,
@@ -891,6 +917,9 @@ fn test_compatibility_of_tokens_with_different_precision() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -951,6 +980,8 @@ fn test_compatibility_of_tokens_with_different_precision() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     app.execute_contract(owner.clone(), pair_instance.clone(), &msg, &[])
@@ -973,6 +1004,7 @@ fn test_compatibility_of_tokens_with_different_precision() {
             to: Some(user.to_string()),
             referral_address: None,
             referral_commission: None,
+            deadline: None,
         })
         .unwrap(),
         amount: x_offer,
@@ -1145,6 +1177,58 @@ fn create_pair_with_same_assets() {
     )
 }
 
+#[test]
+fn create_pair_with_custom_lp_token() {
+    let owner = Addr::unchecked(OWNER);
+    let mut router = mock_app(owner.clone(), vec![]);
+    let factory = instantiate_factory(&mut router, &owner);
+
+    let asset_infos = vec![
+        AssetInfo::Native("uusd".to_string()),
+        AssetInfo::Native("uluna".to_string()),
+    ];
+    let create_pair_msg = |lp_token_decimals| FactoryExecuteMsg::CreatePair {
+        pair_type: PairType::Lsd {},
+        asset_infos: asset_infos.clone(),
+        init_params: Some(
+            to_binary(&StablePoolParams {
+                amp: 100,
+                owner: None,
+                lsd: None,
+                lp_token_symbol: Some("uLUNALP".to_string()),
+                lp_token_decimals: Some(lp_token_decimals),
+                rate_providers: vec![],
+            })
+            .unwrap(),
+        ),
+        total_fee_bps: None,
+        staking_config: PartialStakeConfig::default(),
+    };
+
+    let err = router
+        .execute_contract(owner.clone(), factory.clone(), &create_pair_msg(19), &[])
+        .unwrap_err();
+    assert_eq!(
+        "LP token decimals must not exceed 18",
+        err.root_cause().to_string()
+    );
+
+    router
+        .execute_contract(owner.clone(), factory.clone(), &create_pair_msg(8), &[])
+        .unwrap();
+
+    let pair_info = router
+        .wrap()
+        .query_wasm_smart::<PairInfo>(factory, &FactoryQueryMsg::Pair { asset_infos })
+        .unwrap();
+    let token_info: TokenInfoResponse = router
+        .wrap()
+        .query_wasm_smart(pair_info.liquidity_token, &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(token_info.symbol, "uLUNALP");
+    assert_eq!(token_info.decimals, 8);
+}
+
 #[test]
 fn provide_liquidity_with_one_asset() {
     let owner = Addr::unchecked("owner");
@@ -1185,6 +1269,8 @@ fn provide_liquidity_with_one_asset() {
         assets: vec![AssetInfo::Native("uusd".to_string()).with_balance(100_000u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     router
         .execute_contract(
@@ -1230,6 +1316,8 @@ fn provide_liquidity_sad_path() {
         assets: vec![AssetInfo::Native("uusd".to_string()).with_balance(100_000u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     let err = router
         .execute_contract(
@@ -1262,6 +1350,8 @@ fn provide_liquidity_sad_path() {
         assets: vec![AssetInfo::Native("uusd".to_string()).with_balance(0u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     let err = router
         .execute_contract(owner.clone(), pair.clone(), &msg, &[])
@@ -1274,6 +1364,8 @@ fn provide_liquidity_sad_path() {
         assets: vec![],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
 
     let err = router
@@ -1313,6 +1405,9 @@ fn provide_liquidity_with_one_cw20_asset() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -1363,6 +1458,8 @@ fn provide_liquidity_with_one_cw20_asset() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     router
         .execute_contract(owner.clone(), pair.clone(), &msg, &[])
@@ -1373,6 +1470,8 @@ fn provide_liquidity_with_one_cw20_asset() {
         assets: vec![AssetInfo::Token(token2.to_string()).with_balance(100_000u128)],
         slippage_tolerance: None,
         receiver: None,
+        deadline: None,
+        require_all_assets: None,
     };
     router
         .execute_contract(owner.clone(), pair.clone(), &msg, &[])
@@ -1438,6 +1537,7 @@ fn swap_with_referral() {
             to: None,
             referral_address: Some(referral.clone()),
             referral_commission: Some(Decimal::percent(1)),
+            deadline: None,
         },
         &[Coin::new(100, "uluna")],
     )
@@ -1510,6 +1610,9 @@ fn update_pair_config() {
                 amp: 100,
                 owner: None,
                 lsd: None,
+                lp_token_symbol: None,
+                lp_token_decimals: None,
+                rate_providers: vec![],
             })
             .unwrap(),
         ),
@@ -1558,8 +1661,8 @@ fn update_pair_config() {
     assert_eq!(
         resp.root_cause().to_string(),
         format!(
-            "Amp coefficient must be greater than 0 and less than or equal to {}",
-            MAX_AMP
+            "Amp coefficient must be between {} and {}",
+            MIN_AMP, MAX_AMP
         )
     );
 
@@ -1701,6 +1804,77 @@ fn update_pair_config() {
     assert_eq!(params.amp, Decimal::from_ratio(150u32, 1u32));
 }
 
+#[test]
+fn amp_ramp_via_execute() {
+    let owner = Addr::unchecked(OWNER);
+    let mut router = mock_app(owner.clone(), vec![]);
+    let pair = instantiate_pair(&mut router, &owner);
+
+    let query_amp = |router: &App| -> Decimal {
+        let res: ConfigResponse = router
+            .wrap()
+            .query_wasm_smart(pair.clone(), &QueryMsg::Config {})
+            .unwrap();
+        from_binary::<StablePoolConfig>(&res.params.unwrap())
+            .unwrap()
+            .amp
+    };
+    assert_eq!(query_amp(&router), Decimal::from_ratio(100u32, 1u32));
+
+    router.update_block(|b| {
+        b.time = b.time.plus_seconds(MIN_AMP_CHANGING_TIME);
+    });
+    let msg = ExecuteMsg::StartAmpRamp {
+        next_amp: 200,
+        next_amp_time: router.block_info().time.seconds() + MIN_AMP_CHANGING_TIME,
+    };
+
+    // only the owner can start a ramp
+    let err = router
+        .execute_contract(Addr::unchecked("random"), pair.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+
+    // the max change guard applies
+    let err = router
+        .execute_contract(
+            owner.clone(),
+            pair.clone(),
+            &ExecuteMsg::StartAmpRamp {
+                next_amp: 100 * MAX_AMP_CHANGE + 1,
+                next_amp_time: router.block_info().time.seconds() + MIN_AMP_CHANGING_TIME,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!(
+            "The difference between the old and new amp value must not exceed {} times",
+            MAX_AMP_CHANGE
+        )
+    );
+
+    router
+        .execute_contract(owner.clone(), pair.clone(), &msg, &[])
+        .unwrap();
+
+    // amp is interpolated during the ramp
+    router.update_block(|b| {
+        b.time = b.time.plus_seconds(MIN_AMP_CHANGING_TIME / 4);
+    });
+    assert_eq!(query_amp(&router), Decimal::from_ratio(125u32, 1u32));
+
+    // stopping pins the current value
+    router
+        .execute_contract(owner, pair.clone(), &ExecuteMsg::StopAmpRamp {}, &[])
+        .unwrap();
+    router.update_block(|b| {
+        b.time = b.time.plus_seconds(MIN_AMP_CHANGING_TIME);
+    });
+    assert_eq!(query_amp(&router), Decimal::from_ratio(125u32, 1u32));
+}
+
 // Integration test showing the incorrect behaviour:
 #[test]
 fn test_mixed_twap_calculation() {
@@ -303,6 +303,8 @@ pub fn reply_one(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
             // TODO: set some value here?
             slippage_tolerance: None,
             receiver: None,
+            deadline: None,
+            require_all_assets: None,
         })?,
     };
 
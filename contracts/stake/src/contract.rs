@@ -1,41 +1,67 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    ensure_eq, from_slice, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env,
-    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+    ensure_eq, from_slice, to_binary, Addr, Binary, BlockInfo, Decimal, Deps, DepsMut, Empty, Env,
+    Event, MessageInfo, Order, Response, StdError, StdResult, Storage, Timestamp, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use cw_controllers::Claim;
-use cw_storage_plus::Map;
-use wyndex::asset::{addr_opt_validate, AssetInfo, AssetInfoValidated};
+use cw_storage_plus::{Bound, Map};
+use wyndex::asset::{addr_opt_validate, AssetInfo, AssetInfoExt, AssetInfoValidated};
 use wyndex::common::validate_addresses;
 use wyndex::lp_converter::ExecuteMsg as ConverterExecuteMsg;
 use wyndex::stake::{FundingInfo, InstantiateMsg, ReceiveMsg, UnbondingPeriod};
 
 use crate::distribution::{
-    apply_points_correction, execute_delegate_withdrawal, execute_distribute_rewards,
-    execute_withdraw_rewards, query_delegated, query_distributed_rewards, query_distribution_data,
-    query_undistributed_rewards, query_withdraw_adjustment_data, query_withdrawable_rewards,
+    add_funded, add_withdrawn, apply_points_correction, execute_delegate_withdrawal,
+    execute_distribute_rewards, execute_withdraw_rewards, execute_withdraw_rewards_batch,
+    query_asset_reconciliation, query_delegated, query_distributed_rewards,
+    query_distribution_data, query_distribution_preview, query_earned_since_withdraw,
+    query_flow_accounting, query_flows_schedule, query_lifetime_funded, query_next_reward_change,
+    query_pending_distribution, query_rate_at, query_reward_receiver, query_reward_token_balance,
+    query_total_to_be_distributed, query_undistributed_rewards, query_withdraw_adjustment_data,
+    query_withdrawable_at, query_withdrawable_rewards, query_withdrawable_rewards_detailed,
+    sub_funded, undistributed_rewards, withdrawable_rewards,
 };
-use crate::utils::{create_undelegate_msg, CurveExt};
+use crate::utils::{create_undelegate_msg, scale_down_from, CurveExt};
 use cw2::set_contract_version;
 use cw_utils::{ensure_from_older_version, maybe_addr, Expiration};
 
 use crate::error::ContractError;
 use crate::msg::{
     AllStakedResponse, AnnualizedReward, AnnualizedRewardsResponse, BondingInfoResponse,
-    BondingPeriodInfo, ExecuteMsg, MigrateMsg, QueryMsg, RewardsPowerResponse, StakedResponse,
-    TotalStakedResponse, TotalUnbondingResponse, UnbondAllResponse,
+    BondingPeriodInfo, DistributionFunding, ExecuteMsg, FundingSource, LastInteractionResponse,
+    MigrateMsg, PowerDecayConfig, QueryMsg, RebondCooldownResponse, RewardsPowerResponse,
+    StakedResponse, StakerCountResponse, TotalStakedResponse, TotalUnbondingResponse,
+    UnbondAllResponse, UserSummaryResponse,
 };
 use crate::state::{
-    Config, ConverterConfig, Distribution, TokenInfo, TotalStake, ADMIN, CLAIMS, CONFIG,
-    DISTRIBUTION, REWARD_CURVE, STAKE, TOTAL_PER_PERIOD, TOTAL_STAKED, UNBOND_ALL,
+    default_max_reward_multiplier, load_power_factor, load_power_rounding, load_total_of_period,
+    record_multiplier_change, Config, ConverterConfig, DelegationRights, Distribution,
+    DistributionUpdate, FundingSourceInfo, InstantUnbondConfig, InteractionBlock, MinBondUpdate,
+    PendingClaim, PowerDecay, RewardsPower, RoundingMode, ScheduledMultipliers, TokenInfo,
+    TotalStake, ADMIN, AUTO_COMPOUND, CLAIMS, CLAIM_RECEIVER, CONFIG, DELEGATION_RIGHTS,
+    DISTRIBUTION, FLOW_TOTALS, FUNDERS, FUNDER_SCHEDULES, FUNDING_SOURCES, LAST_INTERACTION,
+    MIGRATION_TARGET, MULTIPLIER_HISTORY, PENDING_CLAIMS, PENDING_CLAIM_MATURITY, POWER_DECAY,
+    POWER_FACTOR, POWER_ROUNDING, REMOVED_FLOWS, REWARD_CURVE, REWARD_RECEIVER,
+    SCHEDULED_MULTIPLIERS, STAKE, STAKER_COUNT, TOTAL_PENDING_PER_PERIOD, TOTAL_PER_PERIOD,
+    TOTAL_STAKED, UNBOND_ALL, WITHDRAW_ADJUSTMENT,
 };
 use wynd_curve_utils::Curve;
 
 const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+/// Number of stakers a single [`ExecuteMsg::ApplyDistributionUpdate`] or
+/// [`ExecuteMsg::ApplyMinBond`] processes by default
+const DEFAULT_UPDATE_LIMIT: u32 = 30;
+/// Maximum number of stakers a single [`ExecuteMsg::ApplyDistributionUpdate`] or
+/// [`ExecuteMsg::ApplyMinBond`] processes
+const MAX_UPDATE_LIMIT: u32 = 100;
+/// Share of the distributed rewards that can be left unwithdrawn when removing a distribution flow
+const REMOVAL_DUST_TOLERANCE: Decimal = Decimal::permille(1);
+/// Maximum number of unbonding periods that can be exited by a single [`ExecuteMsg::ExitAll`]
+pub const MAX_EXIT_PERIODS: usize = 10;
 
 // version info for migration info
 const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_CRATE_NAME"));
@@ -93,6 +119,11 @@ pub fn instantiate(
                 })
             })
             .transpose()?,
+        min_distribute_interval: 0,
+        max_reward_multiplier: default_max_reward_multiplier(),
+        power_rounding: RoundingMode::Floor,
+        instant_unbond: None,
+        min_bond_update: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -116,47 +147,178 @@ pub fn execute(
             manager,
             asset,
             rewards,
-        } => execute_create_distribution_flow(deps, info, manager, asset, rewards),
+            claimant_reward_share,
+        } => execute_create_distribution_flow(
+            deps,
+            env,
+            info,
+            manager,
+            asset,
+            rewards,
+            claimant_reward_share,
+        ),
+        ExecuteMsg::RemoveDistributionFlow { asset } => {
+            execute_remove_distribution_flow(deps, env, info, asset)
+        }
         ExecuteMsg::Rebond {
             tokens,
             bond_from,
             bond_to,
-        } => execute_rebond(deps, env, info, tokens, bond_from, bond_to),
+            owner,
+        } => execute_rebond(deps, env, info, tokens, bond_from, bond_to, owner),
+        ExecuteMsg::RebondSplit { bond_from, targets } => {
+            execute_rebond_split(deps, env, info, bond_from, targets)
+        }
         ExecuteMsg::Unbond {
             tokens: amount,
             unbonding_period,
-        } => execute_unbond(deps, env, info, amount, unbonding_period),
+            owner,
+        } => execute_unbond(deps, env, info, amount, unbonding_period, owner),
+        ExecuteMsg::InstantUnbond {
+            tokens,
+            unbonding_period,
+        } => execute_instant_unbond(deps, env, info, tokens, unbonding_period),
+        ExecuteMsg::CancelUnbonding {
+            unbonding_period,
+            amount,
+        } => execute_cancel_unbonding(deps, env, info, unbonding_period, amount),
+        ExecuteMsg::ExitAll {} => execute_exit_all(deps, env, info),
         ExecuteMsg::QuickUnbond { stakers } => execute_quick_unbond(deps, env, info, stakers),
         ExecuteMsg::UnbondAll {} => execute_unbond_all(deps, info),
         ExecuteMsg::StopUnbondAll {} => execute_stop_unbond_all(deps, info),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::ClaimAndWithdrawAll {} => execute_claim_and_withdraw_all(deps, env, info),
+        ExecuteMsg::SetClaimReceiver { receiver } => {
+            execute_set_claim_receiver(deps, info, receiver)
+        }
+        ExecuteMsg::SetRewardReceiver { receiver } => {
+            execute_set_reward_receiver(deps, info, receiver)
+        }
         ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
         ExecuteMsg::DistributeRewards { sender } => {
             execute_distribute_rewards(deps, env, info, sender)
         }
         ExecuteMsg::WithdrawRewards { owner, receiver } => {
-            execute_withdraw_rewards(deps, info, owner, receiver)
+            execute_withdraw_rewards(deps, env, info, owner, receiver)
+        }
+        ExecuteMsg::WithdrawRewardsBatch { owners } => {
+            execute_withdraw_rewards_batch(deps, env, info, owners)
+        }
+        ExecuteMsg::CompoundRewards { unbonding_period } => {
+            execute_compound_rewards(deps, env, info, unbonding_period)
+        }
+        ExecuteMsg::WithdrawAndRestake { unbonding_period } => {
+            execute_withdraw_and_restake(deps, env, info, unbonding_period)
         }
+        ExecuteMsg::SetAutoCompound {
+            enabled,
+            unbonding_period,
+        } => execute_set_auto_compound(deps, info, enabled, unbonding_period),
+        ExecuteMsg::RunAutoCompound { users } => execute_run_auto_compound(deps, env, users),
         ExecuteMsg::DelegateWithdrawal { delegated } => {
             execute_delegate_withdrawal(deps, info, delegated)
         }
-        ExecuteMsg::FundDistribution { funding_info } => {
-            execute_fund_distribution(env, deps, info, funding_info)
-        }
+        ExecuteMsg::SetDelegationRights {
+            unbond_controller,
+            withdraw_to,
+        } => execute_set_delegation_rights(deps, info, unbond_controller, withdraw_to),
+        ExecuteMsg::FundDistribution {
+            funding_info,
+            on_behalf_of,
+        } => execute_fund_distribution(env, deps, info, funding_info, on_behalf_of),
+        ExecuteMsg::FundDistributionMulti {
+            fundings,
+            on_behalf_of,
+        } => execute_fund_distribution_multi(env, deps, info, fundings, on_behalf_of),
+        ExecuteMsg::RescueTokens {
+            asset,
+            recipient,
+            amount,
+        } => execute_rescue_tokens(deps, env, info, asset, recipient, amount),
         ExecuteMsg::MigrateStake {
             amount,
             unbonding_period,
         } => execute_migrate_stake(deps, env, info, amount, unbonding_period),
+        ExecuteMsg::SetMigrationTarget { target } => {
+            execute_set_migration_target(deps, info, target)
+        }
+        ExecuteMsg::MigratePosition { target_contract } => {
+            execute_migrate_position(deps, env, info, target_contract)
+        }
+        ExecuteMsg::UpdatePowerDecay { power_decay } => {
+            execute_update_power_decay(deps, env, info, power_decay)
+        }
+        ExecuteMsg::ApplyDecay { stakers } => execute_apply_decay(deps, env, stakers),
+        ExecuteMsg::SetMinBond { value } => execute_set_min_bond(deps, info, value),
+        ExecuteMsg::ApplyMinBond { limit } => execute_apply_min_bond(deps, limit),
+        ExecuteMsg::SetMinDistributeInterval { interval } => {
+            execute_set_min_distribute_interval(deps, info, interval)
+        }
+        ExecuteMsg::SetInstantUnbond { fee, treasury } => {
+            execute_set_instant_unbond(deps, info, fee, treasury)
+        }
+        ExecuteMsg::SetMaxRewardMultiplier { max } => {
+            execute_set_max_reward_multiplier(deps, info, max)
+        }
+        ExecuteMsg::SetMaxDistributions { new_max } => {
+            execute_set_max_distributions(deps, info, new_max)
+        }
+        ExecuteMsg::SetPowerRounding { mode } => execute_set_power_rounding(deps, info, mode),
+        ExecuteMsg::RemoveUnbondingPeriod { unbonding_period } => {
+            execute_remove_unbonding_period(deps, env, info, unbonding_period)
+        }
+        ExecuteMsg::AddUnbondingPeriod {
+            unbonding_period,
+            rewards,
+        } => execute_add_unbonding_period(deps, env, info, unbonding_period, rewards),
+        ExecuteMsg::SetFundingSource { asset, source } => {
+            execute_set_funding_source(deps, info, asset, source)
+        }
+        ExecuteMsg::AcceptFundingSource {
+            asset,
+            amount,
+            epoch,
+        } => execute_accept_funding_source(deps, info, asset, amount, epoch),
+        ExecuteMsg::PrepayFunding {} => execute_prepay_funding(deps, info),
+        ExecuteMsg::PullFunding { asset } => execute_pull_funding(deps, env, asset),
+        ExecuteMsg::SetMinPowerToEarn { asset, min_power } => {
+            execute_set_min_power_to_earn(deps, info, asset, min_power)
+        }
+        ExecuteMsg::SetDistributionPaused { asset, paused } => {
+            execute_set_distribution_paused(deps, env, info, asset, paused)
+        }
+        ExecuteMsg::PauseDistribution { asset } => {
+            execute_pause_distribution(deps, env, info, asset)
+        }
+        ExecuteMsg::ResumeDistribution { asset } => {
+            execute_resume_distribution(deps, env, info, asset)
+        }
+        ExecuteMsg::UpdateDistributionMultipliers { asset, rewards } => {
+            execute_update_distribution_multipliers(deps, env, info, asset, rewards)
+        }
+        ExecuteMsg::ApplyDistributionUpdate { asset, limit } => {
+            execute_apply_distribution_update(deps, asset, limit)
+        }
+        ExecuteMsg::ScheduleMultiplierUpdate {
+            asset,
+            rewards,
+            effective_at,
+        } => execute_schedule_multiplier_update(deps, env, info, asset, rewards, effective_at),
+        ExecuteMsg::RecallUndistributed { asset, amount } => {
+            execute_recall_undistributed(deps, env, info, asset, amount)
+        }
     }
 }
 
 /// Fund a previously created distribution flow with the given amount of native tokens.
 /// Allows for providing multiple native tokens at once to update multiple distribution flows with the same optionally provided Curve.
+/// The funds are attributed to `on_behalf_of` if given, otherwise to the sender.
 pub fn execute_fund_distribution(
     env: Env,
     deps: DepsMut,
     info: MessageInfo,
     funding_info: FundingInfo,
+    on_behalf_of: Option<String>,
 ) -> Result<Response, ContractError> {
     if UNBOND_ALL.load(deps.storage)? {
         return Err(ContractError::CannotDistributeIfUnbondAll {
@@ -171,14 +333,452 @@ pub fn execute_fund_distribution(
     let api = deps.api;
     let storage = deps.storage;
 
+    let funder = addr_opt_validate(api, &on_behalf_of)?.unwrap_or(info.sender);
+
     for fund in info.funds {
         let asset = AssetInfo::Native(fund.denom);
         let validated_asset = asset.validate(api)?;
-        update_reward_config(storage, validated_asset, fund.amount, funding_info.clone())?;
+        let schedule =
+            update_reward_config(storage, &validated_asset, fund.amount, funding_info.clone())?;
+        add_funding(storage, &validated_asset, &funder, fund.amount, &schedule)?;
     }
     Ok(Response::default())
 }
 
+/// Fund multiple previously created distribution flows at once, each with its own funding curve.
+/// Native tokens have to be sent along with exactly the funded amount,
+/// cw20 tokens are transferred from the sender using an allowance.
+pub fn execute_fund_distribution_multi(
+    env: Env,
+    deps: DepsMut,
+    info: MessageInfo,
+    fundings: Vec<DistributionFunding>,
+    on_behalf_of: Option<String>,
+) -> Result<Response, ContractError> {
+    if UNBOND_ALL.load(deps.storage)? {
+        return Err(ContractError::CannotDistributeIfUnbondAll {
+            what: "funds".into(),
+        });
+    }
+    if fundings.is_empty() {
+        return Err(ContractError::NoFunds {});
+    }
+
+    let funder = addr_opt_validate(deps.api, &on_behalf_of)?.unwrap_or_else(|| info.sender.clone());
+
+    let mut funded_assets: Vec<AssetInfoValidated> = Vec::with_capacity(fundings.len());
+    let mut transfers = vec![];
+    for DistributionFunding {
+        asset,
+        funding_info,
+    } in fundings
+    {
+        if funding_info.start_time < env.block.time.seconds() {
+            return Err(ContractError::PastStartingTime {});
+        }
+        let asset = asset.validate(deps.api)?;
+        if funded_assets.contains(&asset) {
+            return Err(ContractError::DuplicateFunding(asset));
+        }
+
+        let amount = funding_info.amount;
+        match &asset {
+            AssetInfoValidated::Native(denom) => {
+                let sent = info
+                    .funds
+                    .iter()
+                    .find(|coin| &coin.denom == denom)
+                    .map(|coin| coin.amount)
+                    .unwrap_or_default();
+                if sent != amount {
+                    return Err(ContractError::FundingMismatch(asset));
+                }
+            }
+            AssetInfoValidated::Token(token) => {
+                transfers.push(WasmMsg::Execute {
+                    contract_addr: token.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                        owner: info.sender.to_string(),
+                        recipient: env.contract.address.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                });
+            }
+        }
+
+        let schedule = update_reward_config(deps.storage, &asset, amount, funding_info)?;
+        add_funding(deps.storage, &asset, &funder, amount, &schedule)?;
+        funded_assets.push(asset);
+    }
+
+    // all sent native tokens need to be used for a funding
+    for coin in &info.funds {
+        let asset = AssetInfoValidated::Native(coin.denom.clone());
+        if !funded_assets.contains(&asset) {
+            return Err(ContractError::FundingMismatch(asset));
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(transfers)
+        .add_attribute("action", "fund_distribution_multi")
+        .add_attribute("funder", funder)
+        .add_attribute(
+            "assets",
+            funded_assets
+                .iter()
+                .map(|asset| asset.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        ))
+}
+
+/// Links the distribution flow of the given asset to a funding source, or unlinks it.
+/// Only the manager of the distribution flow can do this.
+pub fn execute_set_funding_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: AssetInfo,
+    source: Option<FundingSource>,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    if info.sender != distribution.manager {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // native tokens prepaid by the previous source are returned to it
+    let previous = FUNDING_SOURCES.may_load(deps.storage, &asset)?;
+    let mut resp = Response::new()
+        .add_attribute("action", "set_funding_source")
+        .add_attribute("asset", asset.to_string());
+    let mut prepaid = Uint128::zero();
+    if let Some(previous) = previous {
+        match &source {
+            Some(source) if source.address == previous.address => prepaid = previous.prepaid,
+            _ if !previous.prepaid.is_zero() => {
+                resp = resp.add_message(
+                    asset
+                        .with_balance(previous.prepaid)
+                        .into_msg(previous.address)?,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    match source {
+        Some(FundingSource {
+            address,
+            amount,
+            epoch,
+        }) => {
+            if amount.is_zero() {
+                return Err(ContractError::NoFunds {});
+            }
+            if epoch == 0 {
+                return Err(ContractError::ZeroRewardDuration {});
+            }
+            let address = deps.api.addr_validate(&address)?;
+            resp = resp.add_attribute("source", address.as_str());
+            FUNDING_SOURCES.save(
+                deps.storage,
+                &asset,
+                &FundingSourceInfo {
+                    address,
+                    amount,
+                    epoch,
+                    prepaid,
+                    // the source has to accept the new terms
+                    accepted: false,
+                },
+            )?;
+        }
+        None => FUNDING_SOURCES.remove(deps.storage, &asset),
+    }
+
+    Ok(resp)
+}
+
+/// Confirms the terms of the funding source of the given asset, allowing to pull funds from it.
+/// Only the configured funding source can do this.
+pub fn execute_accept_funding_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: AssetInfo,
+    amount: Uint128,
+    epoch: u64,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let mut source = FUNDING_SOURCES
+        .may_load(deps.storage, &asset)?
+        .ok_or_else(|| ContractError::NoFundingSource(asset.clone()))?;
+    if source.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    // the manager could have changed the terms in the meantime
+    if source.amount != amount || source.epoch != epoch {
+        return Err(ContractError::FundingSourceTermsMismatch {});
+    }
+
+    source.accepted = true;
+    FUNDING_SOURCES.save(deps.storage, &asset, &source)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_funding_source")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("source", info.sender))
+}
+
+/// Deposits the sent native tokens with the funding sources configured for them
+pub fn execute_prepay_funding(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    if info.funds.is_empty() {
+        return Err(ContractError::NoFunds {});
+    }
+
+    for coin in &info.funds {
+        let asset = AssetInfoValidated::Native(coin.denom.clone());
+        let mut source = FUNDING_SOURCES
+            .may_load(deps.storage, &asset)?
+            .ok_or_else(|| ContractError::NoFundingSource(asset.clone()))?;
+        if source.address != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        source.prepaid = source.prepaid.checked_add(coin.amount)?;
+        FUNDING_SOURCES.save(deps.storage, &asset, &source)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "prepay_funding")
+        .add_attribute("source", info.sender))
+}
+
+/// Starts a new funding curve from the configured funding source once the current one has ended
+pub fn execute_pull_funding(
+    deps: DepsMut,
+    env: Env,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    if UNBOND_ALL.load(deps.storage)? {
+        return Err(ContractError::CannotDistributeIfUnbondAll {
+            what: "funds".into(),
+        });
+    }
+
+    let asset = asset.validate(deps.api)?;
+    let mut source = FUNDING_SOURCES
+        .may_load(deps.storage, &asset)?
+        .ok_or_else(|| ContractError::NoFundingSource(asset.clone()))?;
+
+    let now = env.block.time.seconds();
+    if !REWARD_CURVE
+        .load(deps.storage, &asset)?
+        .value(now)
+        .is_zero()
+    {
+        return Err(ContractError::FundingNotEnded(asset));
+    }
+
+    let amount = source.amount;
+    let mut resp = Response::new()
+        .add_attribute("action", "pull_funding")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("source", source.address.as_str())
+        .add_attribute("amount", amount);
+    match &asset {
+        AssetInfoValidated::Native(_) => {
+            source.prepaid = source.prepaid.checked_sub(amount).map_err(|_| {
+                ContractError::InsufficientPrepaidFunding {
+                    available: source.prepaid,
+                }
+            })?;
+            FUNDING_SOURCES.save(deps.storage, &asset, &source)?;
+        }
+        AssetInfoValidated::Token(token) => {
+            // an allowance alone is no consent to fund this flow
+            if !source.accepted {
+                return Err(ContractError::FundingSourceNotAccepted(asset));
+            }
+            resp = resp.add_message(WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: source.address.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            });
+        }
+    }
+
+    let schedule = update_reward_config(
+        deps.storage,
+        &asset,
+        amount,
+        FundingInfo {
+            start_time: now,
+            distribution_duration: source.epoch,
+            amount,
+        },
+    )?;
+    add_funding(deps.storage, &asset, &source.address, amount, &schedule)?;
+
+    Ok(resp)
+}
+
+/// Adds the given amount to the funds provided by `funder` for the given asset
+/// and the rewards locked by its `schedule` to the ones that can be recalled for them.
+fn add_funding(
+    storage: &mut dyn Storage,
+    asset: &AssetInfoValidated,
+    funder: &Addr,
+    amount: Uint128,
+    schedule: &Curve,
+) -> StdResult<()> {
+    FUNDERS.update(storage, (asset, funder), |funded| -> StdResult<_> {
+        Ok(funded.unwrap_or_default().checked_add(amount)?)
+    })?;
+    FUNDER_SCHEDULES.update(storage, (asset, funder), |previous| -> StdResult<_> {
+        Ok(match previous {
+            Some(previous) => previous.combine(schedule),
+            None => schedule.clone(),
+        })
+    })?;
+    add_funded(storage, asset, amount)
+}
+
+/// Returns up to `amount` of the still locked rewards of the given distribution flow to its funders,
+/// proportionally to how much of their funding is still locked, and scales down the rest of
+/// the reward curve accordingly.
+/// Only the manager of the distribution flow can do this.
+pub fn execute_recall_undistributed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    if info.sender != distribution.manager {
+        return Err(ContractError::Unauthorized {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+
+    // only what the curve still locks can be recalled, released rewards belong to the stakers
+    let now = env.block.time.seconds();
+    let curve = REWARD_CURVE.load(deps.storage, &asset)?;
+    let locked = curve.value(now);
+    if amount > locked {
+        return Err(ContractError::NotEnoughLocked { available: locked });
+    }
+    REWARD_CURVE.save(
+        deps.storage,
+        &asset,
+        &scale_down_from(&curve, now, locked - amount),
+    )?;
+
+    // only funders whose rewards are still locked get something back,
+    // so nobody is refunded from someone else's over-funding
+    let funders = FUNDER_SCHEDULES
+        .prefix(&asset)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (funder, schedule) = item?;
+            let refundable = schedule.value(now);
+            Ok((funder, schedule, refundable))
+        })
+        .filter(|item| !matches!(item, Ok((_, _, refundable)) if refundable.is_zero()))
+        .collect::<StdResult<Vec<_>>>()?;
+    let total_refundable = funders
+        .iter()
+        .fold(Uint128::zero(), |total, (_, _, refundable)| {
+            total + refundable
+        });
+    if total_refundable.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+
+    let mut resp = Response::new()
+        .add_attribute("action", "recall_undistributed")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("amount", amount);
+    let mut remaining = amount;
+    for (i, (funder, schedule, refundable)) in funders.iter().enumerate() {
+        // the last funder gets the rounding leftovers
+        let share = if i + 1 == funders.len() {
+            remaining
+        } else {
+            amount.multiply_ratio(*refundable, total_refundable)
+        };
+        remaining -= share;
+        if share.is_zero() {
+            continue;
+        }
+        FUNDER_SCHEDULES.save(
+            deps.storage,
+            (&asset, funder),
+            &scale_down_from(schedule, now, refundable.saturating_sub(share)),
+        )?;
+        FUNDERS.update(deps.storage, (&asset, funder), |funded| -> StdResult<_> {
+            Ok(funded.unwrap_or_default().saturating_sub(share))
+        })?;
+        resp = resp.add_message(asset.with_balance(share).into_msg(funder.clone())?);
+    }
+    sub_funded(deps.storage, &asset, amount)?;
+
+    Ok(resp)
+}
+
+/// Sends accidentally sent tokens to the given recipient.
+/// Only tokens that do not back any of the contract's obligations can be moved.
+pub fn execute_rescue_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let asset = asset.validate(deps.api)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    let available = rescuable_amount(deps.as_ref(), &env, &asset)?;
+    if amount > available {
+        return Err(ContractError::RescueAmountTooHigh { available });
+    }
+
+    Ok(Response::new()
+        .add_message(asset.with_balance(amount).into_msg(recipient.clone())?)
+        .add_attribute("action", "rescue_tokens")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
+/// Returns the amount of the given asset held by this contract that exceeds its obligations.
+fn rescuable_amount(deps: Deps, env: &Env, asset: &AssetInfoValidated) -> StdResult<Uint128> {
+    let balance = asset.query_balance(&deps.querier, &env.contract.address)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let obligations = if DISTRIBUTION.has(deps.storage, asset) {
+        // the whole balance of a distributed asset is owed to the stakers eventually
+        balance
+    } else if *asset == AssetInfoValidated::Token(config.cw20_contract) {
+        // bonded tokens and unbonding tokens waiting to be claimed
+        TOTAL_STAKED.load(deps.storage)?.total()
+    } else {
+        Uint128::zero()
+    };
+
+    Ok(balance.saturating_sub(obligations))
+}
+
 /// Triggers moving the stake from this staking contract to another staking contract
 pub fn execute_migrate_stake(
     mut deps: DepsMut,
@@ -192,8 +792,10 @@ pub fn execute_migrate_stake(
         .converter
         .as_ref()
         .ok_or(ContractError::NoConverter {})?;
+    assert_unbond_controller(deps.storage, &info.sender, &info.sender)?;
+    record_interaction(deps.storage, &cfg, &info.sender, &env.block)?;
 
-    remove_stake_without_total(
+    let staker_count_event = remove_stake_without_total(
         deps.branch(),
         &env,
         &cfg,
@@ -212,6 +814,7 @@ pub fn execute_migrate_stake(
 
     // directly send the tokens to the converter instead of providing claim
     Ok(Response::new()
+        .add_events(staker_count_event)
         // send the tokens to the converter
         .add_message(WasmMsg::Execute {
             contract_addr: cfg.cw20_contract.into_string(),
@@ -238,44 +841,153 @@ pub fn execute_migrate_stake(
         .add_attribute("sender", info.sender))
 }
 
-/// Update reward config for the given asset with an additional amount of funding
-fn update_reward_config(
-    storage: &mut dyn Storage,
-    validated_asset: AssetInfoValidated,
-    sent_amount: Uint128,
-    FundingInfo {
-        start_time,
-        distribution_duration,
-        amount,
-    }: FundingInfo,
-) -> Result<(), ContractError> {
-    // How can we validate the amount and curve? Monotonic decreasing check is below, given this is there still a need to test the amount?
-    let previous_reward_curve = REWARD_CURVE.load(storage, &validated_asset)?;
-
-    let end_time = start_time + distribution_duration;
-    let schedule = Curve::saturating_linear((start_time, amount.u128()), (end_time, 0));
+pub fn execute_set_migration_target(
+    deps: DepsMut,
+    info: MessageInfo,
+    target: Option<String>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
-    let (min, max) = schedule.range();
-    // Validate the the curve locks at most the amount provided and also fully unlocks all rewards sent
-    if min != 0 || max > sent_amount.u128() {
-        return Err(ContractError::InvalidRewards {});
+    let target = addr_opt_validate(deps.api, &target)?;
+    match &target {
+        Some(target) => MIGRATION_TARGET.save(deps.storage, target)?,
+        None => MIGRATION_TARGET.remove(deps.storage),
     }
 
-    // combine the two curves
-    let new_reward_curve = previous_reward_curve.combine(&schedule);
-    new_reward_curve.validate_monotonic_decreasing()?;
-
-    REWARD_CURVE.save(storage, &validated_asset, &new_reward_curve)?;
-    Ok(())
+    Ok(Response::new()
+        .add_attribute("action", "set_migration_target")
+        .add_attribute(
+            "target",
+            target.map_or_else(|| "none".to_string(), Addr::into_string),
+        ))
 }
 
-/// Create a new rewards distribution flow for the given asset as a reward
-pub fn execute_create_distribution_flow(
-    deps: DepsMut,
+/// Moves all of the sender's unlocked stake into the migration target contract,
+/// delegating it there for the same unbonding periods. Rewards are withdrawn first.
+pub fn execute_migrate_position(
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    manager: String,
-    asset: AssetInfo,
+    target_contract: String,
+) -> Result<Response, ContractError> {
+    let target = deps.api.addr_validate(&target_contract)?;
+    if MIGRATION_TARGET.may_load(deps.storage)?.as_ref() != Some(&target) {
+        return Err(ContractError::InvalidMigrationTarget(target));
+    }
+    let cfg = CONFIG.load(deps.storage)?;
+    assert_unbond_controller(deps.storage, &info.sender, &info.sender)?;
+
+    let withdraw_resp =
+        execute_withdraw_rewards(deps.branch(), env.clone(), info.clone(), None, None)?;
+
+    let stakes = STAKE
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|stake| {
+            let (unbonding_period, bonding_info) = stake?;
+            Ok((unbonding_period, bonding_info.total_unlocked(&env)))
+        })
+        .filter(|stake| !matches!(stake, Ok((_, amount)) if amount.is_zero()))
+        .collect::<StdResult<Vec<_>>>()?;
+    if stakes.is_empty() {
+        return Err(ContractError::NothingToMigrate {});
+    }
+    // rewards keep going to the same address in the target contract
+    let withdraw_to = DELEGATION_RIGHTS
+        .may_load(deps.storage, &info.sender)?
+        .and_then(|rights| rights.withdraw_to);
+
+    let mut resp = Response::new()
+        .add_attribute("action", "migrate_position")
+        .add_attribute("sender", info.sender.as_str())
+        .add_attribute("target", target.as_str())
+        .add_submessages(withdraw_resp.messages);
+    let mut total = Uint128::zero();
+    for (unbonding_period, amount) in stakes {
+        let staker_count_event = remove_stake_without_total(
+            deps.branch(),
+            &env,
+            &cfg,
+            &info.sender,
+            unbonding_period,
+            amount,
+        )?;
+        total += amount;
+        resp = resp.add_events(staker_count_event);
+
+        // delegate the tokens in the target contract on behalf of the sender
+        resp = resp
+            .add_message(WasmMsg::Execute {
+                contract_addr: cfg.cw20_contract.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: target.to_string(),
+                    amount,
+                    msg: to_binary(&ReceiveMsg::Delegate {
+                        unbonding_period,
+                        delegate_as: Some(info.sender.to_string()),
+                        unbond_controller: None,
+                        withdraw_to: withdraw_to.as_ref().map(Addr::to_string),
+                    })?,
+                })?,
+                funds: vec![],
+            })
+            .add_attribute(format!("migrated_{}", unbonding_period), amount);
+    }
+
+    TOTAL_STAKED.update::<_, StdError>(deps.storage, |token_info| {
+        Ok(TokenInfo {
+            staked: token_info.staked.saturating_sub(total),
+            unbonding: token_info.unbonding,
+        })
+    })?;
+
+    Ok(resp)
+}
+
+/// Update reward config for the given asset with an additional amount of funding.
+/// Returns the reward schedule of this funding.
+fn update_reward_config(
+    storage: &mut dyn Storage,
+    validated_asset: &AssetInfoValidated,
+    sent_amount: Uint128,
+    FundingInfo {
+        start_time,
+        distribution_duration,
+        amount,
+    }: FundingInfo,
+) -> Result<Curve, ContractError> {
+    // How can we validate the amount and curve? Monotonic decreasing check is below, given this is there still a need to test the amount?
+    let previous_reward_curve = REWARD_CURVE.load(storage, validated_asset)?;
+    if DISTRIBUTION.load(storage, validated_asset)?.paused {
+        return Err(ContractError::DistributionPaused(validated_asset.clone()));
+    }
+
+    let end_time = start_time + distribution_duration;
+    let schedule = Curve::saturating_linear((start_time, amount.u128()), (end_time, 0));
+
+    let (min, max) = schedule.range();
+    // Validate the the curve locks at most the amount provided and also fully unlocks all rewards sent
+    if min != 0 || max > sent_amount.u128() {
+        return Err(ContractError::InvalidRewards {});
+    }
+
+    // combine the two curves
+    let new_reward_curve = previous_reward_curve.combine(&schedule);
+    new_reward_curve.validate_monotonic_decreasing()?;
+
+    REWARD_CURVE.save(storage, validated_asset, &new_reward_curve)?;
+    Ok(schedule)
+}
+
+/// Create a new rewards distribution flow for the given asset as a reward
+pub fn execute_create_distribution_flow(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    manager: String,
+    asset: AssetInfo,
     rewards: Vec<(UnbondingPeriod, Decimal)>,
+    claimant_reward_share: Option<Decimal>,
 ) -> Result<Response, ContractError> {
     // only admin can create distribution flow
     ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
@@ -284,27 +996,18 @@ pub fn execute_create_distribution_flow(
     let asset = asset.validate(deps.api)?;
     let manager = deps.api.addr_validate(&manager)?;
 
-    // make sure the asset is not the staked token, since we distribute this contract's balance
-    // and we definitely do not want to distribute the staked tokens.
+    // the staked token can be distributed as well, the bonded and unbonding tokens are
+    // excluded from its reward balance.
     let config = CONFIG.load(deps.storage)?;
-    if let AssetInfoValidated::Token(addr) = &asset {
-        if addr == config.cw20_contract {
-            return Err(ContractError::InvalidAsset {});
-        }
+    // neither of these contracts can act as a manager
+    if manager == env.contract.address || manager == config.cw20_contract {
+        return Err(ContractError::InvalidManager(manager));
     }
 
-    // validate rewards unbonding periods
-    if rewards
-        .iter()
-        .map(|(period, _)| period)
-        .ne(config.unbonding_periods.iter())
-    {
-        return Err(ContractError::InvalidRewards {});
-    }
-    // make sure rewards are monotonically increasing (equality is allowed)
-    // this assumes that `config.unbonding_periods` (and therefore also `rewards`) is sorted (checked in instantiate)
-    if rewards.windows(2).any(|w| w[0].1 > w[1].1) {
-        return Err(ContractError::InvalidRewards {});
+    validate_reward_multipliers(&config, &rewards)?;
+    let claimant_reward_share = claimant_reward_share.unwrap_or_default();
+    if claimant_reward_share > Decimal::one() {
+        return Err(ContractError::InvalidClaimantRewardShare {});
     }
 
     // make sure to respect the distribution count limit to create an upper bound for all the staking operations
@@ -323,6 +1026,12 @@ pub fn execute_create_distribution_flow(
     }
 
     REWARD_CURVE.save(deps.storage, &asset, &Curve::constant(0))?;
+    record_multiplier_change(
+        deps.storage,
+        &asset,
+        env.block.time.seconds(),
+        rewards.clone(),
+    )?;
 
     DISTRIBUTION.save(
         deps.storage,
@@ -334,12 +1043,246 @@ pub fn execute_create_distribution_flow(
             shares_leftover: 0,
             distributed_total: Uint128::zero(),
             withdrawable_total: Uint128::zero(),
+            claimant_reward_share,
+            min_power_to_earn: Uint128::zero(),
+            excluded_power: Uint128::zero(),
+            paused: false,
+            paused_at: None,
+            rounding_surplus: Uint128::zero(),
+            update: None,
+            generation: REMOVED_FLOWS
+                .may_load(deps.storage, &asset)?
+                .unwrap_or_default(),
         },
     )?;
 
     Ok(Response::default())
 }
 
+pub fn execute_remove_distribution_flow(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    if info.sender != distribution.manager && !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // everything beyond the distributed rewards still has to be distributed, including prepaid funding
+    let prepaid = FUNDING_SOURCES
+        .may_load(deps.storage, &asset)?
+        .map(|source| source.prepaid)
+        .unwrap_or_default();
+    let balance = undistributed_rewards(deps.as_ref(), &cfg, &asset, env.contract.address)?;
+    if !prepaid.is_zero() || balance > distribution.withdrawable_total {
+        return Err(ContractError::DistributionNotEmpty(asset));
+    }
+
+    // the distributed rewards only need to be withdrawn down to rounding leftovers and dust
+    // that is not worth claiming
+    if distribution.withdrawable_total > distribution.distributed_total * REMOVAL_DUST_TOLERANCE {
+        return Err(ContractError::DistributionNotEmpty(asset));
+    }
+
+    // the adjustments of the stakers are not removed here, a new flow for the same asset
+    // ignores them and they are overwritten once the staker interacts with it
+    REMOVED_FLOWS.update(deps.storage, &asset, |removed| -> StdResult<_> {
+        Ok(removed.unwrap_or_default() + 1)
+    })?;
+    let funders = FUNDERS
+        .prefix(&asset)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for funder in funders {
+        FUNDERS.remove(deps.storage, (&asset, &funder));
+        FUNDER_SCHEDULES.remove(deps.storage, (&asset, &funder));
+    }
+    DISTRIBUTION.remove(deps.storage, &asset);
+    REWARD_CURVE.remove(deps.storage, &asset);
+    FUNDING_SOURCES.remove(deps.storage, &asset);
+    FLOW_TOTALS.remove(deps.storage, &asset);
+    SCHEDULED_MULTIPLIERS.remove(deps.storage, &asset);
+    MULTIPLIER_HISTORY.remove(deps.storage, &asset);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_distribution_flow")
+        .add_attribute("asset", asset.to_string()))
+}
+
+/// Checks that the given multipliers cover exactly the configured unbonding periods,
+/// never decrease with longer periods and stay within the configured maximum.
+fn validate_reward_multipliers(
+    config: &Config,
+    rewards: &[(UnbondingPeriod, Decimal)],
+) -> Result<(), ContractError> {
+    // validate rewards unbonding periods
+    if rewards
+        .iter()
+        .map(|(period, _)| period)
+        .ne(config.unbonding_periods.iter())
+    {
+        return Err(ContractError::InvalidRewards {});
+    }
+    // make sure rewards are monotonically increasing (equality is allowed)
+    // this assumes that `config.unbonding_periods` (and therefore also `rewards`) is sorted (checked in instantiate)
+    if rewards.windows(2).any(|w| w[0].1 > w[1].1) {
+        return Err(ContractError::InvalidRewards {});
+    }
+    // the multipliers are sorted, so the last one is the highest
+    if let Some(&(_, multiplier)) = rewards.last() {
+        if multiplier > config.max_reward_multiplier {
+            return Err(ContractError::RewardMultiplierTooHigh {
+                multiplier,
+                max: config.max_reward_multiplier,
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_update_distribution_multipliers(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    rewards: Vec<(UnbondingPeriod, Decimal)>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let asset = asset.validate(deps.api)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    validate_reward_multipliers(&cfg, &rewards)?;
+
+    start_distribution_update(deps.storage, &asset, rewards, env.block.time.seconds())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_distribution_multipliers")
+        .add_attribute("asset", asset.to_string()))
+}
+
+/// Replaces the rewards multipliers of the distribution flow of `asset`.
+/// The stakers keep their current multipliers until [`ExecuteMsg::ApplyDistributionUpdate`]
+/// reaches them, so the flow stops distributing until then.
+pub(crate) fn start_distribution_update(
+    storage: &mut dyn Storage,
+    asset: &AssetInfoValidated,
+    rewards: Vec<(UnbondingPeriod, Decimal)>,
+    now: u64,
+) -> Result<(), ContractError> {
+    let mut distribution = DISTRIBUTION.load(storage, asset)?;
+    begin_distribution_update(storage, asset, &mut distribution)?;
+    distribution.reward_multipliers = rewards.clone();
+    DISTRIBUTION.save(storage, asset, &distribution)?;
+    record_multiplier_change(storage, asset, now, rewards)?;
+
+    Ok(())
+}
+
+/// Makes the stakers keep the current rewards multipliers and `min_power_to_earn` of
+/// `distribution` until [`ExecuteMsg::ApplyDistributionUpdate`] reaches them.
+/// Fails if another update is still running.
+fn begin_distribution_update(
+    storage: &dyn Storage,
+    asset: &AssetInfoValidated,
+    distribution: &mut Distribution,
+) -> Result<(), ContractError> {
+    if distribution.update.is_some() {
+        return Err(ContractError::DistributionUpdateInProgress(asset.clone()));
+    }
+    // nothing to apply if nobody has rewards power
+    if !stakers_after(storage, None, 1)?.is_empty() {
+        distribution.update = Some(DistributionUpdate {
+            old_rewards: distribution.reward_multipliers.clone(),
+            old_min_power_to_earn: distribution.min_power_to_earn,
+            last_updated: None,
+        });
+    }
+    Ok(())
+}
+
+/// Applies the pending update of the distribution flow of `asset` to the next `limit` stakers,
+/// re-applying the points correction of the ones whose rewards power changes.
+pub fn execute_apply_distribution_update(
+    deps: DepsMut,
+    asset: AssetInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    let mut distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    let last_updated = match &distribution.update {
+        Some(update) => update.last_updated.clone(),
+        None => return Err(ContractError::NoDistributionUpdate(asset)),
+    };
+
+    let limit = limit.unwrap_or(DEFAULT_UPDATE_LIMIT).min(MAX_UPDATE_LIMIT) as usize;
+    let stakers = stakers_after(deps.storage, last_updated.as_ref(), limit)?;
+    for staker in &stakers {
+        let old_reward_power = distribution.calc_rewards_power(deps.storage, &cfg, staker)?;
+        if let Some(update) = distribution.update.as_mut() {
+            update.last_updated = Some(staker.clone());
+        }
+        let new_reward_power = distribution.calc_rewards_power(deps.storage, &cfg, staker)?;
+        update_rewards(
+            deps.storage,
+            &asset,
+            staker,
+            &mut distribution,
+            old_reward_power,
+            new_reward_power,
+        )?;
+    }
+    let finished = stakers.len() < limit;
+    if finished {
+        distribution.update = None;
+    }
+    DISTRIBUTION.save(deps.storage, &asset, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "apply_distribution_update")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("stakers", stakers.len().to_string())
+        .add_attribute("finished", finished.to_string()))
+}
+
+pub fn execute_schedule_multiplier_update(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    rewards: Vec<(UnbondingPeriod, Decimal)>,
+    effective_at: u64,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    if info.sender != distribution.manager {
+        return Err(ContractError::Unauthorized {});
+    }
+    if effective_at <= env.block.time.seconds() {
+        return Err(ContractError::EffectiveTimeNotInFuture {});
+    }
+    let cfg = CONFIG.load(deps.storage)?;
+    validate_reward_multipliers(&cfg, &rewards)?;
+
+    SCHEDULED_MULTIPLIERS.save(
+        deps.storage,
+        &asset,
+        &ScheduledMultipliers {
+            rewards,
+            effective_at,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "schedule_multiplier_update")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("effective_at", effective_at.to_string()))
+}
+
 pub fn execute_rebond(
     deps: DepsMut,
     env: Env,
@@ -347,11 +1290,15 @@ pub fn execute_rebond(
     amount: Uint128,
     bond_from: u64,
     bond_to: u64,
+    owner: Option<String>,
 ) -> Result<Response, ContractError> {
     if UNBOND_ALL.load(deps.storage)? {
         return Err(ContractError::CannotRebondIfUnbondAll {});
     }
 
+    let staker = addr_opt_validate(deps.api, &owner)?.unwrap_or_else(|| info.sender.clone());
+    assert_unbond_controller(deps.storage, &staker, &info.sender)?;
+
     // Raise if no amount was provided
     if amount == Uint128::zero() {
         return Err(ContractError::NoRebondAmount {});
@@ -369,20 +1316,24 @@ pub fn execute_rebond(
     if cfg.unbonding_periods.binary_search(&bond_to).is_err() {
         return Err(ContractError::NoUnbondingPeriodFound(bond_to));
     }
+    // a rebond by the unbond controller does not count as an interaction of the staker
+    if staker == info.sender {
+        record_interaction(deps.storage, &cfg, &staker, &env.block)?;
+    }
 
     let distributions: Vec<_> = DISTRIBUTION
         .range(deps.storage, None, None, Order::Ascending)
         .collect::<StdResult<Vec<_>>>()?;
 
     // calculate rewards power before updating the stake
-    let old_rewards = calc_rewards_powers(deps.storage, &cfg, &info.sender, distributions.iter())?;
+    let old_rewards = calc_rewards_powers(deps.storage, &cfg, &staker, distributions.iter())?;
 
     // Reduce the bond_from
     let mut old_stake_from = Uint128::zero();
     let new_stake_from = STAKE
         .update(
             deps.storage,
-            (&info.sender, bond_from),
+            (&staker, bond_from),
             |bonding_info| -> StdResult<_> {
                 let mut bonding_info = bonding_info.unwrap_or_default();
                 old_stake_from = bonding_info.total_stake();
@@ -398,7 +1349,7 @@ pub fn execute_rebond(
     let new_stake_to = STAKE
         .update(
             deps.storage,
-            (&info.sender, bond_to),
+            (&staker, bond_to),
             |bonding_info| -> StdResult<_> {
                 let mut bonding_info = bonding_info.unwrap_or_default();
                 old_stake_to = bonding_info.total_stake();
@@ -419,21 +1370,29 @@ pub fn execute_rebond(
     update_total_stake(
         deps.storage,
         &cfg,
+        &staker,
         bond_from,
         old_stake_from,
         new_stake_from,
     )?;
-    update_total_stake(deps.storage, &cfg, bond_to, old_stake_to, new_stake_to)?;
+    update_total_stake(
+        deps.storage,
+        &cfg,
+        &staker,
+        bond_to,
+        old_stake_to,
+        new_stake_to,
+    )?;
 
     // update the adjustment data for all distributions
     for ((asset_info, mut distribution), old_reward_power) in
         distributions.into_iter().zip(old_rewards.into_iter())
     {
-        let new_reward_power = distribution.calc_rewards_power(deps.storage, &cfg, &info.sender)?;
+        let new_reward_power = distribution.calc_rewards_power(deps.storage, &cfg, &staker)?;
         update_rewards(
             deps.storage,
             &asset_info,
-            &info.sender,
+            &staker,
             &mut distribution,
             old_reward_power,
             new_reward_power,
@@ -445,74 +1404,249 @@ pub fn execute_rebond(
 
     Ok(Response::new()
         .add_attribute("action", "rebond")
+        .add_attribute("owner", staker)
         .add_attribute("amount", amount)
         .add_attribute("bond_from", bond_from.to_string())
         .add_attribute("bond_to", bond_to.to_string()))
 }
 
-pub fn execute_bond(
+/// Rebonds from `bond_from` into multiple target periods at once.
+pub fn execute_rebond_split(
     deps: DepsMut,
     env: Env,
-    sender_cw20_contract: Addr,
-    amount: Uint128,
-    unbonding_period: u64,
-    sender: Addr,
+    info: MessageInfo,
+    bond_from: u64,
+    targets: Vec<(UnbondingPeriod, Uint128)>,
 ) -> Result<Response, ContractError> {
-    let delegations = vec![(sender.to_string(), amount)];
-    let res = execute_mass_bond(
-        deps,
-        env,
-        sender_cw20_contract,
-        amount,
-        unbonding_period,
-        delegations,
-    )?;
-    Ok(res.add_attribute("sender", sender))
-}
+    if UNBOND_ALL.load(deps.storage)? {
+        return Err(ContractError::CannotRebondIfUnbondAll {});
+    }
+    assert_unbond_controller(deps.storage, &info.sender, &info.sender)?;
 
-pub fn execute_mass_bond(
-    deps: DepsMut,
-    _env: Env,
-    sender_cw20_contract: Addr,
-    amount_sent: Uint128,
-    unbonding_period: u64,
-    delegate_to: Vec<(String, Uint128)>,
-) -> Result<Response, ContractError> {
     let cfg = CONFIG.load(deps.storage)?;
 
-    // ensure that cw20 token contract's addresses matches
-    if cfg.cw20_contract != sender_cw20_contract {
-        return Err(ContractError::Cw20AddressesNotMatch {
-            got: sender_cw20_contract.into(),
-            expected: cfg.cw20_contract.into(),
-        });
+    if cfg.unbonding_periods.binary_search(&bond_from).is_err() {
+        return Err(ContractError::NoUnbondingPeriodFound(bond_from));
     }
-
-    if cfg
-        .unbonding_periods
-        .binary_search(&unbonding_period)
-        .is_err()
-    {
-        return Err(ContractError::NoUnbondingPeriodFound(unbonding_period));
+    for &(bond_to, amount) in &targets {
+        if amount.is_zero() {
+            return Err(ContractError::NoRebondAmount {});
+        }
+        if bond_from == bond_to {
+            return Err(ContractError::SameUnbondingRebond {});
+        }
+        if cfg.unbonding_periods.binary_search(&bond_to).is_err() {
+            return Err(ContractError::NoUnbondingPeriodFound(bond_to));
+        }
     }
-
+    let total: Uint128 = targets.iter().map(|(_, amount)| amount).sum();
+    if total.is_zero() {
+        return Err(ContractError::NoRebondAmount {});
+    }
+    record_interaction(deps.storage, &cfg, &info.sender, &env.block)?;
+
+    let distributions: Vec<_> = DISTRIBUTION
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // calculate rewards power before updating the stake
+    let old_rewards = calc_rewards_powers(deps.storage, &cfg, &info.sender, distributions.iter())?;
+
+    // Reduce the bond_from by the sum of all targets
+    let mut old_stake_from = Uint128::zero();
+    let new_stake_from = STAKE
+        .update(
+            deps.storage,
+            (&info.sender, bond_from),
+            |bonding_info| -> StdResult<_> {
+                let mut bonding_info = bonding_info.unwrap_or_default();
+                old_stake_from = bonding_info.total_stake();
+                // Release the stake, also accounting for locked tokens, raising if there is not enough tokens
+                bonding_info.release_stake(&env, total)?;
+                Ok(bonding_info)
+            },
+        )?
+        .total_stake();
+    update_total_stake(
+        deps.storage,
+        &cfg,
+        &info.sender,
+        bond_from,
+        old_stake_from,
+        new_stake_from,
+    )?;
+
+    // Increase each of the targets
+    for &(bond_to, amount) in &targets {
+        let mut old_stake_to = Uint128::zero();
+        let new_stake_to = STAKE
+            .update(
+                deps.storage,
+                (&info.sender, bond_to),
+                |bonding_info| -> StdResult<_> {
+                    let mut bonding_info = bonding_info.unwrap_or_default();
+                    old_stake_to = bonding_info.total_stake();
+
+                    if bond_from > bond_to {
+                        bonding_info.add_locked_tokens(
+                            env.block.time.plus_seconds(bond_from - bond_to),
+                            amount,
+                        );
+                    } else {
+                        bonding_info.add_unlocked_tokens(amount);
+                    };
+                    Ok(bonding_info)
+                },
+            )?
+            .total_stake();
+        update_total_stake(
+            deps.storage,
+            &cfg,
+            &info.sender,
+            bond_to,
+            old_stake_to,
+            new_stake_to,
+        )?;
+    }
+
+    // update the adjustment data for all distributions
+    for ((asset_info, mut distribution), old_reward_power) in
+        distributions.into_iter().zip(old_rewards.into_iter())
+    {
+        let new_reward_power = distribution.calc_rewards_power(deps.storage, &cfg, &info.sender)?;
+        update_rewards(
+            deps.storage,
+            &asset_info,
+            &info.sender,
+            &mut distribution,
+            old_reward_power,
+            new_reward_power,
+        )?;
+
+        // save updated distribution
+        DISTRIBUTION.save(deps.storage, &asset_info, &distribution)?;
+    }
+
+    let targets = targets
+        .iter()
+        .map(|(bond_to, amount)| format!("{}:{}", bond_to, amount))
+        .collect::<Vec<_>>()
+        .join(",");
+    Ok(Response::new()
+        .add_attribute("action", "rebond_split")
+        .add_attribute("amount", total)
+        .add_attribute("bond_from", bond_from.to_string())
+        .add_attribute("targets", targets))
+}
+
+pub fn execute_bond(
+    deps: DepsMut,
+    env: Env,
+    sender_cw20_contract: Addr,
+    amount: Uint128,
+    unbonding_period: u64,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    let delegations = vec![(sender.to_string(), amount)];
+    let res = execute_mass_bond(
+        deps,
+        env,
+        sender_cw20_contract,
+        amount,
+        unbonding_period,
+        delegations,
+    )?;
+    Ok(res.add_attribute("sender", sender))
+}
+
+/// Splits `amount` according to the given shares, giving the rounding leftovers to the last one.
+/// The shares have to sum up to one and every part has to be at least `min_bond`.
+fn split_shared_delegation(
+    deps: Deps,
+    amount: Uint128,
+    shares: Vec<(String, Decimal)>,
+) -> Result<Vec<(String, Uint128)>, ContractError> {
+    let total = shares
+        .iter()
+        .try_fold(Decimal::zero(), |total, (_, share)| {
+            total.checked_add(*share)
+        })?;
+    if total != Decimal::one() {
+        return Err(ContractError::InvalidShares {});
+    }
+
+    let min_bond = CONFIG.load(deps.storage)?.min_bond;
+    let count = shares.len();
+    let mut remaining = amount;
+    shares
+        .into_iter()
+        .enumerate()
+        .map(|(i, (staker, share))| {
+            let part = if i + 1 == count {
+                remaining
+            } else {
+                amount * share
+            };
+            remaining -= part;
+            if part < min_bond {
+                return Err(ContractError::SharedDelegationBelowMinBond {
+                    amount: part,
+                    min_bond,
+                });
+            }
+            Ok((staker, part))
+        })
+        .collect()
+}
+
+pub fn execute_mass_bond(
+    deps: DepsMut,
+    _env: Env,
+    sender_cw20_contract: Addr,
+    amount_sent: Uint128,
+    unbonding_period: u64,
+    delegate_to: Vec<(String, Uint128)>,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // ensure that cw20 token contract's addresses matches
+    if cfg.cw20_contract != sender_cw20_contract {
+        return Err(ContractError::Cw20AddressesNotMatch {
+            got: sender_cw20_contract.into(),
+            expected: cfg.cw20_contract.into(),
+        });
+    }
+
+    if cfg
+        .unbonding_periods
+        .binary_search(&unbonding_period)
+        .is_err()
+    {
+        return Err(ContractError::NoUnbondingPeriodFound(unbonding_period));
+    }
+
     // ensure total is <= amount sent
     let total = delegate_to.iter().map(|(_, x)| x).sum();
     if total > amount_sent {
         return Err(ContractError::MassDelegateTooMuch { total, amount_sent });
     }
 
+    let delegate_to = delegate_to
+        .into_iter()
+        .map(|(sender, amount)| Ok((deps.api.addr_validate(&sender)?, amount)))
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
     // update this for every user
     let mut distributions: Vec<_> = DISTRIBUTION
         .range(deps.storage, None, None, Order::Ascending)
         .collect::<StdResult<Vec<_>>>()?;
 
     // loop over all delegates, adding to their stake
+    let mut events = vec![];
     for (sender, amount) in delegate_to {
-        let sender = deps.api.addr_validate(&sender)?;
-
         // calculate rewards power before updating the stake
         let old_rewards = calc_rewards_powers(deps.storage, &cfg, &sender, distributions.iter())?;
+        let had_stake = has_stake(deps.storage, &sender)?;
 
         // add to the sender's stake
         let mut old_stake = Uint128::zero();
@@ -529,7 +1663,15 @@ pub fn execute_mass_bond(
             )?
             .total_stake();
 
-        update_total_stake(deps.storage, &cfg, unbonding_period, old_stake, new_stake)?;
+        update_total_stake(
+            deps.storage,
+            &cfg,
+            &sender,
+            unbonding_period,
+            old_stake,
+            new_stake,
+        )?;
+        events.extend(update_staker_count(deps.storage, &sender, had_stake)?);
 
         // update the adjustment data for all distributions
         distributions = distributions
@@ -565,19 +1707,214 @@ pub fn execute_mass_bond(
     })?;
 
     Ok(Response::new()
+        .add_events(events)
         .add_attribute("action", "bond")
         .add_attribute("amount", amount_sent))
 }
 
+/// Bonds the sender's withdrawable rewards in the staked token instead of sending them out.
+/// The rewards are already held by this contract, so they only move from the reward balance to
+/// the bonded principal.
+pub fn execute_compound_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    unbonding_period: u64,
+) -> Result<Response, ContractError> {
+    if UNBOND_ALL.load(deps.storage)? {
+        return Err(ContractError::CannotDelegateIfUnbondAll {});
+    }
+    // rewards that have to go to a `withdraw_to` address cannot be bonded by the staker
+    if DELEGATION_RIGHTS
+        .may_load(deps.storage, &info.sender)?
+        .and_then(|rights| rights.withdraw_to)
+        .is_some()
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let reward = take_compoundable_rewards(deps.branch(), &cfg, &info.sender)?;
+    if reward.is_zero() {
+        return Err(ContractError::NoRewardsToCompound {});
+    }
+    record_interaction(deps.storage, &cfg, &info.sender, &env.block)?;
+
+    let bond_resp = execute_mass_bond(
+        deps,
+        env,
+        cfg.cw20_contract,
+        reward,
+        unbonding_period,
+        vec![(info.sender.to_string(), reward)],
+    )?;
+
+    Ok(Response::new()
+        .add_events(bond_resp.events)
+        .add_attribute("action", "compound_rewards")
+        .add_attribute("sender", info.sender)
+        .add_attribute("amount", reward)
+        .add_attribute("unbonding_period", unbonding_period.to_string()))
+}
+
+/// Bonds all of the sender's withdrawable rewards like [`execute_compound_rewards`],
+/// but fails instead of leaving rewards in other assets behind.
+pub fn execute_withdraw_and_restake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    unbonding_period: u64,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let staked_token = AssetInfoValidated::Token(cfg.cw20_contract.clone());
+    for distribution in DISTRIBUTION.range(deps.storage, None, None, Order::Ascending) {
+        let (asset_info, distribution) = distribution?;
+        if asset_info == staked_token {
+            continue;
+        }
+        let adjustment = distribution.adjustment_of(deps.storage, &info.sender, &asset_info)?;
+        if !withdrawable_rewards(
+            deps.as_ref(),
+            &cfg,
+            &info.sender,
+            &distribution,
+            &adjustment,
+        )?
+        .is_zero()
+        {
+            return Err(ContractError::CannotRestakeAsset(asset_info));
+        }
+    }
+
+    execute_compound_rewards(deps, env, info, unbonding_period)
+}
+
+/// Marks the staker's withdrawable rewards in the staked token as withdrawn and returns them,
+/// so they can be bonded instead of being sent out.
+fn take_compoundable_rewards(
+    deps: DepsMut,
+    cfg: &Config,
+    staker: &Addr,
+) -> Result<Uint128, ContractError> {
+    let asset_info = AssetInfoValidated::Token(cfg.cw20_contract.clone());
+    let mut distribution = DISTRIBUTION
+        .may_load(deps.storage, &asset_info)?
+        .ok_or(ContractError::NoRewardsToCompound {})?;
+    let mut adjustment = distribution.adjustment_of(deps.storage, staker, &asset_info)?;
+
+    let reward = withdrawable_rewards(deps.as_ref(), cfg, staker, &distribution, &adjustment)?;
+    if reward.is_zero() {
+        return Ok(reward);
+    }
+    adjustment.withdrawn_rewards += reward;
+    WITHDRAW_ADJUSTMENT.save(deps.storage, (staker, &asset_info), &adjustment)?;
+    distribution.withdrawable_total -= reward;
+    DISTRIBUTION.save(deps.storage, &asset_info, &distribution)?;
+    add_withdrawn(deps.storage, &asset_info, reward)?;
+
+    Ok(reward)
+}
+
+pub fn execute_set_auto_compound(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+    unbonding_period: UnbondingPeriod,
+) -> Result<Response, ContractError> {
+    if enabled {
+        let cfg = CONFIG.load(deps.storage)?;
+        if cfg
+            .unbonding_periods
+            .binary_search(&unbonding_period)
+            .is_err()
+        {
+            return Err(ContractError::NoUnbondingPeriodFound(unbonding_period));
+        }
+        AUTO_COMPOUND.save(deps.storage, &info.sender, &unbonding_period)?;
+    } else {
+        AUTO_COMPOUND.remove(deps.storage, &info.sender);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_auto_compound")
+        .add_attribute("sender", info.sender)
+        .add_attribute("enabled", enabled.to_string())
+        .add_attribute("unbonding_period", unbonding_period.to_string()))
+}
+
+/// Compounds the staked token rewards of the given users that opted in with
+/// [`ExecuteMsg::SetAutoCompound`]. Users that did not opt in, whose rewards go to a `withdraw_to`
+/// address or who have nothing to compound are skipped.
+pub fn execute_run_auto_compound(
+    mut deps: DepsMut,
+    env: Env,
+    users: Vec<String>,
+) -> Result<Response, ContractError> {
+    if UNBOND_ALL.load(deps.storage)? {
+        return Err(ContractError::CannotDelegateIfUnbondAll {});
+    }
+    let cfg = CONFIG.load(deps.storage)?;
+    if !DISTRIBUTION.has(
+        deps.storage,
+        &AssetInfoValidated::Token(cfg.cw20_contract.clone()),
+    ) {
+        return Err(ContractError::NoRewardsToCompound {});
+    }
+
+    // group the rewards by unbonding period, so each period is bonded at once
+    let mut compounds: BTreeMap<UnbondingPeriod, Vec<(String, Uint128)>> = BTreeMap::new();
+    for user in validate_addresses(deps.api, &users)? {
+        let unbonding_period = match AUTO_COMPOUND.may_load(deps.storage, &user)? {
+            Some(unbonding_period) => unbonding_period,
+            None => continue,
+        };
+        if DELEGATION_RIGHTS
+            .may_load(deps.storage, &user)?
+            .and_then(|rights| rights.withdraw_to)
+            .is_some()
+        {
+            continue;
+        }
+        let reward = take_compoundable_rewards(deps.branch(), &cfg, &user)?;
+        if !reward.is_zero() {
+            compounds
+                .entry(unbonding_period)
+                .or_default()
+                .push((user.to_string(), reward));
+        }
+    }
+
+    let mut resp = Response::new().add_attribute("action", "run_auto_compound");
+    for (unbonding_period, delegate_to) in compounds {
+        let amount = delegate_to.iter().map(|(_, amount)| amount).sum();
+        let bond_resp = execute_mass_bond(
+            deps.branch(),
+            env.clone(),
+            cfg.cw20_contract.clone(),
+            amount,
+            unbonding_period,
+            delegate_to,
+        )?;
+        resp = resp
+            .add_events(bond_resp.events)
+            .add_attribute(format!("amount_{}", unbonding_period), amount);
+    }
+
+    Ok(resp)
+}
+
 /// Updates the total stake for the given unbonding period
-/// Make sure to always pass in the full old and new stake of one staker for the given unbonding period
+/// Make sure to always pass in the full old and new stake of `staker` for the given unbonding period
 fn update_total_stake(
     storage: &mut dyn Storage,
     cfg: &Config,
+    staker: &Addr,
     unbonding_period: UnbondingPeriod,
     old_stake: Uint128,
     new_stake: Uint128,
 ) -> Result<(), ContractError> {
+    let min_bond = cfg.min_bond_of(staker);
+    let power_factor = load_power_factor(storage, staker)?;
     // get current total stakes
     let mut totals = TOTAL_PER_PERIOD.load(storage)?;
     let total_idx = totals
@@ -594,8 +1931,8 @@ fn update_total_stake(
 
     // Update the total of all stakes above min_bond.
     // Some variables and consts for readability
-    let previously_above_min_bond = old_stake >= cfg.min_bond;
-    let now_above_min_bond = new_stake >= cfg.min_bond;
+    let previously_above_min_bond = old_stake >= min_bond;
+    let now_above_min_bond = new_stake >= min_bond;
     // Case distinction:
     match (previously_above_min_bond, now_above_min_bond) {
         (false, false) => {} // rewards power does not change, so do nothing
@@ -616,6 +1953,11 @@ fn update_total_stake(
             }
         }
     }
+    // the staker's power factor applies to the new stake as well
+    total.decayed_stake = total
+        .decayed_stake
+        .checked_add(decayed_stake(min_bond, new_stake, power_factor))?
+        .checked_sub(decayed_stake(min_bond, old_stake, power_factor))?;
 
     // save updated total
     TOTAL_PER_PERIOD.save(storage, &totals)?;
@@ -640,17 +1982,31 @@ pub fn execute_receive(
         ReceiveMsg::Delegate {
             unbonding_period,
             delegate_as,
+            unbond_controller,
+            withdraw_to,
         } => {
             if UNBOND_ALL.load(deps.storage)? {
                 return Err(ContractError::CannotDelegateIfUnbondAll {});
             }
+            let staker =
+                api.addr_validate(&delegate_as.unwrap_or_else(|| wrapper.sender.clone()))?;
+            // only bonding to their own stake counts as an interaction of the staker
+            if staker.as_str() == wrapper.sender {
+                let cfg = CONFIG.load(deps.storage)?;
+                record_interaction(deps.storage, &cfg, &staker, &env.block)?;
+            }
+            let rights = DelegationRights {
+                unbond_controller: addr_opt_validate(api, &unbond_controller)?,
+                withdraw_to: addr_opt_validate(api, &withdraw_to)?,
+            };
+            set_delegation_rights(deps.storage, &staker, &wrapper.sender, rights)?;
             execute_bond(
                 deps,
                 env,
                 info.sender,
                 wrapper.amount,
                 unbonding_period,
-                api.addr_validate(&delegate_as.unwrap_or(wrapper.sender))?,
+                staker,
             )
         }
         ReceiveMsg::MassDelegate {
@@ -660,6 +2016,43 @@ pub fn execute_receive(
             if UNBOND_ALL.load(deps.storage)? {
                 return Err(ContractError::CannotDelegateIfUnbondAll {});
             }
+            record_sender_interaction(deps.storage, &env, &wrapper.sender, &delegate_to)?;
+            for (staker, _) in &delegate_to {
+                let staker = api.addr_validate(staker)?;
+                set_delegation_rights(
+                    deps.storage,
+                    &staker,
+                    &wrapper.sender,
+                    DelegationRights::default(),
+                )?;
+            }
+            execute_mass_bond(
+                deps,
+                env,
+                info.sender,
+                wrapper.amount,
+                unbonding_period,
+                delegate_to,
+            )
+        }
+        ReceiveMsg::DelegateShared {
+            shares,
+            unbonding_period,
+        } => {
+            if UNBOND_ALL.load(deps.storage)? {
+                return Err(ContractError::CannotDelegateIfUnbondAll {});
+            }
+            let delegate_to = split_shared_delegation(deps.as_ref(), wrapper.amount, shares)?;
+            record_sender_interaction(deps.storage, &env, &wrapper.sender, &delegate_to)?;
+            for (staker, _) in &delegate_to {
+                let staker = api.addr_validate(staker)?;
+                set_delegation_rights(
+                    deps.storage,
+                    &staker,
+                    &wrapper.sender,
+                    DelegationRights::default(),
+                )?;
+            }
             execute_mass_bond(
                 deps,
                 env,
@@ -669,7 +2062,10 @@ pub fn execute_receive(
                 delegate_to,
             )
         }
-        ReceiveMsg::Fund { funding_info } => {
+        ReceiveMsg::Fund {
+            funding_info,
+            on_behalf_of,
+        } => {
             if UNBOND_ALL.load(deps.storage)? {
                 return Err(ContractError::CannotDistributeIfUnbondAll {
                     what: "funds".into(),
@@ -679,7 +2075,19 @@ pub fn execute_receive(
                 return Err(ContractError::PastStartingTime {});
             }
             let validated_asset = AssetInfo::Token(info.sender.to_string()).validate(deps.api)?;
-            update_reward_config(deps.storage, validated_asset, wrapper.amount, funding_info)?;
+            let schedule =
+                update_reward_config(deps.storage, &validated_asset, wrapper.amount, funding_info)?;
+            let funder = match on_behalf_of {
+                Some(on_behalf_of) => api.addr_validate(&on_behalf_of)?,
+                None => api.addr_validate(&wrapper.sender)?,
+            };
+            add_funding(
+                deps.storage,
+                &validated_asset,
+                &funder,
+                wrapper.amount,
+                &schedule,
+            )?;
             Ok(Response::default())
         }
     }
@@ -691,19 +2099,21 @@ pub fn execute_unbond(
     info: MessageInfo,
     amount: Uint128,
     unbonding_period: u64,
+    owner: Option<String>,
 ) -> Result<Response, ContractError> {
     let cfg = CONFIG.load(deps.storage)?;
     // If unbond all flag has been set to true, no unbonding period is required: !true as u64 == 0
     let unbond_all = UNBOND_ALL.load(deps.storage)?;
 
-    remove_stake_without_total(
-        deps.branch(),
-        &env,
-        &cfg,
-        &info.sender,
-        unbonding_period,
-        amount,
-    )?;
+    let staker = addr_opt_validate(deps.api, &owner)?.unwrap_or_else(|| info.sender.clone());
+    assert_unbond_controller(deps.storage, &staker, &info.sender)?;
+    // an unbond by the unbond controller does not count as an interaction of the staker
+    if staker == info.sender {
+        record_interaction(deps.storage, &cfg, &staker, &env.block)?;
+    }
+
+    let staker_count_event =
+        remove_stake_without_total(deps.branch(), &env, &cfg, &staker, unbonding_period, amount)?;
 
     // update total
     TOTAL_STAKED.update::<_, StdError>(deps.storage, |token_info| {
@@ -715,9 +2125,11 @@ pub fn execute_unbond(
     })?;
 
     let resp = Response::new()
+        .add_events(staker_count_event)
         .add_attribute("action", "unbond")
         .add_attribute("amount", amount)
-        .add_attribute("sender", info.sender.clone());
+        .add_attribute("sender", info.sender.clone())
+        .add_attribute("owner", &staker);
 
     // If unbond all flag set to true we don't need to create a claim and send directly. Sending
     // directly instead of send a Claim submessage resolves in 2 messages instead of 3.
@@ -733,10 +2145,283 @@ pub fn execute_unbond(
             // If unbond all flag set to true the claim has no delay.
             Expiration::AtTime(env.block.time.plus_seconds(unbonding_period)),
         )?;
+        // keep track of the claim for distributions that reward claimants,
+        // the rewards belong to the staker even if the controller holds the claim
+        if rewards_claimants(deps.storage)? {
+            let claimant = (info.sender != staker).then(|| info.sender.clone());
+            update_pending_claims(deps.storage, &cfg, &staker, |claims| {
+                claims.retain(|claim| claim.release_at > env.block.time);
+                claims.push(PendingClaim {
+                    unbonding_period,
+                    amount,
+                    release_at: env.block.time.plus_seconds(unbonding_period),
+                    claimant,
+                })
+            })?;
+        }
         Ok(resp)
     }
 }
 
+/// Unbonds the sender's tokens without a claim, keeping the configured fee as a penalty.
+pub fn execute_instant_unbond(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    unbonding_period: UnbondingPeriod,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let instant_unbond = cfg
+        .instant_unbond
+        .clone()
+        .ok_or(ContractError::InstantUnbondDisabled {})?;
+    // stakers with an unbond controller cannot bypass it
+    assert_unbond_controller(deps.storage, &info.sender, &info.sender)?;
+    record_interaction(deps.storage, &cfg, &info.sender, &env.block)?;
+
+    let staker_count_event = remove_stake_without_total(
+        deps.branch(),
+        &env,
+        &cfg,
+        &info.sender,
+        unbonding_period,
+        amount,
+    )?;
+
+    // update total, the tokens are not unbonding, they leave the contract right away
+    TOTAL_STAKED.update::<_, StdError>(deps.storage, |token_info| {
+        Ok(TokenInfo {
+            staked: token_info.staked.saturating_sub(amount),
+            unbonding: token_info.unbonding,
+        })
+    })?;
+
+    let penalty = amount * instant_unbond.fee;
+    let payout = amount - penalty;
+
+    let mut resp = Response::new()
+        .add_events(staker_count_event)
+        .add_attribute("action", "instant_unbond")
+        .add_attribute("amount", amount)
+        .add_attribute("penalty", penalty)
+        .add_attribute("sender", info.sender.clone());
+    if !payout.is_zero() {
+        resp = resp.add_submessage(create_undelegate_msg(
+            info.sender,
+            payout,
+            cfg.cw20_contract.clone(),
+        )?);
+    }
+    if !penalty.is_zero() {
+        resp = match instant_unbond.treasury {
+            Some(treasury) => {
+                resp.add_submessage(create_undelegate_msg(treasury, penalty, cfg.cw20_contract)?)
+            }
+            None => resp.add_message(WasmMsg::Execute {
+                contract_addr: cfg.cw20_contract.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn { amount: penalty })?,
+                funds: vec![],
+            }),
+        };
+    }
+
+    Ok(resp)
+}
+
+/// Moves `amount` of the sender's unmatured claims from `unbonding_period` back into the stake.
+pub fn execute_cancel_unbonding(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    unbonding_period: UnbondingPeriod,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    if cfg
+        .unbonding_periods
+        .binary_search(&unbonding_period)
+        .is_err()
+    {
+        return Err(ContractError::NoUnbondingPeriodFound(unbonding_period));
+    }
+    let staker = info.sender;
+    // the tokens are bonded to the sender's own position, which must not be controlled by someone else
+    assert_unbond_controller(deps.storage, &staker, &staker)?;
+
+    // this updates the distributions, so it has to happen before loading them below
+    record_interaction(deps.storage, &cfg, &staker, &env.block)?;
+    let distributions: Vec<_> = DISTRIBUTION
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    // calculate rewards power before updating the stake and claims
+    let old_rewards = calc_rewards_powers(deps.storage, &cfg, &staker, distributions.iter())?;
+    let had_stake = has_stake(deps.storage, &staker)?;
+
+    let mut pending_claims = PENDING_CLAIMS
+        .may_load(deps.storage, &staker)?
+        .unwrap_or_default();
+    let cancellable = |claim: &PendingClaim| {
+        claim.unbonding_period == unbonding_period
+            && claim.release_at > env.block.time
+            && claim.claimant.is_none()
+    };
+    let available: Uint128 = pending_claims
+        .iter()
+        .filter(|claim| cancellable(claim))
+        .map(|claim| claim.amount)
+        .sum();
+    if amount.is_zero() || amount > available {
+        return Err(ContractError::NotEnoughUnbonding { available });
+    }
+
+    // in order to modify unmature claims, we need to create a new Map with the same key,
+    // because the `Claims` API does not provide a way to do that.
+    const CLAIMS_MAP: Map<&Addr, Vec<Claim>> = Map::new("claims");
+    let mut claims = CLAIMS_MAP
+        .may_load(deps.storage, &staker)?
+        .unwrap_or_default();
+
+    // cancel the claims that mature last first
+    pending_claims.sort_by_key(|claim| claim.release_at);
+    let mut remaining = amount;
+    for pending in pending_claims.iter_mut().rev() {
+        if remaining.is_zero() {
+            break;
+        }
+        if !cancellable(pending) {
+            continue;
+        }
+        let cancelled = pending.amount.min(remaining);
+        pending.amount -= cancelled;
+        remaining -= cancelled;
+
+        // remove the same amount from the claims maturing at the same time
+        let mut to_remove = cancelled;
+        for claim in claims
+            .iter_mut()
+            .filter(|claim| claim.release_at == Expiration::AtTime(pending.release_at))
+        {
+            let removed = claim.amount.min(to_remove);
+            claim.amount -= removed;
+            to_remove -= removed;
+        }
+        // the tokens would be bonded and claimable at the same time otherwise
+        if !to_remove.is_zero() {
+            return Err(StdError::generic_err("pending claims do not match the claims").into());
+        }
+    }
+    pending_claims.retain(|claim| !claim.amount.is_zero());
+    claims.retain(|claim| !claim.amount.is_zero());
+    save_pending_claims(deps.storage, &staker, pending_claims)?;
+    CLAIMS_MAP.save(deps.storage, &staker, &claims)?;
+
+    // add the tokens back to the stake
+    let mut old_stake = Uint128::zero();
+    let new_stake = STAKE
+        .update(
+            deps.storage,
+            (&staker, unbonding_period),
+            |bonding_info| -> StdResult<_> {
+                let mut bonding_info = bonding_info.unwrap_or_default();
+                old_stake = bonding_info.total_stake();
+                bonding_info.add_unlocked_tokens(amount);
+                Ok(bonding_info)
+            },
+        )?
+        .total_stake();
+    update_total_stake(
+        deps.storage,
+        &cfg,
+        &staker,
+        unbonding_period,
+        old_stake,
+        new_stake,
+    )?;
+    let staker_count_event = update_staker_count(deps.storage, &staker, had_stake)?;
+    TOTAL_STAKED.update::<_, StdError>(deps.storage, |token_info| {
+        Ok(TokenInfo {
+            staked: token_info.staked + amount,
+            unbonding: token_info.unbonding.saturating_sub(amount),
+        })
+    })?;
+
+    // update the adjustment data for all distributions
+    for ((asset_info, mut distribution), old_reward_power) in
+        distributions.into_iter().zip(old_rewards.into_iter())
+    {
+        let new_reward_power = distribution.calc_rewards_power(deps.storage, &cfg, &staker)?;
+        update_rewards(
+            deps.storage,
+            &asset_info,
+            &staker,
+            &mut distribution,
+            old_reward_power,
+            new_reward_power,
+        )?;
+        DISTRIBUTION.save(deps.storage, &asset_info, &distribution)?;
+    }
+
+    Ok(Response::new()
+        .add_events(staker_count_event)
+        .add_attribute("action", "cancel_unbonding")
+        .add_attribute("amount", amount)
+        .add_attribute("unbonding_period", unbonding_period.to_string())
+        .add_attribute("sender", staker))
+}
+
+/// Withdraws all rewards and unbonds all unlocked tokens of the sender in one go.
+pub fn execute_exit_all(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let withdraw_resp =
+        execute_withdraw_rewards(deps.branch(), env.clone(), info.clone(), None, None)?;
+
+    // fail instead of leaving part of the stake behind
+    let stakes = STAKE
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|stake| {
+            let (unbonding_period, bonding_info) = stake?;
+            let locked = bonding_info.total_locked(&env);
+            if !locked.is_zero() {
+                return Err(ContractError::StakeLocked {
+                    unbonding_period,
+                    amount: locked,
+                });
+            }
+            Ok((unbonding_period, bonding_info.total_unlocked(&env)))
+        })
+        .filter(|stake| !matches!(stake, Ok((_, amount)) if amount.is_zero()))
+        .collect::<Result<Vec<_>, _>>()?;
+    if stakes.len() > MAX_EXIT_PERIODS {
+        return Err(ContractError::TooManyExitPeriods(MAX_EXIT_PERIODS));
+    }
+
+    let mut resp = Response::new()
+        .add_attribute("action", "exit_all")
+        .add_attribute("sender", info.sender.as_str())
+        .add_submessages(withdraw_resp.messages);
+    for (unbonding_period, amount) in stakes {
+        let unbond_resp = execute_unbond(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            amount,
+            unbonding_period,
+            None,
+        )?;
+        resp = resp
+            .add_submessages(unbond_resp.messages)
+            .add_events(unbond_resp.events)
+            .add_attribute(format!("unbonded_{}", unbonding_period), amount);
+    }
+
+    Ok(resp)
+}
+
 pub fn execute_quick_unbond(
     deps: DepsMut,
     env: Env,
@@ -756,12 +2441,12 @@ pub fn execute_quick_unbond(
         .add_attribute("action", "quick_unbond")
         .add_attribute("stakers", stakers.join(","));
 
-    // Keep track of unbonded amounts per period.
+    // Keep track of unbonded amounts and the powered and decayed part of them per period.
     // This is used to update the total per period and the total staked amount in one go at the end
     // to avoid unnecessary stores for each staker.
     let mut unbonded_by_period = HashMap::with_capacity(cfg.unbonding_periods.len());
     for period in &cfg.unbonding_periods {
-        unbonded_by_period.insert(period, Uint128::zero());
+        unbonded_by_period.insert(*period, (Uint128::zero(), Uint128::zero(), Uint128::zero()));
     }
     // Also keep track of the total amount of claims removed.
     let mut claimed_total = Uint128::zero();
@@ -776,6 +2461,9 @@ pub fn execute_quick_unbond(
 
         // the amount the staker unbonds in this call
         let mut staker_unbonds = Uint128::zero();
+        let had_stake = has_stake(deps.storage, &staker)?;
+        let min_bond = cfg.min_bond_of(&staker);
+        let power_factor = load_power_factor(deps.storage, &staker)?;
 
         let stakes = STAKE
             .prefix(&staker)
@@ -783,20 +2471,27 @@ pub fn execute_quick_unbond(
             .collect::<StdResult<Vec<_>>>()?;
         for (unbonding_period, mut bonding_info) in stakes {
             let old_stake = bonding_info.total_stake();
-            // increase the unbonding counter
-            *unbonded_by_period.get_mut(&unbonding_period).unwrap() += old_stake;
+            // increase the unbonding counters
+            let (unbonded, powered, decayed) =
+                unbonded_by_period.get_mut(&unbonding_period).unwrap();
+            *unbonded += old_stake;
+            if old_stake >= min_bond {
+                *powered += old_stake;
+            }
+            *decayed += decayed_stake(min_bond, old_stake, power_factor);
             staker_unbonds += old_stake;
             // unlock all locked tokens and release all of them
             bonding_info.force_unlock_all()?;
             bonding_info.release_stake(&env, old_stake)?;
             STAKE.save(deps.storage, (&staker, unbonding_period), &bonding_info)?;
         }
+        response = response.add_events(update_staker_count(deps.storage, &staker, had_stake)?);
 
         // update the adjustment data for all distributions
         for ((asset_info, distribution), old_reward_power) in
             distributions.iter_mut().zip(old_rewards.into_iter())
         {
-            if old_reward_power.is_zero() {
+            if old_reward_power.power.is_zero() {
                 continue;
             }
             // new power is always zero, since we unbonded all stake
@@ -806,10 +2501,13 @@ pub fn execute_quick_unbond(
                 &staker,
                 distribution,
                 old_reward_power,
-                Uint128::zero(),
+                RewardsPower::default(),
             )?;
         }
 
+        // open claims are paid out below, so they stop accruing rewards
+        save_pending_claims(deps.storage, &staker, vec![])?;
+
         let open_claims: Uint128 = CLAIMS
             .query_claims(deps.as_ref(), &staker)?
             .claims
@@ -822,12 +2520,23 @@ pub fn execute_quick_unbond(
         CLAIMS_MAP.save(deps.storage, &staker, &vec![])?;
         claimed_total += open_claims;
 
-        let amount = staker_unbonds + open_claims;
-        if !amount.is_zero() {
+        // the bonded tokens belong to the unbond controller, if there is one
+        let mut payouts = vec![(staker.clone(), open_claims)];
+        match DELEGATION_RIGHTS
+            .may_load(deps.storage, &staker)?
+            .and_then(|rights| rights.unbond_controller)
+        {
+            Some(controller) => payouts.push((controller, staker_unbonds)),
+            None => payouts[0].1 += staker_unbonds,
+        }
+        for (recipient, amount) in payouts {
+            if amount.is_zero() {
+                continue;
+            }
             let undelegate_msg = WasmMsg::Execute {
                 contract_addr: cfg.cw20_contract.to_string(),
                 msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: staker.to_string(),
+                    recipient: recipient.to_string(),
                     amount,
                 })?,
                 funds: vec![],
@@ -840,16 +2549,19 @@ pub fn execute_quick_unbond(
     for (asset_info, distribution) in distributions.into_iter() {
         DISTRIBUTION.save(deps.storage, &asset_info, &distribution)?;
     }
-    let unbonded_total = unbonded_by_period.values().sum::<Uint128>();
-    for (unbonding_period, unbonded) in unbonded_by_period {
-        update_total_stake(
-            deps.storage,
-            &cfg,
-            *unbonding_period,
-            unbonded,
-            Uint128::zero(),
-        )?;
+    let mut totals = TOTAL_PER_PERIOD.load(deps.storage)?;
+    for (unbonding_period, total) in totals.iter_mut() {
+        if let Some((unbonded, powered, decayed)) = unbonded_by_period.get(unbonding_period) {
+            total.staked = total.staked.checked_sub(*unbonded)?;
+            total.powered_stake -= *powered;
+            total.decayed_stake = total.decayed_stake.checked_sub(*decayed)?;
+        }
     }
+    TOTAL_PER_PERIOD.save(deps.storage, &totals)?;
+    let unbonded_total = unbonded_by_period
+        .values()
+        .map(|(unbonded, _, _)| unbonded)
+        .sum::<Uint128>();
     TOTAL_STAKED.update::<_, StdError>(deps.storage, |token_info| {
         Ok(TokenInfo {
             staked: token_info.staked - unbonded_total,
@@ -909,7 +2621,7 @@ fn calc_rewards_powers<'a>(
     cfg: &Config,
     staker: &Addr,
     distributions: impl Iterator<Item = &'a (AssetInfoValidated, Distribution)>,
-) -> StdResult<Vec<Uint128>> {
+) -> StdResult<Vec<RewardsPower>> {
     // go through distributions and calculate old reward power for all of them
     let old_rewards = distributions
         .map(|(_, distribution)| {
@@ -926,79 +2638,1104 @@ fn update_rewards(
     asset_info: &AssetInfoValidated,
     sender: &Addr,
     distribution: &mut Distribution,
-    old_reward_power: Uint128,
-    new_reward_power: Uint128,
+    old_reward_power: RewardsPower,
+    new_reward_power: RewardsPower,
 ) -> StdResult<()> {
     // short-circuit if no change
     if old_reward_power == new_reward_power {
         return Ok(());
     }
 
+    // the totals are rounded down, so keep track of what the stakers gain by rounding
+    distribution.rounding_surplus = distribution
+        .rounding_surplus
+        .checked_add(new_reward_power.rounding_surplus)?
+        .checked_sub(old_reward_power.rounding_surplus)?;
+
+    // keep track of the power that is too small to earn from this distribution
+    distribution.excluded_power = distribution
+        .excluded_power
+        .checked_add(new_reward_power.power - new_reward_power.earning)?
+        .checked_sub(old_reward_power.power - old_reward_power.earning)?;
+    let old_reward_power = old_reward_power.earning;
+    let new_reward_power = new_reward_power.earning;
+    if old_reward_power == new_reward_power {
+        return Ok(());
+    }
+
     // update their share of the distribution
-    let ppw = distribution.shares_per_point.u128();
     let diff = new_reward_power.u128() as i128 - old_reward_power.u128() as i128;
-    apply_points_correction(storage, sender, asset_info, ppw, diff)?;
+    apply_points_correction(storage, sender, asset_info, distribution, diff)?;
 
     Ok(())
 }
 
-/// Removes the stake from the given unbonding period and staker,
-/// updating `DISTRIBUTION`, `TOTAL_PER_PERIOD` and `STAKE`, but *not* `TOTAL_STAKED`.
-fn remove_stake_without_total(
-    deps: DepsMut,
-    env: &Env,
+/// Returns the part of `stake` that does not count towards the rewards power
+/// when only `power_factor` of the power is applied.
+fn decayed_stake(min_bond: Uint128, stake: Uint128, power_factor: Decimal) -> Uint128 {
+    if stake < min_bond {
+        // not part of the powered stake anyway
+        Uint128::zero()
+    } else {
+        stake - stake * power_factor
+    }
+}
+
+/// Sets the fraction of the rewards power applied to `staker`,
+/// updating the decayed stake in `TOTAL_PER_PERIOD` and the staker's rewards in all distributions.
+fn set_power_factor(
+    storage: &mut dyn Storage,
     cfg: &Config,
     staker: &Addr,
-    unbonding_period: UnbondingPeriod,
-    amount: Uint128,
+    power_factor: Decimal,
 ) -> Result<(), ContractError> {
-    if cfg
-        .unbonding_periods
-        .binary_search(&unbonding_period)
-        .is_err()
+    let old_power_factor = load_power_factor(storage, staker)?;
+    if old_power_factor == power_factor {
+        return Ok(());
+    }
+
+    let distributions: Vec<_> = DISTRIBUTION
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    // calculate rewards power before updating the power factor
+    let old_rewards = calc_rewards_powers(storage, cfg, staker, distributions.iter())?;
+
+    let min_bond = cfg.min_bond_of(staker);
+    let mut totals = TOTAL_PER_PERIOD.load(storage)?;
+    for (unbonding_period, total) in totals.iter_mut() {
+        let stake = STAKE
+            .may_load(storage, (staker, *unbonding_period))?
+            .unwrap_or_default()
+            .total_stake();
+        total.decayed_stake = total
+            .decayed_stake
+            .checked_add(decayed_stake(min_bond, stake, power_factor))?
+            .checked_sub(decayed_stake(min_bond, stake, old_power_factor))?;
+    }
+    TOTAL_PER_PERIOD.save(storage, &totals)?;
+    if power_factor == Decimal::one() {
+        POWER_FACTOR.remove(storage, staker);
+    } else {
+        POWER_FACTOR.save(storage, staker, &power_factor)?;
+    }
+
+    // update the adjustment data for all distributions
+    for ((asset_info, mut distribution), old_reward_power) in
+        distributions.into_iter().zip(old_rewards.into_iter())
     {
-        return Err(ContractError::NoUnbondingPeriodFound(unbonding_period));
+        let new_reward_power = distribution.calc_rewards_power(storage, cfg, staker)?;
+        update_rewards(
+            storage,
+            &asset_info,
+            staker,
+            &mut distribution,
+            old_reward_power,
+            new_reward_power,
+        )?;
+        DISTRIBUTION.save(storage, &asset_info, &distribution)?;
+    }
+    Ok(())
+}
+
+/// Switches the rewards power of `staker` to the configured rounding, if it is not applied yet,
+/// updating the staker's rewards in all distributions.
+fn apply_power_rounding(
+    storage: &mut dyn Storage,
+    cfg: &Config,
+    staker: &Addr,
+) -> Result<(), ContractError> {
+    if load_power_rounding(storage, staker)? == cfg.power_rounding {
+        return Ok(());
+    }
+
+    let distributions: Vec<_> = DISTRIBUTION
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    // calculate rewards power before changing the rounding
+    let old_rewards = calc_rewards_powers(storage, cfg, staker, distributions.iter())?;
+
+    match cfg.power_rounding {
+        RoundingMode::Floor => POWER_ROUNDING.remove(storage, staker),
+        mode => POWER_ROUNDING.save(storage, staker, &mode)?,
+    }
+
+    // update the adjustment data for all distributions
+    for ((asset_info, mut distribution), old_reward_power) in
+        distributions.into_iter().zip(old_rewards.into_iter())
+    {
+        let new_reward_power = distribution.calc_rewards_power(storage, cfg, staker)?;
+        update_rewards(
+            storage,
+            &asset_info,
+            staker,
+            &mut distribution,
+            old_reward_power,
+            new_reward_power,
+        )?;
+        DISTRIBUTION.save(storage, &asset_info, &distribution)?;
+    }
+    Ok(())
+}
+
+/// Records a bond, rebond, unbond or withdrawal of `staker`, restoring their full rewards power.
+/// Only call this for actions of the staker itself, actions of keepers or controllers on their
+/// behalf must not prevent the decay.
+pub(crate) fn record_interaction(
+    storage: &mut dyn Storage,
+    cfg: &Config,
+    staker: &Addr,
+    block: &BlockInfo,
+) -> Result<(), ContractError> {
+    set_power_factor(storage, cfg, staker, Decimal::one())?;
+    apply_power_rounding(storage, cfg, staker)?;
+    LAST_INTERACTION.save(
+        storage,
+        staker,
+        &InteractionBlock {
+            height: block.height,
+            time: block.time.seconds(),
+        },
+    )?;
+    Ok(())
+}
+
+/// Records an interaction of the `sender` of a delegation, if they bond to their own stake.
+fn record_sender_interaction(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &str,
+    delegate_to: &[(String, Uint128)],
+) -> Result<(), ContractError> {
+    if delegate_to.iter().any(|(staker, _)| staker == sender) {
+        let cfg = CONFIG.load(storage)?;
+        record_interaction(storage, &cfg, &Addr::unchecked(sender), &env.block)?;
+    }
+    Ok(())
+}
+
+pub fn execute_update_power_decay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    power_decay: Option<PowerDecayConfig>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    match power_decay {
+        Some(PowerDecayConfig {
+            inactivity_window,
+            decay_duration,
+        }) => {
+            if decay_duration == 0 {
+                return Err(ContractError::InvalidPowerDecay {});
+            }
+            // updating the parameters does not reset everyone's inactivity
+            let enabled_at = POWER_DECAY
+                .may_load(deps.storage)?
+                .map_or(env.block.time.seconds(), |decay| decay.enabled_at);
+            POWER_DECAY.save(
+                deps.storage,
+                &PowerDecay {
+                    inactivity_window,
+                    decay_duration,
+                    enabled_at,
+                },
+            )?;
+        }
+        None => POWER_DECAY.remove(deps.storage),
     }
 
+    Ok(Response::new().add_attribute("action", "update_power_decay"))
+}
+
+/// Brings the power factor of the given stakers up to date with the current power decay.
+/// This also raises factors that were applied with an earlier configuration,
+/// so disabling or re-enabling the decay does not keep stale factors around.
+pub fn execute_apply_decay(
+    deps: DepsMut,
+    env: Env,
+    stakers: Vec<String>,
+) -> Result<Response, ContractError> {
+    let power_decay = POWER_DECAY.may_load(deps.storage)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    for staker in validate_addresses(deps.api, &stakers)? {
+        let power_factor = match &power_decay {
+            Some(power_decay) => {
+                let last_interaction = LAST_INTERACTION
+                    .may_load(deps.storage, &staker)?
+                    .map(|block| block.time);
+                power_decay.power_factor(last_interaction, now)
+            }
+            None => Decimal::one(),
+        };
+        set_power_factor(deps.storage, &cfg, &staker, power_factor)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "apply_decay")
+        .add_attribute("stakers", stakers.join(",")))
+}
+
+/// Changes `min_bond`. The stakers are switched to it by [`ExecuteMsg::ApplyMinBond`].
+/// Rewards that were already distributed are not affected.
+pub fn execute_set_min_bond(
+    deps: DepsMut,
+    info: MessageInfo,
+    value: Uint128,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+    if cfg.min_bond_update.is_some() {
+        return Err(ContractError::MinBondUpdateInProgress {});
+    }
+    // nothing to apply if nobody has stake
+    if STAKE
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        cfg.min_bond_update = Some(MinBondUpdate {
+            old_min_bond: cfg.min_bond,
+            last_updated: None,
+        });
+    }
+    // min_bond is at least 1, so 0 stake -> non-membership
+    cfg.min_bond = std::cmp::max(value, Uint128::new(1));
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_min_bond")
+        .add_attribute("min_bond", cfg.min_bond))
+}
+
+/// Applies the pending change of `min_bond` to the next `limit` stakers.
+/// Their stakes that cross the threshold are moved into or out of the totals and their points
+/// correction is re-applied in all distributions.
+pub fn execute_apply_min_bond(
+    deps: DepsMut,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let (old_min_bond, last_updated) = match &cfg.min_bond_update {
+        Some(update) => (update.old_min_bond, update.last_updated.clone()),
+        None => return Err(ContractError::NoMinBondUpdate {}),
+    };
+
+    let limit = limit.unwrap_or(DEFAULT_UPDATE_LIMIT).min(MAX_UPDATE_LIMIT) as usize;
+    let stakers = stakers_after(deps.storage, last_updated.as_ref(), limit)?;
+    let mut distributions: Vec<_> = DISTRIBUTION
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut totals = TOTAL_PER_PERIOD.load(deps.storage)?;
+    for staker in &stakers {
+        let old_rewards = calc_rewards_powers(deps.storage, &cfg, staker, distributions.iter())?;
+
+        // find the stakes that cross the threshold and update the totals accordingly
+        let power_factor = load_power_factor(deps.storage, staker)?;
+        for item in STAKE
+            .prefix(staker)
+            .range(deps.storage, None, None, Order::Ascending)
+        {
+            let (unbonding_period, bonding_info) = item?;
+            let stake = bonding_info.total_stake();
+            if (stake >= old_min_bond) == (stake >= cfg.min_bond) {
+                continue;
+            }
+
+            let total_idx = totals
+                .binary_search_by(|(period, _)| period.cmp(&unbonding_period))
+                .map_err(|_| ContractError::NoUnbondingPeriodFound(unbonding_period))?;
+            let total = &mut totals[total_idx].1;
+            if stake >= cfg.min_bond {
+                total.powered_stake += stake;
+                total.decayed_stake += decayed_stake(cfg.min_bond, stake, power_factor);
+            } else {
+                total.powered_stake -= stake;
+                total.decayed_stake = total.decayed_stake.checked_sub(decayed_stake(
+                    old_min_bond,
+                    stake,
+                    power_factor,
+                ))?;
+            }
+        }
+        if let Some(update) = cfg.min_bond_update.as_mut() {
+            update.last_updated = Some(staker.clone());
+        }
+
+        // update the adjustment data of the staker for all distributions
+        for ((asset_info, distribution), old_reward_power) in
+            distributions.iter_mut().zip(old_rewards.into_iter())
+        {
+            let new_reward_power = distribution.calc_rewards_power(deps.storage, &cfg, staker)?;
+            update_rewards(
+                deps.storage,
+                asset_info,
+                staker,
+                distribution,
+                old_reward_power,
+                new_reward_power,
+            )?;
+        }
+    }
+    let finished = stakers.len() < limit;
+    if finished {
+        cfg.min_bond_update = None;
+    }
+    TOTAL_PER_PERIOD.save(deps.storage, &totals)?;
+    CONFIG.save(deps.storage, &cfg)?;
+    for (asset_info, distribution) in &distributions {
+        DISTRIBUTION.save(deps.storage, asset_info, distribution)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "apply_min_bond")
+        .add_attribute("stakers", stakers.len().to_string())
+        .add_attribute("finished", finished.to_string()))
+}
+
+/// Changes how rewards power is rounded.
+/// Each staker switches to the new rounding on their next interaction,
+/// so rewards that were already distributed are not affected.
+pub fn execute_set_power_rounding(
+    deps: DepsMut,
+    info: MessageInfo,
+    mode: RoundingMode,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    CONFIG.update(deps.storage, |mut cfg| -> StdResult<_> {
+        cfg.power_rounding = mode;
+        Ok(cfg)
+    })?;
+
+    let mode = match mode {
+        RoundingMode::Floor => "floor",
+        RoundingMode::Nearest => "nearest",
+    };
+    Ok(Response::new()
+        .add_attribute("action", "set_power_rounding")
+        .add_attribute("mode", mode))
+}
+
+/// Changes the minimum number of seconds between two distributions.
+pub fn execute_set_min_distribute_interval(
+    deps: DepsMut,
+    info: MessageInfo,
+    interval: u64,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    CONFIG.update(deps.storage, |mut cfg| -> StdResult<_> {
+        cfg.min_distribute_interval = interval;
+        Ok(cfg)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_min_distribute_interval")
+        .add_attribute("interval", interval.to_string()))
+}
+
+pub fn execute_set_instant_unbond(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee: Option<Decimal>,
+    treasury: Option<String>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let instant_unbond = fee
+        .map(|fee| -> Result<_, ContractError> {
+            if fee > Decimal::one() {
+                return Err(ContractError::InvalidInstantUnbondFee {});
+            }
+            Ok(InstantUnbondConfig {
+                fee,
+                treasury: addr_opt_validate(deps.api, &treasury)?,
+            })
+        })
+        .transpose()?;
+
+    CONFIG.update(deps.storage, |mut cfg| -> StdResult<_> {
+        cfg.instant_unbond = instant_unbond;
+        Ok(cfg)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_instant_unbond")
+        .add_attribute(
+            "fee",
+            fee.map_or_else(|| "none".to_owned(), |fee| fee.to_string()),
+        ))
+}
+
+pub fn execute_set_max_reward_multiplier(
+    deps: DepsMut,
+    info: MessageInfo,
+    max: Decimal,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    CONFIG.update(deps.storage, |mut cfg| -> StdResult<_> {
+        cfg.max_reward_multiplier = max;
+        Ok(cfg)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_max_reward_multiplier")
+        .add_attribute("max", max.to_string()))
+}
+
+pub fn execute_set_max_distributions(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_max: u32,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let existing = DISTRIBUTION
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u32;
+    if new_max < existing {
+        return Err(ContractError::MaxDistributionsBelowExisting(existing));
+    }
+
+    CONFIG.update(deps.storage, |mut cfg| -> StdResult<_> {
+        cfg.max_distributions = new_max;
+        Ok(cfg)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_max_distributions")
+        .add_attribute("max_distributions", new_max.to_string()))
+}
+
+/// Removes an unbonding period that nobody has tokens bonded in anymore.
+/// The rewards power of every staker stays the same, so no points correction is needed.
+pub fn execute_remove_unbonding_period(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    unbonding_period: UnbondingPeriod,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let idx = cfg
+        .unbonding_periods
+        .binary_search(&unbonding_period)
+        .map_err(|_| ContractError::NoUnbondingPeriodFound(unbonding_period))?;
+
+    let total = load_total_of_period(deps.storage, unbonding_period)?;
+    let pending = TOTAL_PENDING_PER_PERIOD
+        .may_load(deps.storage, unbonding_period)?
+        .unwrap_or_default();
+    if !total.staked.is_zero() || !pending.is_zero() {
+        return Err(ContractError::UnbondingPeriodInUse(unbonding_period));
+    }
+
+    cfg.unbonding_periods.remove(idx);
+    CONFIG.save(deps.storage, &cfg)?;
+    TOTAL_PER_PERIOD.update(deps.storage, |mut totals| -> StdResult<_> {
+        totals.retain(|(period, _)| *period != unbonding_period);
+        Ok(totals)
+    })?;
+    TOTAL_PENDING_PER_PERIOD.remove(deps.storage, unbonding_period);
+
+    let assets = DISTRIBUTION
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for asset in assets {
+        let mut distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+        distribution
+            .reward_multipliers
+            .retain(|(period, _)| *period != unbonding_period);
+        if let Some(update) = distribution.update.as_mut() {
+            update
+                .old_rewards
+                .retain(|(period, _)| *period != unbonding_period);
+        }
+        DISTRIBUTION.save(deps.storage, &asset, &distribution)?;
+        record_multiplier_change(
+            deps.storage,
+            &asset,
+            env.block.time.seconds(),
+            distribution.reward_multipliers,
+        )?;
+
+        if let Some(mut scheduled) = SCHEDULED_MULTIPLIERS.may_load(deps.storage, &asset)? {
+            scheduled
+                .rewards
+                .retain(|(period, _)| *period != unbonding_period);
+            SCHEDULED_MULTIPLIERS.save(deps.storage, &asset, &scheduled)?;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_unbonding_period")
+        .add_attribute("unbonding_period", unbonding_period.to_string()))
+}
+
+/// Adds a new unbonding period with the given reward multiplier for every distribution flow.
+/// Nobody has tokens bonded in it yet, so no points correction is needed.
+pub fn execute_add_unbonding_period(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    unbonding_period: UnbondingPeriod,
+    rewards: Vec<(AssetInfo, Decimal)>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut cfg = CONFIG.load(deps.storage)?;
+    let idx = match cfg.unbonding_periods.binary_search(&unbonding_period) {
+        Ok(_) => {
+            return Err(ContractError::UnbondingPeriodAlreadyExists(
+                unbonding_period,
+            ))
+        }
+        Err(idx) => idx,
+    };
+    cfg.unbonding_periods.insert(idx, unbonding_period);
+
+    let mut rewards = rewards
+        .into_iter()
+        .map(|(asset, multiplier)| Ok((asset.validate(deps.api)?, multiplier)))
+        .collect::<Result<HashMap<_, _>, ContractError>>()?;
+
+    let distributions = DISTRIBUTION
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (asset, mut distribution) in distributions {
+        let multiplier = rewards
+            .remove(&asset)
+            .ok_or_else(|| ContractError::MissingRewardMultiplier(asset.clone()))?;
+        distribution
+            .reward_multipliers
+            .insert(idx, (unbonding_period, multiplier));
+        validate_reward_multipliers(&cfg, &distribution.reward_multipliers)?;
+        if let Some(update) = distribution.update.as_mut() {
+            update
+                .old_rewards
+                .insert(idx, (unbonding_period, multiplier));
+        }
+        DISTRIBUTION.save(deps.storage, &asset, &distribution)?;
+        record_multiplier_change(
+            deps.storage,
+            &asset,
+            env.block.time.seconds(),
+            distribution.reward_multipliers,
+        )?;
+
+        if let Some(mut scheduled) = SCHEDULED_MULTIPLIERS.may_load(deps.storage, &asset)? {
+            scheduled
+                .rewards
+                .insert(idx, (unbonding_period, multiplier));
+            validate_reward_multipliers(&cfg, &scheduled.rewards)?;
+            SCHEDULED_MULTIPLIERS.save(deps.storage, &asset, &scheduled)?;
+        }
+    }
+    // multipliers for assets without a distribution flow
+    if !rewards.is_empty() {
+        return Err(ContractError::InvalidRewards {});
+    }
+
+    CONFIG.save(deps.storage, &cfg)?;
+    TOTAL_PER_PERIOD.update(deps.storage, |mut totals| -> StdResult<_> {
+        totals.insert(idx, (unbonding_period, TotalStake::default()));
+        Ok(totals)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_unbonding_period")
+        .add_attribute("unbonding_period", unbonding_period.to_string()))
+}
+
+/// Sets the minimum rewards power a staker needs to earn from the distribution flow of `asset`.
+/// The stakers are switched to it by [`ExecuteMsg::ApplyDistributionUpdate`].
+/// Only the manager of the distribution flow can do this.
+pub fn execute_set_min_power_to_earn(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: AssetInfo,
+    min_power: Uint128,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let mut distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    if info.sender != distribution.manager {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    begin_distribution_update(deps.storage, &asset, &mut distribution)?;
+    distribution.min_power_to_earn = min_power;
+    DISTRIBUTION.save(deps.storage, &asset, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_min_power_to_earn")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("min_power", min_power))
+}
+
+/// Halts or resumes the distribution flow of `asset`.
+/// Its reward curve is frozen while paused and continues where it was paused once resumed.
+/// Only the manager of the distribution flow or the admin can do this.
+pub fn execute_set_distribution_paused(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let mut distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    if info.sender != distribution.manager && !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if paused {
+        // freeze the reward curve, so nothing is released while paused
+        if !distribution.paused {
+            distribution.paused = true;
+            distribution.paused_at = Some(env.block.time.seconds());
+        }
+    } else {
+        unpause_distribution(deps.storage, &env, &asset, &mut distribution)?;
+    }
+    DISTRIBUTION.save(deps.storage, &asset, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_distribution_paused")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("paused", paused.to_string()))
+}
+
+/// Halts the distribution flow of `asset` and freezes its reward curve,
+/// so that no rewards are released until it is resumed.
+/// Only the manager of the distribution flow can do this.
+pub fn execute_pause_distribution(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let mut distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    if info.sender != distribution.manager {
+        return Err(ContractError::Unauthorized {});
+    }
+    if distribution.paused {
+        return Err(ContractError::DistributionPaused(asset));
+    }
+
+    distribution.paused = true;
+    distribution.paused_at = Some(env.block.time.seconds());
+    DISTRIBUTION.save(deps.storage, &asset, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause_distribution")
+        .add_attribute("asset", asset.to_string()))
+}
+
+/// Resumes the distribution flow of `asset`, continuing its reward curve where it was paused.
+/// Only the manager of the distribution flow can do this.
+pub fn execute_resume_distribution(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    let asset = asset.validate(deps.api)?;
+    let mut distribution = DISTRIBUTION.load(deps.storage, &asset)?;
+    if info.sender != distribution.manager {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !distribution.paused {
+        return Err(ContractError::DistributionNotPaused(asset));
+    }
+
+    unpause_distribution(deps.storage, &env, &asset, &mut distribution)?;
+    DISTRIBUTION.save(deps.storage, &asset, &distribution)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "resume_distribution")
+        .add_attribute("asset", asset.to_string()))
+}
+
+/// Unpauses the given distribution flow. If its reward curve was frozen,
+/// the curve is delayed by the paused time, so nothing is released for that time.
+fn unpause_distribution(
+    storage: &mut dyn Storage,
+    env: &Env,
+    asset: &AssetInfoValidated,
+    distribution: &mut Distribution,
+) -> StdResult<()> {
+    if let Some(paused_at) = distribution.paused_at.take() {
+        let paused_for = env.block.time.seconds().saturating_sub(paused_at);
+        let curve = REWARD_CURVE.load(storage, asset)?;
+        REWARD_CURVE.save(storage, asset, &curve.shift(paused_for))?;
+        // the funders' schedules are delayed the same way
+        let schedules = FUNDER_SCHEDULES
+            .prefix(asset)
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (funder, schedule) in schedules {
+            FUNDER_SCHEDULES.save(storage, (asset, &funder), &schedule.shift(paused_for))?;
+        }
+    }
+    distribution.paused = false;
+    Ok(())
+}
+
+/// Sets the delegation rights of `staker` for new stake on behalf of `sender`.
+/// Only the staker itself can change its rights, everyone else has to use the ones the staker
+/// set before. The rights can only change while the staker has no bonded tokens, so that nobody
+/// can take control over existing stake and no stake is added to a bond controlled by someone else.
+fn set_delegation_rights(
+    storage: &mut dyn Storage,
+    staker: &Addr,
+    sender: &str,
+    rights: DelegationRights,
+) -> Result<(), ContractError> {
+    let current = DELEGATION_RIGHTS
+        .may_load(storage, staker)?
+        .unwrap_or_default();
+    if current == rights {
+        return Ok(());
+    }
+    if staker.as_str() != sender {
+        return Err(ContractError::DelegationRightsMismatch {});
+    }
+
+    let stakes = STAKE
+        .prefix(staker)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if stakes
+        .iter()
+        .any(|(_, bonding_info)| !bonding_info.total_stake().is_zero())
+    {
+        return Err(ContractError::DelegationRightsMismatch {});
+    }
+
+    if rights == DelegationRights::default() {
+        DELEGATION_RIGHTS.remove(storage, staker);
+    } else {
+        DELEGATION_RIGHTS.save(storage, staker, &rights)?;
+    }
+    Ok(())
+}
+
+/// Sets the rights the sender gives away over its future stake, so that others can delegate to it
+/// with these rights. Only possible while the sender has no bonded tokens.
+pub fn execute_set_delegation_rights(
+    deps: DepsMut,
+    info: MessageInfo,
+    unbond_controller: Option<String>,
+    withdraw_to: Option<String>,
+) -> Result<Response, ContractError> {
+    let rights = DelegationRights {
+        unbond_controller: addr_opt_validate(deps.api, &unbond_controller)?,
+        withdraw_to: addr_opt_validate(deps.api, &withdraw_to)?,
+    };
+    set_delegation_rights(deps.storage, &info.sender, info.sender.as_str(), rights)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_delegation_rights")
+        .add_attribute("sender", info.sender))
+}
+
+/// Checks that `sender` is allowed to unbond or rebond the stake of `staker`.
+/// This is the staker itself, unless an unbond controller was set on delegation.
+fn assert_unbond_controller(
+    storage: &dyn Storage,
+    staker: &Addr,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    let controller = DELEGATION_RIGHTS
+        .may_load(storage, staker)?
+        .and_then(|rights| rights.unbond_controller)
+        .unwrap_or_else(|| staker.clone());
+    if controller != *sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Removes the stake from the given unbonding period and staker,
+/// updating `DISTRIBUTION`, `TOTAL_PER_PERIOD` and `STAKE`, but *not* `TOTAL_STAKED`.
+fn remove_stake_without_total(
+    deps: DepsMut,
+    env: &Env,
+    cfg: &Config,
+    staker: &Addr,
+    unbonding_period: UnbondingPeriod,
+    amount: Uint128,
+) -> Result<Option<Event>, ContractError> {
+    if cfg
+        .unbonding_periods
+        .binary_search(&unbonding_period)
+        .is_err()
+    {
+        return Err(ContractError::NoUnbondingPeriodFound(unbonding_period));
+    }
+
+    let distributions: Vec<_> = DISTRIBUTION
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    // calculate rewards power before updating the stake
+    let old_rewards = calc_rewards_powers(deps.storage, cfg, staker, distributions.iter())?;
+    let had_stake = has_stake(deps.storage, staker)?;
+
+    // reduce the sender's stake - aborting if insufficient
+    let mut old_stake = Uint128::zero();
+    let new_stake = STAKE
+        .update(
+            deps.storage,
+            (staker, unbonding_period),
+            |bonding_info| -> StdResult<_> {
+                let mut bonding_info = bonding_info.unwrap_or_default();
+                old_stake = bonding_info.total_stake();
+                bonding_info.release_stake(env, amount)?;
+                Ok(bonding_info)
+            },
+        )?
+        .total_stake();
+
+    update_total_stake(
+        deps.storage,
+        cfg,
+        staker,
+        unbonding_period,
+        old_stake,
+        new_stake,
+    )?;
+
+    // update the adjustment data for all distributions
+    for ((asset_info, mut distribution), old_reward_power) in
+        distributions.into_iter().zip(old_rewards.into_iter())
+    {
+        let new_reward_power = distribution.calc_rewards_power(deps.storage, cfg, staker)?;
+        update_rewards(
+            deps.storage,
+            &asset_info,
+            staker,
+            &mut distribution,
+            old_reward_power,
+            new_reward_power,
+        )?;
+
+        // save updated distribution
+        DISTRIBUTION.save(deps.storage, &asset_info, &distribution)?;
+    }
+    Ok(update_staker_count(deps.storage, staker, had_stake)?)
+}
+
+/// Returns `true` if the address has stake in any unbonding period
+fn has_stake(storage: &dyn Storage, staker: &Addr) -> StdResult<bool> {
+    for stake in STAKE
+        .prefix(staker)
+        .range(storage, None, None, Order::Ascending)
+    {
+        if !stake?.1.total_stake().is_zero() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Updates the number of stakers after the stake of `staker` changed.
+/// Returns an event with the new count if it changed.
+fn update_staker_count(
+    storage: &mut dyn Storage,
+    staker: &Addr,
+    had_stake: bool,
+) -> StdResult<Option<Event>> {
+    let has_stake = has_stake(storage, staker)?;
+    if has_stake == had_stake {
+        return Ok(None);
+    }
+
+    let count = STAKER_COUNT.may_load(storage)?.unwrap_or_default();
+    let count = if has_stake {
+        count + 1
+    } else {
+        count.saturating_sub(1)
+    };
+    STAKER_COUNT.save(storage, &count)?;
+
+    Ok(Some(
+        Event::new("staker_count")
+            .add_attribute("staker", staker)
+            .add_attribute("count", count.to_string()),
+    ))
+}
+
+/// Returns up to `limit` addresses with stake or pending claims after `start_after`, in ascending order.
+/// These are all addresses that can have rewards power.
+fn stakers_after(
+    storage: &dyn Storage,
+    start_after: Option<&Addr>,
+    limit: usize,
+) -> StdResult<Vec<Addr>> {
+    let mut stakers = BTreeSet::new();
+    // the stake of an address is spread over several keys, one per unbonding period
+    let mut staked = STAKE
+        .keys(
+            storage,
+            start_after.map(|addr| Bound::exclusive((addr, UnbondingPeriod::MAX))),
+            None,
+            Order::Ascending,
+        )
+        .map(|key| key.map(|(staker, _)| staker));
+    while stakers.len() < limit {
+        match staked.next().transpose()? {
+            Some(staker) => stakers.insert(staker),
+            None => break,
+        };
+    }
+    // the first `limit` addresses of both maps contain the first `limit` addresses overall
+    for staker in PENDING_CLAIMS
+        .keys(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+    {
+        stakers.insert(staker?);
+    }
+    Ok(stakers.into_iter().take(limit).collect())
+}
+
+/// Updates the pending claims of the given staker using `update`,
+/// updating `PENDING_CLAIMS`, `TOTAL_PENDING_PER_PERIOD` and the staker's rewards in all distributions.
+fn update_pending_claims(
+    storage: &mut dyn Storage,
+    cfg: &Config,
+    staker: &Addr,
+    update: impl FnOnce(&mut Vec<PendingClaim>),
+) -> Result<(), ContractError> {
     let distributions: Vec<_> = DISTRIBUTION
-        .range(deps.storage, None, None, Order::Ascending)
+        .range(storage, None, None, Order::Ascending)
         .collect::<StdResult<Vec<_>>>()?;
-    // calculate rewards power before updating the stake
-    let old_rewards = calc_rewards_powers(deps.storage, cfg, staker, distributions.iter())?;
-
-    // reduce the sender's stake - aborting if insufficient
-    let mut old_stake = Uint128::zero();
-    let new_stake = STAKE
-        .update(
-            deps.storage,
-            (staker, unbonding_period),
-            |bonding_info| -> StdResult<_> {
-                let mut bonding_info = bonding_info.unwrap_or_default();
-                old_stake = bonding_info.total_stake();
-                bonding_info.release_stake(env, amount)?;
-                Ok(bonding_info)
-            },
-        )?
-        .total_stake();
+    // calculate rewards power before updating the claims
+    let old_rewards = calc_rewards_powers(storage, cfg, staker, distributions.iter())?;
 
-    update_total_stake(deps.storage, cfg, unbonding_period, old_stake, new_stake)?;
+    let mut claims = PENDING_CLAIMS
+        .may_load(storage, staker)?
+        .unwrap_or_default();
+    update(&mut claims);
+    save_pending_claims(storage, staker, claims)?;
 
     // update the adjustment data for all distributions
     for ((asset_info, mut distribution), old_reward_power) in
         distributions.into_iter().zip(old_rewards.into_iter())
     {
-        let new_reward_power = distribution.calc_rewards_power(deps.storage, cfg, staker)?;
+        let new_reward_power = distribution.calc_rewards_power(storage, cfg, staker)?;
         update_rewards(
-            deps.storage,
+            storage,
             &asset_info,
             staker,
             &mut distribution,
             old_reward_power,
             new_reward_power,
         )?;
+        DISTRIBUTION.save(storage, &asset_info, &distribution)?;
+    }
+    Ok(())
+}
 
-        // save updated distribution
-        DISTRIBUTION.save(deps.storage, &asset_info, &distribution)?;
+/// Returns whether any distribution flow rewards pending claims.
+/// New claims only have to be tracked in that case.
+fn rewards_claimants(storage: &dyn Storage) -> StdResult<bool> {
+    for distribution in DISTRIBUTION.range(storage, None, None, Order::Ascending) {
+        if !distribution?.1.claimant_reward_share.is_zero() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Stops the pending claims of `staker` that matured until `now` from accruing rewards.
+pub(crate) fn expire_own_pending_claims(
+    storage: &mut dyn Storage,
+    cfg: &Config,
+    staker: &Addr,
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    let matured = PENDING_CLAIMS
+        .may_load(storage, staker)?
+        .unwrap_or_default()
+        .iter()
+        .any(|claim| claim.release_at <= now);
+    if matured {
+        update_pending_claims(storage, cfg, staker, |claims| {
+            claims.retain(|claim| claim.release_at > now)
+        })?;
+    }
+    Ok(())
+}
+
+/// Stops the pending claims that matured until `now` from accruing rewards, oldest first.
+/// At most [`MAX_UPDATE_LIMIT`] stakers are processed, the rest follows with the next call.
+pub(crate) fn expire_pending_claims(
+    storage: &mut dyn Storage,
+    cfg: &Config,
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    let matured = PENDING_CLAIM_MATURITY
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive((now.seconds() + 1, &Addr::unchecked("")))),
+            Order::Ascending,
+        )
+        .map(|key| key.map(|(_, staker)| staker))
+        .take(MAX_UPDATE_LIMIT as usize)
+        .collect::<StdResult<BTreeSet<_>>>()?;
+    for staker in matured {
+        update_pending_claims(storage, cfg, &staker, |claims| {
+            claims.retain(|claim| claim.release_at > now)
+        })?;
+    }
+    Ok(())
+}
+
+/// Replaces the pending claims of the given staker, keeping `TOTAL_PENDING_PER_PERIOD` and
+/// `PENDING_CLAIM_MATURITY` in sync.
+/// This does *not* update the staker's rewards.
+fn save_pending_claims(
+    storage: &mut dyn Storage,
+    staker: &Addr,
+    claims: Vec<PendingClaim>,
+) -> StdResult<()> {
+    let old_claims = PENDING_CLAIMS
+        .may_load(storage, staker)?
+        .unwrap_or_default();
+    for claim in &old_claims {
+        TOTAL_PENDING_PER_PERIOD.update(storage, claim.unbonding_period, |total| {
+            Ok::<_, StdError>(total.unwrap_or_default().checked_sub(claim.amount)?)
+        })?;
+    }
+    for claim in &claims {
+        TOTAL_PENDING_PER_PERIOD.update(storage, claim.unbonding_period, |total| {
+            Ok::<_, StdError>(total.unwrap_or_default().checked_add(claim.amount)?)
+        })?;
+    }
+    for claim in old_claims {
+        PENDING_CLAIM_MATURITY.remove(storage, (claim.release_at.seconds(), staker));
+    }
+    for claim in &claims {
+        PENDING_CLAIM_MATURITY.save(storage, (claim.release_at.seconds(), staker), &())?;
+    }
+
+    if claims.is_empty() {
+        PENDING_CLAIMS.remove(storage, staker);
+    } else {
+        PENDING_CLAIMS.save(storage, staker, &claims)?;
     }
     Ok(())
 }
@@ -1014,8 +3751,16 @@ pub fn execute_claim(
     }
 
     let config = CONFIG.load(deps.storage)?;
+    // claimed tokens stop accruing rewards
+    update_pending_claims(deps.storage, &config, &info.sender, |claims| {
+        claims.retain(|claim| claim.release_at > env.block.time)
+    })?;
+
     let amount_str = coin_to_string(release, config.cw20_contract.as_str());
-    let undelegate_msg = create_undelegate_msg(info.sender.clone(), release, config.cw20_contract)?;
+    let receiver = CLAIM_RECEIVER
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_else(|| info.sender.clone());
+    let undelegate_msg = create_undelegate_msg(receiver.clone(), release, config.cw20_contract)?;
 
     TOTAL_STAKED.update::<_, StdError>(deps.storage, |token_info| {
         Ok(TokenInfo {
@@ -1028,7 +3773,81 @@ pub fn execute_claim(
         .add_submessage(undelegate_msg)
         .add_attribute("action", "claim")
         .add_attribute("tokens", amount_str)
-        .add_attribute("sender", info.sender))
+        .add_attribute("sender", info.sender)
+        .add_attribute("receiver", receiver))
+}
+
+pub fn execute_set_claim_receiver(
+    deps: DepsMut,
+    info: MessageInfo,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let receiver = addr_opt_validate(deps.api, &receiver)?;
+    match &receiver {
+        Some(receiver) => CLAIM_RECEIVER.save(deps.storage, &info.sender, receiver)?,
+        None => CLAIM_RECEIVER.remove(deps.storage, &info.sender),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_claim_receiver")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "receiver",
+            receiver.map_or_else(|| "none".to_string(), Addr::into_string),
+        ))
+}
+
+pub fn execute_set_reward_receiver(
+    deps: DepsMut,
+    info: MessageInfo,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let receiver = addr_opt_validate(deps.api, &receiver)?;
+    match &receiver {
+        Some(receiver) => REWARD_RECEIVER.save(deps.storage, &info.sender, receiver)?,
+        None => REWARD_RECEIVER.remove(deps.storage, &info.sender),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_reward_receiver")
+        .add_attribute("sender", info.sender)
+        .add_attribute(
+            "receiver",
+            receiver.map_or_else(|| "none".to_string(), Addr::into_string),
+        ))
+}
+
+/// Claims all matured tokens and withdraws all rewards of the sender in one go.
+pub fn execute_claim_and_withdraw_all(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut resp = Response::new()
+        .add_attribute("action", "claim_and_withdraw_all")
+        .add_attribute("sender", info.sender.as_str());
+
+    match execute_claim(deps.branch(), env.clone(), info.clone()) {
+        Ok(claim_resp) => {
+            resp = resp.add_submessages(claim_resp.messages).add_attributes(
+                claim_resp
+                    .attributes
+                    .into_iter()
+                    .filter(|attr| attr.key == "tokens"),
+            );
+        }
+        // there might only be rewards to withdraw
+        Err(ContractError::NothingToClaim {}) => {}
+        Err(err) => return Err(err),
+    }
+
+    let withdraw_resp = execute_withdraw_rewards(deps, env, info, None, None)?;
+    Ok(resp.add_submessages(withdraw_resp.messages).add_attributes(
+        withdraw_resp
+            .attributes
+            .into_iter()
+            .filter(|attr| attr.key.starts_with("reward_")),
+    ))
 }
 
 #[inline]
@@ -1046,7 +3865,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             address,
             unbonding_period,
         } => to_binary(&query_staked(deps, &env, address, unbonding_period)?),
-        QueryMsg::AnnualizedRewards {} => to_binary(&query_annualized_rewards(deps, env)?),
+        QueryMsg::AnnualizedRewards {} => {
+            to_binary(&query_annualized_rewards(deps, env.block.time.seconds())?)
+        }
+        QueryMsg::AnnualizedRewardsAt { timestamp } => {
+            to_binary(&query_annualized_rewards(deps, timestamp)?)
+        }
         QueryMsg::BondingInfo {} => to_binary(&query_bonding_info(deps)?),
         QueryMsg::AllStaked { address } => to_binary(&query_all_staked(deps, env, address)?),
         QueryMsg::TotalStaked {} => to_binary(&query_total_staked(deps)?),
@@ -1054,17 +3878,82 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
         QueryMsg::TotalRewardsPower {} => to_binary(&query_total_rewards(deps)?),
         QueryMsg::RewardsPower { address } => to_binary(&query_rewards(deps, address)?),
+        QueryMsg::UserEarningAssets { address } => {
+            let rewards = query_rewards(deps, address)?.rewards;
+            to_binary(
+                &rewards
+                    .into_iter()
+                    .map(|(asset_info, _)| asset_info)
+                    .collect::<Vec<_>>(),
+            )
+        }
+        QueryMsg::TotalRewardsPowerExcluding { address } => {
+            to_binary(&query_total_rewards_excluding(deps, address)?)
+        }
         QueryMsg::WithdrawableRewards { owner } => {
             to_binary(&query_withdrawable_rewards(deps, owner)?)
         }
+        QueryMsg::WithdrawableRewardsDetailed { owner } => {
+            to_binary(&query_withdrawable_rewards_detailed(deps, owner)?)
+        }
         QueryMsg::DistributedRewards {} => to_binary(&query_distributed_rewards(deps)?),
         QueryMsg::UndistributedRewards {} => to_binary(&query_undistributed_rewards(deps, env)?),
         QueryMsg::Delegated { owner } => to_binary(&query_delegated(deps, owner)?),
+        QueryMsg::RewardReceiver { owner } => to_binary(&query_reward_receiver(deps, owner)?),
         QueryMsg::DistributionData {} => to_binary(&query_distribution_data(deps)?),
         QueryMsg::WithdrawAdjustmentData { addr, asset } => {
             to_binary(&query_withdraw_adjustment_data(deps, addr, asset)?)
         }
         QueryMsg::UnbondAll {} => to_binary(&query_unbond_all(deps)?),
+        QueryMsg::RebondCooldown {
+            address,
+            unbonding_period,
+        } => to_binary(&query_rebond_cooldown(
+            deps,
+            &env,
+            address,
+            unbonding_period,
+        )?),
+        QueryMsg::UserSummary { address } => to_binary(&query_user_summary(deps, env, address)?),
+        QueryMsg::LastInteraction { address } => to_binary(&query_last_interaction(deps, address)?),
+        QueryMsg::TotalToBeDistributed { asset } => {
+            to_binary(&query_total_to_be_distributed(deps, env, asset)?)
+        }
+        QueryMsg::RewardTokenBalance {} => to_binary(&query_reward_token_balance(deps, env)?),
+        QueryMsg::NextRewardChange { owner } => {
+            to_binary(&query_next_reward_change(deps, env, owner)?)
+        }
+        QueryMsg::PendingDistribution {} => to_binary(&query_pending_distribution(deps, env)?),
+        QueryMsg::FlowAccounting { asset } => to_binary(&query_flow_accounting(deps, env, asset)?),
+        QueryMsg::LifetimeFunded {} => to_binary(&query_lifetime_funded(deps)?),
+        QueryMsg::AssetReconciliation { asset } => {
+            to_binary(&query_asset_reconciliation(deps, env, asset)?)
+        }
+        QueryMsg::UserMultipliers { address } => to_binary(&query_user_multipliers(deps, address)?),
+        QueryMsg::ScheduledMultipliers { asset } => {
+            let asset = asset.validate(deps.api)?;
+            to_binary(&SCHEDULED_MULTIPLIERS.may_load(deps.storage, &asset)?)
+        }
+        QueryMsg::MultiplierHistory { asset } => {
+            let asset = asset.validate(deps.api)?;
+            to_binary(
+                &MULTIPLIER_HISTORY
+                    .may_load(deps.storage, &asset)?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::StakerCount {} => to_binary(&query_staker_count(deps)?),
+        QueryMsg::DistributionPreview { asset, amount } => {
+            to_binary(&query_distribution_preview(deps, asset, amount)?)
+        }
+        QueryMsg::WithdrawableAt { owner, at_time } => {
+            to_binary(&query_withdrawable_at(deps, env, owner, at_time)?)
+        }
+        QueryMsg::EarnedSinceWithdraw { owner } => {
+            to_binary(&query_earned_since_withdraw(deps, owner)?)
+        }
+        QueryMsg::RateAt { asset, at_time } => to_binary(&query_rate_at(deps, asset, at_time)?),
+        QueryMsg::FlowsSchedule {} => to_binary(&query_flows_schedule(deps, env)?),
     }
 }
 
@@ -1078,9 +3967,9 @@ struct DistStats {
     annualized_payout: Decimal,
 }
 
-fn query_annualized_rewards(deps: Deps, env: Env) -> StdResult<AnnualizedRewardsResponse> {
+/// Annualizes the rewards the reward curves release at time `now`, based on the current stake.
+fn query_annualized_rewards(deps: Deps, now: u64) -> StdResult<AnnualizedRewardsResponse> {
     let config = CONFIG.load(deps.storage)?;
-    let now = env.block.time.seconds();
 
     // reward info for each distribution flow... do all the heavy calcs per distribution once.
     // we can then just read this for each unbonding period
@@ -1191,7 +4080,7 @@ fn query_rewards(deps: Deps, addr: String) -> StdResult<RewardsPowerResponse> {
             let cfg = CONFIG.load(deps.storage)?;
 
             distribution
-                .calc_rewards_power(deps.storage, &cfg, &addr)
+                .calc_earning_power(deps.storage, &cfg, &addr)
                 .map(|power| (asset_info, power))
         })
         .filter(|dist| matches!(dist, Ok((_, power)) if !power.is_zero()))
@@ -1200,6 +4089,34 @@ fn query_rewards(deps: Deps, addr: String) -> StdResult<RewardsPowerResponse> {
     Ok(RewardsPowerResponse { rewards })
 }
 
+fn query_user_multipliers(
+    deps: Deps,
+    address: String,
+) -> StdResult<Vec<(AssetInfoValidated, UnbondingPeriod, Decimal)>> {
+    let address = deps.api.addr_validate(&address)?;
+    let periods = STAKE
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|stake| match stake {
+            Ok((_, bonding_info)) if bonding_info.total_stake().is_zero() => None,
+            stake => Some(stake.map(|(period, _)| period)),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut multipliers = vec![];
+    for distribution in DISTRIBUTION.range(deps.storage, None, None, Order::Ascending) {
+        let (asset_info, distribution) = distribution?;
+        for &period in &periods {
+            let multiplier = distribution
+                .rewards_multiplier(period)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+            multipliers.push((asset_info.clone(), period, multiplier));
+        }
+    }
+
+    Ok(multipliers)
+}
+
 fn query_total_rewards(deps: Deps) -> StdResult<RewardsPowerResponse> {
     Ok(RewardsPowerResponse {
         rewards: DISTRIBUTION
@@ -1217,6 +4134,22 @@ fn query_total_rewards(deps: Deps) -> StdResult<RewardsPowerResponse> {
     })
 }
 
+fn query_total_rewards_excluding(deps: Deps, address: String) -> StdResult<RewardsPowerResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    Ok(RewardsPowerResponse {
+        rewards: DISTRIBUTION
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|distr| {
+                let (asset_info, distribution) = distr?;
+                let total = distribution.total_rewards_power(deps.storage, &cfg);
+                let own = distribution.calc_earning_power(deps.storage, &cfg, &address)?;
+                Ok((asset_info, total.saturating_sub(own)))
+            })
+            .collect::<StdResult<Vec<_>>>()?,
+    })
+}
+
 fn query_bonding_info(deps: Deps) -> StdResult<BondingInfoResponse> {
     let total_stakes = TOTAL_PER_PERIOD.load(deps.storage)?;
 
@@ -1282,12 +4215,41 @@ pub fn query_all_staked(deps: Deps, env: Env, addr: String) -> StdResult<AllStak
     Ok(AllStakedResponse { stakes })
 }
 
+pub fn query_user_summary(deps: Deps, env: Env, addr: String) -> StdResult<UserSummaryResponse> {
+    let validated = deps.api.addr_validate(&addr)?;
+
+    let stakes = query_all_staked(deps, env.clone(), addr.clone())?.stakes;
+    let total_staked = stakes.iter().map(|stake| stake.stake).sum();
+
+    let (claimable, pending_claims): (Vec<_>, Vec<_>) = CLAIMS
+        .query_claims(deps, &validated)?
+        .claims
+        .into_iter()
+        .partition(|claim| claim.release_at.is_expired(&env.block));
+
+    Ok(UserSummaryResponse {
+        stakes,
+        total_staked,
+        rewards_power: query_rewards(deps, addr.clone())?.rewards,
+        withdrawable_rewards: query_withdrawable_rewards(deps, addr.clone())?.rewards,
+        pending_claims,
+        claimable: claimable.iter().map(|claim| claim.amount).sum(),
+        next_reward_change: query_next_reward_change(deps, env, addr)?.timestamp,
+    })
+}
+
 pub fn query_total_staked(deps: Deps) -> StdResult<TotalStakedResponse> {
     Ok(TotalStakedResponse {
         total_staked: TOTAL_STAKED.load(deps.storage).unwrap_or_default().staked,
     })
 }
 
+pub fn query_staker_count(deps: Deps) -> StdResult<StakerCountResponse> {
+    Ok(StakerCountResponse {
+        count: STAKER_COUNT.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
 pub fn query_total_unbonding(deps: Deps) -> StdResult<TotalUnbondingResponse> {
     Ok(TotalUnbondingResponse {
         total_unbonding: TOTAL_STAKED
@@ -1303,10 +4265,46 @@ pub fn query_unbond_all(deps: Deps) -> StdResult<UnbondAllResponse> {
     })
 }
 
+pub fn query_rebond_cooldown(
+    deps: Deps,
+    env: &Env,
+    addr: String,
+    unbonding_period: u64,
+) -> StdResult<RebondCooldownResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    if cfg
+        .unbonding_periods
+        .binary_search(&unbonding_period)
+        .is_err()
+    {
+        return Err(StdError::generic_err(format!(
+            "No unbonding period found: {}",
+            unbonding_period
+        )));
+    }
+
+    // tokens rebonded from a longer period are locked for the difference between the periods
+    let remaining = STAKE
+        .may_load(deps.storage, (&addr, unbonding_period))?
+        .and_then(|stake| stake.locked_until(env))
+        .map(|until| until.seconds() - env.block.time.seconds())
+        .unwrap_or_default();
+
+    Ok(RebondCooldownResponse { remaining })
+}
+
+pub fn query_last_interaction(deps: Deps, addr: String) -> StdResult<LastInteractionResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    Ok(LastInteractionResponse {
+        last_interaction: LAST_INTERACTION.may_load(deps.storage, &addr)?,
+    })
+}
+
 /// Manages the contract migration.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let old_version = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     // add unbonder to config
     let mut config = CONFIG.load(deps.storage)?;
@@ -1325,12 +4323,29 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
     // set unbond all flag
     UNBOND_ALL.save(deps.storage, &msg.unbond_all)?;
 
-    Ok(Response::new())
+    // summary of what this migration touched, so operators can audit it
+    let distribution_flows = DISTRIBUTION
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    let report = Event::new("migration_report")
+        .add_attribute("from_version", old_version.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("distribution_flows", distribution_flows.to_string())
+        .add_attribute("unbond_all", msg.unbond_all.to_string())
+        .add_attribute(
+            "staker_count",
+            STAKER_COUNT
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                .to_string(),
+        );
+
+    Ok(Response::new().add_event(report))
 }
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
     use cosmwasm_std::{from_slice, Coin, CosmosMsg, Decimal, WasmMsg};
     use cw_controllers::Claim;
     use cw_utils::Duration;
@@ -1409,6 +4424,8 @@ mod tests {
                     msg: to_binary(&ReceiveMsg::Delegate {
                         unbonding_period,
                         delegate_as: None,
+                        unbond_controller: None,
+                        withdraw_to: None,
                     })
                     .unwrap(),
                 });
@@ -1440,6 +4457,7 @@ mod tests {
                     bond_from,
                     bond_to,
                     tokens: Uint128::new(*stake),
+                    owner: None,
                 };
                 let info = mock_info(addr, &[]);
                 execute(deps.branch(), env.clone(), info, msg).unwrap();
@@ -1463,6 +4481,7 @@ mod tests {
                 let msg = ExecuteMsg::Unbond {
                     tokens: Uint128::new(*stake),
                     unbonding_period,
+                    owner: None,
                 };
                 let info = mock_info(addr, &[]);
                 execute(deps.branch(), env.clone(), info, msg).unwrap();
@@ -1491,10 +4510,12 @@ mod tests {
         // setup distribution flow
         execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
             vec![(UNBONDING_PERIOD, Decimal::percent(1))],
+            None,
         )
         .unwrap();
 
@@ -1512,6 +4533,14 @@ mod tests {
                     withdrawable_total: Uint128::zero(),
                     manager: Addr::unchecked(INIT_ADMIN),
                     reward_multipliers: vec![(UNBONDING_PERIOD, Decimal::percent(1))],
+                    claimant_reward_share: Decimal::zero(),
+                    min_power_to_earn: Uint128::zero(),
+                    excluded_power: Uint128::zero(),
+                    paused: false,
+                    paused_at: None,
+                    rounding_surplus: Uint128::zero(),
+                    update: None,
+                    generation: 0,
                 }
             )]
         );
@@ -1531,6 +4560,7 @@ mod tests {
             WithdrawAdjustment {
                 shares_correction: 0,
                 withdrawn_rewards: Uint128::zero(),
+                generation: 0,
             }
         );
     }
@@ -1813,10 +4843,12 @@ mod tests {
         // create distribution flow to be able to receive rewards
         execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
             vec![(UNBONDING_PERIOD, Decimal::percent(1))],
+            None,
         )
         .unwrap();
 
@@ -1881,6 +4913,7 @@ mod tests {
         // create distribution flow to be able to receive rewards
         execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
@@ -1888,6 +4921,7 @@ mod tests {
                 (UNBONDING_PERIOD, Decimal::percent(1)),
                 (UNBONDING_PERIOD_2, Decimal::percent(10)),
             ],
+            None,
         )
         .unwrap();
 
@@ -2029,20 +5063,24 @@ mod tests {
         for denom in &DENOMS {
             execute_create_distribution_flow(
                 deps.as_mut(),
+                mock_env(),
                 mock_info(INIT_ADMIN, &[]),
                 INIT_ADMIN.to_string(),
                 native_asset_info(denom),
                 vec![(UNBONDING_PERIOD, Decimal::one())],
+                None,
             )
             .unwrap();
         }
         // next one should fail
         let err = execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
             vec![(UNBONDING_PERIOD, Decimal::one())],
+            None,
         )
         .unwrap_err();
         assert_eq!(err, ContractError::TooManyDistributions(6));
@@ -2056,20 +5094,24 @@ mod tests {
         // create distribution flow
         execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
             vec![(UNBONDING_PERIOD, Decimal::one())],
+            None,
         )
         .unwrap();
 
         // next one should fail
         let err = execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
             vec![(UNBONDING_PERIOD, Decimal::one())],
+            None,
         )
         .unwrap_err();
 
@@ -2081,6 +5123,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn invalid_distribution_manager() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut(), mock_env());
+
+        let mut create_flow = |manager: &str| {
+            execute_create_distribution_flow(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(INIT_ADMIN, &[]),
+                manager.to_string(),
+                native_asset_info(DENOM),
+                vec![(UNBONDING_PERIOD, Decimal::one())],
+                None,
+            )
+        };
+
+        // the staking contract itself cannot be the manager
+        let err = create_flow(MOCK_CONTRACT_ADDR).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidManager(Addr::unchecked(MOCK_CONTRACT_ADDR))
+        );
+
+        // neither can the staked token
+        let err = create_flow(CW20_ADDRESS).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidManager(Addr::unchecked(CW20_ADDRESS))
+        );
+
+        // invalid addresses are rejected
+        let err = create_flow("").unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::Std(StdError::GenericErr { .. })
+        ));
+
+        // a normal address is fine
+        create_flow(USER1).unwrap();
+    }
+
     #[test]
     fn distribute_unsupported_token_fails() {
         let mut deps = mock_dependencies();
@@ -2089,10 +5173,12 @@ mod tests {
         // create distribution flow
         execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
             vec![(UNBONDING_PERIOD, Decimal::one())],
+            None,
         )
         .unwrap();
 
@@ -2113,21 +5199,26 @@ mod tests {
     }
 
     #[test]
-    fn cannot_distribute_staking_token() {
+    fn can_distribute_staking_token() {
         let mut deps = mock_dependencies();
         default_instantiate(deps.as_mut(), mock_env());
 
-        // try to create distribution flow for staking token
-        let err = execute_create_distribution_flow(
+        // create distribution flow for staking token
+        execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             token_asset_info(CW20_ADDRESS),
             vec![(UNBONDING_PERIOD, Decimal::one())],
+            None,
         )
-        .unwrap_err();
+        .unwrap();
 
-        assert_eq!(err, ContractError::InvalidAsset {});
+        assert!(DISTRIBUTION.has(
+            &deps.storage,
+            &AssetInfoValidated::Token(Addr::unchecked(CW20_ADDRESS))
+        ));
     }
 
     #[test]
@@ -2138,10 +5229,12 @@ mod tests {
         // try to create distribution flow for staking token
         let _res = execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
             vec![(UNBONDING_PERIOD, Decimal::one())],
+            None,
         )
         .unwrap();
         let err = execute_fund_distribution(
@@ -2159,12 +5252,80 @@ mod tests {
                 distribution_duration: mock_env().block.time.seconds() + 10u64,
                 amount: Uint128::new(1),
             },
+            None,
         )
         .unwrap_err();
 
         assert_eq!(err, ContractError::InvalidRewards {});
     }
 
+    #[test]
+    fn fund_distribution_on_behalf_of() {
+        let mut deps = mock_dependencies();
+        default_instantiate(deps.as_mut(), mock_env());
+
+        execute_create_distribution_flow(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(INIT_ADMIN, &[]),
+            INIT_ADMIN.to_string(),
+            native_asset_info(DENOM),
+            vec![(UNBONDING_PERIOD, Decimal::one())],
+            None,
+        )
+        .unwrap();
+
+        let funding_info = FundingInfo {
+            start_time: mock_env().block.time.seconds(),
+            distribution_duration: 100,
+            amount: Uint128::new(1000),
+        };
+        let funds = [Coin {
+            denom: DENOM.to_string(),
+            amount: Uint128::new(1000),
+        }];
+
+        // invalid address is rejected
+        let err = execute_fund_distribution(
+            mock_env(),
+            deps.as_mut(),
+            mock_info("executor", &funds),
+            funding_info.clone(),
+            Some("".to_string()),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Std(_)));
+
+        // fund on behalf of the treasury
+        execute_fund_distribution(
+            mock_env(),
+            deps.as_mut(),
+            mock_info("executor", &funds),
+            funding_info.clone(),
+            Some("treasury".to_string()),
+        )
+        .unwrap();
+        // fund for the sender itself
+        execute_fund_distribution(
+            mock_env(),
+            deps.as_mut(),
+            mock_info(USER1, &funds),
+            funding_info,
+            None,
+        )
+        .unwrap();
+
+        let asset = native_asset_info(DENOM).validate(&deps.api).unwrap();
+        let funded = |deps: Deps, addr: &str| {
+            FUNDERS
+                .may_load(deps.storage, (&asset, &Addr::unchecked(addr)))
+                .unwrap()
+        };
+        assert_eq!(funded(deps.as_ref(), "treasury"), Some(Uint128::new(1000)));
+        assert_eq!(funded(deps.as_ref(), "executor"), None);
+        assert_eq!(funded(deps.as_ref(), USER1), Some(Uint128::new(1000)));
+    }
+
     #[test]
     fn distribution_flow_wrong_unbonding_period_fails() {
         let mut deps = mock_dependencies();
@@ -2173,10 +5334,12 @@ mod tests {
         // try to create distribution flow with wrong unbonding period
         let err = execute_create_distribution_flow(
             deps.as_mut(),
+            mock_env(),
             mock_info(INIT_ADMIN, &[]),
             INIT_ADMIN.to_string(),
             native_asset_info(DENOM),
             vec![(UNBONDING_PERIOD + 1, Decimal::one())],
+            None,
         )
         .unwrap_err();
         assert_eq!(err, ContractError::InvalidRewards {});
@@ -2197,6 +5360,8 @@ mod tests {
                 msg: to_binary(&ReceiveMsg::Delegate {
                     unbonding_period: UNBONDING_PERIOD,
                     delegate_as: Some("owner_of_stake".to_string()),
+                    unbond_controller: None,
+                    withdraw_to: None,
                 })
                 .unwrap(),
             },
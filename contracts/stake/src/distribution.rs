@@ -1,17 +1,30 @@
 use std::collections::HashSet;
 
-use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
-use wyndex::asset::{AssetInfo, AssetInfoExt, AssetInfoValidated};
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult, Storage, Uint128,
+};
+use wyndex::asset::{AssetInfo, AssetInfoExt, AssetInfoValidated, AssetValidated};
+use wyndex::stake::UnbondingPeriod;
 
+use crate::contract::{
+    expire_own_pending_claims, expire_pending_claims, record_interaction, start_distribution_update,
+};
 use crate::error::ContractError;
 use crate::msg::{
-    DelegatedResponse, DistributedRewardsResponse, DistributionDataResponse,
-    UndistributedRewardsResponse, WithdrawAdjustmentDataResponse, WithdrawableRewardsResponse,
+    AssetReconciliationResponse, DelegatedResponse, DetailedWithdrawableRewards,
+    DistributedRewardsResponse, DistributionDataResponse, EarnedSinceWithdrawResponse,
+    FlowAccountingResponse, FlowSchedule, NextRewardChangeResponse, RateAtResponse,
+    RewardReceiverResponse, RewardTokenBalanceResponse, TotalToBeDistributedResponse,
+    UndistributedRewardsResponse, WithdrawAdjustmentDataResponse,
+    WithdrawableRewardsDetailedResponse, WithdrawableRewardsResponse,
 };
 use crate::state::{
-    Config, Distribution, WithdrawAdjustment, CONFIG, DELEGATED, DISTRIBUTION, REWARD_CURVE,
-    SHARES_SHIFT, UNBOND_ALL, WITHDRAW_ADJUSTMENT,
+    Config, Distribution, WithdrawAdjustment, CONFIG, DELEGATED, DELEGATION_RIGHTS, DISTRIBUTION,
+    EARNED_AT_LAST_WITHDRAW, FLOW_TOTALS, FUNDING_SOURCES, LAST_DISTRIBUTION, LAST_WITHDRAW,
+    REWARD_CURVE, REWARD_RECEIVER, SCHEDULED_MULTIPLIERS, SHARES_SHIFT, TOTAL_STAKED, UNBOND_ALL,
+    WITHDRAW_ADJUSTMENT,
 };
+use crate::utils::CurveExt;
 
 pub fn execute_distribute_rewards(
     deps: DepsMut,
@@ -30,6 +43,11 @@ pub fn execute_distribute_rewards(
         .transpose()?
         .unwrap_or(info.sender);
 
+    let cfg = CONFIG.load(deps.storage)?;
+    // matured claims stop accruing rewards before anything new is distributed,
+    // this updates the distributions, so it has to happen before loading them below
+    expire_pending_claims(deps.storage, &cfg, env.block.time)?;
+
     let distributions = DISTRIBUTION
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
         .collect::<StdResult<Vec<_>>>()?;
@@ -47,69 +65,226 @@ pub fn execute_distribute_rewards(
     {
         return Err(ContractError::NoDistributionFlow(unsupported_coin.clone()));
     }
+    // paused flows cannot be funded either
+    if let Some((asset_info, _)) = distributions.iter().find(|(asset_info, distribution)| {
+        distribution.paused
+            && asset_info
+                .native_denom()
+                .map_or(false, |denom| info.funds.iter().any(|c| c.denom == denom))
+    }) {
+        return Err(ContractError::DistributionPaused(asset_info.clone()));
+    }
+    for coin in &info.funds {
+        add_funded(
+            deps.storage,
+            &AssetInfoValidated::Native(coin.denom.clone()),
+            coin.amount,
+        )?;
+    }
 
     let mut resp = Response::new()
         .add_attribute("action", "distribute_rewards")
         .add_attribute("sender", sender.as_str());
 
-    let cfg = CONFIG.load(deps.storage)?;
+    // skip the distribution if the last one was too recent, the rewards are distributed on the next tick.
+    // Rewards sent along are not held back though, so they never wait for someone else's tick.
+    // Only the flows receiving them are distributed then, all others still wait for the next tick.
+    let now = env.block.time.seconds();
+    let too_soon = LAST_DISTRIBUTION
+        .may_load(deps.storage)?
+        .map_or(false, |last_distribution| {
+            now < last_distribution.saturating_add(cfg.min_distribute_interval)
+        });
+    if too_soon {
+        resp = resp.add_attribute("skipped", "min_distribute_interval");
+        if info.funds.is_empty() {
+            return Ok(resp);
+        }
+    } else {
+        LAST_DISTRIBUTION.save(deps.storage, &now)?;
+    }
+
     for (asset_info, mut distribution) in distributions {
+        // flows that are still being updated hold back their rewards until all stakers are updated
+        if distribution.paused || distribution.update.is_some() {
+            continue;
+        }
+        if too_soon
+            && !asset_info
+                .native_denom()
+                .map_or(false, |denom| info.funds.iter().any(|c| c.denom == denom))
+        {
+            continue;
+        }
         let total_rewards = distribution.total_rewards_power(deps.storage, &cfg);
         // There are no shares in play - noone to distribute to
         if total_rewards.is_zero() {
             continue;
         }
 
-        let withdrawable: u128 = distribution.withdrawable_total.into();
-
-        // Query current reward balance
-        let balance =
-            undistributed_rewards(deps.as_ref(), &asset_info, env.contract.address.clone())?.u128();
-
-        let curve = REWARD_CURVE.load(deps.storage, &asset_info)?;
-
-        // Calculate how much we have received since the last time Distributed was called,
-        // including only the reward config amount that is eligible for distribution.
-        // This is the amount we will distribute to all members.
-        let amount = balance - withdrawable - curve.value(env.block.time.seconds()).u128();
+        let amount = distributable_amount(
+            deps.as_ref(),
+            &cfg,
+            &asset_info,
+            &distribution,
+            env.contract.address.clone(),
+            env.block.time.seconds(),
+        )?;
 
         if amount == 0 {
             continue;
         }
 
-        let leftover: u128 = distribution.shares_leftover.into();
-        let points = (amount << SHARES_SHIFT) + leftover;
-        let points_per_share = points / total_rewards.u128();
-        distribution.shares_leftover = (points % total_rewards.u128()) as u64;
-
-        // Everything goes back to 128-bits/16-bytes
-        // Full amount is added here to total withdrawable, as it should not be considered on its own
-        // on future distributions - even if because of calculation offsets it is not fully
-        // distributed, the error is handled by leftover.
-        distribution.shares_per_point += Uint128::new(points_per_share);
-        distribution.distributed_total += Uint128::new(amount);
-        distribution.withdrawable_total += Uint128::new(amount);
+        apply_distribution(&mut distribution, amount, total_rewards);
 
         DISTRIBUTION.save(deps.storage, &asset_info, &distribution)?;
 
-        resp = resp.add_attribute(format!("amount_{}", asset_info), amount.to_string());
+        resp = resp
+            .add_attribute(format!("amount_{}", asset_info), amount.to_string())
+            .add_event(
+                Event::new("rewards_distributed")
+                    .add_attribute("asset", asset_info.to_string())
+                    .add_attribute("amount", amount.to_string())
+                    .add_attribute("distributed_total", distribution.distributed_total)
+                    .add_attribute("total_rewards_power", total_rewards),
+            );
+    }
+
+    // scheduled multiplier changes only apply to rewards distributed from now on.
+    // They are only started here, the stakers are updated by `ApplyDistributionUpdate`.
+    let due = SCHEDULED_MULTIPLIERS
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .filter(|scheduled| {
+            scheduled
+                .as_ref()
+                .map_or(true, |(_, scheduled)| scheduled.effective_at <= now)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for (asset_info, scheduled) in due {
+        // wait for a running update to finish first
+        if DISTRIBUTION
+            .load(deps.storage, &asset_info)?
+            .update
+            .is_some()
+        {
+            continue;
+        }
+        start_distribution_update(deps.storage, &asset_info, scheduled.rewards, now)?;
+        SCHEDULED_MULTIPLIERS.remove(deps.storage, &asset_info);
+        resp = resp.add_attribute(format!("multipliers_updated_{}", asset_info), "true");
     }
 
     Ok(resp)
 }
 
+/// Calculates how much the distribution has received since the last time Distributed was called,
+/// including only the reward config amount that is eligible for distribution at time `at`.
+/// This is the amount that is distributed to all members.
+fn distributable_amount(
+    deps: Deps,
+    cfg: &Config,
+    asset_info: &AssetInfoValidated,
+    distribution: &Distribution,
+    contract: Addr,
+    at: u64,
+) -> StdResult<u128> {
+    let withdrawable: u128 = distribution.withdrawable_total.into();
+
+    // Query current reward balance
+    let balance = undistributed_rewards(deps, cfg, asset_info, contract)?.u128();
+
+    let curve = REWARD_CURVE.load(deps.storage, asset_info)?;
+
+    Ok(balance - withdrawable - curve.value(at).u128())
+}
+
+/// Distributes `amount` to all members, proportionally to their share of `total_rewards`.
+fn apply_distribution(distribution: &mut Distribution, amount: u128, total_rewards: Uint128) {
+    let leftover: u128 = distribution.shares_leftover.into();
+    let points = (amount << SHARES_SHIFT) + leftover;
+    let points_per_share = points / total_rewards.u128();
+    distribution.shares_leftover = (points % total_rewards.u128()) as u64;
+
+    // Everything goes back to 128-bits/16-bytes
+    // Full amount is added here to total withdrawable, as it should not be considered on its own
+    // on future distributions - even if because of calculation offsets it is not fully
+    // distributed, the error is handled by leftover.
+    distribution.shares_per_point += Uint128::new(points_per_share);
+    distribution.distributed_total += Uint128::new(amount);
+    distribution.withdrawable_total += Uint128::new(amount);
+}
+
+/// Adds to the total amount funded into the given distribution flow
+pub(crate) fn add_funded(
+    storage: &mut dyn Storage,
+    asset_info: &AssetInfoValidated,
+    amount: Uint128,
+) -> StdResult<()> {
+    FLOW_TOTALS.update(storage, asset_info, |totals| -> StdResult<_> {
+        let mut totals = totals.unwrap_or_default();
+        totals.funded = totals.funded.checked_add(amount)?;
+        Ok(totals)
+    })?;
+    Ok(())
+}
+
+/// Subtracts funds that were returned to the funders from the total amount funded into the given
+/// distribution flow
+pub(crate) fn sub_funded(
+    storage: &mut dyn Storage,
+    asset_info: &AssetInfoValidated,
+    amount: Uint128,
+) -> StdResult<()> {
+    FLOW_TOTALS.update(storage, asset_info, |totals| -> StdResult<_> {
+        let mut totals = totals.unwrap_or_default();
+        totals.funded = totals.funded.checked_sub(amount)?;
+        Ok(totals)
+    })?;
+    Ok(())
+}
+
+/// Adds to the total amount withdrawn from the given distribution flow
+pub(crate) fn add_withdrawn(
+    storage: &mut dyn Storage,
+    asset_info: &AssetInfoValidated,
+    amount: Uint128,
+) -> StdResult<()> {
+    FLOW_TOTALS.update(storage, asset_info, |totals| -> StdResult<_> {
+        let mut totals = totals.unwrap_or_default();
+        totals.withdrawn = totals.withdrawn.checked_add(amount)?;
+        Ok(totals)
+    })?;
+    Ok(())
+}
+
 /// Query current reward balance of the given asset.
-/// Make sure not to call this for the staking token
-fn undistributed_rewards(
+/// For the staking token, the bonded and unbonding tokens are not part of the reward balance.
+/// Native tokens prepaid by a funding source are not part of it either until they are pulled.
+pub(crate) fn undistributed_rewards(
     deps: Deps,
+    cfg: &Config,
     asset_info: &AssetInfoValidated,
     contract_address: impl Into<String>,
 ) -> StdResult<Uint128> {
-    asset_info.query_balance(&deps.querier, contract_address)
+    let balance = asset_info.query_balance(&deps.querier, contract_address)?;
+    match asset_info {
+        AssetInfoValidated::Token(addr) if *addr == cfg.cw20_contract => {
+            Ok(balance.saturating_sub(TOTAL_STAKED.load(deps.storage)?.total()))
+        }
+        AssetInfoValidated::Native(_) => {
+            let prepaid = FUNDING_SOURCES
+                .may_load(deps.storage, asset_info)?
+                .map(|source| source.prepaid)
+                .unwrap_or_default();
+            Ok(balance.saturating_sub(prepaid))
+        }
+        _ => Ok(balance),
+    }
 }
 
 pub fn execute_withdraw_rewards(
-    deps: DepsMut,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     owner: Option<String>,
     receiver: Option<String>,
@@ -118,10 +293,19 @@ pub fn execute_withdraw_rewards(
         || Ok(info.sender.clone()),
         |owner| deps.api.addr_validate(&owner),
     )?;
-    let receiver = receiver
-        .map(|receiver| deps.api.addr_validate(&receiver))
-        .transpose()?
-        .unwrap_or_else(|| info.sender.clone());
+    let withdraw_to = DELEGATION_RIGHTS
+        .may_load(deps.storage, &owner)?
+        .and_then(|rights| rights.withdraw_to);
+    // rewards always go to `withdraw_to` if it was set on delegation
+    let receiver = match &withdraw_to {
+        Some(withdraw_to) => withdraw_to.clone(),
+        None => match receiver {
+            Some(receiver) => deps.api.addr_validate(&receiver)?,
+            None => REWARD_RECEIVER
+                .may_load(deps.storage, &owner)?
+                .unwrap_or_else(|| info.sender.clone()),
+        },
+    };
 
     let mut resp = Response::new()
         .add_attribute("action", "withdraw_rewards")
@@ -129,43 +313,121 @@ pub fn execute_withdraw_rewards(
         .add_attribute("owner", owner.as_str())
         .add_attribute("receiver", receiver.as_str());
 
-    let distributions = DISTRIBUTION
-        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .collect::<StdResult<Vec<_>>>()?;
-
-    let delegated = DELEGATED
-        .may_load(deps.storage, &owner)?
-        .unwrap_or_else(|| owner.clone());
-    if ![&owner, &delegated].contains(&&info.sender) {
+    if !can_withdraw_for(deps.as_ref(), &info.sender, &owner, withdraw_to.as_ref())? {
         return Err(ContractError::Unauthorized {});
     }
 
     let cfg = CONFIG.load(deps.storage)?;
+    for reward in withdraw_all_rewards(deps.branch(), &env, &cfg, &info.sender, &owner)? {
+        // send rewards to receiver
+        resp = resp
+            .add_attribute(format!("reward_{}", reward.info), reward.amount)
+            .add_message(reward.into_msg(receiver.clone())?);
+    }
+
+    Ok(resp)
+}
+
+/// Withdraws the rewards of all given owners the sender is allowed to withdraw for,
+/// sending them to the owner or the `withdraw_to` address set on delegation.
+/// Owners the sender is not allowed to withdraw for and owners without rewards are skipped.
+pub fn execute_withdraw_rewards_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owners: Vec<String>,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+
+    let mut resp = Response::new()
+        .add_attribute("action", "withdraw_rewards_batch")
+        .add_attribute("sender", info.sender.as_str());
+    let mut withdrawn = vec![];
+    for owner in owners {
+        let owner = deps.api.addr_validate(&owner)?;
+        let withdraw_to = DELEGATION_RIGHTS
+            .may_load(deps.storage, &owner)?
+            .and_then(|rights| rights.withdraw_to);
+        if !can_withdraw_for(deps.as_ref(), &info.sender, &owner, withdraw_to.as_ref())? {
+            continue;
+        }
+
+        let rewards = withdraw_all_rewards(deps.branch(), &env, &cfg, &info.sender, &owner)?;
+        if rewards.is_empty() {
+            continue;
+        }
+        let receiver = match withdraw_to {
+            Some(withdraw_to) => withdraw_to,
+            None => REWARD_RECEIVER
+                .may_load(deps.storage, &owner)?
+                .unwrap_or_else(|| owner.clone()),
+        };
+        for reward in rewards {
+            resp = resp.add_message(reward.into_msg(receiver.clone())?);
+        }
+        withdrawn.push(owner.to_string());
+    }
+
+    Ok(resp.add_attribute("owners", withdrawn.join(",")))
+}
+
+/// Checks whether `sender` is allowed to withdraw the rewards of `owner`
+fn can_withdraw_for(
+    deps: Deps,
+    sender: &Addr,
+    owner: &Addr,
+    withdraw_to: Option<&Addr>,
+) -> StdResult<bool> {
+    let delegated = DELEGATED
+        .may_load(deps.storage, owner)?
+        .unwrap_or_else(|| owner.clone());
+    Ok([owner, &delegated].contains(&sender) || withdraw_to == Some(sender))
+}
+
+/// Marks all rewards of `owner` as withdrawn and returns the non-zero ones.
+/// This only counts as an interaction of `owner` if they withdraw themselves.
+fn withdraw_all_rewards(
+    deps: DepsMut,
+    env: &Env,
+    cfg: &Config,
+    sender: &Addr,
+    owner: &Addr,
+) -> Result<Vec<AssetValidated>, ContractError> {
+    if sender == owner {
+        record_interaction(deps.storage, cfg, owner, &env.block)?;
+    }
+    // matured claims that were not expired by a distribution yet stop earning now
+    expire_own_pending_claims(deps.storage, cfg, owner, env.block.time)?;
+    // loaded after the interaction was recorded, since that can update the distributions
+    let distributions = DISTRIBUTION
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut rewards = vec![];
     for (asset_info, mut distribution) in distributions {
         // get adjustment data
-        let mut adjustment = WITHDRAW_ADJUSTMENT
-            .may_load(deps.storage, (&owner, &asset_info))?
-            .unwrap_or_default();
+        let mut adjustment = distribution.adjustment_of(deps.storage, owner, &asset_info)?;
 
-        let reward = withdrawable_rewards(deps.as_ref(), &cfg, &owner, &distribution, &adjustment)?;
+        let reward = withdrawable_rewards(deps.as_ref(), cfg, owner, &distribution, &adjustment)?;
+        EARNED_AT_LAST_WITHDRAW.save(
+            deps.storage,
+            (owner, &asset_info),
+            &(adjustment.withdrawn_rewards + reward),
+        )?;
 
         if reward.is_zero() {
             // Just do nothing
             continue;
         }
         adjustment.withdrawn_rewards += reward;
-        WITHDRAW_ADJUSTMENT.save(deps.storage, (&owner, &asset_info), &adjustment)?;
+        WITHDRAW_ADJUSTMENT.save(deps.storage, (owner, &asset_info), &adjustment)?;
         distribution.withdrawable_total -= reward;
         DISTRIBUTION.save(deps.storage, &asset_info, &distribution)?;
-        // send rewards to receiver
-        let msg = asset_info.with_balance(reward).into_msg(receiver.clone())?;
-
-        resp = resp
-            .add_message(msg)
-            .add_attribute(format!("reward_{}", asset_info), reward);
+        add_withdrawn(deps.storage, &asset_info, reward)?;
+        rewards.push(asset_info.with_balance(reward));
     }
+    LAST_WITHDRAW.save(deps.storage, owner, &env.block.time.seconds())?;
 
-    Ok(resp)
+    Ok(rewards)
 }
 
 pub fn execute_delegate_withdrawal(
@@ -184,6 +446,39 @@ pub fn execute_delegate_withdrawal(
     Ok(resp)
 }
 
+pub fn query_earned_since_withdraw(
+    deps: Deps,
+    owner: String,
+) -> StdResult<EarnedSinceWithdrawResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let earned = DISTRIBUTION
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|distr| -> StdResult<_> {
+            let (asset_info, distribution) = distr?;
+            let adjustment = distribution.adjustment_of(deps.storage, &owner, &asset_info)?;
+            let withdrawable =
+                withdrawable_rewards(deps, &cfg, &owner, &distribution, &adjustment)?;
+            // nothing can have been earned at the last withdrawal that was not withdrawn since,
+            // this also ignores values left over from a removed flow of the same asset
+            let earned_at_last_withdraw = EARNED_AT_LAST_WITHDRAW
+                .may_load(deps.storage, (&owner, &asset_info))?
+                .unwrap_or_default()
+                .min(adjustment.withdrawn_rewards);
+            let earned = (adjustment.withdrawn_rewards + withdrawable)
+                .saturating_sub(earned_at_last_withdraw);
+
+            Ok(asset_info.with_balance(earned))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(EarnedSinceWithdrawResponse {
+        last_withdraw: LAST_WITHDRAW.may_load(deps.storage, &owner)?,
+        earned,
+    })
+}
+
 pub fn query_withdrawable_rewards(
     deps: Deps,
     owner: String,
@@ -199,9 +494,91 @@ pub fn query_withdrawable_rewards(
     let rewards = distributions
         .map(|distr| -> StdResult<_> {
             let (asset_info, distribution) = distr?;
-            let adjustment = WITHDRAW_ADJUSTMENT
-                .may_load(deps.storage, (&owner, &asset_info))?
-                .unwrap_or_default();
+            let adjustment = distribution.adjustment_of(deps.storage, &owner, &asset_info)?;
+            let rewards = withdrawable_rewards(deps, &cfg, &owner, &distribution, &adjustment)?;
+
+            Ok(asset_info.with_balance(rewards))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(WithdrawableRewardsResponse { rewards })
+}
+
+pub fn query_withdrawable_rewards_detailed(
+    deps: Deps,
+    owner: String,
+) -> StdResult<WithdrawableRewardsDetailedResponse> {
+    let owner = Addr::unchecked(owner);
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let rewards = DISTRIBUTION
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|distr| -> StdResult<_> {
+            let (asset_info, distribution) = distr?;
+            let adjustment = distribution.adjustment_of(deps.storage, &owner, &asset_info)?;
+            let total = withdrawable_rewards(deps, &cfg, &owner, &distribution, &adjustment)?;
+
+            let mut per_period: Vec<_> = distribution
+                .calc_rewards_power_per_period(deps.storage, &cfg, &owner)?
+                .into_iter()
+                .filter(|(_, power)| !power.is_zero())
+                .collect();
+            let total_power: Uint128 = per_period.iter().map(|(_, power)| *power).sum();
+            let mut remaining = total;
+            for (_, amount) in per_period.iter_mut() {
+                *amount = total.multiply_ratio(*amount, total_power);
+                remaining -= *amount;
+            }
+            // assign the rounding leftovers to the longest unbonding period, so the sum matches
+            if let Some((_, amount)) = per_period.last_mut() {
+                *amount += remaining;
+            }
+
+            Ok(DetailedWithdrawableRewards {
+                total: asset_info.with_balance(total),
+                per_period,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(WithdrawableRewardsDetailedResponse { rewards })
+}
+
+/// Returns what `owner` could withdraw at `at_time`, assuming everything the reward curves
+/// release until then is distributed based on the current stake.
+pub fn query_withdrawable_at(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    at_time: u64,
+) -> StdResult<WithdrawableRewardsResponse> {
+    let owner = Addr::unchecked(owner);
+    // past rewards were already released, so they are all distributed at the latest right now
+    let at_time = at_time.max(env.block.time.seconds());
+
+    let cfg = CONFIG.load(deps.storage)?;
+    // no distributions happen while everything is unbonded
+    let unbond_all = UNBOND_ALL.load(deps.storage)?;
+    let distributions =
+        DISTRIBUTION.range(deps.storage, None, None, cosmwasm_std::Order::Ascending);
+
+    let rewards = distributions
+        .map(|distr| -> StdResult<_> {
+            let (asset_info, mut distribution) = distr?;
+            let total_rewards = distribution.total_rewards_power(deps.storage, &cfg);
+            if !unbond_all && !total_rewards.is_zero() {
+                let amount = distributable_amount(
+                    deps,
+                    &cfg,
+                    &asset_info,
+                    &distribution,
+                    env.contract.address.clone(),
+                    at_time,
+                )?;
+                if amount > 0 {
+                    apply_distribution(&mut distribution, amount, total_rewards);
+                }
+            }
+
+            let adjustment = distribution.adjustment_of(deps.storage, &owner, &asset_info)?;
             let rewards = withdrawable_rewards(deps, &cfg, &owner, &distribution, &adjustment)?;
 
             Ok(asset_info.with_balance(rewards))
@@ -214,13 +591,15 @@ pub fn query_undistributed_rewards(
     deps: Deps,
     env: Env,
 ) -> StdResult<UndistributedRewardsResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
     let distributions =
         DISTRIBUTION.range(deps.storage, None, None, cosmwasm_std::Order::Ascending);
 
     let rewards = distributions
         .map(|distribution| -> StdResult<_> {
             let (asset_info, distribution) = distribution?;
-            let balance = undistributed_rewards(deps, &asset_info, env.contract.address.clone())?;
+            let balance =
+                undistributed_rewards(deps, &cfg, &asset_info, env.contract.address.clone())?;
 
             Ok(asset_info.with_balance(balance - distribution.withdrawable_total))
         })
@@ -229,6 +608,235 @@ pub fn query_undistributed_rewards(
     Ok(UndistributedRewardsResponse { rewards })
 }
 
+/// Returns the amount per distribution flow that `execute_distribute_rewards` would move
+/// from undistributed to withdrawable at the current block.
+pub fn query_pending_distribution(deps: Deps, env: Env) -> StdResult<Vec<AssetValidated>> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let unbond_all = UNBOND_ALL.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    DISTRIBUTION
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|distribution| {
+            let (asset_info, distribution) = distribution?;
+            // nothing is distributed if there is noone to distribute to
+            if unbond_all
+                || distribution.paused
+                || distribution.update.is_some()
+                || distribution
+                    .total_rewards_power(deps.storage, &cfg)
+                    .is_zero()
+            {
+                return Ok(asset_info.with_balance(0u128));
+            }
+
+            let balance =
+                undistributed_rewards(deps, &cfg, &asset_info, env.contract.address.clone())?;
+            let locked = REWARD_CURVE.load(deps.storage, &asset_info)?.value(now);
+            let amount = balance
+                .saturating_sub(distribution.withdrawable_total)
+                .saturating_sub(locked);
+
+            Ok(asset_info.with_balance(amount))
+        })
+        .collect()
+}
+
+pub fn query_total_to_be_distributed(
+    deps: Deps,
+    env: Env,
+    asset: AssetInfo,
+) -> StdResult<TotalToBeDistributedResponse> {
+    let asset_info = asset.validate(deps.api)?;
+    let distribution = DISTRIBUTION.load(deps.storage, &asset_info)?;
+    let cfg = CONFIG.load(deps.storage)?;
+
+    // everything in the contract's balance that is not withdrawable yet will be distributed eventually
+    let balance = undistributed_rewards(deps, &cfg, &asset_info, env.contract.address)?;
+    let amount = balance - distribution.withdrawable_total;
+
+    let end_time = REWARD_CURVE.load(deps.storage, &asset_info)?.end();
+
+    Ok(TotalToBeDistributedResponse {
+        amount: asset_info.with_balance(amount),
+        end_time,
+    })
+}
+
+pub fn query_flows_schedule(deps: Deps, env: Env) -> StdResult<Vec<FlowSchedule>> {
+    let cfg = CONFIG.load(deps.storage)?;
+    DISTRIBUTION
+        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|distr| {
+            let (asset, distribution) = distr?;
+            let balance = undistributed_rewards(deps, &cfg, &asset, env.contract.address.clone())?;
+            let curve_end = REWARD_CURVE.load(deps.storage, &asset)?.end();
+            Ok(FlowSchedule {
+                asset,
+                curve_end,
+                undistributed: balance.saturating_sub(distribution.withdrawable_total),
+            })
+        })
+        .collect()
+}
+
+pub fn query_rate_at(deps: Deps, asset: AssetInfo, at_time: u64) -> StdResult<RateAtResponse> {
+    let asset_info = asset.validate(deps.api)?;
+    // the reward curve holds what is still locked, so it decreases as rewards are released
+    let rate = REWARD_CURVE
+        .load(deps.storage, &asset_info)?
+        .decrease_rate(at_time);
+
+    Ok(RateAtResponse { rate })
+}
+
+pub fn query_flow_accounting(
+    deps: Deps,
+    env: Env,
+    asset: AssetInfo,
+) -> StdResult<FlowAccountingResponse> {
+    let asset_info = asset.validate(deps.api)?;
+    let distribution = DISTRIBUTION.load(deps.storage, &asset_info)?;
+    let totals = FLOW_TOTALS
+        .may_load(deps.storage, &asset_info)?
+        .unwrap_or_default();
+    let cfg = CONFIG.load(deps.storage)?;
+
+    let balance = undistributed_rewards(deps, &cfg, &asset_info, env.contract.address)?;
+    // the leftover points are worth less than a token per share, so this is usually zero
+    let leftover = Uint128::from(distribution.shares_leftover >> SHARES_SHIFT);
+
+    Ok(FlowAccountingResponse {
+        funded_total: totals.funded,
+        distributed_total: distribution.distributed_total,
+        withdrawn_total: totals.withdrawn,
+        undistributed: balance.saturating_sub(distribution.withdrawable_total),
+        withdrawable_outstanding: distribution.withdrawable_total.saturating_sub(leftover),
+        leftover,
+    })
+}
+
+pub fn query_lifetime_funded(deps: Deps) -> StdResult<Vec<AssetValidated>> {
+    DISTRIBUTION
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .map(|asset_info| {
+            let asset_info = asset_info?;
+            let funded = FLOW_TOTALS
+                .may_load(deps.storage, &asset_info)?
+                .unwrap_or_default()
+                .funded;
+            Ok(asset_info.with_balance(funded))
+        })
+        .collect()
+}
+
+pub fn query_asset_reconciliation(
+    deps: Deps,
+    env: Env,
+    asset: AssetInfo,
+) -> StdResult<AssetReconciliationResponse> {
+    let asset_info = asset.validate(deps.api)?;
+    let distribution = DISTRIBUTION.load(deps.storage, &asset_info)?;
+    let cfg = CONFIG.load(deps.storage)?;
+
+    let contract_balance = asset_info.query_balance(&deps.querier, env.contract.address)?;
+    let principal = match &asset_info {
+        AssetInfoValidated::Token(addr) if *addr == cfg.cw20_contract => {
+            TOTAL_STAKED.load(deps.storage)?.total()
+        }
+        _ => Uint128::zero(),
+    };
+    let prepaid = FUNDING_SOURCES
+        .may_load(deps.storage, &asset_info)?
+        .map(|source| source.prepaid)
+        .unwrap_or_default();
+    let locked = REWARD_CURVE
+        .load(deps.storage, &asset_info)?
+        .value(env.block.time.seconds());
+    let owed_undistributed = locked + prepaid;
+
+    Ok(AssetReconciliationResponse {
+        contract_balance,
+        owed_undistributed,
+        owed_withdrawable: distribution.withdrawable_total,
+        owed_claims_if_staking_token: principal,
+        surplus: contract_balance
+            .saturating_sub(principal)
+            .saturating_sub(distribution.withdrawable_total)
+            .saturating_sub(owed_undistributed),
+    })
+}
+
+/// Splits `amount` across the unbonding periods the same way a distribution of it right now would,
+/// i.e. proportionally to each period's share of the distribution's rewards power.
+pub fn query_distribution_preview(
+    deps: Deps,
+    asset: AssetInfo,
+    amount: Uint128,
+) -> StdResult<Vec<(UnbondingPeriod, Uint128)>> {
+    let asset_info = asset.validate(deps.api)?;
+    let distribution = DISTRIBUTION.load(deps.storage, &asset_info)?;
+    let cfg = CONFIG.load(deps.storage)?;
+
+    let powers = distribution.rewards_power_per_period(deps.storage, &cfg);
+    let total_rewards = powers.iter().map(|(_, power)| power).sum::<Uint128>();
+
+    Ok(powers
+        .into_iter()
+        .map(|(unbonding_period, power)| {
+            let share = if total_rewards.is_zero() {
+                // nobody to distribute to, so nothing would be distributed
+                Uint128::zero()
+            } else {
+                amount.multiply_ratio(power, total_rewards)
+            };
+            (unbonding_period, share)
+        })
+        .collect())
+}
+
+pub fn query_reward_token_balance(deps: Deps, env: Env) -> StdResult<RewardTokenBalanceResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let principal = TOTAL_STAKED.load(deps.storage)?.total();
+    let rewards = undistributed_rewards(
+        deps,
+        &cfg,
+        &AssetInfoValidated::Token(cfg.cw20_contract.clone()),
+        env.contract.address,
+    )?;
+
+    Ok(RewardTokenBalanceResponse { principal, rewards })
+}
+
+pub fn query_next_reward_change(
+    deps: Deps,
+    env: Env,
+    owner: String,
+) -> StdResult<NextRewardChangeResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    let mut timestamp: Option<u64> = None;
+    for distribution in DISTRIBUTION.range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+    {
+        let (asset_info, distribution) = distribution?;
+        // only flows the owner takes part in are relevant
+        if distribution
+            .calc_earning_power(deps.storage, &cfg, &owner)?
+            .is_zero()
+        {
+            continue;
+        }
+        let next_change = REWARD_CURVE
+            .load(deps.storage, &asset_info)?
+            .next_change(now);
+        timestamp = timestamp.into_iter().chain(next_change).min();
+    }
+
+    Ok(NextRewardChangeResponse { timestamp })
+}
+
 pub fn query_distributed_rewards(deps: Deps) -> StdResult<DistributedRewardsResponse> {
     let distributions = DISTRIBUTION
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
@@ -254,6 +862,14 @@ pub fn query_delegated(deps: Deps, owner: String) -> StdResult<DelegatedResponse
     Ok(DelegatedResponse { delegated })
 }
 
+pub fn query_reward_receiver(deps: Deps, owner: String) -> StdResult<RewardReceiverResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+
+    let receiver = REWARD_RECEIVER.may_load(deps.storage, &owner)?;
+
+    Ok(RewardReceiverResponse { receiver })
+}
+
 pub fn query_distribution_data(deps: Deps) -> StdResult<DistributionDataResponse> {
     Ok(DistributionDataResponse {
         distributions: DISTRIBUTION
@@ -269,33 +885,26 @@ pub fn query_withdraw_adjustment_data(
 ) -> StdResult<WithdrawAdjustmentDataResponse> {
     let addr = deps.api.addr_validate(&owner)?;
     let asset = asset.validate(deps.api)?;
-    let adjust = WITHDRAW_ADJUSTMENT
-        .may_load(deps.storage, (&addr, &asset))?
-        .unwrap_or(WithdrawAdjustmentDataResponse {
-            shares_correction: 0,
-            withdrawn_rewards: Uint128::zero(),
-        });
-    Ok(adjust)
+    match DISTRIBUTION.may_load(deps.storage, &asset)? {
+        Some(distribution) => distribution.adjustment_of(deps.storage, &addr, &asset),
+        None => Ok(WithdrawAdjustmentDataResponse::default()),
+    }
 }
 
 /// Applies points correction for given address.
-/// `shares_per_point` is current value from `SHARES_PER_POINT` - not loaded in function, to
-/// avoid multiple queries on bulk updates.
+/// `distribution` is the current state of the distribution flow of `asset_info` - not loaded in
+/// function, to avoid multiple queries on bulk updates.
 /// `diff` is the points change
 pub fn apply_points_correction(
     storage: &mut dyn Storage,
     addr: &Addr,
     asset_info: &AssetInfoValidated,
-    shares_per_point: u128,
+    distribution: &Distribution,
     diff: i128,
 ) -> StdResult<()> {
-    WITHDRAW_ADJUSTMENT.update(storage, (addr, asset_info), |old| -> StdResult<_> {
-        let mut old = old.unwrap_or_default();
-        let shares_correction: i128 = old.shares_correction;
-        old.shares_correction = shares_correction - shares_per_point as i128 * diff;
-        Ok(old)
-    })?;
-    Ok(())
+    let mut adjustment = distribution.adjustment_of(storage, addr, asset_info)?;
+    adjustment.shares_correction -= distribution.shares_per_point.u128() as i128 * diff;
+    WITHDRAW_ADJUSTMENT.save(storage, (addr, asset_info), &adjustment)
 }
 
 /// This is customized for the use case of the contract
@@ -309,7 +918,7 @@ pub fn withdrawable_rewards(
 ) -> StdResult<Uint128> {
     let ppw = distribution.shares_per_point.u128();
     let points = distribution
-        .calc_rewards_power(deps.storage, cfg, owner)?
+        .calc_earning_power(deps.storage, cfg, owner)?
         .u128();
 
     let correction = adjustment.shares_correction;
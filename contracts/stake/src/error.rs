@@ -1,4 +1,4 @@
-use cosmwasm_std::{Coin, OverflowError, StdError, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
 use cw_controllers::{AdminError, HookError};
@@ -31,6 +31,9 @@ pub enum ContractError {
     #[error("No claims that can be released currently")]
     NothingToClaim {},
 
+    #[error("Only {available} tokens are unbonding from this unbonding period")]
+    NotEnoughUnbonding { available: Uint128 },
+
     #[error(
         "Sender's CW20 token contract address {got} does not match one from config {expected}"
     )]
@@ -42,6 +45,12 @@ pub enum ContractError {
         amount_sent: Uint128,
     },
 
+    #[error("The shares of a shared delegation have to sum up to one")]
+    InvalidShares {},
+
+    #[error("Shared delegation of {amount} tokens is below the minimum bond of {min_bond}")]
+    SharedDelegationBelowMinBond { amount: Uint128, min_bond: Uint128 },
+
     #[error("No funds sent")]
     NoFunds {},
 
@@ -51,14 +60,26 @@ pub enum ContractError {
     #[error("No unbonding period found: {0}")]
     NoUnbondingPeriodFound(u64),
 
+    #[error("Unbonding period {0} still has bonded tokens")]
+    UnbondingPeriodInUse(u64),
+
+    #[error("Unbonding period {0} already exists")]
+    UnbondingPeriodAlreadyExists(u64),
+
+    #[error("No reward multiplier for the new unbonding period given for {0}")]
+    MissingRewardMultiplier(AssetInfoValidated),
+
     #[error("No members to distribute tokens to")]
     NoMembersToDistributeTo {},
 
     #[error("There already is a distribution for {0}")]
     DistributionAlreadyExists(AssetInfoValidated),
 
-    #[error("Cannot distribute the staked token")]
-    InvalidAsset {},
+    #[error("No rewards in the staked token to compound")]
+    NoRewardsToCompound {},
+
+    #[error("Rewards in {0} cannot be restaked, only the staked token can. Withdraw them first")]
+    CannotRestakeAsset(AssetInfoValidated),
 
     #[error("No distribution flow for this token: {0}")]
     NoDistributionFlow(Coin),
@@ -69,9 +90,24 @@ pub enum ContractError {
     #[error("Cannot create new distribution after someone staked")]
     ExistingStakes {},
 
+    #[error("{0} cannot be the manager of a distribution flow")]
+    InvalidManager(Addr),
+
     #[error("Invalid distribution rewards")]
     InvalidRewards {},
 
+    #[error("Claimant reward share must be less than or equal to 1")]
+    InvalidClaimantRewardShare {},
+
+    #[error("Instant unbond fee must be less than or equal to 1")]
+    InvalidInstantUnbondFee {},
+
+    #[error("Instant unbonding is not enabled")]
+    InstantUnbondDisabled {},
+
+    #[error("Rewards multiplier {multiplier} is higher than the maximum of {max}")]
+    RewardMultiplierTooHigh { multiplier: Decimal, max: Decimal },
+
     #[error("No reward duration provided for rewards distribution")]
     ZeroRewardDuration {},
 
@@ -90,8 +126,83 @@ pub enum ContractError {
     #[error("Cannot distribute {what} when unbond all flag is set to true")]
     CannotDistributeIfUnbondAll { what: String },
 
+    #[error("Cannot rescue more than {available} tokens")]
+    RescueAmountTooHigh { available: Uint128 },
+
     #[error("Cannot rebond when unbond all flag is set to true, unbond instead")]
     CannotRebondIfUnbondAll {},
+
+    #[error("Delegation rights differ from the ones of the staker's existing bond")]
+    DelegationRightsMismatch {},
+
+    #[error("Sent funds do not match the funding for {0}")]
+    FundingMismatch(AssetInfoValidated),
+
+    #[error("Distribution flow for {0} can only be funded once per message")]
+    DuplicateFunding(AssetInfoValidated),
+
+    #[error("{0} is not the migration target")]
+    InvalidMigrationTarget(Addr),
+
+    #[error("No stake to migrate")]
+    NothingToMigrate {},
+
+    #[error("Power decay duration must be greater than 0")]
+    InvalidPowerDecay {},
+
+    #[error("No funding source configured for {0}")]
+    NoFundingSource(AssetInfoValidated),
+
+    #[error("The current funding of {0} has not ended yet")]
+    FundingNotEnded(AssetInfoValidated),
+
+    #[error("The funding source of {0} did not accept its terms")]
+    FundingSourceNotAccepted(AssetInfoValidated),
+
+    #[error("The accepted terms do not match the configured funding source")]
+    FundingSourceTermsMismatch {},
+
+    #[error("Only {available} of the funding source were prepaid")]
+    InsufficientPrepaidFunding { available: Uint128 },
+
+    #[error("Distribution flow for {0} is paused")]
+    DistributionPaused(AssetInfoValidated),
+
+    #[error("Distribution flow for {0} is not paused")]
+    DistributionNotPaused(AssetInfoValidated),
+
+    #[error("Distribution flow for {0} is still being updated")]
+    DistributionUpdateInProgress(AssetInfoValidated),
+
+    #[error("Distribution flow for {0} has no update to apply")]
+    NoDistributionUpdate(AssetInfoValidated),
+
+    #[error("The previous change of min_bond is still being applied")]
+    MinBondUpdateInProgress {},
+
+    #[error("There is no change of min_bond to apply")]
+    NoMinBondUpdate {},
+
+    #[error("The multiplier change has to take effect in the future")]
+    EffectiveTimeNotInFuture {},
+
+    #[error("Distribution flow for {0} still has undistributed funds or unwithdrawn rewards")]
+    DistributionNotEmpty(AssetInfoValidated),
+
+    #[error("Cannot lower max distributions below the {0} existing distributions")]
+    MaxDistributionsBelowExisting(u32),
+
+    #[error("Only {available} of the distribution flow's funds are still locked")]
+    NotEnoughLocked { available: Uint128 },
+
+    #[error("{amount} tokens bonded for unbonding period {unbonding_period} are still locked")]
+    StakeLocked {
+        unbonding_period: u64,
+        amount: Uint128,
+    },
+
+    #[error("Cannot exit more than {0} unbonding periods at once, unbond some of them first")]
+    TooManyExitPeriods(usize),
 }
 
 impl From<OverflowError> for ContractError {
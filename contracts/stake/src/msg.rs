@@ -6,6 +6,8 @@ use wyndex::asset::{AssetInfo, AssetInfoValidated, AssetValidated};
 
 use wyndex::stake::{ConverterConfig, FundingInfo, UnbondingPeriod};
 
+use crate::state::RoundingMode;
+
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Rebond will update an amount of bonded tokens from one bond period to the other
@@ -14,6 +16,15 @@ pub enum ExecuteMsg {
         // these must be valid time periods
         bond_from: u64,
         bond_to: u64,
+        /// Staker whose tokens are rebonded; `sender` by default. `sender` has to be the
+        /// `unbond_controller` of `owner` if one was set on delegation.
+        owner: Option<String>,
+    },
+    /// Moves the summed amount of `targets` out of `bond_from` and into each of the target periods
+    /// by the given amounts, same as multiple `Rebond` messages at once.
+    RebondSplit {
+        bond_from: u64,
+        targets: Vec<(UnbondingPeriod, Uint128)>,
     },
     /// Unbond will start the unbonding process for the given number of tokens.
     /// The sender immediately loses power from these tokens, and can claim them
@@ -23,7 +34,32 @@ pub enum ExecuteMsg {
         /// As each unbonding period in delegation corresponds to particular voting
         /// multiplier, unbonding_period needs to be passed in unbond as well
         unbonding_period: u64,
+        /// Staker whose tokens are unbonded; `sender` by default. `sender` has to be the
+        /// `unbond_controller` of `owner` if one was set on delegation.
+        /// The claim is always created for `sender`, so when unbonding for `owner`, the controller
+        /// receives the tokens (and any claimant rewards), not `owner`.
+        owner: Option<String>,
+    },
+    /// Unbonds the given number of tokens and sends them to the sender right away, without a claim.
+    /// The instant unbond fee is kept from the tokens and sent to the treasury, or burned if there is none.
+    /// Only possible if enabled using [`ExecuteMsg::SetInstantUnbond`].
+    InstantUnbond {
+        tokens: Uint128,
+        unbonding_period: UnbondingPeriod,
     },
+    /// Bonds tokens of the sender's claims that did not mature yet again, in the unbonding period
+    /// they were unbonded from. The claims maturing last are cancelled first.
+    /// The tokens are bonded for the sender, so this fails if the sender's bond is controlled by
+    /// another address.
+    CancelUnbonding {
+        unbonding_period: UnbondingPeriod,
+        amount: Uint128,
+    },
+    /// Withdraws all rewards of the sender and unbonds all their unlocked tokens in every unbonding period,
+    /// creating the corresponding claims.
+    /// Fails if some of the tokens are still locked or if they are bonded in more than
+    /// [`crate::contract::MAX_EXIT_PERIODS`] unbonding periods.
+    ExitAll {},
     /// Will immediately unbond all tokens for the given addresses.
     /// Can only be called by the `unbonder` account.
     QuickUnbond {
@@ -39,6 +75,16 @@ pub enum ExecuteMsg {
     /// Claim is used to claim your native tokens that you previously "unbonded"
     /// after the contract-defined waiting period (eg. 1 week)
     Claim {},
+    /// Claims all matured tokens like [`ExecuteMsg::Claim`] and withdraws all rewards of the sender
+    /// like [`ExecuteMsg::WithdrawRewards`] in one go. Does not fail if there is nothing to claim.
+    ClaimAndWithdrawAll {},
+    /// Sets the address that receives the tokens of future [`ExecuteMsg::Claim`] calls of the sender.
+    /// `None` resets it, so the sender receives them again.
+    SetClaimReceiver { receiver: Option<String> },
+    /// Sets the address that receives the sender's rewards on withdrawals that do not specify
+    /// a receiver, see [`ExecuteMsg::WithdrawRewards`].
+    /// `None` resets it, so the rewards go to the withdrawing address again.
+    SetRewardReceiver { receiver: Option<String> },
 
     /// Change the admin
     UpdateAdmin { admin: Option<String> },
@@ -53,14 +99,33 @@ pub enum ExecuteMsg {
         /// Rewards multiplier by unbonding period for this distribution
         /// Only periods that are defined in the contract can be used here
         rewards: Vec<(UnbondingPeriod, Decimal)>,
+
+        /// Fraction of their former rewards power that unbonded tokens keep until they are claimed.
+        /// Defaults to zero, meaning rewards stop as soon as tokens are unbonded.
+        /// Only tokens unbonded while a flow with a non-zero share exists are tracked.
+        claimant_reward_share: Option<Decimal>,
     },
 
+    /// Removes the distribution flow of `asset`, freeing its slot for a new flow.
+    /// Fails if the flow still has undistributed funds or more than 0.1% of its distributed rewards
+    /// were not withdrawn yet.
+    /// Can only be called by the manager of the distribution flow or the admin.
+    RemoveDistributionFlow { asset: AssetInfo },
+
     /// This accepts a properly-encoded ReceiveMsg from a cw20 contract
     Receive(Cw20ReceiveMsg),
 
     /// Distributes rewards sent with this message, and all rewards transferred since last call of this
     /// to members, proportionally to their points. Rewards are not immediately send to members, but
     /// assigned to them for later withdrawal (see: `ExecuteMsg::WithdrawFunds`)
+    /// Native rewards sent with this message are distributed right away, even within the
+    /// minimum distribution interval. The flows of all other assets still wait for the interval.
+    ///
+    /// For every asset distributed, a `rewards_distributed` event is emitted with the attributes:
+    /// - `asset`: the distributed asset
+    /// - `amount`: the amount distributed by this call
+    /// - `distributed_total`: the amount distributed by the flow so far, including this call
+    /// - `total_rewards_power`: the total rewards power the amount was distributed across
     DistributeRewards {
         /// Original source of rewards, informational. If present overwrites "sender" field on
         /// propagated event.
@@ -72,9 +137,32 @@ pub enum ExecuteMsg {
         /// to be eligible for withdrawal from `owner` address to perform this call (`owner` has to
         /// call `DelegateWithdrawal { delegated: sender }` before)
         owner: Option<String>,
-        /// Address where to transfer funds. If not present, funds would be sent to `sender`.
+        /// Address where to transfer funds. If not present, funds would be sent to the receiver
+        /// set by `owner` using [`ExecuteMsg::SetRewardReceiver`], or `sender` if there is none.
         receiver: Option<String>,
     },
+    /// Withdraws the rewards of all `owners` the sender is allowed to withdraw for (see
+    /// [`ExecuteMsg::WithdrawRewards`]), sending them to the owners themselves, to the
+    /// `withdraw_to` address set on delegation or to the receiver set by the owner using
+    /// [`ExecuteMsg::SetRewardReceiver`]. Owners without rewards or that did not allow the
+    /// sender to withdraw for them are skipped.
+    WithdrawRewardsBatch { owners: Vec<String> },
+    /// Bonds the sender's withdrawable rewards in the staked token to the given unbonding period,
+    /// instead of sending them out. Requires a distribution flow for the staked token.
+    CompoundRewards { unbonding_period: u64 },
+    /// Withdraws the sender's rewards and bonds them to the given unbonding period right away,
+    /// instead of sending them out. Only works for rewards in the staked token, so this fails
+    /// if the sender has withdrawable rewards in any other asset.
+    WithdrawAndRestake { unbonding_period: u64 },
+    /// Opts the sender in to (or out of) having their rewards in the staked token compounded
+    /// into `unbonding_period` by anyone calling [`ExecuteMsg::RunAutoCompound`].
+    SetAutoCompound {
+        enabled: bool,
+        unbonding_period: u64,
+    },
+    /// Compounds the rewards in the staked token of all given users that opted in with
+    /// [`ExecuteMsg::SetAutoCompound`] into their chosen unbonding period. Can be called by anyone.
+    RunAutoCompound { users: Vec<String> },
     /// Sets given address as allowed for senders funds withdrawal. Funds still can be withdrawn by
     /// sender himself, but this additional account is allowed to perform it as well. There can be only
     /// one account delegated for withdrawal for any owner at any single time.
@@ -83,9 +171,43 @@ pub enum ExecuteMsg {
         /// to own address.
         delegated: String,
     },
+    /// Gives away rights over the sender's future stake, like `Delegate` does for its sender.
+    /// Others can only delegate to the sender with exactly these rights afterwards.
+    /// Only possible while the sender has no bonded tokens.
+    SetDelegationRights {
+        /// If set, only this address can unbond or rebond the sender's stake and it receives the claims
+        unbond_controller: Option<String>,
+        /// If set, all rewards of the sender are sent to this address when withdrawn
+        withdraw_to: Option<String>,
+    },
     /// Fund a distribution flow with 1 or more native tokens, updating each provided native token's reward config appropriately.
     /// Funds to be provided are included in `info.funds`
-    FundDistribution { funding_info: FundingInfo },
+    FundDistribution {
+        funding_info: FundingInfo,
+        /// The address the funds are attributed to. Defaults to the sender.
+        /// Set this when funding on behalf of someone else (e.g. a treasury), so that any
+        /// reclaimed funds go back to them instead of the executing contract.
+        on_behalf_of: Option<String>,
+    },
+    /// Fund multiple distribution flows at once, each with its own funding curve.
+    /// Native tokens have to be included in `info.funds` with exactly the amount of their funding.
+    /// Cw20 tokens are transferred from the sender, which needs to give this contract
+    /// an allowance for them first.
+    FundDistributionMulti {
+        fundings: Vec<DistributionFunding>,
+        /// The address the funds are attributed to. Defaults to the sender.
+        on_behalf_of: Option<String>,
+    },
+
+    /// Sends tokens that were accidentally sent to this contract to the given recipient.
+    /// Only the amount exceeding the contract's obligations (bonded and unbonding tokens for the
+    /// staked token, the whole balance for assets with a distribution flow) can be moved.
+    /// Can only be called by the ADMIN.
+    RescueTokens {
+        asset: AssetInfo,
+        recipient: String,
+        amount: Uint128,
+    },
 
     /// Moves the given amount of LP tokens staked to the given unbonding period from the sender's
     /// account to a different pool (by converting one or more of the pool tokens).
@@ -93,6 +215,174 @@ pub enum ExecuteMsg {
         amount: Uint128,
         unbonding_period: u64,
     },
+    /// Sets the staking contract that stakers can move their positions to using
+    /// [`ExecuteMsg::MigratePosition`], or disables it (`None`).
+    /// Can only be called by the ADMIN.
+    SetMigrationTarget { target: Option<String> },
+    /// Withdraws the sender's rewards and moves all of their unlocked stake directly into the
+    /// `target_contract`, keeping the unbonding periods. No unbonding wait applies.
+    /// The target contract has to be the one set by the ADMIN and needs the same unbonding periods.
+    MigratePosition { target_contract: String },
+
+    /// Enables, updates or disables (`None`) the decay of rewards power for inactive stakers.
+    /// Can only be called by the ADMIN.
+    UpdatePowerDecay {
+        power_decay: Option<PowerDecayConfig>,
+    },
+    /// Applies the power decay to the given stakers, reducing the rewards power of the ones that
+    /// did not bond, rebond, unbond or withdraw rewards for longer than the inactivity window.
+    /// Can be called by anyone. Their full rewards power is restored on their next interaction.
+    /// Actions of keepers or unbond controllers on their behalf do not count as interaction.
+    /// While the decay is disabled, this restores the full rewards power of the given stakers.
+    ApplyDecay { stakers: Vec<String> },
+    /// Changes the minimum stake per unbonding period needed to get rewards power.
+    /// Stakes crossing the new threshold gain or lose their rewards power for future distributions,
+    /// once [`ExecuteMsg::ApplyMinBond`] reached them.
+    /// Fails while the previous change is still being applied.
+    /// Can only be called by the ADMIN.
+    SetMinBond { value: Uint128 },
+    /// Applies the pending change of the minimum stake to the next `limit` stakers.
+    /// Can be called by anyone.
+    ApplyMinBond { limit: Option<u32> },
+    /// Changes the minimum number of seconds between two distributions.
+    /// Calling [`ExecuteMsg::DistributeRewards`] sooner does nothing, to avoid wasteful repeated ticks.
+    /// Can only be called by the ADMIN.
+    SetMinDistributeInterval { interval: u64 },
+    /// Enables [`ExecuteMsg::InstantUnbond`] with the given fee, or disables it if `fee` is `None`.
+    /// The fee is sent to `treasury`, or burned if it is not set.
+    /// Can only be called by the ADMIN.
+    SetInstantUnbond {
+        fee: Option<Decimal>,
+        treasury: Option<String>,
+    },
+    /// Changes the highest rewards multiplier new distribution flows can use.
+    /// Existing distribution flows are not affected.
+    /// Can only be called by the ADMIN.
+    SetMaxRewardMultiplier { max: Decimal },
+    /// Changes the maximum number of distribution flows.
+    /// Cannot be lower than the number of existing distribution flows.
+    /// Can only be called by the ADMIN.
+    SetMaxDistributions { new_max: u32 },
+    /// Changes how the rewards power of a stake is rounded, updating the rewards power of all stakers.
+    /// Rewards that were already distributed are not affected.
+    /// Can only be called by the ADMIN.
+    SetPowerRounding { mode: RoundingMode },
+    /// Removes an unbonding period together with its reward multipliers in all distribution flows.
+    /// Fails if there are still tokens bonded or unbonding from that period.
+    /// Can only be called by the ADMIN.
+    RemoveUnbondingPeriod { unbonding_period: UnbondingPeriod },
+    /// Adds a new unbonding period. `rewards` has to contain the reward multiplier of the new period
+    /// for every existing distribution flow, so it is rewarded right away.
+    /// Can only be called by the ADMIN.
+    AddUnbondingPeriod {
+        unbonding_period: UnbondingPeriod,
+        rewards: Vec<(AssetInfo, Decimal)>,
+    },
+    /// Links the distribution flow of `asset` to a source it is re-funded from, or unlinks it (`None`).
+    /// Unlinking returns any native tokens the previous source prepaid.
+    /// A cw20 source has to confirm the terms with [`ExecuteMsg::AcceptFundingSource`]
+    /// before anything is pulled from it.
+    /// Can only be called by the manager of the distribution flow.
+    SetFundingSource {
+        asset: AssetInfo,
+        source: Option<FundingSource>,
+    },
+    /// Sets the minimum rewards power a staker needs in the distribution flow of `asset` to earn from it.
+    /// Stakers below it are left out of its distributions and their share goes to the others.
+    /// The stakers are switched to it by [`ExecuteMsg::ApplyDistributionUpdate`].
+    /// Can only be called by the manager of the distribution flow.
+    SetMinPowerToEarn {
+        asset: AssetInfo,
+        min_power: Uint128,
+    },
+    /// Halts or resumes funding and distributing the distribution flow of `asset`.
+    /// Its reward curve is frozen while paused, so no rewards are released for the paused time.
+    /// Can only be called by the manager of the distribution flow or the admin.
+    SetDistributionPaused { asset: AssetInfo, paused: bool },
+    /// Pauses the distribution flow of `asset` like [`ExecuteMsg::SetDistributionPaused`],
+    /// but fails if it is paused already.
+    /// Can only be called by the manager of the distribution flow.
+    PauseDistribution { asset: AssetInfo },
+    /// Resumes the distribution flow of `asset` like [`ExecuteMsg::SetDistributionPaused`],
+    /// continuing its reward curve where it was paused. Fails if it is not paused.
+    /// Can only be called by the manager of the distribution flow.
+    ResumeDistribution { asset: AssetInfo },
+    /// Replaces the rewards multipliers of the distribution flow of `asset`.
+    /// The same rules as in [`ExecuteMsg::CreateDistributionFlow`] apply.
+    /// Rewards that were already distributed are not affected.
+    /// The stakers are switched to the new multipliers by [`ExecuteMsg::ApplyDistributionUpdate`].
+    /// Can only be called by the ADMIN.
+    UpdateDistributionMultipliers {
+        asset: AssetInfo,
+        rewards: Vec<(UnbondingPeriod, Decimal)>,
+    },
+    /// Applies the pending update of the distribution flow of `asset` to the next `limit` stakers.
+    /// The flow does not distribute any rewards until the update reached all stakers.
+    /// Can be called by anyone.
+    ApplyDistributionUpdate {
+        asset: AssetInfo,
+        limit: Option<u32>,
+    },
+    /// Announces new rewards multipliers for the distribution flow of `asset` that take effect at
+    /// `effective_at` (block time in seconds). The first reward distribution at or after that time
+    /// starts the update, after the rewards released until then were distributed with the
+    /// current multipliers. Like for [`ExecuteMsg::UpdateDistributionMultipliers`], the stakers
+    /// are switched by [`ExecuteMsg::ApplyDistributionUpdate`].
+    /// Replaces any change that was scheduled before.
+    /// Can only be called by the manager of the distribution flow.
+    ScheduleMultiplierUpdate {
+        asset: AssetInfo,
+        rewards: Vec<(UnbondingPeriod, Decimal)>,
+        effective_at: u64,
+    },
+    /// Accepts to re-fund the distribution flow of `asset` with `amount` per `epoch`.
+    /// The terms have to match the ones configured with [`ExecuteMsg::SetFundingSource`].
+    /// Can only be called by the configured funding source.
+    AcceptFundingSource {
+        asset: AssetInfo,
+        amount: Uint128,
+        epoch: u64,
+    },
+    /// Deposits the sent native tokens to be pulled by [`ExecuteMsg::PullFunding`] later.
+    /// Can only be called by the funding source configured for each of the sent denoms.
+    PrepayFunding {},
+    /// Starts a new funding curve of the configured amount and epoch for the distribution flow
+    /// of `asset` once its current funding curve has ended.
+    /// Cw20 tokens are transferred from the source using an allowance, once it accepted the terms.
+    /// Native tokens are taken from what the source prepaid. Can be called by anyone.
+    PullFunding { asset: AssetInfo },
+    /// Returns up to `amount` of the rewards of `asset` that are still locked in its reward curve
+    /// to the funders, proportionally to how much of each one's funding is still locked.
+    /// The remaining schedule is scaled down accordingly, so rewards that were already released
+    /// or distributed are never taken back.
+    /// Can only be called by the manager of the distribution flow.
+    RecallUndistributed { asset: AssetInfo, amount: Uint128 },
+}
+
+#[cw_serde]
+pub struct FundingSource {
+    /// The address the funds are pulled from
+    pub address: String,
+    /// The amount pulled per epoch
+    pub amount: Uint128,
+    /// Duration in seconds over which each pulled amount is distributed
+    pub epoch: u64,
+}
+
+#[cw_serde]
+pub struct DistributionFunding {
+    /// The asset to fund the distribution flow of
+    pub asset: AssetInfo,
+    /// When and how much of the asset to distribute
+    pub funding_info: FundingInfo,
+}
+
+#[cw_serde]
+pub struct PowerDecayConfig {
+    /// Seconds without interaction after which the rewards power starts to decay
+    pub inactivity_window: u64,
+    /// Seconds it takes for the rewards power to decay linearly from full to zero
+    pub decay_duration: u64,
 }
 
 #[cw_serde]
@@ -123,6 +413,12 @@ pub enum QueryMsg {
     /// Show the outstanding rewards for this address
     #[returns(RewardsPowerResponse)]
     RewardsPower { address: String },
+    /// Returns the reward assets this address currently has non-zero rewards power in
+    #[returns(Vec<AssetInfoValidated>)]
+    UserEarningAssets { address: String },
+    /// Show the total rewards power of everyone except this address, per distribution
+    #[returns(RewardsPowerResponse)]
+    TotalRewardsPowerExcluding { address: String },
     /// Return AdminResponse
     #[returns(cw_controllers::AdminResponse)]
     Admin {},
@@ -132,10 +428,18 @@ pub enum QueryMsg {
     /// Return how many rewards will be received per token in each unbonding period in one year
     #[returns(AnnualizedRewardsResponse)]
     AnnualizedRewards {},
+    /// Like [`QueryMsg::AnnualizedRewards`], but based on the funded reward curves at `timestamp`
+    /// (in seconds) instead of now. The current stake is used, since past stake is not stored.
+    #[returns(AnnualizedRewardsResponse)]
+    AnnualizedRewardsAt { timestamp: u64 },
     /// Return how many rewards are assigned for withdrawal from the given address. Returns
     /// `RewardsResponse`.
     #[returns(WithdrawableRewardsResponse)]
     WithdrawableRewards { owner: String },
+    /// Like [`QueryMsg::WithdrawableRewards`], but also splits each amount across the unbonding
+    /// periods of the address, by the rewards power it currently has in each of them.
+    #[returns(WithdrawableRewardsDetailedResponse)]
+    WithdrawableRewardsDetailed { owner: String },
     /// Return how many rewards were distributed in total by this contract. Returns
     /// `RewardsResponse`.
     #[returns(DistributedRewardsResponse)]
@@ -147,6 +451,9 @@ pub enum QueryMsg {
     /// Return address allowed for withdrawal of the funds assigned to owner. Returns `DelegatedResponse`
     #[returns(DelegatedResponse)]
     Delegated { owner: String },
+    /// Returns the default receiver of the rewards of `owner`, as set by [`ExecuteMsg::SetRewardReceiver`]
+    #[returns(RewardReceiverResponse)]
+    RewardReceiver { owner: String },
     /// Returns rewards distribution data
     #[returns(DistributionDataResponse)]
     DistributionData {},
@@ -156,6 +463,83 @@ pub enum QueryMsg {
     /// Returns the value of unbond all flag
     #[returns(UnbondAllResponse)]
     UnbondAll {},
+    /// Returns how many seconds remain until all of the address' stake in the given unbonding period
+    /// can be rebonded to a shorter period.
+    #[returns(RebondCooldownResponse)]
+    RebondCooldown {
+        address: String,
+        /// Unbonding period in seconds
+        unbonding_period: u64,
+    },
+    /// Returns the address' complete staking state at once, see [`UserSummaryResponse`]
+    #[returns(UserSummaryResponse)]
+    UserSummary { address: String },
+    /// Returns the block of the address' last bond, rebond, unbond or rewards withdrawal
+    #[returns(LastInteractionResponse)]
+    LastInteraction { address: String },
+    /// Returns how many tokens of the given asset will still be distributed until the
+    /// distribution flow's currently funded reward curve fully emits.
+    #[returns(TotalToBeDistributedResponse)]
+    TotalToBeDistributed { asset: AssetInfo },
+    /// Returns the contract's balance of the staked token, split into the bonded principal and
+    /// the part that is designated as rewards.
+    #[returns(RewardTokenBalanceResponse)]
+    RewardTokenBalance {},
+    /// Returns the next point in time at which the rate of rewards the given address accrues
+    /// changes, because one of the reward curves of its distribution flows changes slope or ends.
+    #[returns(NextRewardChangeResponse)]
+    NextRewardChange { owner: String },
+    /// Returns the amount of each distribution flow that would become withdrawable
+    /// if `DistributeRewards` was called at the current block.
+    #[returns(Vec<AssetValidated>)]
+    PendingDistribution {},
+    /// Returns the cumulative funded, distributed and withdrawn amounts of the given distribution flow,
+    /// together with what is currently left in each stage.
+    #[returns(FlowAccountingResponse)]
+    FlowAccounting { asset: AssetInfo },
+    /// Returns the total amount ever funded into each distribution flow,
+    /// no matter how much of it was distributed or withdrawn since.
+    #[returns(Vec<AssetValidated>)]
+    LifetimeFunded {},
+    /// Returns the contract's balance of the given asset next to everything it owes,
+    /// so it can be verified that the contract holds enough to cover all obligations.
+    #[returns(AssetReconciliationResponse)]
+    AssetReconciliation { asset: AssetInfo },
+    /// Returns the rewards multiplier applied to the address' stake, per distribution flow and
+    /// unbonding period. Only the unbonding periods the address has stake in are included.
+    #[returns(Vec<(AssetInfoValidated, UnbondingPeriod, Decimal)>)]
+    UserMultipliers { address: String },
+    /// Returns every set of rewards multipliers the distribution flow of `asset` had, oldest first
+    #[returns(Vec<crate::state::MultiplierChange>)]
+    MultiplierHistory { asset: AssetInfo },
+    /// Returns the multiplier change scheduled for the distribution flow of `asset`, if any
+    #[returns(Option<crate::state::ScheduledMultipliers>)]
+    ScheduledMultipliers { asset: AssetInfo },
+    /// Returns the number of addresses with stake in at least one unbonding period.
+    /// Addresses that already had stake before the count was introduced are not included.
+    #[returns(StakerCountResponse)]
+    StakerCount {},
+    /// Returns how the given amount would be split across the unbonding periods if it was distributed
+    /// by the given distribution flow right now, based on the current stake and multipliers.
+    /// Each entry is the amount all stakers of that unbonding period would receive collectively.
+    #[returns(Vec<(UnbondingPeriod, Uint128)>)]
+    DistributionPreview { asset: AssetInfo, amount: Uint128 },
+    /// Returns the rewards `owner` could withdraw at `at_time` (in seconds), assuming everything
+    /// the reward curves release until then is distributed based on the current stake.
+    #[returns(WithdrawableRewardsResponse)]
+    WithdrawableAt { owner: String, at_time: u64 },
+    /// Returns the rewards `owner` earned since their last rewards withdrawal, whether they were
+    /// withdrawn since (e.g. by compounding) or not
+    #[returns(EarnedSinceWithdrawResponse)]
+    EarnedSinceWithdraw { owner: String },
+    /// Returns the rate (tokens per second) at which the funded reward curve of the distribution
+    /// flow of `asset` releases rewards at `at_time` (in seconds), based on the current funding.
+    #[returns(RateAtResponse)]
+    RateAt { asset: AssetInfo, at_time: u64 },
+    /// Returns when the reward curve of every distribution flow ends and how much of it
+    /// is not distributed yet, e.g. for scheduling new fundings.
+    #[returns(Vec<FlowSchedule>)]
+    FlowsSchedule {},
 }
 
 #[cw_serde]
@@ -187,6 +571,11 @@ pub struct TotalStakedResponse {
     pub total_staked: Uint128,
 }
 
+#[cw_serde]
+pub struct StakerCountResponse {
+    pub count: u64,
+}
+
 #[cw_serde]
 pub struct TotalUnbondingResponse {
     pub total_unbonding: Uint128,
@@ -236,11 +625,31 @@ pub struct WithdrawableRewardsResponse {
     pub rewards: Vec<AssetValidated>,
 }
 
+#[cw_serde]
+pub struct WithdrawableRewardsDetailedResponse {
+    pub rewards: Vec<DetailedWithdrawableRewards>,
+}
+
+#[cw_serde]
+pub struct DetailedWithdrawableRewards {
+    /// Amount of rewards assigned for withdrawal from the given address.
+    pub total: AssetValidated,
+    /// `total` split by the rewards power the address currently has in each unbonding period.
+    /// Past rewards power is not stored, so this is an estimate if the stake changed since.
+    /// Empty if the address has no rewards power left.
+    pub per_period: Vec<(UnbondingPeriod, Uint128)>,
+}
+
 #[cw_serde]
 pub struct DelegatedResponse {
     pub delegated: Addr,
 }
 
+#[cw_serde]
+pub struct RewardReceiverResponse {
+    pub receiver: Option<Addr>,
+}
+
 #[cw_serde]
 pub struct DistributedRewardsResponse {
     /// Total number of tokens sent to the contract over all time.
@@ -249,6 +658,30 @@ pub struct DistributedRewardsResponse {
     pub withdrawable: Vec<AssetValidated>,
 }
 
+#[cw_serde]
+pub struct EarnedSinceWithdrawResponse {
+    /// Block time in seconds of the last rewards withdrawal.
+    /// `None` if `owner` never withdrew, in which case `earned` covers all rewards ever earned.
+    pub last_withdraw: Option<u64>,
+    /// Rewards earned since then, per asset
+    pub earned: Vec<AssetValidated>,
+}
+
+#[cw_serde]
+pub struct RateAtResponse {
+    /// Tokens released per second
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+pub struct FlowSchedule {
+    pub asset: AssetInfoValidated,
+    /// Time at which the reward curve fully emits, if any rewards are scheduled
+    pub curve_end: Option<u64>,
+    /// Rewards that were funded or sent to the contract, but not distributed yet
+    pub undistributed: Uint128,
+}
+
 pub type UndistributedRewardsResponse = WithdrawableRewardsResponse;
 #[cw_serde]
 pub struct DistributionDataResponse {
@@ -261,3 +694,93 @@ pub struct UnbondAllResponse {
     /// Value of unbond all flag.
     pub unbond_all: bool,
 }
+
+#[cw_serde]
+pub struct RebondCooldownResponse {
+    /// Seconds until the stake is fully unlocked. Zero if it can be rebonded now.
+    pub remaining: u64,
+}
+
+#[cw_serde]
+pub struct UserSummaryResponse {
+    /// Stake per unbonding period, like [`QueryMsg::AllStaked`]
+    pub stakes: Vec<StakedResponse>,
+    /// Sum of the stake over all unbonding periods
+    pub total_staked: Uint128,
+    /// Rewards power per distribution flow, like [`QueryMsg::RewardsPower`]
+    pub rewards_power: Vec<(AssetInfoValidated, Uint128)>,
+    /// Rewards ready to be withdrawn, like [`QueryMsg::WithdrawableRewards`]
+    pub withdrawable_rewards: Vec<AssetValidated>,
+    /// Claims that are still unbonding
+    pub pending_claims: Vec<cw_controllers::Claim>,
+    /// Total of the claims that can be claimed now
+    pub claimable: Uint128,
+    /// Like [`QueryMsg::NextRewardChange`]
+    pub next_reward_change: Option<u64>,
+}
+
+#[cw_serde]
+pub struct LastInteractionResponse {
+    /// `None` if the address never interacted with the contract
+    pub last_interaction: Option<crate::state::InteractionBlock>,
+}
+
+#[cw_serde]
+pub struct TotalToBeDistributedResponse {
+    /// Amount of rewards that were funded or sent to the contract, but not distributed yet.
+    /// This includes both the rewards still locked in the reward curve and the ones already
+    /// unlocked, but waiting for the next [`ExecuteMsg::DistributeRewards`].
+    pub amount: AssetValidated,
+    /// Time at which the reward curve fully emits, if any rewards are scheduled
+    pub end_time: Option<u64>,
+}
+
+#[cw_serde]
+pub struct FlowAccountingResponse {
+    /// Total rewards funded, including native tokens sent along with [`ExecuteMsg::DistributeRewards`].
+    /// Always equals `distributed_total + undistributed`, unless tokens were sent to the contract directly.
+    pub funded_total: Uint128,
+    /// Total rewards distributed to the stakers.
+    /// Always equals `withdrawn_total + withdrawable_outstanding + leftover`.
+    pub distributed_total: Uint128,
+    /// Total rewards withdrawn or compounded by the stakers
+    pub withdrawn_total: Uint128,
+    /// Rewards held by the contract that were not distributed yet
+    pub undistributed: Uint128,
+    /// Distributed rewards the stakers can still withdraw
+    pub withdrawable_outstanding: Uint128,
+    /// Distributed rewards that were not assigned to any staker yet because of rounding.
+    /// They are carried over to the next distribution.
+    pub leftover: Uint128,
+}
+
+#[cw_serde]
+pub struct AssetReconciliationResponse {
+    /// The contract's balance of the asset
+    pub contract_balance: Uint128,
+    /// Rewards still locked by the reward curve, plus native tokens prepaid by a funding source
+    pub owed_undistributed: Uint128,
+    /// Distributed rewards the stakers can still withdraw
+    pub owed_withdrawable: Uint128,
+    /// Bonded and unbonding tokens that belong to the stakers.
+    /// Always zero if the asset is not the staking token.
+    pub owed_claims_if_staking_token: Uint128,
+    /// What is left of `contract_balance` after all of the above. These tokens are not owed to anyone
+    /// yet and are handed out by the next distribution. Zero if the obligations exceed the balance.
+    pub surplus: Uint128,
+}
+
+#[cw_serde]
+pub struct RewardTokenBalanceResponse {
+    /// Bonded and unbonding tokens that belong to the stakers
+    pub principal: Uint128,
+    /// Staked tokens held by the contract on top of the principal. These are distributed as rewards
+    /// by the staked token's distribution flow, if one exists.
+    pub rewards: Uint128,
+}
+
+#[cw_serde]
+pub struct NextRewardChangeResponse {
+    /// Timestamp in seconds of the next change, `None` if the rate will not change anymore
+    pub timestamp: Option<u64>,
+}
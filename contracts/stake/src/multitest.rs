@@ -1,7 +1,32 @@
+mod add_unbonding_period;
+mod cancel_unbonding;
+mod claim_and_withdraw_all;
+mod claim_receiver;
+mod claimant_rewards;
 mod delegate;
+mod delegation_rights;
 mod distribution;
+mod earned_since_withdraw;
+mod exit_all;
+mod funding_source;
+mod instant_unbond;
+mod last_interaction;
+mod max_distributions;
+mod migrate_position;
 mod migration;
+mod min_bond;
+mod multiplier_history;
+mod power_decay;
 mod quick_unbond;
+mod rebond;
+mod remove_distribution_flow;
+mod remove_unbonding_period;
+mod rescue;
+mod reward_receiver;
+mod scheduled_multipliers;
 mod staking_rewards;
+mod staking_token_rewards;
 mod suite;
 mod unbond_all;
+mod user_earning_assets;
+mod user_summary;
@@ -0,0 +1,99 @@
+use cosmwasm_std::{Decimal, Uint128};
+use cw_controllers::AdminError;
+use wyndex::asset::{AssetInfo, AssetInfoValidated};
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM};
+use crate::ContractError;
+
+const UNBONDING_PERIODS: &[u64; 2] = &[100, 200];
+
+#[test]
+fn add_unbonding_period_with_rewards() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![("short", 10_000), ("long", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("distributor", 4_000)])
+        .build();
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            asset.clone(),
+            vec![(100, Decimal::one()), (200, Decimal::percent(200))],
+        )
+        .unwrap();
+
+    let err = suite
+        .add_unbonding_period("short", 300, vec![(asset.clone(), Decimal::percent(300))])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .add_unbonding_period("admin", 200, vec![(asset.clone(), Decimal::percent(300))])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UnbondingPeriodAlreadyExists(200),
+        err.downcast().unwrap()
+    );
+    // every flow needs a multiplier
+    let err = suite
+        .add_unbonding_period("admin", 300, vec![])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::MissingRewardMultiplier(AssetInfoValidated::Native(JUNO_DENOM.to_string())),
+        err.downcast().unwrap()
+    );
+    // but no multipliers for assets without a flow
+    let err = suite
+        .add_unbonding_period(
+            "admin",
+            300,
+            vec![
+                (asset.clone(), Decimal::percent(300)),
+                (AssetInfo::Native("other".to_string()), Decimal::one()),
+            ],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidRewards {}, err.downcast().unwrap());
+    // longer periods cannot have lower multipliers
+    let err = suite
+        .add_unbonding_period("admin", 300, vec![(asset.clone(), Decimal::one())])
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidRewards {}, err.downcast().unwrap());
+
+    suite
+        .add_unbonding_period("admin", 300, vec![(asset, Decimal::percent(300))])
+        .unwrap();
+    let periods = suite.query_staked_periods().unwrap();
+    assert_eq!(
+        periods
+            .iter()
+            .map(|info| (info.unbonding_period, info.total_staked))
+            .collect::<Vec<_>>(),
+        vec![
+            (100, Uint128::zero()),
+            (200, Uint128::zero()),
+            (300, Uint128::zero())
+        ]
+    );
+
+    // the new period is rewarded right away
+    suite.delegate("short", 10_000, 100).unwrap();
+    suite.delegate("long", 10_000, 300).unwrap();
+    suite
+        .distribute_funds("distributor", None, Some(juno(4_000)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("short").unwrap(),
+        vec![juno(1_000)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards("long").unwrap(),
+        vec![juno(3_000)]
+    );
+}
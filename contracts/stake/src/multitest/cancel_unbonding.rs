@@ -0,0 +1,110 @@
+use cosmwasm_std::{Decimal, Uint128};
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno_power, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const STAKER: &str = "staker";
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn cancel_unbonding_restores_power() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_min_bond(1_000)
+        .with_initial_balances(vec![(STAKER, 10_000)])
+        .build();
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(STAKER, 10_000, SEVEN_DAYS).unwrap();
+    let power_before = suite.query_rewards_power(STAKER).unwrap();
+    assert_eq!(power_before, juno_power(10));
+
+    suite.unbond(STAKER, 2_000, SEVEN_DAYS).unwrap();
+    suite.update_time(DAY);
+    suite.unbond(STAKER, 4_000, SEVEN_DAYS).unwrap();
+    assert_eq!(suite.query_rewards_power(STAKER).unwrap(), juno_power(4));
+
+    let err = suite
+        .cancel_unbonding(STAKER, 7_000, SEVEN_DAYS)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotEnoughUnbonding {
+            available: Uint128::new(6_000)
+        },
+        err.downcast().unwrap()
+    );
+
+    // the last claim is cancelled first
+    suite.cancel_unbonding(STAKER, 5_000, SEVEN_DAYS).unwrap();
+    assert_eq!(suite.query_rewards_power(STAKER).unwrap(), juno_power(9));
+    let claims = suite.query_claims(STAKER).unwrap();
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].amount, Uint128::new(1_000));
+
+    suite.cancel_unbonding(STAKER, 1_000, SEVEN_DAYS).unwrap();
+    assert_eq!(suite.query_rewards_power(STAKER).unwrap(), power_before);
+    assert_eq!(suite.query_claims(STAKER).unwrap(), vec![]);
+    assert_eq!(suite.query_staked(STAKER, SEVEN_DAYS).unwrap(), 10_000);
+    assert_eq!(suite.query_total_staked().unwrap(), 10_000);
+}
+
+#[test]
+fn cannot_cancel_into_bond_controlled_by_someone_else() {
+    let mut suite = SuiteBuilder::new()
+        .with_initial_balances(vec![(STAKER, 10_000), ("escrow", 5_000)])
+        .build();
+    suite.delegate(STAKER, 10_000, SEVEN_DAYS).unwrap();
+    suite.unbond(STAKER, 10_000, SEVEN_DAYS).unwrap();
+
+    // an escrow stakes for the staker, keeping control over the bond
+    suite
+        .set_delegation_rights(STAKER, Some("escrow"), None)
+        .unwrap();
+    suite
+        .delegate_with_rights(
+            "escrow",
+            5_000,
+            SEVEN_DAYS,
+            Some(STAKER),
+            Some("escrow"),
+            None,
+        )
+        .unwrap();
+
+    // the staker's own claims would end up in the escrow's control
+    let err = suite
+        .cancel_unbonding(STAKER, 1_000, SEVEN_DAYS)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    assert_eq!(suite.query_staked(STAKER, SEVEN_DAYS).unwrap(), 5_000);
+}
+
+#[test]
+fn matured_claims_cannot_be_cancelled() {
+    let mut suite = SuiteBuilder::new()
+        .with_initial_balances(vec![(STAKER, 10_000)])
+        .build();
+    suite.delegate(STAKER, 10_000, SEVEN_DAYS).unwrap();
+    suite.unbond(STAKER, 1_000, SEVEN_DAYS).unwrap();
+    suite.update_time(SEVEN_DAYS);
+
+    let err = suite
+        .cancel_unbonding(STAKER, 1_000, SEVEN_DAYS)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotEnoughUnbonding {
+            available: Uint128::zero()
+        },
+        err.downcast().unwrap()
+    );
+    suite.claim(STAKER).unwrap();
+    assert_eq!(suite.query_balance_vesting_contract(STAKER).unwrap(), 1_000);
+}
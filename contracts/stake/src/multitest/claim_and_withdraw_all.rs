@@ -0,0 +1,46 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+
+const USER: &str = "user";
+const DISTRIBUTOR: &str = "distributor";
+
+#[test]
+fn claims_tokens_and_withdraws_rewards() {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(vec![SEVEN_DAYS])
+        .with_initial_balances(vec![(USER, 10_000)])
+        .with_admin("admin")
+        .with_native_balances(JUNO_DENOM, vec![(DISTRIBUTOR, 400)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(USER, 10_000, SEVEN_DAYS).unwrap();
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(400)))
+        .unwrap();
+    suite.unbond(USER, 4_000, SEVEN_DAYS).unwrap();
+    suite.update_time(SEVEN_DAYS);
+
+    suite.claim_and_withdraw_all(USER).unwrap();
+
+    // both the matured claim and the rewards arrived
+    assert_eq!(suite.query_balance_vesting_contract(USER).unwrap(), 4_000);
+    assert_eq!(suite.query_balance(USER, JUNO_DENOM).unwrap(), 400);
+    assert_eq!(suite.query_claims(USER).unwrap(), vec![]);
+    assert_eq!(suite.withdrawable_rewards(USER).unwrap(), vec![juno(0)]);
+
+    // nothing left to claim or withdraw is not an error
+    suite.claim_and_withdraw_all(USER).unwrap();
+    assert_eq!(suite.query_balance_vesting_contract(USER).unwrap(), 4_000);
+    assert_eq!(suite.query_balance(USER, JUNO_DENOM).unwrap(), 400);
+}
@@ -0,0 +1,37 @@
+use super::suite::{SuiteBuilder, SEVEN_DAYS};
+
+const USER: &str = "user";
+const COLD_WALLET: &str = "cold_wallet";
+
+#[test]
+fn claim_goes_to_configured_receiver() {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(vec![SEVEN_DAYS])
+        .with_initial_balances(vec![(USER, 10_000)])
+        .build();
+
+    suite.delegate(USER, 10_000, SEVEN_DAYS).unwrap();
+    suite.set_claim_receiver(USER, Some(COLD_WALLET)).unwrap();
+
+    suite.unbond(USER, 4_000, SEVEN_DAYS).unwrap();
+    suite.update_time(SEVEN_DAYS);
+    suite.claim(USER).unwrap();
+
+    // the matured tokens landed at the configured receiver
+    assert_eq!(
+        suite.query_balance_vesting_contract(COLD_WALLET).unwrap(),
+        4_000
+    );
+    assert_eq!(suite.query_balance_vesting_contract(USER).unwrap(), 0);
+
+    // after resetting the receiver, claims go to the user again
+    suite.set_claim_receiver(USER, None).unwrap();
+    suite.unbond(USER, 6_000, SEVEN_DAYS).unwrap();
+    suite.update_time(SEVEN_DAYS);
+    suite.claim(USER).unwrap();
+    assert_eq!(suite.query_balance_vesting_contract(USER).unwrap(), 6_000);
+    assert_eq!(
+        suite.query_balance_vesting_contract(COLD_WALLET).unwrap(),
+        4_000
+    );
+}
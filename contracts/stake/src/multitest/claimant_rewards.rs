@@ -0,0 +1,179 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, Suite, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::ContractError;
+
+const UNBONDING_PERIOD: u64 = 1000;
+const STAKER: &str = "staker";
+const CLAIMANT: &str = "claimant";
+const DISTRIBUTOR: &str = "distributor";
+
+/// Sets up two stakers with equal stake, one of which unbonds everything.
+fn setup(claimant_reward_share: Decimal) -> Suite {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(vec![UNBONDING_PERIOD])
+        .with_initial_balances(vec![(STAKER, 10_000), (CLAIMANT, 10_000)])
+        .with_admin("admin")
+        .with_native_balances(JUNO_DENOM, vec![(DISTRIBUTOR, 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow_with_claimant_share(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(UNBONDING_PERIOD, Decimal::one())],
+            claimant_reward_share,
+        )
+        .unwrap();
+
+    suite.delegate(STAKER, 10_000, UNBONDING_PERIOD).unwrap();
+    suite.delegate(CLAIMANT, 10_000, UNBONDING_PERIOD).unwrap();
+    suite.unbond(CLAIMANT, 10_000, UNBONDING_PERIOD).unwrap();
+
+    suite
+}
+
+#[test]
+fn claimants_get_no_rewards_without_share() {
+    let mut suite = setup(Decimal::zero());
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(300)))
+        .unwrap();
+
+    assert_eq!(suite.withdrawable_rewards(STAKER).unwrap(), vec![juno(300)]);
+    assert_eq!(suite.withdrawable_rewards(CLAIMANT).unwrap(), vec![juno(0)]);
+}
+
+#[test]
+fn claimants_get_reduced_rewards_until_claimed() {
+    let mut suite = setup(Decimal::percent(50));
+
+    // staker has 10 power, claimant keeps half of their former 10 power
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(300)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(STAKER).unwrap(), vec![juno(200)]);
+    assert_eq!(
+        suite.withdrawable_rewards(CLAIMANT).unwrap(),
+        vec![juno(100)]
+    );
+
+    // once the tokens are claimed, the claimant does not get any more rewards
+    suite.update_time(UNBONDING_PERIOD);
+    suite.claim(CLAIMANT).unwrap();
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(300)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(STAKER).unwrap(), vec![juno(500)]);
+    assert_eq!(
+        suite.withdrawable_rewards(CLAIMANT).unwrap(),
+        vec![juno(100)]
+    );
+
+    // the rewards earned while unbonding can still be withdrawn
+    suite.withdraw_funds(CLAIMANT, None, None).unwrap();
+    assert_eq!(suite.query_balance(CLAIMANT, JUNO_DENOM).unwrap(), 100);
+}
+
+#[test]
+fn matured_claims_stop_earning_without_claiming() {
+    let mut suite = setup(Decimal::percent(50));
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(300)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(CLAIMANT).unwrap(),
+        vec![juno(100)]
+    );
+
+    // the claim matured, so it does not get anything anymore, even though it was not claimed yet
+    suite.update_time(UNBONDING_PERIOD);
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(300)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(STAKER).unwrap(), vec![juno(500)]);
+    assert_eq!(
+        suite.withdrawable_rewards(CLAIMANT).unwrap(),
+        vec![juno(100)]
+    );
+
+    // claiming still works afterwards
+    suite.claim(CLAIMANT).unwrap();
+    assert_eq!(
+        suite.query_balance_vesting_contract(CLAIMANT).unwrap(),
+        10_000
+    );
+}
+
+#[test]
+fn claims_of_the_unbond_controller_reward_the_staker() {
+    const ESCROW: &str = "escrow";
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(vec![UNBONDING_PERIOD])
+        .with_initial_balances(vec![(STAKER, 10_000), (ESCROW, 10_000)])
+        .with_admin("admin")
+        .with_native_balances(JUNO_DENOM, vec![(DISTRIBUTOR, 1_000)])
+        .build();
+    suite
+        .create_distribution_flow_with_claimant_share(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(UNBONDING_PERIOD, Decimal::one())],
+            Decimal::percent(50),
+        )
+        .unwrap();
+    suite.delegate(STAKER, 10_000, UNBONDING_PERIOD).unwrap();
+
+    // the escrow controls the bond of the claimant and unbonds it
+    suite
+        .set_delegation_rights(CLAIMANT, Some(ESCROW), None)
+        .unwrap();
+    suite
+        .delegate_with_rights(
+            ESCROW,
+            10_000,
+            UNBONDING_PERIOD,
+            Some(CLAIMANT),
+            Some(ESCROW),
+            None,
+        )
+        .unwrap();
+    suite
+        .unbond_for(ESCROW, Some(CLAIMANT), 10_000, UNBONDING_PERIOD)
+        .unwrap();
+
+    // the escrow holds the claim, but the rewards of the unbonding tokens go to their owner
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(300)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(STAKER).unwrap(), vec![juno(200)]);
+    assert_eq!(
+        suite.withdrawable_rewards(CLAIMANT).unwrap(),
+        vec![juno(100)]
+    );
+    assert_eq!(suite.withdrawable_rewards(ESCROW).unwrap(), vec![juno(0)]);
+}
+
+#[test]
+fn claimant_share_cannot_exceed_one() {
+    let mut suite = SuiteBuilder::new().with_admin("admin").build();
+
+    let err = suite
+        .create_distribution_flow_with_claimant_share(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+            Decimal::percent(101),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidClaimantRewardShare {},
+        err.downcast().unwrap()
+    );
+}
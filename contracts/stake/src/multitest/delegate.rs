@@ -1,7 +1,10 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 use cw_controllers::Claim;
+use cw_multi_test::AppResponse;
+use wyndex::asset::AssetInfo;
 
-use super::suite::{SuiteBuilder, SEVEN_DAYS};
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::ContractError;
 
 #[test]
 fn delegate_and_unbond_tokens_still_vested() {
@@ -162,3 +165,106 @@ fn mass_delegation_simple_case() {
         50_000u128
     );
 }
+
+#[test]
+fn staker_count_tracks_addresses_with_stake() {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(vec![100, 200])
+        .with_initial_balances(vec![("alice", 10_000), ("bob", 10_000)])
+        .build();
+    assert_eq!(suite.query_staker_count().unwrap(), 0);
+
+    let has_count_event =
+        |resp: &AppResponse| resp.events.iter().any(|e| e.ty == "wasm-staker_count");
+
+    let resp = suite.delegate("alice", 5_000, 100).unwrap();
+    assert!(has_count_event(&resp));
+    // bonding in another period does not make alice a new staker
+    let resp = suite.delegate("alice", 5_000, 200).unwrap();
+    assert!(!has_count_event(&resp));
+    suite.delegate("bob", 10_000, 100).unwrap();
+    assert_eq!(suite.query_staker_count().unwrap(), 2);
+
+    // alice still has stake left after the first unbond
+    suite.unbond("alice", 5_000, 100).unwrap();
+    assert_eq!(suite.query_staker_count().unwrap(), 2);
+    let resp = suite.unbond("alice", 5_000, 200).unwrap();
+    assert!(has_count_event(&resp));
+    assert_eq!(suite.query_staker_count().unwrap(), 1);
+
+    // moving stake between periods does not change the count
+    suite.rebond("bob", 10_000, 100, 200).unwrap();
+    assert_eq!(suite.query_staker_count().unwrap(), 1);
+}
+
+#[test]
+fn shared_delegation_creates_independent_stakes() {
+    let team = "team";
+    let alice = "alice";
+    let bob = "bob";
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_initial_balances(vec![(team, 120_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 1_000)])
+        .build();
+    suite
+        .create_distribution_flow(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+
+    // the shares have to sum up to one
+    let err = suite
+        .delegate_shared(
+            team,
+            10_000,
+            None,
+            &[(alice, Decimal::percent(60)), (bob, Decimal::percent(30))],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidShares {}, err.downcast().unwrap());
+    // and every part has to reach the minimum bond
+    let err = suite
+        .delegate_shared(
+            team,
+            10_000,
+            None,
+            &[(alice, Decimal::percent(90)), (bob, Decimal::percent(10))],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::SharedDelegationBelowMinBond {
+            amount: Uint128::new(1_000),
+            min_bond: Uint128::new(5_000),
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .delegate_shared(
+            team,
+            100_000,
+            None,
+            &[(alice, Decimal::percent(60)), (bob, Decimal::percent(40))],
+        )
+        .unwrap();
+    assert_eq!(suite.query_balance_vesting_contract(team).unwrap(), 20_000);
+    assert_eq!(suite.query_staked(team, None).unwrap(), 0);
+    assert_eq!(suite.query_staked(alice, None).unwrap(), 60_000);
+    assert_eq!(suite.query_staked(bob, None).unwrap(), 40_000);
+
+    // both earn rewards on their own
+    suite
+        .distribute_funds("funder", None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(alice).unwrap(), vec![juno(600)]);
+    assert_eq!(suite.withdrawable_rewards(bob).unwrap(), vec![juno(400)]);
+
+    // and can unbond independently
+    suite.unbond(bob, 40_000, None).unwrap();
+    assert_eq!(suite.query_staked(alice, None).unwrap(), 60_000);
+    assert_eq!(suite.query_staked(bob, None).unwrap(), 0);
+}
@@ -0,0 +1,219 @@
+use cosmwasm_std::{Decimal, Uint128};
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const ESCROW: &str = "escrow";
+const BENEFICIARY: &str = "beneficiary";
+
+#[test]
+fn unbond_controller_controls_bond() {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(vec![SEVEN_DAYS, SEVEN_DAYS * 2])
+        .with_initial_balances(vec![(ESCROW, 11_000), (BENEFICIARY, 1_000)])
+        .build();
+
+    // the beneficiary agrees to the escrow controlling its bond
+    suite
+        .set_delegation_rights(BENEFICIARY, Some(ESCROW), None)
+        .unwrap();
+    // then the escrow stakes for the beneficiary, but keeps control over unbonding
+    suite
+        .delegate_with_rights(
+            ESCROW,
+            10_000,
+            SEVEN_DAYS,
+            Some(BENEFICIARY),
+            Some(ESCROW),
+            None,
+        )
+        .unwrap();
+    assert_eq!(suite.query_staked(BENEFICIARY, SEVEN_DAYS).unwrap(), 10_000);
+
+    // the beneficiary can neither unbond nor rebond
+    let err = suite.unbond(BENEFICIARY, 1_000, SEVEN_DAYS).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .rebond(BENEFICIARY, 1_000, SEVEN_DAYS, SEVEN_DAYS * 2)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // the controller can, and the claim belongs to it
+    suite
+        .rebond_for(ESCROW, Some(BENEFICIARY), 2_000, SEVEN_DAYS, SEVEN_DAYS * 2)
+        .unwrap();
+    assert_eq!(
+        suite.query_staked(BENEFICIARY, SEVEN_DAYS * 2).unwrap(),
+        2_000
+    );
+    suite
+        .unbond_for(ESCROW, Some(BENEFICIARY), 4_000, SEVEN_DAYS)
+        .unwrap();
+    assert_eq!(suite.query_staked(BENEFICIARY, SEVEN_DAYS).unwrap(), 4_000);
+    assert_eq!(suite.query_claims(BENEFICIARY).unwrap(), vec![]);
+    let claims = suite.query_claims(ESCROW).unwrap();
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].amount, Uint128::new(4_000));
+
+    // nobody can add stake with different rights to the controlled bond
+    let err = suite.delegate(BENEFICIARY, 1_000, SEVEN_DAYS).unwrap_err();
+    assert_eq!(
+        ContractError::DelegationRightsMismatch {},
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .delegate_with_rights(
+            ESCROW,
+            1_000,
+            SEVEN_DAYS,
+            Some(BENEFICIARY),
+            Some(BENEFICIARY),
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DelegationRightsMismatch {},
+        err.downcast().unwrap()
+    );
+    // but the same rights can be used to top up the bond
+    suite
+        .delegate_with_rights(
+            ESCROW,
+            1_000,
+            SEVEN_DAYS,
+            Some(BENEFICIARY),
+            Some(ESCROW),
+            None,
+        )
+        .unwrap();
+    assert_eq!(suite.query_staked(BENEFICIARY, SEVEN_DAYS).unwrap(), 5_000);
+}
+
+#[test]
+fn cannot_take_control_over_existing_bond() {
+    let mut suite = SuiteBuilder::new()
+        .with_initial_balances(vec![(ESCROW, 1_000), (BENEFICIARY, 10_000)])
+        .build();
+
+    suite.delegate(BENEFICIARY, 10_000, SEVEN_DAYS).unwrap();
+
+    let err = suite
+        .delegate_with_rights(
+            ESCROW,
+            1_000,
+            SEVEN_DAYS,
+            Some(BENEFICIARY),
+            Some(ESCROW),
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DelegationRightsMismatch {},
+        err.downcast().unwrap()
+    );
+
+    // the beneficiary keeps full control
+    suite.unbond(BENEFICIARY, 10_000, SEVEN_DAYS).unwrap();
+}
+
+#[test]
+fn cannot_set_rights_on_someone_else() {
+    let mut suite = SuiteBuilder::new()
+        .with_initial_balances(vec![(ESCROW, 2_000), (BENEFICIARY, 10_000)])
+        .build();
+
+    // neither on an address that never staked
+    let err = suite
+        .delegate_with_rights(
+            ESCROW,
+            1_000,
+            SEVEN_DAYS,
+            Some(BENEFICIARY),
+            Some(ESCROW),
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DelegationRightsMismatch {},
+        err.downcast().unwrap()
+    );
+
+    // nor on a staker that fully unbonded
+    suite.delegate(BENEFICIARY, 10_000, SEVEN_DAYS).unwrap();
+    suite.unbond(BENEFICIARY, 10_000, SEVEN_DAYS).unwrap();
+    let err = suite
+        .delegate_with_rights(
+            ESCROW,
+            1_000,
+            SEVEN_DAYS,
+            Some(BENEFICIARY),
+            None,
+            Some(ESCROW),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DelegationRightsMismatch {},
+        err.downcast().unwrap()
+    );
+
+    // delegating without rights still works and the staker keeps full control
+    suite
+        .delegate_as(ESCROW, 1_000, SEVEN_DAYS, Some(BENEFICIARY))
+        .unwrap();
+    suite.unbond(BENEFICIARY, 1_000, SEVEN_DAYS).unwrap();
+    assert_eq!(suite.query_staked(BENEFICIARY, SEVEN_DAYS).unwrap(), 0);
+}
+
+#[test]
+fn rewards_reach_withdraw_to() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_initial_balances(vec![(ESCROW, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(ADMIN, 2_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+
+    // the escrow keeps the bond, but the rewards go to the beneficiary
+    suite
+        .delegate_with_rights(ESCROW, 10_000, SEVEN_DAYS, None, None, Some(BENEFICIARY))
+        .unwrap();
+
+    // only the escrow can unbond
+    let err = suite
+        .unbond_for(BENEFICIARY, Some(ESCROW), 1_000, SEVEN_DAYS)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite.unbond(ESCROW, 1_000, SEVEN_DAYS).unwrap();
+
+    suite
+        .distribute_funds(ADMIN, None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(ESCROW).unwrap(),
+        vec![juno(1_000)]
+    );
+
+    // even when the escrow withdraws to itself, the rewards reach the beneficiary
+    suite.withdraw_funds(ESCROW, None, ESCROW).unwrap();
+    assert_eq!(suite.query_balance(ESCROW, JUNO_DENOM).unwrap(), 0);
+    assert_eq!(suite.query_balance(BENEFICIARY, JUNO_DENOM).unwrap(), 1_000);
+
+    // the beneficiary can also trigger the withdrawal itself
+    suite
+        .distribute_funds(ADMIN, None, Some(juno(1_000)))
+        .unwrap();
+    let err = suite.withdraw_funds("random", ESCROW, None).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite.withdraw_funds(BENEFICIARY, ESCROW, None).unwrap();
+    assert_eq!(suite.query_balance(BENEFICIARY, JUNO_DENOM).unwrap(), 2_000);
+}
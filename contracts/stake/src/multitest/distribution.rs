@@ -1,4 +1,4 @@
-use cosmwasm_std::{assert_approx_eq, Addr, Decimal, Uint128};
+use cosmwasm_std::{assert_approx_eq, coin, Addr, Decimal, Event, Uint128};
 use cw20::{Cw20Coin, MinterResponse};
 use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
 use cw_multi_test::Executor;
@@ -7,7 +7,9 @@ use wyndex::stake::FundingInfo;
 
 use super::suite::{contract_token, SuiteBuilder};
 use crate::{
+    msg::{AnnualizedReward, ExecuteMsg, FlowAccountingResponse, FlowSchedule},
     multitest::suite::{juno, juno_power, native_token, JUNO_DENOM},
+    state::DEFAULT_MAX_REWARD_MULTIPLIER,
     ContractError,
 };
 
@@ -908,6 +910,85 @@ fn calculate_apr() {
     assert_eq!(annual_rewards[2].1[0].amount, Some(Decimal::zero()));
 }
 
+#[test]
+fn calculate_apr_at_timestamp() {
+    let distributor = "distributor";
+    let member = "member";
+    let unbonding_periods = vec![100u64, 1000u64];
+
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(unbonding_periods.clone())
+        .with_admin("admin")
+        .with_initial_balances(vec![(member, 200_000_000)])
+        .with_native_balances("juno", vec![(distributor, 1_000_000_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            distributor,
+            AssetInfo::Native("juno".to_string()),
+            vec![
+                (unbonding_periods[0], Decimal::one()),
+                (unbonding_periods[1], Decimal::percent(200)),
+            ],
+        )
+        .unwrap();
+    let start = suite.app.block_info().time.seconds();
+
+    // nothing staked, so there is no APR at any time
+    let annual_rewards = suite.query_annualized_rewards_at(start + 100).unwrap();
+    assert_eq!(annual_rewards[0].1[0].amount, None);
+
+    suite
+        .delegate(member, 100_000_000, unbonding_periods[0])
+        .unwrap();
+    suite
+        .delegate(member, 100_000_000, unbonding_periods[1])
+        .unwrap();
+
+    // 30 JUNO for 1 week, 300_000 reward points in total
+    suite
+        .execute_fund_distribution_curve(distributor, JUNO_DENOM, 30_000_000, 86400 * 7)
+        .unwrap();
+    // promille per unbonding period, to ignore rounding of the curve values
+    let promille = |rewards: Vec<(u64, Vec<AnnualizedReward>)>| {
+        rewards
+            .into_iter()
+            .map(|(_, rewards)| rewards[0].amount.unwrap() * Uint128::new(1000))
+            .collect::<Vec<_>>()
+    };
+    let current = promille(suite.query_annualized_rewards().unwrap());
+    assert_eq!(current, vec![Uint128::new(521), Uint128::new(1042)]);
+
+    // during the funding, the APR stays the same
+    assert_eq!(
+        promille(
+            suite
+                .query_annualized_rewards_at(start + 3 * 86_400)
+                .unwrap()
+        ),
+        current
+    );
+    // after the funding ended, there is nothing left to earn
+    let annual_rewards = suite
+        .query_annualized_rewards_at(start + 8 * 86_400)
+        .unwrap();
+    assert_eq!(annual_rewards[0].1[0].amount, Some(Decimal::zero()));
+    assert_eq!(annual_rewards[1].1[0].amount, Some(Decimal::zero()));
+
+    // past timestamps still see the funding after it ended
+    suite.update_time(8 * 86_400);
+    assert_eq!(
+        suite.query_annualized_rewards().unwrap()[0].1[0].amount,
+        Some(Decimal::zero())
+    );
+    assert_eq!(
+        promille(suite.query_annualized_rewards_at(start + 86_400).unwrap()),
+        current
+    );
+}
+
 #[test]
 fn apr_cw20() {
     let distributor = "distributor";
@@ -1969,6 +2050,67 @@ fn funds_withdrawal_delegation() {
     assert_eq!(suite.query_balance(&members[2], "juno").unwrap(), 0);
 }
 
+#[test]
+fn batch_withdrawal_of_delegated_rewards() {
+    let members = ["member1", "member2", "member3"];
+    let keeper = "keeper";
+    let unbonding_period = 1000u64;
+
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(vec![unbonding_period])
+        .with_min_bond(1000)
+        .with_admin("admin")
+        .with_native_balances("juno", vec![("executor", 600)])
+        .with_initial_balances(vec![
+            (members[0], 1_000u128),
+            (members[1], 2_000u128),
+            (members[2], 3_000u128),
+        ])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "admin",
+            AssetInfo::Native("juno".to_string()),
+            vec![(unbonding_period, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(members[0], 1_000, unbonding_period).unwrap();
+    suite.delegate(members[1], 2_000, unbonding_period).unwrap();
+    suite.delegate(members[2], 3_000, unbonding_period).unwrap();
+    suite
+        .distribute_funds("executor", None, Some(juno(600)))
+        .unwrap();
+
+    // member3 did not delegate withdrawal to the keeper, "unknown" has no rewards
+    suite.delegate_withdrawal(members[0], keeper).unwrap();
+    suite.delegate_withdrawal(members[1], keeper).unwrap();
+    suite
+        .withdraw_funds_batch(keeper, &[members[0], members[1], members[2], "unknown"])
+        .unwrap();
+
+    assert_eq!(suite.query_balance(members[0], "juno").unwrap(), 100);
+    assert_eq!(suite.query_balance(members[1], "juno").unwrap(), 200);
+    assert_eq!(suite.query_balance(members[2], "juno").unwrap(), 0);
+    assert_eq!(suite.query_balance(keeper, "juno").unwrap(), 0);
+    assert_eq!(
+        suite.withdrawable_rewards(members[2]).unwrap(),
+        vec![juno(300)]
+    );
+
+    // owners can always batch withdraw for themselves, already withdrawn owners are skipped
+    suite
+        .withdraw_funds_batch(members[2], &[members[0], members[2]])
+        .unwrap();
+    assert_eq!(suite.query_balance(members[0], "juno").unwrap(), 100);
+    assert_eq!(suite.query_balance(members[2], "juno").unwrap(), 300);
+    assert_eq!(
+        suite.withdrawable_rewards(members[0]).unwrap(),
+        vec![juno(0)]
+    );
+}
+
 #[test]
 fn querying_unknown_address() {
     let suite = SuiteBuilder::new().build();
@@ -2396,24 +2538,6 @@ fn multiple_rewards() {
     );
 }
 
-#[test]
-fn distribute_staking_token_should_fail() {
-    let executor = "executor";
-    let mut suite = SuiteBuilder::new().with_admin("admin").build();
-
-    // try to add staking token distribution
-    let err = suite
-        .create_distribution_flow(
-            "admin",
-            executor,
-            AssetInfo::Token(suite.token_contract()),
-            vec![],
-        )
-        .unwrap_err();
-
-    assert_eq!(ContractError::InvalidAsset {}, err.downcast().unwrap());
-}
-
 #[test]
 fn unbond_after_new_distribution() {
     let executor = "executor";
@@ -2527,3 +2651,1391 @@ fn withdraw_adjustment_handled_lazily() {
     // member should get rewards
     assert_eq!(suite.query_balance(member, "juno").unwrap(), 500);
 }
+
+#[test]
+fn total_to_be_distributed_projects_remaining_funding() {
+    let manager = "manager";
+    let member = "member";
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 100_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            manager,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(member, 10_000, 100).unwrap();
+
+    // nothing funded yet
+    let projected = suite
+        .query_total_to_be_distributed(AssetInfo::Native(JUNO_DENOM.to_string()))
+        .unwrap();
+    assert_eq!(projected.amount, juno(0));
+    assert_eq!(projected.end_time, None);
+
+    let start = suite.app.block_info().time.seconds();
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 100_000, 100)
+        .unwrap();
+    let projected = suite
+        .query_total_to_be_distributed(AssetInfo::Native(JUNO_DENOM.to_string()))
+        .unwrap();
+    assert_eq!(projected.amount, juno(100_000));
+    assert_eq!(projected.end_time, Some(start + 100));
+
+    // half of the curve is distributed
+    suite.update_time(50);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(suite.distributed_funds().unwrap(), vec![juno(50_000)]);
+    let projected = suite
+        .query_total_to_be_distributed(AssetInfo::Native(JUNO_DENOM.to_string()))
+        .unwrap();
+    assert_eq!(projected.amount, juno(100_000 - 50_000));
+
+    // withdrawing does not change the projection
+    suite.withdraw_funds(member, None, None).unwrap();
+    let projected = suite
+        .query_total_to_be_distributed(AssetInfo::Native(JUNO_DENOM.to_string()))
+        .unwrap();
+    assert_eq!(projected.amount, juno(50_000));
+
+    // once the flow fully emitted, nothing is left
+    suite.update_time(50);
+    suite.distribute_funds(manager, None, None).unwrap();
+    let projected = suite
+        .query_total_to_be_distributed(AssetInfo::Native(JUNO_DENOM.to_string()))
+        .unwrap();
+    assert_eq!(projected.amount, juno(0));
+
+    // unknown assets have no distribution flow
+    suite
+        .query_total_to_be_distributed(AssetInfo::Native("unknown".to_string()))
+        .unwrap_err();
+}
+
+#[test]
+fn next_reward_change_is_next_curve_segment_boundary() {
+    let manager = "manager";
+    let member = "member";
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 2_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            manager,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+
+    // no stake, so nothing changes for the member
+    assert_eq!(suite.query_next_reward_change(member).unwrap(), None);
+    suite.delegate(member, 10_000, 100).unwrap();
+    // not funded yet
+    assert_eq!(suite.query_next_reward_change(member).unwrap(), None);
+
+    // two overlapping fundings make a piecewise curve:
+    // first from start to start + 100, second from start + 50 to start + 150
+    let start = suite.app.block_info().time.seconds();
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    assert_eq!(
+        suite.query_next_reward_change(member).unwrap(),
+        Some(start + 100)
+    );
+    suite.update_time(50);
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    assert_eq!(
+        suite.query_next_reward_change(member).unwrap(),
+        Some(start + 100)
+    );
+
+    // after the first funding ends, the next change is the end of the second one
+    suite.update_time(60);
+    assert_eq!(
+        suite.query_next_reward_change(member).unwrap(),
+        Some(start + 150)
+    );
+
+    // everything is unlocked
+    suite.update_time(40);
+    assert_eq!(suite.query_next_reward_change(member).unwrap(), None);
+}
+
+#[test]
+fn rate_at_is_slope_of_reward_curve() {
+    let manager = "manager";
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow("admin", manager, asset.clone(), vec![(100, Decimal::one())])
+        .unwrap();
+    let start = suite.app.block_info().time.seconds();
+    assert_eq!(
+        suite.query_rate_at(asset.clone(), start).unwrap(),
+        Decimal::zero()
+    );
+
+    // 1_000 tokens over 100 seconds
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    assert_eq!(
+        suite.query_rate_at(asset.clone(), start).unwrap(),
+        Decimal::from_atomics(10u128, 0).unwrap()
+    );
+    assert_eq!(
+        suite.query_rate_at(asset.clone(), start + 99).unwrap(),
+        Decimal::from_atomics(10u128, 0).unwrap()
+    );
+    // nothing is released after the end
+    assert_eq!(
+        suite.query_rate_at(asset.clone(), start + 100).unwrap(),
+        Decimal::zero()
+    );
+    assert_eq!(
+        suite.query_rate_at(asset, start + 1_000).unwrap(),
+        Decimal::zero()
+    );
+}
+
+#[test]
+fn fund_multiple_distributions_at_once() {
+    let funder = "funder";
+    let member = "member";
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(funder, 1_000)])
+        .with_native_balances("luna", vec![(funder, 2_000)])
+        .build();
+
+    for denom in [JUNO_DENOM, "luna"] {
+        suite
+            .create_distribution_flow(
+                "admin",
+                funder,
+                AssetInfo::Native(denom.to_string()),
+                vec![(100, Decimal::one())],
+            )
+            .unwrap();
+    }
+    suite.delegate(member, 10_000, 100).unwrap();
+
+    let start_time = suite.app.block_info().time.seconds();
+    let fundings = vec![
+        (
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            FundingInfo {
+                start_time,
+                distribution_duration: 100,
+                amount: Uint128::new(1_000),
+            },
+        ),
+        (
+            AssetInfo::Native("luna".to_string()),
+            FundingInfo {
+                start_time,
+                distribution_duration: 200,
+                amount: Uint128::new(2_000),
+            },
+        ),
+    ];
+
+    // sent funds have to match the fundings exactly
+    let err = suite
+        .execute_fund_distribution_multi(
+            funder,
+            fundings.clone(),
+            &[coin(1_000, JUNO_DENOM), coin(1_000, "luna")],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::FundingMismatch(AssetInfoValidated::Native("luna".to_string())),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .execute_fund_distribution_multi(
+            funder,
+            fundings[..1].to_vec(),
+            &[coin(1_000, JUNO_DENOM), coin(2_000, "luna")],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::FundingMismatch(AssetInfoValidated::Native("luna".to_string())),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .execute_fund_distribution_multi(
+            funder,
+            fundings,
+            &[coin(1_000, JUNO_DENOM), coin(2_000, "luna")],
+        )
+        .unwrap();
+
+    // both curves release the same amount per second for the first 100 seconds
+    suite.update_time(50);
+    suite.distribute_funds(funder, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(500), native_token("luna".to_string(), 500)]
+    );
+
+    // the juno curve is done after 100 seconds, the luna one after 200
+    suite.update_time(50);
+    suite.distribute_funds(funder, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(1_000), native_token("luna".to_string(), 1_000)]
+    );
+    suite.update_time(100);
+    suite.distribute_funds(funder, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(1_000), native_token("luna".to_string(), 2_000)]
+    );
+}
+
+#[test]
+fn pending_distribution_matches_distributed_amount() {
+    let manager = "manager";
+    let member = "member";
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            manager,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+
+    // no stake yet, so nothing would be distributed
+    suite.update_time(10);
+    assert_eq!(suite.pending_distribution().unwrap(), vec![juno(0)]);
+
+    suite.delegate(member, 10_000, 100).unwrap();
+    assert_eq!(suite.pending_distribution().unwrap(), vec![juno(100)]);
+
+    suite.update_time(30);
+    assert_eq!(suite.pending_distribution().unwrap(), vec![juno(400)]);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(suite.withdrawable_rewards(member).unwrap(), vec![juno(400)]);
+    assert_eq!(suite.pending_distribution().unwrap(), vec![juno(0)]);
+
+    // the rest is pending once the curve is done
+    suite.update_time(100);
+    assert_eq!(suite.pending_distribution().unwrap(), vec![juno(600)]);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(1_000)]
+    );
+    assert_eq!(suite.pending_distribution().unwrap(), vec![juno(0)]);
+}
+
+#[test]
+fn flow_accounting_over_lifecycle() {
+    let manager = "manager";
+    let members = ["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(members[0], 10_000), (members[1], 20_000)])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 1_000)])
+        .build();
+    let juno_info = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            manager,
+            juno_info.clone(),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(members[0], 10_000, 100).unwrap();
+    suite.delegate(members[1], 20_000, 100).unwrap();
+
+    let check_sums = |accounting: &FlowAccountingResponse| {
+        assert_eq!(
+            accounting.funded_total,
+            accounting.distributed_total + accounting.undistributed
+        );
+        assert_eq!(
+            accounting.distributed_total,
+            accounting.withdrawn_total + accounting.withdrawable_outstanding + accounting.leftover
+        );
+    };
+
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    let accounting = suite.query_flow_accounting(juno_info.clone()).unwrap();
+    check_sums(&accounting);
+    assert_eq!(accounting.funded_total.u128(), 1_000);
+    assert_eq!(accounting.undistributed.u128(), 1_000);
+    assert_eq!(accounting.distributed_total.u128(), 0);
+
+    suite.update_time(40);
+    suite.distribute_funds(manager, None, None).unwrap();
+    let accounting = suite.query_flow_accounting(juno_info.clone()).unwrap();
+    check_sums(&accounting);
+    assert_eq!(accounting.distributed_total.u128(), 400);
+    assert_eq!(accounting.undistributed.u128(), 600);
+    assert_eq!(accounting.withdrawn_total.u128(), 0);
+
+    // one of the members withdraws
+    suite.withdraw_funds(members[0], None, None).unwrap();
+    let withdrawn = suite.query_balance(members[0], JUNO_DENOM).unwrap();
+    assert_eq!(withdrawn, 133);
+    let accounting = suite.query_flow_accounting(juno_info.clone()).unwrap();
+    check_sums(&accounting);
+    assert_eq!(accounting.withdrawn_total.u128(), withdrawn);
+    assert_eq!(accounting.withdrawable_outstanding.u128(), 400 - withdrawn);
+
+    // everything distributed and withdrawn
+    suite.update_time(60);
+    suite.distribute_funds(manager, None, None).unwrap();
+    suite.withdraw_funds(members[0], None, None).unwrap();
+    suite.withdraw_funds(members[1], None, None).unwrap();
+    let accounting = suite.query_flow_accounting(juno_info).unwrap();
+    check_sums(&accounting);
+    assert_eq!(accounting.distributed_total.u128(), 1_000);
+    assert_eq!(accounting.undistributed.u128(), 0);
+    assert_eq!(
+        accounting.withdrawn_total.u128(),
+        suite.query_balance(members[0], JUNO_DENOM).unwrap()
+            + suite.query_balance(members[1], JUNO_DENOM).unwrap()
+    );
+}
+
+#[test]
+fn user_multipliers_per_held_period() {
+    let member = "member";
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100, 200, 300])
+        .with_initial_balances(vec![(member, 10_000)])
+        .build();
+    let juno_info = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            juno_info,
+            vec![
+                (100, Decimal::percent(50)),
+                (200, Decimal::one()),
+                (300, Decimal::percent(300)),
+            ],
+        )
+        .unwrap();
+
+    // nothing staked yet, so no multipliers apply
+    assert_eq!(suite.query_user_multipliers(member).unwrap(), vec![]);
+
+    suite.delegate(member, 4_000, 100).unwrap();
+    suite.delegate(member, 6_000, 200).unwrap();
+
+    let juno = AssetInfoValidated::Native(JUNO_DENOM.to_string());
+    assert_eq!(
+        suite.query_user_multipliers(member).unwrap(),
+        vec![
+            (juno.clone(), 100, Decimal::percent(50)),
+            (juno, 200, Decimal::one()),
+        ]
+    );
+}
+
+#[test]
+fn distribution_preview_matches_distribution() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100, 200])
+        .with_initial_balances(vec![("alice", 10_000), ("bob", 10_000), ("carol", 20_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 1_000)])
+        .build();
+    let juno_info = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            juno_info.clone(),
+            vec![(100, Decimal::one()), (200, Decimal::percent(200))],
+        )
+        .unwrap();
+
+    // nobody staked yet, so nothing would be distributed
+    assert_eq!(
+        suite
+            .query_distribution_preview(juno_info.clone(), 1_000)
+            .unwrap(),
+        vec![(100, Uint128::zero()), (200, Uint128::zero())]
+    );
+
+    suite.delegate("alice", 10_000, 100).unwrap();
+    suite.delegate("bob", 10_000, 200).unwrap();
+    suite.delegate("carol", 20_000, 200).unwrap();
+
+    // powers are 10 for period 100 and (10 + 20) * 2 = 60 for period 200
+    let preview = suite
+        .query_distribution_preview(juno_info.clone(), 700)
+        .unwrap();
+    assert_eq!(
+        preview,
+        vec![(100, Uint128::new(100)), (200, Uint128::new(600))]
+    );
+
+    suite
+        .distribute_funds("funder", None, Some(juno(700)))
+        .unwrap();
+
+    let alice = suite.withdrawable_rewards("alice").unwrap();
+    let bob = suite.withdrawable_rewards("bob").unwrap();
+    let carol = suite.withdrawable_rewards("carol").unwrap();
+    assert_eq!(alice, vec![juno(preview[0].1.u128())]);
+    assert_eq!(
+        bob[0].amount + carol[0].amount,
+        preview[1].1,
+        "period 200 stakers should collectively receive the previewed amount"
+    );
+}
+
+#[test]
+fn withdrawable_at_projects_curve_release() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![("alice", 10_000), ("bob", 30_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate("alice", 10_000, 100).unwrap();
+    suite.delegate("bob", 30_000, 100).unwrap();
+
+    // release 1000 juno linearly over 100 seconds
+    suite
+        .execute_fund_distribution_curve("funder", JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    let now = suite.app.block_info().time.seconds();
+
+    // nothing released yet
+    assert_eq!(
+        suite.withdrawable_rewards_at("alice", now).unwrap(),
+        vec![juno(0)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards_at("alice", now + 40).unwrap(),
+        vec![juno(100)]
+    );
+    // everything is released after the curve ends
+    assert_eq!(
+        suite.withdrawable_rewards_at("bob", now + 1_000).unwrap(),
+        vec![juno(750)]
+    );
+
+    // the projection matches what is withdrawable after the time passed
+    suite.update_time(40);
+    suite.distribute_funds("funder", None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(100)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards_at("alice", now + 40).unwrap(),
+        vec![juno(100)]
+    );
+    // times in the past are treated as now
+    assert_eq!(
+        suite.withdrawable_rewards_at("alice", now).unwrap(),
+        vec![juno(100)]
+    );
+}
+
+#[test]
+fn distribute_within_min_interval_is_noop() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![("alice", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate("alice", 10_000, 100).unwrap();
+
+    // only the admin can set the interval
+    let err = suite.set_min_distribute_interval("alice", 50).unwrap_err();
+    assert_eq!(
+        ContractError::Admin(cw_controllers::AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    suite.set_min_distribute_interval("admin", 50).unwrap();
+
+    suite
+        .distribute_funds("funder", None, Some(juno(400)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(400)]
+    );
+
+    // the second distribution within the interval does nothing
+    suite.update_time(30);
+    let res = suite
+        .distribute_funds("funder", None, Some(juno(600)))
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .flat_map(|e| &e.attributes)
+        .any(|a| a.key == "skipped" && a.value == "min_distribute_interval"));
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(400)]
+    );
+
+    // once the interval passed, the skipped rewards are distributed as well
+    suite.update_time(20);
+    suite.distribute_funds("funder", None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(1_000)]
+    );
+}
+
+#[test]
+fn distribute_with_attached_funds() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![("alice", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate("alice", 10_000, 100).unwrap();
+    suite.set_min_distribute_interval("admin", 50).unwrap();
+
+    // funds sent along are distributed in the same message
+    suite
+        .distribute_with_funds("funder", &[coin(400, JUNO_DENOM)])
+        .unwrap();
+    assert_eq!(suite.query_balance("funder", JUNO_DENOM).unwrap(), 600);
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(400)]
+    );
+
+    // even within the minimum distribution interval
+    suite.update_time(10);
+    suite
+        .distribute_with_funds("funder", &[coin(600, JUNO_DENOM)])
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(1_000)]
+    );
+    assert_eq!(suite.query_balance("funder", JUNO_DENOM).unwrap(), 0);
+}
+
+#[test]
+fn attached_funds_do_not_release_other_flows_early() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![("alice", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 1_000)])
+        .with_native_balances("uusd", vec![("funder", 1_000)])
+        .build();
+
+    for denom in [JUNO_DENOM, "uusd"] {
+        suite
+            .create_distribution_flow(
+                "admin",
+                "manager",
+                AssetInfo::Native(denom.to_string()),
+                vec![(100, Decimal::one())],
+            )
+            .unwrap();
+    }
+    suite.delegate("alice", 10_000, 100).unwrap();
+    suite.set_min_distribute_interval("admin", 50).unwrap();
+
+    suite
+        .distribute_with_funds("funder", &[coin(400, JUNO_DENOM)])
+        .unwrap();
+
+    // uusd is sent without a distribution, juno is attached to one within the interval
+    suite.update_time(10);
+    suite
+        .distribute_funds("funder", None, Some(native_token("uusd".to_owned(), 500)))
+        .unwrap();
+    suite
+        .distribute_with_funds("funder", &[coin(600, JUNO_DENOM)])
+        .unwrap();
+
+    // only the attached juno is distributed, the uusd still waits for the interval
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(1_000), native_token("uusd".to_owned(), 0)]
+    );
+
+    suite.update_time(40);
+    suite.distribute_funds("funder", None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(1_000), native_token("uusd".to_owned(), 500)]
+    );
+}
+
+#[test]
+fn min_power_to_earn_excludes_small_stakers() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![("alice", 6_000), ("bob", 8_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 2_400)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    // alice has a rewards power of 2, bob of 8
+    suite.delegate("alice", 2_000, 100).unwrap();
+    suite.delegate("bob", 8_000, 100).unwrap();
+
+    // only the manager can set the threshold
+    let err = suite
+        .set_min_power_to_earn("admin", AssetInfo::Native(JUNO_DENOM.to_string()), 5)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .set_min_power_to_earn("manager", AssetInfo::Native(JUNO_DENOM.to_string()), 5)
+        .unwrap();
+    suite
+        .apply_distribution_update("anyone", AssetInfo::Native(JUNO_DENOM.to_string()), None)
+        .unwrap();
+
+    // alice is below the threshold, so bob gets everything
+    suite
+        .distribute_funds("funder", None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards("alice").unwrap(), vec![juno(0)]);
+    assert_eq!(
+        suite.withdrawable_rewards("bob").unwrap(),
+        vec![juno(1_000)]
+    );
+
+    // once alice crosses the threshold, she earns as well
+    suite.delegate("alice", 4_000, 100).unwrap();
+    suite
+        .distribute_funds("funder", None, Some(juno(1_400)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(600)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards("bob").unwrap(),
+        vec![juno(1_800)]
+    );
+}
+
+#[test]
+fn paused_flow_halts_while_others_continue() {
+    let manager = "manager";
+    let member = "member";
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 2_000)])
+        .with_native_balances("luna", vec![(manager, 1_000)])
+        .build();
+    let juno_info = AssetInfo::Native(JUNO_DENOM.to_string());
+    let luna_info = AssetInfo::Native("luna".to_string());
+
+    for asset in [juno_info.clone(), luna_info.clone()] {
+        suite
+            .create_distribution_flow("admin", manager, asset, vec![(100, Decimal::one())])
+            .unwrap();
+    }
+    suite.delegate(member, 10_000, 100).unwrap();
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    suite
+        .execute_fund_distribution_curve(manager, "luna", 1_000, 100)
+        .unwrap();
+
+    suite.update_time(50);
+    suite.distribute_funds(manager, None, None).unwrap();
+
+    // only the manager or the admin can pause a flow
+    let err = suite
+        .set_distribution_paused(member, juno_info.clone(), true)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .set_distribution_paused("admin", juno_info.clone(), true)
+        .unwrap();
+
+    // the paused flow cannot be funded
+    let err = suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DistributionPaused(AssetInfoValidated::Native(JUNO_DENOM.to_string())),
+        err.downcast().unwrap()
+    );
+    let stake_contract = Addr::unchecked(suite.stake_contract());
+    let err = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(manager),
+            stake_contract,
+            &ExecuteMsg::DistributeRewards { sender: None },
+            &[coin(1_000, JUNO_DENOM)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DistributionPaused(AssetInfoValidated::Native(JUNO_DENOM.to_string())),
+        err.downcast().unwrap()
+    );
+
+    // nor distributed, while the other flow carries on
+    suite.update_time(50);
+    assert_eq!(
+        suite.pending_distribution().unwrap(),
+        vec![juno(0), native_token("luna".to_string(), 500)]
+    );
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(500), native_token("luna".to_string(), 1_000)]
+    );
+
+    // the rewards earned before the pause can still be withdrawn
+    suite.withdraw_funds(member, None, None).unwrap();
+    assert_eq!(suite.query_balance(member, JUNO_DENOM).unwrap(), 500);
+    assert_eq!(suite.query_balance(member, "luna").unwrap(), 1_000);
+
+    // once resumed, the reward curve continues where it was paused,
+    // so nothing is released for the paused time
+    suite
+        .set_distribution_paused(manager, juno_info, false)
+        .unwrap();
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(0), native_token("luna".to_string(), 0)]
+    );
+    suite.update_time(50);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(500), native_token("luna".to_string(), 0)]
+    );
+}
+
+#[test]
+fn paused_flow_does_not_accrue_rewards() {
+    let manager = "manager";
+    let member = "member";
+    let juno_info = AssetInfo::Native(JUNO_DENOM.to_string());
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            manager,
+            juno_info.clone(),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(member, 10_000, 100).unwrap();
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+
+    // only the manager can pause and resume
+    let err = suite
+        .pause_distribution("admin", juno_info.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .resume_distribution(manager, juno_info.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DistributionNotPaused(AssetInfoValidated::Native(JUNO_DENOM.to_string())),
+        err.downcast().unwrap()
+    );
+
+    suite.update_time(20);
+    suite
+        .pause_distribution(manager, juno_info.clone())
+        .unwrap();
+    let err = suite
+        .pause_distribution(manager, juno_info.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DistributionPaused(AssetInfoValidated::Native(JUNO_DENOM.to_string())),
+        err.downcast().unwrap()
+    );
+
+    // distributing while paused does nothing
+    suite.update_time(50);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(suite.withdrawable_rewards(member).unwrap(), vec![juno(0)]);
+
+    // after resuming, only what was released before the pause is distributed
+    suite
+        .resume_distribution(manager, juno_info.clone())
+        .unwrap();
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(suite.withdrawable_rewards(member).unwrap(), vec![juno(200)]);
+    let now = suite.app.block_info().time.seconds();
+    assert_eq!(
+        suite
+            .query_total_to_be_distributed(juno_info)
+            .unwrap()
+            .end_time,
+        Some(now + 80)
+    );
+
+    // and the curve continues where it was paused
+    suite.update_time(30);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(suite.withdrawable_rewards(member).unwrap(), vec![juno(500)]);
+    suite.update_time(50);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(1_000)]
+    );
+}
+
+#[test]
+fn reward_multipliers_are_capped() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100, 200])
+        .build();
+    let max = Decimal::percent(DEFAULT_MAX_REWARD_MULTIPLIER * 100);
+
+    // exactly at the maximum is fine
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one()), (200, max)],
+        )
+        .unwrap();
+
+    // above it is rejected
+    let above = max + Decimal::percent(1);
+    let err = suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native("luna".to_string()),
+            vec![(100, Decimal::one()), (200, above)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::RewardMultiplierTooHigh {
+            multiplier: above,
+            max
+        },
+        err.downcast().unwrap()
+    );
+
+    // only the admin can raise the cap
+    let err = suite
+        .set_max_reward_multiplier("manager", above)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(cw_controllers::AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    suite.set_max_reward_multiplier("admin", above).unwrap();
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native("luna".to_string()),
+            vec![(100, Decimal::one()), (200, above)],
+        )
+        .unwrap();
+}
+
+#[test]
+fn withdrawable_rewards_split_by_unbonding_period() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100, 200])
+        .with_initial_balances(vec![("alice", 20_000), ("bob", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one()), (200, Decimal::percent(200))],
+        )
+        .unwrap();
+    suite.delegate("alice", 10_000, 100).unwrap();
+    suite.delegate("alice", 10_000, 200).unwrap();
+    suite.delegate("bob", 10_000, 100).unwrap();
+
+    // 40 power in total, alice has 10 in the short and 20 in the long period
+    suite
+        .distribute_funds("funder", None, Some(juno(1_000)))
+        .unwrap();
+    let detailed = suite.withdrawable_rewards_detailed("alice").unwrap();
+    assert_eq!(detailed.len(), 1);
+    assert_eq!(detailed[0].total, juno(750));
+    assert_eq!(
+        detailed[0].per_period,
+        vec![(100, Uint128::new(250)), (200, Uint128::new(500))]
+    );
+    // the sum matches the flat query
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(750)]
+    );
+
+    let detailed = suite.withdrawable_rewards_detailed("bob").unwrap();
+    assert_eq!(detailed[0].total, juno(250));
+    assert_eq!(detailed[0].per_period, vec![(100, Uint128::new(250))]);
+
+    // nothing left to split after withdrawing
+    suite.withdraw_funds("alice", None, None).unwrap();
+    let detailed = suite.withdrawable_rewards_detailed("alice").unwrap();
+    assert_eq!(detailed[0].total, juno(0));
+    assert_eq!(
+        detailed[0].per_period,
+        vec![(100, Uint128::zero()), (200, Uint128::zero())]
+    );
+}
+
+#[test]
+fn lifetime_funded_sums_all_fundings() {
+    let manager = "manager";
+    let member = "member";
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 2_000)])
+        .build();
+
+    suite
+        .create_distribution_flow("admin", manager, asset, vec![(100, Decimal::one())])
+        .unwrap();
+    assert_eq!(suite.query_lifetime_funded().unwrap(), vec![juno(0)]);
+    suite.delegate(member, 10_000, 100).unwrap();
+
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 500, 100)
+        .unwrap();
+    suite
+        .distribute_with_funds(manager, &[coin(300, JUNO_DENOM)])
+        .unwrap();
+    assert_eq!(suite.query_lifetime_funded().unwrap(), vec![juno(1_800)]);
+
+    // distributing and withdrawing does not change it
+    suite.update_time(100);
+    suite.distribute_funds(manager, None, None).unwrap();
+    suite.withdraw_funds(member, None, None).unwrap();
+    assert_eq!(suite.query_lifetime_funded().unwrap(), vec![juno(1_800)]);
+
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 200, 100)
+        .unwrap();
+    assert_eq!(suite.query_lifetime_funded().unwrap(), vec![juno(2_000)]);
+}
+
+#[test]
+fn distribute_rewards_emits_event_per_asset() {
+    let members = ["member1", "member2"];
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(members[0], 1_000), (members[1], 3_000)])
+        .with_min_bond(1_000)
+        .with_native_balances(JUNO_DENOM, vec![("executor", 800)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(members[0], 1_000, 100).unwrap();
+    suite.delegate(members[1], 3_000, 100).unwrap();
+
+    let resp = suite
+        .distribute_funds("executor", None, Some(juno(400)))
+        .unwrap();
+    resp.assert_event(
+        &Event::new("wasm-rewards_distributed")
+            .add_attribute("asset", JUNO_DENOM)
+            .add_attribute("amount", "400")
+            .add_attribute("distributed_total", "400")
+            .add_attribute("total_rewards_power", "4"),
+    );
+
+    let resp = suite
+        .distribute_funds("executor", None, Some(juno(400)))
+        .unwrap();
+    resp.assert_event(
+        &Event::new("wasm-rewards_distributed")
+            .add_attribute("asset", JUNO_DENOM)
+            .add_attribute("amount", "400")
+            .add_attribute("distributed_total", "800")
+            .add_attribute("total_rewards_power", "4"),
+    );
+}
+
+#[test]
+fn flows_schedule_lists_all_flows() {
+    let manager = "manager";
+    let member = "member";
+    let denoms = ["juno", "luna", "usdc"];
+    let mut builder = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)]);
+    for denom in denoms {
+        builder = builder.with_native_balances(denom, vec![(manager, 1_000)]);
+    }
+    let mut suite = builder.build();
+
+    for denom in denoms {
+        suite
+            .create_distribution_flow(
+                "admin",
+                manager,
+                AssetInfo::Native(denom.to_string()),
+                vec![(100, Decimal::one())],
+            )
+            .unwrap();
+    }
+    suite.delegate(member, 10_000, 100).unwrap();
+
+    let start = suite.app.block_info().time.seconds();
+    suite
+        .execute_fund_distribution_curve(manager, "juno", 1_000, 100)
+        .unwrap();
+
+    // half of the juno funding is distributed before luna is funded
+    suite.update_time(50);
+    suite.distribute_funds(manager, None, None).unwrap();
+    suite
+        .execute_fund_distribution_curve(manager, "luna", 500, 300)
+        .unwrap();
+
+    assert_eq!(
+        suite.query_flows_schedule().unwrap(),
+        vec![
+            FlowSchedule {
+                asset: AssetInfoValidated::Native("juno".to_string()),
+                curve_end: Some(start + 100),
+                undistributed: Uint128::new(500),
+            },
+            FlowSchedule {
+                asset: AssetInfoValidated::Native("luna".to_string()),
+                curve_end: Some(start + 350),
+                undistributed: Uint128::new(500),
+            },
+            FlowSchedule {
+                asset: AssetInfoValidated::Native("usdc".to_string()),
+                curve_end: None,
+                undistributed: Uint128::zero(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn recall_undistributed_only_takes_locked_funds() {
+    let manager = "manager";
+    let member = "member";
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(manager, 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow("admin", manager, asset.clone(), vec![(100, Decimal::one())])
+        .unwrap();
+    suite.delegate(member, 10_000, 100).unwrap();
+
+    // fund 1000 instead of 400 over 100 seconds
+    suite
+        .execute_fund_distribution_curve(manager, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    let start = suite.app.block_info().time.seconds();
+    assert_eq!(
+        suite.query_rate_at(asset.clone(), start).unwrap(),
+        Decimal::from_atomics(10u128, 0).unwrap()
+    );
+
+    // only the manager can recall
+    let err = suite
+        .recall_undistributed(member, asset.clone(), 600)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // recall the over-funded part before anything is distributed
+    suite
+        .recall_undistributed(manager, asset.clone(), 600)
+        .unwrap();
+    assert_eq!(suite.query_balance(manager, JUNO_DENOM).unwrap(), 600);
+    assert_eq!(
+        suite.query_rate_at(asset.clone(), start).unwrap(),
+        Decimal::from_atomics(4u128, 0).unwrap()
+    );
+    assert_eq!(suite.query_lifetime_funded().unwrap(), vec![juno(400)]);
+
+    // rewards released since then can not be recalled anymore
+    suite.update_time(50);
+    let err = suite
+        .recall_undistributed(manager, asset.clone(), 300)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotEnoughLocked {
+            available: Uint128::new(200)
+        },
+        err.downcast().unwrap()
+    );
+    // recalling the rest of the schedule leaves the released rewards to the stakers
+    suite
+        .recall_undistributed(manager, asset.clone(), 100)
+        .unwrap();
+    assert_eq!(suite.query_balance(manager, JUNO_DENOM).unwrap(), 700);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(suite.withdrawable_rewards(member).unwrap(), vec![juno(200)]);
+
+    // the remaining schedule is halved
+    let now = suite.app.block_info().time.seconds();
+    assert_eq!(
+        suite.query_rate_at(asset, now).unwrap(),
+        Decimal::from_atomics(2u128, 0).unwrap()
+    );
+    suite.update_time(50);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(suite.withdrawable_rewards(member).unwrap(), vec![juno(300)]);
+    assert_eq!(suite.query_lifetime_funded().unwrap(), vec![juno(300)]);
+}
+
+#[test]
+fn recall_undistributed_refunds_the_over_funder() {
+    let manager = "manager";
+    let member = "member";
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("short", 1_000), ("long", 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow("admin", manager, asset.clone(), vec![(100, Decimal::one())])
+        .unwrap();
+    suite.delegate(member, 10_000, 100).unwrap();
+
+    // both fund the same amount, but "long" spreads it over a longer time
+    suite
+        .execute_fund_distribution_curve("short", JUNO_DENOM, 1_000, 50)
+        .unwrap();
+    suite
+        .execute_fund_distribution_curve("long", JUNO_DENOM, 1_000, 200)
+        .unwrap();
+
+    // all of "short"'s funding is released already, so only "long" gets something back
+    suite.update_time(50);
+    suite
+        .recall_undistributed(manager, asset.clone(), 500)
+        .unwrap();
+    assert_eq!(suite.query_balance("short", JUNO_DENOM).unwrap(), 0);
+    assert_eq!(suite.query_balance("long", JUNO_DENOM).unwrap(), 500);
+
+    // the stakers keep everything released so far and the rest of the schedule
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(1_250)]
+    );
+    suite.update_time(150);
+    suite.distribute_funds(manager, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(member).unwrap(),
+        vec![juno(1_500)]
+    );
+
+    // nothing is locked anymore
+    let err = suite.recall_undistributed(manager, asset, 1).unwrap_err();
+    assert_eq!(
+        ContractError::NotEnoughLocked {
+            available: Uint128::zero()
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn closing_flow_funded_on_behalf_of_treasury_refunds_treasury() {
+    let manager = "manager";
+    let member = "member";
+    let executor = "executor";
+    let treasury = "treasury";
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(member, 10_000)])
+        .build();
+
+    let token_id = suite.app.store_code(contract_token());
+    let wynd_token = suite
+        .app
+        .instantiate_contract(
+            token_id,
+            Addr::unchecked("admin"),
+            &Cw20InstantiateMsg {
+                name: "wynd-token".to_owned(),
+                symbol: "WYND".to_owned(),
+                decimals: 9,
+                initial_balances: vec![Cw20Coin {
+                    address: executor.to_owned(),
+                    amount: Uint128::new(1_000),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "wynd",
+            None,
+        )
+        .unwrap();
+    let asset = AssetInfo::Token(wynd_token.to_string());
+
+    suite
+        .create_distribution_flow("admin", manager, asset.clone(), vec![(100, Decimal::one())])
+        .unwrap();
+    suite.delegate(member, 10_000, 100).unwrap();
+
+    // the executing contract funds the flow on behalf of the treasury
+    let start_time = suite.app.block_info().time.seconds();
+    suite
+        .execute_fund_distribution_with_cw20_on_behalf_of(
+            executor,
+            AssetInfoValidated::Token(wynd_token.clone()).with_balance(1_000u128),
+            FundingInfo {
+                start_time,
+                distribution_duration: 100,
+                amount: Uint128::new(1_000),
+            },
+            treasury,
+        )
+        .unwrap();
+    assert_eq!(suite.query_cw20_balance(executor, &wynd_token).unwrap(), 0);
+
+    // the flow is closed halfway through, the rest of the funding goes back to the treasury
+    suite.update_time(50);
+    suite
+        .recall_undistributed(manager, asset.clone(), 500)
+        .unwrap();
+    assert_eq!(
+        suite.query_cw20_balance(treasury, &wynd_token).unwrap(),
+        500
+    );
+    assert_eq!(suite.query_cw20_balance(executor, &wynd_token).unwrap(), 0);
+
+    suite.distribute_funds(manager, None, None).unwrap();
+    suite.withdraw_funds(member, None, None).unwrap();
+    assert_eq!(suite.query_cw20_balance(member, &wynd_token).unwrap(), 500);
+    suite.remove_distribution_flow(manager, asset).unwrap();
+}
@@ -0,0 +1,51 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+
+#[test]
+fn earned_since_withdraw_only_counts_new_rewards() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_initial_balances(vec![("staker", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("distributor", 1_400)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate("staker", 10_000, SEVEN_DAYS).unwrap();
+
+    // without any withdrawal, everything ever earned counts
+    suite
+        .distribute_funds("distributor", None, Some(juno(1_000)))
+        .unwrap();
+    let res = suite.query_earned_since_withdraw("staker").unwrap();
+    assert_eq!(res.last_withdraw, None);
+    assert_eq!(res.earned, vec![juno(1_000)]);
+
+    suite.update_time(100);
+    suite.withdraw_funds("staker", None, None).unwrap();
+    let withdrawn_at = suite.app.block_info().time.seconds();
+    let res = suite.query_earned_since_withdraw("staker").unwrap();
+    assert_eq!(res.last_withdraw, Some(withdrawn_at));
+    assert_eq!(res.earned, vec![juno(0)]);
+
+    // only the new accrual counts, not the lifetime total
+    suite.update_time(100);
+    suite
+        .distribute_funds("distributor", None, Some(juno(400)))
+        .unwrap();
+    let res = suite.query_earned_since_withdraw("staker").unwrap();
+    assert_eq!(res.last_withdraw, Some(withdrawn_at));
+    assert_eq!(res.earned, vec![juno(400)]);
+    assert_eq!(
+        suite.withdrawable_rewards("staker").unwrap(),
+        vec![juno(400)]
+    );
+}
@@ -0,0 +1,137 @@
+use cosmwasm_std::{Decimal, Uint128};
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::{contract::MAX_EXIT_PERIODS, ContractError};
+
+const USER: &str = "user";
+const OTHER: &str = "other";
+const DISTRIBUTOR: &str = "distributor";
+const UNBONDING_PERIODS: &[u64; 2] = &[SEVEN_DAYS, 2 * SEVEN_DAYS];
+
+#[test]
+fn exit_all_withdraws_rewards_and_unbonds_every_period() {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![(USER, 30_000), (OTHER, 10_000)])
+        .with_admin("admin")
+        .with_native_balances(JUNO_DENOM, vec![(DISTRIBUTOR, 400)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![
+                (UNBONDING_PERIODS[0], Decimal::one()),
+                (UNBONDING_PERIODS[1], Decimal::one()),
+            ],
+        )
+        .unwrap();
+
+    suite.delegate(USER, 10_000, UNBONDING_PERIODS[0]).unwrap();
+    suite.delegate(USER, 20_000, UNBONDING_PERIODS[1]).unwrap();
+    suite.delegate(OTHER, 10_000, UNBONDING_PERIODS[0]).unwrap();
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(400)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(USER).unwrap(), vec![juno(300)]);
+
+    suite.exit_all(USER).unwrap();
+
+    // rewards were paid out
+    assert_eq!(suite.query_balance(USER, JUNO_DENOM).unwrap(), 300);
+    assert_eq!(suite.withdrawable_rewards(USER).unwrap(), vec![juno(0)]);
+
+    // all stake was moved to claims
+    for stake in suite.query_all_staked(USER).unwrap().stakes {
+        assert_eq!(stake.stake, Uint128::zero());
+    }
+    let claims = suite.query_claims(USER).unwrap();
+    assert_eq!(claims.len(), 2);
+    assert_eq!(
+        claims.iter().map(|c| c.amount).sum::<Uint128>(),
+        Uint128::new(30_000)
+    );
+    assert_eq!(suite.query_total_staked().unwrap(), 10_000);
+
+    // the other staker is unaffected
+    assert_eq!(
+        suite.query_staked(OTHER, UNBONDING_PERIODS[0]).unwrap(),
+        10_000
+    );
+
+    // tokens can be claimed after the longest unbonding period
+    suite.update_time(UNBONDING_PERIODS[1]);
+    suite.claim(USER).unwrap();
+    assert_eq!(suite.query_balance_vesting_contract(USER).unwrap(), 30_000);
+}
+
+#[test]
+fn exit_all_fails_while_stake_is_locked() {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![(USER, 30_000)])
+        .build();
+
+    suite.delegate(USER, 30_000, UNBONDING_PERIODS[1]).unwrap();
+    // moving to a shorter unbonding period locks the tokens for the difference
+    suite
+        .rebond(USER, 10_000, UNBONDING_PERIODS[1], UNBONDING_PERIODS[0])
+        .unwrap();
+
+    let err = suite.exit_all(USER).unwrap_err();
+    assert_eq!(
+        ContractError::StakeLocked {
+            unbonding_period: UNBONDING_PERIODS[0],
+            amount: Uint128::new(10_000),
+        },
+        err.downcast().unwrap()
+    );
+    // nothing was unbonded
+    assert_eq!(suite.query_claims(USER).unwrap().len(), 0);
+
+    // once the tokens are unlocked, everything is unbonded
+    suite.update_time(SEVEN_DAYS);
+    suite.exit_all(USER).unwrap();
+    assert_eq!(
+        suite
+            .query_claims(USER)
+            .unwrap()
+            .iter()
+            .map(|c| c.amount)
+            .sum::<Uint128>(),
+        Uint128::new(30_000)
+    );
+}
+
+#[test]
+fn exit_all_fails_with_too_many_periods() {
+    let unbonding_periods = (1..=MAX_EXIT_PERIODS as u64 + 1)
+        .map(|i| i * SEVEN_DAYS)
+        .collect::<Vec<_>>();
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(unbonding_periods.clone())
+        .with_initial_balances(vec![(USER, 11_000)])
+        .build();
+
+    for period in &unbonding_periods {
+        suite.delegate(USER, 1_000, *period).unwrap();
+    }
+
+    let err = suite.exit_all(USER).unwrap_err();
+    assert_eq!(
+        ContractError::TooManyExitPeriods(MAX_EXIT_PERIODS),
+        err.downcast().unwrap()
+    );
+
+    // after unbonding one period manually, the rest can be exited
+    suite.unbond(USER, 1_000, unbonding_periods[0]).unwrap();
+    suite.exit_all(USER).unwrap();
+    for stake in suite.query_all_staked(USER).unwrap().stakes {
+        assert_eq!(stake.stake, Uint128::zero());
+    }
+    assert_eq!(suite.query_claims(USER).unwrap().len(), 11);
+}
@@ -0,0 +1,193 @@
+use cosmwasm_std::{coin, Addr, Decimal};
+use wyndex::asset::{AssetInfo, AssetInfoExt, AssetInfoValidated};
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM};
+use crate::msg::FundingSource;
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const MANAGER: &str = "manager";
+const SOURCE: &str = "source";
+const MEMBER: &str = "member";
+const KEEPER: &str = "keeper";
+
+#[test]
+fn pull_funding_from_prepaid_source() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(MEMBER, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(MANAGER, 1_000), (SOURCE, 2_000)])
+        .build();
+    let juno_info = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            MANAGER,
+            juno_info.clone(),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(MEMBER, 10_000, 100).unwrap();
+
+    let source = FundingSource {
+        address: SOURCE.to_string(),
+        amount: 1_000u128.into(),
+        epoch: 100,
+    };
+    // only the manager can link a source
+    let err = suite
+        .set_funding_source(ADMIN, juno_info.clone(), Some(source.clone()))
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .set_funding_source(MANAGER, juno_info.clone(), Some(source))
+        .unwrap();
+
+    // only the source can prepay
+    let err = suite
+        .prepay_funding(MANAGER, &[coin(1_000, JUNO_DENOM)])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    suite
+        .prepay_funding(SOURCE, &[coin(2_000, JUNO_DENOM)])
+        .unwrap();
+
+    suite
+        .execute_fund_distribution_curve(MANAGER, JUNO_DENOM, 1_000, 100)
+        .unwrap();
+
+    // prepaid tokens are not distributed
+    suite.update_time(50);
+    suite.distribute_funds(KEEPER, None, None).unwrap();
+    assert_eq!(suite.withdrawable_rewards(MEMBER).unwrap(), vec![juno(500)]);
+
+    // the manual funding is still running
+    let err = suite.pull_funding(KEEPER, juno_info.clone()).unwrap_err();
+    assert_eq!(
+        ContractError::FundingNotEnded(AssetInfoValidated::Native(JUNO_DENOM.to_string())),
+        err.downcast().unwrap()
+    );
+
+    // once it expired, anyone can start a new funding from the source
+    suite.update_time(50);
+    suite.pull_funding(KEEPER, juno_info.clone()).unwrap();
+    suite.distribute_funds(KEEPER, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(MEMBER).unwrap(),
+        vec![juno(1_000)]
+    );
+
+    suite.update_time(50);
+    suite.distribute_funds(KEEPER, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(MEMBER).unwrap(),
+        vec![juno(1_500)]
+    );
+
+    suite.update_time(50);
+    suite.pull_funding(KEEPER, juno_info.clone()).unwrap();
+
+    // the prepaid tokens are used up
+    suite.update_time(100);
+    let err = suite.pull_funding(KEEPER, juno_info).unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientPrepaidFunding {
+            available: 0u128.into()
+        },
+        err.downcast().unwrap()
+    );
+    suite.distribute_funds(KEEPER, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(MEMBER).unwrap(),
+        vec![juno(3_000)]
+    );
+}
+
+#[test]
+fn cw20_source_has_to_accept_before_pulling() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(MEMBER, 10_000), (SOURCE, 3_000)])
+        .build();
+    let token_info = AssetInfo::Token(suite.token_contract());
+    let token = AssetInfoValidated::Token(Addr::unchecked(suite.token_contract()));
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            MANAGER,
+            token_info.clone(),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(MEMBER, 10_000, 100).unwrap();
+
+    // an allowance given to the staking contract is no consent to fund the flow
+    let stake_contract = suite.stake_contract();
+    suite
+        .increase_allowance(SOURCE, &stake_contract, 3_000)
+        .unwrap();
+    let source = FundingSource {
+        address: SOURCE.to_string(),
+        amount: 1_000u128.into(),
+        epoch: 100,
+    };
+    suite
+        .set_funding_source(MANAGER, token_info.clone(), Some(source.clone()))
+        .unwrap();
+    let err = suite.pull_funding(KEEPER, token_info.clone()).unwrap_err();
+    assert_eq!(
+        ContractError::FundingSourceNotAccepted(token.clone()),
+        err.downcast().unwrap()
+    );
+
+    // only the source can accept and only the configured terms
+    let err = suite
+        .accept_funding_source(MANAGER, token_info.clone(), 1_000, 100)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .accept_funding_source(SOURCE, token_info.clone(), 2_000, 100)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::FundingSourceTermsMismatch {},
+        err.downcast().unwrap()
+    );
+    suite
+        .accept_funding_source(SOURCE, token_info.clone(), 1_000, 100)
+        .unwrap();
+
+    suite.pull_funding(KEEPER, token_info.clone()).unwrap();
+    assert_eq!(
+        suite
+            .query_cw20_balance(SOURCE, suite.token_contract())
+            .unwrap(),
+        2_000
+    );
+    suite.update_time(100);
+    suite.distribute_funds(KEEPER, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(MEMBER).unwrap(),
+        vec![token.with_balance(1_000u128)]
+    );
+
+    // changed terms have to be accepted again
+    suite
+        .set_funding_source(
+            MANAGER,
+            token_info.clone(),
+            Some(FundingSource {
+                amount: 2_000u128.into(),
+                ..source
+            }),
+        )
+        .unwrap();
+    let err = suite.pull_funding(KEEPER, token_info).unwrap_err();
+    assert_eq!(
+        ContractError::FundingSourceNotAccepted(token),
+        err.downcast().unwrap()
+    );
+}
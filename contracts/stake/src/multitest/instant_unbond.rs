@@ -0,0 +1,81 @@
+use cosmwasm_std::Decimal;
+use cw_controllers::AdminError;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno_power, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const STAKER: &str = "staker";
+const TREASURY: &str = "treasury";
+
+#[test]
+fn instant_unbond_with_penalty() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_initial_balances(vec![(STAKER, 10_000)])
+        .build();
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(STAKER, 10_000, SEVEN_DAYS).unwrap();
+
+    // disabled by default
+    let err = suite.instant_unbond(STAKER, 1_000, SEVEN_DAYS).unwrap_err();
+    assert_eq!(
+        ContractError::InstantUnbondDisabled {},
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .set_instant_unbond(STAKER, Some(Decimal::percent(10)), Some(TREASURY))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .set_instant_unbond(ADMIN, Some(Decimal::percent(150)), Some(TREASURY))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidInstantUnbondFee {},
+        err.downcast().unwrap()
+    );
+    suite
+        .set_instant_unbond(ADMIN, Some(Decimal::percent(10)), Some(TREASURY))
+        .unwrap();
+
+    suite.instant_unbond(STAKER, 4_000, SEVEN_DAYS).unwrap();
+    // the staker gets 90% right away, the treasury the rest
+    assert_eq!(suite.query_balance_vesting_contract(STAKER).unwrap(), 3_600);
+    assert_eq!(suite.query_balance_vesting_contract(TREASURY).unwrap(), 400);
+    assert_eq!(suite.query_claims(STAKER).unwrap(), vec![]);
+    // the rewards power is reduced immediately
+    assert_eq!(suite.query_staked(STAKER, SEVEN_DAYS).unwrap(), 6_000);
+    assert_eq!(suite.query_total_staked().unwrap(), 6_000);
+    assert_eq!(suite.query_rewards_power(STAKER).unwrap(), juno_power(6));
+
+    // without a treasury, the penalty is burned
+    suite
+        .set_instant_unbond(ADMIN, Some(Decimal::percent(20)), None)
+        .unwrap();
+    suite.instant_unbond(STAKER, 1_000, SEVEN_DAYS).unwrap();
+    assert_eq!(suite.query_balance_vesting_contract(STAKER).unwrap(), 4_400);
+    assert_eq!(suite.query_balance_vesting_contract(TREASURY).unwrap(), 400);
+    assert_eq!(suite.query_balance_staking_contract().unwrap(), 5_000);
+
+    // cannot unbond more than is staked
+    suite.instant_unbond(STAKER, 6_000, SEVEN_DAYS).unwrap_err();
+
+    suite.set_instant_unbond(ADMIN, None, None).unwrap();
+    let err = suite.instant_unbond(STAKER, 1_000, SEVEN_DAYS).unwrap_err();
+    assert_eq!(
+        ContractError::InstantUnbondDisabled {},
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,57 @@
+use super::suite::{Suite, SuiteBuilder};
+use crate::state::InteractionBlock;
+
+const DAY: u64 = 24 * 60 * 60;
+const UNBONDING_PERIODS: &[u64; 2] = &[7 * DAY, 14 * DAY];
+const USER: &str = "user";
+const OTHER: &str = "other";
+
+#[test]
+fn last_interaction_updates_on_each_action() {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![(USER, 100_000), (OTHER, 100_000)])
+        .build();
+
+    // never interacted
+    assert_eq!(suite.query_last_interaction(USER).unwrap(), None);
+
+    let current = |suite: &Suite| {
+        let block = suite.app.block_info();
+        Some(InteractionBlock {
+            height: block.height,
+            time: block.time.seconds(),
+        })
+    };
+
+    suite.delegate(USER, 100_000, UNBONDING_PERIODS[0]).unwrap();
+    let bonded = current(&suite);
+    assert_eq!(suite.query_last_interaction(USER).unwrap(), bonded);
+
+    // someone else's interaction does not count
+    suite.next_block();
+    suite
+        .delegate(OTHER, 100_000, UNBONDING_PERIODS[0])
+        .unwrap();
+    assert_eq!(suite.query_last_interaction(USER).unwrap(), bonded);
+    assert_eq!(
+        suite.query_last_interaction(OTHER).unwrap(),
+        current(&suite)
+    );
+
+    suite.next_block();
+    suite
+        .rebond(USER, 50_000, UNBONDING_PERIODS[0], UNBONDING_PERIODS[1])
+        .unwrap();
+    assert_eq!(suite.query_last_interaction(USER).unwrap(), current(&suite));
+
+    suite.next_block();
+    suite.unbond(USER, 10_000, UNBONDING_PERIODS[1]).unwrap();
+    assert_eq!(suite.query_last_interaction(USER).unwrap(), current(&suite));
+
+    suite.next_block();
+    suite.withdraw_funds(USER, None, None).unwrap();
+    let withdrawn = current(&suite);
+    assert_eq!(suite.query_last_interaction(USER).unwrap(), withdrawn);
+    assert_ne!(withdrawn, bonded);
+}
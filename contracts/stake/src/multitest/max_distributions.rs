@@ -0,0 +1,74 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::AssetInfo;
+
+use super::suite::SuiteBuilder;
+use crate::ContractError;
+
+const UNBONDING_PERIODS: &[u64; 2] = &[100, 200];
+
+#[test]
+fn raised_max_allows_more_flows() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .build();
+    let rewards = vec![(100, Decimal::one()), (200, Decimal::one())];
+
+    for denom in ["a", "b", "c", "d", "e", "f"] {
+        suite
+            .create_distribution_flow(
+                "admin",
+                "manager",
+                AssetInfo::Native(denom.to_string()),
+                rewards.clone(),
+            )
+            .unwrap();
+    }
+    let err = suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native("g".to_string()),
+            rewards.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TooManyDistributions(6),
+        err.downcast().unwrap()
+    );
+
+    // only the admin can change the max
+    let err = suite.set_max_distributions("manager", 7).unwrap_err();
+    assert_eq!(
+        ContractError::Admin(cw_controllers::AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    // it cannot be lower than the existing flows
+    let err = suite.set_max_distributions("admin", 5).unwrap_err();
+    assert_eq!(
+        ContractError::MaxDistributionsBelowExisting(6),
+        err.downcast().unwrap()
+    );
+
+    suite.set_max_distributions("admin", 7).unwrap();
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native("g".to_string()),
+            rewards.clone(),
+        )
+        .unwrap();
+    let err = suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native("h".to_string()),
+            rewards,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TooManyDistributions(7),
+        err.downcast().unwrap()
+    );
+}
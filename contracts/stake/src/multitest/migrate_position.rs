@@ -0,0 +1,72 @@
+use cw_controllers::AdminError;
+
+use super::suite::SuiteBuilder;
+use crate::msg::StakedResponse;
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const MEMBER: &str = "member";
+
+const UNBONDING_PERIODS: [u64; 2] = [1000, 4000];
+
+#[test]
+fn migrate_position_to_whitelisted_contract() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![(MEMBER, 20_000)])
+        .build();
+    let target = suite.instantiate_stake_contract(ADMIN, UNBONDING_PERIODS.to_vec());
+
+    suite.delegate(MEMBER, 8_000, 1000).unwrap();
+    suite.delegate(MEMBER, 12_000, 4000).unwrap();
+
+    // target has to be set by the admin first
+    let err = suite.migrate_position(MEMBER, &target).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidMigrationTarget(target.clone()),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .set_migration_target(MEMBER, Some(&target))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    suite.set_migration_target(ADMIN, Some(&target)).unwrap();
+
+    suite.migrate_position(MEMBER, &target).unwrap();
+
+    // the whole position left the old contract
+    assert_eq!(suite.query_staked(MEMBER, 1000).unwrap(), 0);
+    assert_eq!(suite.query_staked(MEMBER, 4000).unwrap(), 0);
+    assert_eq!(suite.query_total_staked().unwrap(), 0);
+    assert_eq!(suite.query_balance_staking_contract().unwrap(), 0);
+    assert_eq!(
+        suite
+            .query_cw20_balance(target.as_str(), suite.token_contract())
+            .unwrap(),
+        20_000
+    );
+
+    // and landed in the target with the same unbonding periods
+    let stakes = suite
+        .query_all_staked_in(&target, MEMBER)
+        .unwrap()
+        .stakes
+        .into_iter()
+        .map(
+            |StakedResponse {
+                 stake,
+                 unbonding_period,
+                 ..
+             }| (unbonding_period, stake.u128()),
+        )
+        .collect::<Vec<_>>();
+    assert_eq!(stakes, vec![(1000, 8_000), (4000, 12_000)]);
+
+    // nothing left to migrate
+    let err = suite.migrate_position(MEMBER, &target).unwrap_err();
+    assert_eq!(ContractError::NothingToMigrate {}, err.downcast().unwrap());
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{to_binary, Addr, Empty, StdError, Uint128};
+use cosmwasm_std::{from_slice, to_binary, Addr, Empty, StdError, Uint128};
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 
 use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
@@ -97,6 +97,8 @@ fn stake_old_migrate_with_unbond_all_and_unbond() {
             msg: to_binary(&ReceiveMsg::Delegate {
                 unbonding_period: SEVEN_DAYS,
                 delegate_as: None,
+                unbond_controller: None,
+                withdraw_to: None,
             })
             .unwrap(),
         },
@@ -123,19 +125,49 @@ fn stake_old_migrate_with_unbond_all_and_unbond() {
     );
     let stake_new_id = app.store_code(new_contract);
 
-    // Migrate to new contract with unbond all.
-    app.migrate_contract(
-        admin,
-        stake_old_contract.clone(),
-        &MigrateMsg {
-            unbonder: Some(UNBONDER.to_owned()),
-            converter: None,
-            unbond_all: true,
-        },
-        stake_new_id,
+    let old_version: cw2::ContractVersion = from_slice(
+        &app.wrap()
+            .query_wasm_raw(stake_old_contract.clone(), b"contract_info".to_vec())
+            .unwrap()
+            .unwrap(),
     )
     .unwrap();
 
+    // Migrate to new contract with unbond all.
+    let res = app
+        .migrate_contract(
+            admin,
+            stake_old_contract.clone(),
+            &MigrateMsg {
+                unbonder: Some(UNBONDER.to_owned()),
+                converter: None,
+                unbond_all: true,
+            },
+            stake_new_id,
+        )
+        .unwrap();
+
+    // The migration reports what it touched.
+    let report = res
+        .events
+        .iter()
+        .find(|e| e.ty == "wasm-migration_report")
+        .unwrap();
+    let attr = |key: &str| {
+        report
+            .attributes
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.value.as_str())
+            .unwrap()
+    };
+    assert_eq!(attr("from_version"), old_version.version);
+    assert_eq!(attr("to_version"), env!("CARGO_PKG_VERSION"));
+    assert_eq!(attr("distribution_flows"), "0");
+    assert_eq!(attr("unbond_all"), "true");
+    // the old version did not count the stakers
+    assert_eq!(attr("staker_count"), "0");
+
     // Check that unbond all has been correctly set.
     let resp: UnbondAllResponse = app
         .wrap()
@@ -164,6 +196,7 @@ fn stake_old_migrate_with_unbond_all_and_unbond() {
         &ExecuteMsg::Unbond {
             tokens: Uint128::new(500_000),
             unbonding_period: SEVEN_DAYS,
+            owner: None,
         },
         &[],
     )
@@ -0,0 +1,191 @@
+use cosmwasm_std::Decimal;
+use cw_controllers::AdminError;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, juno_power, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::state::RoundingMode;
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const SMALL: &str = "small";
+const BIG: &str = "big";
+const DISTRIBUTOR: &str = "distributor";
+
+#[test]
+fn only_admin_can_set_min_bond() {
+    let mut suite = SuiteBuilder::new().with_admin(ADMIN).build();
+
+    let err = suite.set_min_bond(SMALL, 1_000).unwrap_err();
+    assert_eq!(
+        ContractError::Admin(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn changing_min_bond_updates_rewards_power() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_min_bond(5_000)
+        .with_initial_balances(vec![(SMALL, 4_000), (BIG, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(DISTRIBUTOR, 4_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(SMALL, 4_000, None).unwrap();
+    suite.delegate(BIG, 10_000, None).unwrap();
+
+    // the small stake is below min_bond and does not earn anything
+    assert_eq!(suite.query_rewards_power(SMALL).unwrap(), vec![]);
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(10));
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(SMALL).unwrap(), vec![juno(0)]);
+    assert_eq!(suite.withdrawable_rewards(BIG).unwrap(), vec![juno(1_000)]);
+
+    // lowering min_bond gives the small stake rewards power for future distributions,
+    // once the change was applied to it ("big" comes first)
+    suite.set_min_bond(ADMIN, 1_000).unwrap();
+    suite.apply_min_bond(SMALL, 1).unwrap();
+    assert_eq!(suite.query_rewards_power(SMALL).unwrap(), vec![]);
+    let err = suite.set_min_bond(ADMIN, 2_000).unwrap_err();
+    assert_eq!(
+        ContractError::MinBondUpdateInProgress {},
+        err.downcast().unwrap()
+    );
+    suite.apply_min_bond(SMALL, None).unwrap();
+    let err = suite.apply_min_bond(SMALL, None).unwrap_err();
+    assert_eq!(ContractError::NoMinBondUpdate {}, err.downcast().unwrap());
+    assert_eq!(suite.query_rewards_power(SMALL).unwrap(), juno_power(4));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(14));
+    assert_eq!(suite.withdrawable_rewards(SMALL).unwrap(), vec![juno(0)]);
+    assert_eq!(suite.withdrawable_rewards(BIG).unwrap(), vec![juno(1_000)]);
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(1_400)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(SMALL).unwrap(), vec![juno(400)]);
+    assert_eq!(suite.withdrawable_rewards(BIG).unwrap(), vec![juno(2_000)]);
+
+    // raising it again removes the power, but keeps the rewards already distributed
+    suite.set_min_bond(ADMIN, 5_000).unwrap();
+    suite.apply_min_bond(SMALL, None).unwrap();
+    assert_eq!(suite.query_rewards_power(SMALL).unwrap(), vec![]);
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(10));
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(SMALL).unwrap(), vec![juno(400)]);
+    assert_eq!(suite.withdrawable_rewards(BIG).unwrap(), vec![juno(3_000)]);
+
+    // the stake itself is not touched
+    assert_eq!(suite.query_staked(SMALL, None).unwrap(), 4_000);
+    suite.withdraw_funds(SMALL, None, None).unwrap();
+    assert_eq!(suite.query_balance(SMALL, JUNO_DENOM).unwrap(), 400);
+}
+
+#[test]
+fn power_rounding_applies_on_next_interaction() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_min_bond(5_000)
+        .with_initial_balances(vec![(SMALL, 5_000), (BIG, 10_600)])
+        .with_native_balances(JUNO_DENOM, vec![(DISTRIBUTOR, 1_600)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(SMALL, 5_000, None).unwrap();
+    suite.delegate(BIG, 10_600, None).unwrap();
+
+    // rounding down by default
+    assert_eq!(suite.query_rewards_power(SMALL).unwrap(), juno_power(5));
+    assert_eq!(suite.query_rewards_power(BIG).unwrap(), juno_power(10));
+
+    let err = suite
+        .set_power_rounding(SMALL, RoundingMode::Nearest)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+
+    // existing stakes keep their power until the staker interacts again
+    suite
+        .set_power_rounding(ADMIN, RoundingMode::Nearest)
+        .unwrap();
+    assert_eq!(suite.query_rewards_power(BIG).unwrap(), juno_power(10));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(15));
+
+    suite.withdraw_funds(BIG, None, None).unwrap();
+    assert_eq!(suite.query_rewards_power(SMALL).unwrap(), juno_power(5));
+    assert_eq!(suite.query_rewards_power(BIG).unwrap(), juno_power(11));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(16));
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(1_600)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards(SMALL).unwrap(), vec![juno(500)]);
+    assert_eq!(suite.withdrawable_rewards(BIG).unwrap(), vec![juno(1_100)]);
+}
+
+#[test]
+fn rounding_to_nearest_does_not_distribute_more_than_funded() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_min_bond(5_000)
+        .with_initial_balances(vec![(SMALL, 5_500), (BIG, 5_500)])
+        .with_native_balances(JUNO_DENOM, vec![(DISTRIBUTOR, 1_100)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite
+        .set_power_rounding(ADMIN, RoundingMode::Nearest)
+        .unwrap();
+    suite.delegate(SMALL, 5_500, None).unwrap();
+    suite.delegate(BIG, 5_500, None).unwrap();
+
+    // both stakes round up, so the total covers more than the 11 power of the total stake
+    assert_eq!(suite.query_rewards_power(SMALL).unwrap(), juno_power(6));
+    assert_eq!(suite.query_rewards_power(BIG).unwrap(), juno_power(6));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(13));
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(1_100)))
+        .unwrap();
+    let small = suite.withdrawable_rewards(SMALL).unwrap()[0].amount;
+    let big = suite.withdrawable_rewards(BIG).unwrap()[0].amount;
+    assert_eq!(small, big);
+    assert!(small + big <= 1_100u128.into());
+
+    // everyone can withdraw their rewards
+    suite.withdraw_funds(SMALL, None, None).unwrap();
+    suite.withdraw_funds(BIG, None, None).unwrap();
+    assert_eq!(
+        suite.query_balance(SMALL, JUNO_DENOM).unwrap(),
+        small.u128()
+    );
+    assert_eq!(suite.query_balance(BIG, JUNO_DENOM).unwrap(), big.u128());
+}
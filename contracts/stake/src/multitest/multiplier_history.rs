@@ -0,0 +1,210 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::{AssetInfo, AssetInfoValidated};
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM};
+use crate::ContractError;
+
+const UNBONDING_PERIODS: &[u64; 2] = &[100, 200];
+
+#[test]
+fn multiplier_history_records_every_change() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .build();
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    let initial = vec![(100, Decimal::one()), (200, Decimal::one())];
+    suite
+        .create_distribution_flow("admin", "manager", asset.clone(), initial.clone())
+        .unwrap();
+    let created_at = suite.app.block_info().time.seconds();
+
+    suite.update_time(1000);
+    let second = vec![(100, Decimal::one()), (200, Decimal::percent(200))];
+    suite
+        .update_distribution_multipliers("admin", asset.clone(), second.clone())
+        .unwrap();
+
+    suite.update_time(1000);
+    let third = vec![(100, Decimal::percent(50)), (200, Decimal::percent(300))];
+    suite
+        .update_distribution_multipliers("admin", asset.clone(), third.clone())
+        .unwrap();
+
+    let history = suite.query_multiplier_history(asset).unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(
+        history
+            .iter()
+            .map(|change| change.changed_at)
+            .collect::<Vec<_>>(),
+        vec![created_at, created_at + 1000, created_at + 2000]
+    );
+    assert_eq!(history[0].rewards, initial);
+    assert_eq!(history[1].rewards, second);
+    assert_eq!(history[2].rewards, third);
+
+    // unknown flows have no history
+    assert_eq!(
+        suite
+            .query_multiplier_history(AssetInfo::Native("luna".to_string()))
+            .unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn updated_multipliers_apply_to_future_distributions() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![("short", 10_000), ("long", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("distributor", 2_000)])
+        .build();
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            asset.clone(),
+            vec![(100, Decimal::one()), (200, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate("short", 10_000, 100).unwrap();
+    suite.delegate("long", 10_000, 200).unwrap();
+
+    suite
+        .distribute_funds("distributor", None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("short").unwrap(),
+        vec![juno(500)]
+    );
+    assert_eq!(suite.withdrawable_rewards("long").unwrap(), vec![juno(500)]);
+
+    // only the admin can change the multipliers, and the usual rules apply
+    let tripled = vec![(100, Decimal::one()), (200, Decimal::percent(300))];
+    let err = suite
+        .update_distribution_multipliers("manager", asset.clone(), tripled.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(cw_controllers::AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    let err = suite
+        .update_distribution_multipliers(
+            "admin",
+            asset.clone(),
+            vec![(100, Decimal::percent(300)), (200, Decimal::one())],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidRewards {}, err.downcast().unwrap());
+
+    suite
+        .update_distribution_multipliers("admin", asset.clone(), tripled)
+        .unwrap();
+    suite
+        .apply_distribution_update("anyone", asset, None)
+        .unwrap();
+    // already distributed rewards are untouched
+    assert_eq!(
+        suite.withdrawable_rewards("short").unwrap(),
+        vec![juno(500)]
+    );
+    assert_eq!(suite.withdrawable_rewards("long").unwrap(), vec![juno(500)]);
+    assert_eq!(
+        suite.query_rewards_power("long").unwrap()[0].1,
+        3 * suite.query_rewards_power("short").unwrap()[0].1
+    );
+
+    suite
+        .distribute_funds("distributor", None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("short").unwrap(),
+        vec![juno(750)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards("long").unwrap(),
+        vec![juno(1_250)]
+    );
+}
+
+#[test]
+fn multiplier_update_is_applied_in_batches() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![("alice", 10_000), ("bob", 10_000), ("carol", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("distributor", 3_000)])
+        .build();
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            asset.clone(),
+            vec![(100, Decimal::one()), (200, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate("alice", 10_000, 200).unwrap();
+    suite.delegate("bob", 10_000, 200).unwrap();
+    suite.delegate("carol", 10_000, 200).unwrap();
+
+    let err = suite
+        .apply_distribution_update("anyone", asset.clone(), None)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NoDistributionUpdate(AssetInfoValidated::Native(JUNO_DENOM.to_string())),
+        err.downcast().unwrap()
+    );
+
+    let doubled = vec![(100, Decimal::one()), (200, Decimal::percent(200))];
+    suite
+        .update_distribution_multipliers("admin", asset.clone(), doubled.clone())
+        .unwrap();
+    // a second update has to wait for the first one
+    let err = suite
+        .update_distribution_multipliers("admin", asset.clone(), doubled)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DistributionUpdateInProgress(AssetInfoValidated::Native(
+            JUNO_DENOM.to_string()
+        )),
+        err.downcast().unwrap()
+    );
+
+    // the first two stakers are updated, the last one keeps the old multipliers
+    suite
+        .apply_distribution_update("anyone", asset.clone(), 2)
+        .unwrap();
+    assert_eq!(suite.query_rewards_power("alice").unwrap()[0].1, 20);
+    assert_eq!(suite.query_rewards_power("bob").unwrap()[0].1, 20);
+    assert_eq!(suite.query_rewards_power("carol").unwrap()[0].1, 10);
+
+    // nothing is distributed until everyone is updated
+    suite
+        .distribute_funds("distributor", None, Some(juno(3_000)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards("alice").unwrap(), vec![juno(0)]);
+    assert_eq!(suite.withdrawable_rewards("carol").unwrap(), vec![juno(0)]);
+
+    suite.apply_distribution_update("anyone", asset, 2).unwrap();
+    assert_eq!(suite.query_rewards_power("carol").unwrap()[0].1, 20);
+    suite.distribute_funds("distributor", None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("alice").unwrap(),
+        vec![juno(1_000)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards("bob").unwrap(),
+        vec![juno(1_000)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards("carol").unwrap(),
+        vec![juno(1_000)]
+    );
+}
@@ -0,0 +1,184 @@
+use cosmwasm_std::Decimal;
+use cw_controllers::AdminError;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, juno_power, Suite, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const ACTIVE: &str = "active";
+const INACTIVE: &str = "inactive";
+const DISTRIBUTOR: &str = "distributor";
+
+fn suite_with_two_stakers() -> Suite {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_initial_balances(vec![(ACTIVE, 10_000), (INACTIVE, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![(DISTRIBUTOR, 3_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+
+    suite.delegate(ACTIVE, 10_000, None).unwrap();
+    suite.delegate(INACTIVE, 10_000, None).unwrap();
+    suite
+}
+
+#[test]
+fn power_decay_is_disabled_by_default() {
+    let mut suite = suite_with_two_stakers();
+
+    suite.update_time(1_000);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(10));
+
+    // only the admin can enable it
+    let err = suite
+        .update_power_decay(INACTIVE, Some((100, 100)))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+
+    let err = suite.update_power_decay(ADMIN, Some((100, 0))).unwrap_err();
+    assert_eq!(ContractError::InvalidPowerDecay {}, err.downcast().unwrap());
+}
+
+#[test]
+fn power_decays_after_inactivity_window() {
+    let mut suite = suite_with_two_stakers();
+    suite.update_power_decay(ADMIN, Some((100, 100))).unwrap();
+
+    // still within the inactivity window
+    suite.update_time(100);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(10));
+
+    // half way through the decay
+    suite.update_time(50);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(5));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(15));
+
+    // fully decayed
+    suite.update_time(50);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), vec![]);
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(10));
+
+    // stake itself is untouched
+    assert_eq!(suite.query_staked(INACTIVE, None).unwrap(), 10_000);
+}
+
+#[test]
+fn interaction_restores_power() {
+    let mut suite = suite_with_two_stakers();
+    suite.update_power_decay(ADMIN, Some((100, 100))).unwrap();
+
+    suite.update_time(150);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(5));
+
+    // withdrawing rewards counts as an interaction
+    suite.withdraw_funds(INACTIVE, None, None).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(10));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(20));
+
+    // and the inactivity window starts again from there
+    suite.update_time(100);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(10));
+
+    suite.update_time(50);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(5));
+
+    // unbonding is an interaction as well
+    suite.unbond(INACTIVE, 5_000, None).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(5));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(15));
+
+    // disabling the decay restores already decayed power once applied
+    suite.update_time(150);
+    suite.apply_decay(DISTRIBUTOR, &[ACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(ACTIVE).unwrap(), vec![]);
+    suite.update_power_decay(ADMIN, None).unwrap();
+    suite.apply_decay(DISTRIBUTOR, &[ACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(ACTIVE).unwrap(), juno_power(10));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(15));
+}
+
+#[test]
+fn withdrawal_by_someone_else_does_not_restore_power() {
+    let mut suite = suite_with_two_stakers();
+    suite.delegate_withdrawal(INACTIVE, DISTRIBUTOR).unwrap();
+    suite.update_power_decay(ADMIN, Some((100, 100))).unwrap();
+
+    suite.update_time(150);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(5));
+
+    // withdrawals on behalf of the staker are no interaction of the staker
+    suite.withdraw_funds(DISTRIBUTOR, INACTIVE, None).unwrap();
+    suite
+        .withdraw_funds_batch(DISTRIBUTOR, &[INACTIVE])
+        .unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(5));
+    assert_eq!(suite.query_total_rewards_power().unwrap(), juno_power(15));
+
+    suite.update_time(50);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), vec![]);
+}
+
+#[test]
+fn decayed_power_reduces_distribution_share() {
+    let mut suite = suite_with_two_stakers();
+    suite.update_power_decay(ADMIN, Some((100, 100))).unwrap();
+
+    suite.update_time(150);
+    suite.apply_decay(DISTRIBUTOR, &[ACTIVE, INACTIVE]).unwrap();
+    // the active staker interacts and gets full power back
+    suite.withdraw_funds(ACTIVE, None, None).unwrap();
+    assert_eq!(suite.query_rewards_power(ACTIVE).unwrap(), juno_power(10));
+    assert_eq!(suite.query_rewards_power(INACTIVE).unwrap(), juno_power(5));
+
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(1_500)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(ACTIVE).unwrap(),
+        vec![juno(1_000)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards(INACTIVE).unwrap(),
+        vec![juno(500)]
+    );
+
+    // once fully decayed, everything goes to the active staker
+    suite.update_time(50);
+    suite.apply_decay(DISTRIBUTOR, &[INACTIVE]).unwrap();
+    suite
+        .distribute_funds(DISTRIBUTOR, None, Some(juno(1_500)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(ACTIVE).unwrap(),
+        vec![juno(2_500)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards(INACTIVE).unwrap(),
+        vec![juno(500)]
+    );
+
+    // rewards accrued before the decay are still withdrawable
+    suite.withdraw_funds(INACTIVE, None, None).unwrap();
+    assert_eq!(suite.query_balance(INACTIVE, JUNO_DENOM).unwrap(), 500);
+}
@@ -0,0 +1,149 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::AssetInfo;
+
+use crate::multitest::suite::{juno_power, SuiteBuilder, JUNO_DENOM};
+use crate::ContractError;
+
+const DAY: u64 = 24 * 60 * 60;
+const UNBONDING_PERIODS: &[u64; 3] = &[7 * DAY, 14 * DAY, 21 * DAY];
+const USER: &str = "user";
+
+#[test]
+fn rebond_cooldown_decreases_to_zero() {
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![(USER, 100_000)])
+        .build();
+
+    suite.delegate(USER, 100_000, UNBONDING_PERIODS[2]).unwrap();
+
+    // nothing is locked yet
+    assert_eq!(
+        suite
+            .query_rebond_cooldown(USER, UNBONDING_PERIODS[2])
+            .unwrap(),
+        0
+    );
+
+    suite
+        .rebond(USER, 50_000, UNBONDING_PERIODS[2], UNBONDING_PERIODS[1])
+        .unwrap();
+
+    // rebonding down locks the tokens for the difference between the periods
+    assert_eq!(
+        suite
+            .query_rebond_cooldown(USER, UNBONDING_PERIODS[1])
+            .unwrap(),
+        7 * DAY
+    );
+    assert_eq!(
+        suite
+            .query_rebond_cooldown(USER, UNBONDING_PERIODS[2])
+            .unwrap(),
+        0
+    );
+    // locked tokens cannot be rebonded down yet
+    suite
+        .rebond(USER, 50_000, UNBONDING_PERIODS[1], UNBONDING_PERIODS[0])
+        .unwrap_err();
+
+    suite.update_time(3 * DAY);
+    assert_eq!(
+        suite
+            .query_rebond_cooldown(USER, UNBONDING_PERIODS[1])
+            .unwrap(),
+        4 * DAY
+    );
+
+    suite.update_time(4 * DAY);
+    assert_eq!(
+        suite
+            .query_rebond_cooldown(USER, UNBONDING_PERIODS[1])
+            .unwrap(),
+        0
+    );
+    suite
+        .rebond(USER, 50_000, UNBONDING_PERIODS[1], UNBONDING_PERIODS[0])
+        .unwrap();
+}
+
+#[test]
+fn rebond_cooldown_unknown_period() {
+    let suite = SuiteBuilder::new()
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .build();
+
+    suite.query_rebond_cooldown(USER, DAY).unwrap_err();
+}
+
+#[test]
+fn rebond_split_into_multiple_periods() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_min_bond(1_000)
+        .with_tokens_per_power(100)
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![(USER, 3_000)])
+        .build();
+    suite
+        .create_distribution_flow(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![
+                (UNBONDING_PERIODS[0], Decimal::one()),
+                (UNBONDING_PERIODS[1], Decimal::percent(200)),
+                (UNBONDING_PERIODS[2], Decimal::percent(300)),
+            ],
+        )
+        .unwrap();
+
+    suite.delegate(USER, 3_000, UNBONDING_PERIODS[0]).unwrap();
+    // 3_000 * 1 / 100 = 30
+    assert_eq!(suite.query_rewards_power(USER).unwrap(), juno_power(30));
+
+    // the source does not have enough tokens
+    suite
+        .rebond_split(
+            USER,
+            UNBONDING_PERIODS[0],
+            &[(UNBONDING_PERIODS[1], 2_000), (UNBONDING_PERIODS[2], 2_000)],
+        )
+        .unwrap_err();
+    // unknown target period
+    let err = suite
+        .rebond_split(USER, UNBONDING_PERIODS[0], &[(DAY, 1_000)])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NoUnbondingPeriodFound(DAY),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .rebond_split(
+            USER,
+            UNBONDING_PERIODS[0],
+            &[(UNBONDING_PERIODS[1], 1_000), (UNBONDING_PERIODS[2], 2_000)],
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_staked(USER, UNBONDING_PERIODS[0]).unwrap(), 0);
+    assert_eq!(
+        suite.query_staked(USER, UNBONDING_PERIODS[1]).unwrap(),
+        1_000
+    );
+    assert_eq!(
+        suite.query_staked(USER, UNBONDING_PERIODS[2]).unwrap(),
+        2_000
+    );
+    let periods = suite.query_staked_periods().unwrap();
+    assert_eq!(
+        periods
+            .iter()
+            .map(|p| p.total_staked.u128())
+            .collect::<Vec<_>>(),
+        vec![0, 1_000, 2_000]
+    );
+    // 1_000 * 2 / 100 + 2_000 * 3 / 100 = 20 + 60 = 80
+    assert_eq!(suite.query_rewards_power(USER).unwrap(), juno_power(80));
+}
@@ -0,0 +1,161 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::{AssetInfo, AssetInfoValidated};
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM};
+use crate::ContractError;
+
+const UNBONDING_PERIODS: &[u64; 2] = &[100, 200];
+
+#[test]
+fn remove_requires_empty_flow() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![("short", 10_000), ("long", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("distributor", 1_000)])
+        .build();
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+    let not_empty =
+        ContractError::DistributionNotEmpty(AssetInfoValidated::Native(JUNO_DENOM.to_string()));
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            asset.clone(),
+            vec![(100, Decimal::one()), (200, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate("short", 10_000, 100).unwrap();
+    suite.delegate("long", 10_000, 200).unwrap();
+
+    // funds are still waiting to be distributed
+    suite
+        .execute_fund_distribution_curve("distributor", JUNO_DENOM, 1_000, 100)
+        .unwrap();
+    let err = suite
+        .remove_distribution_flow("manager", asset.clone())
+        .unwrap_err();
+    assert_eq!(not_empty, err.downcast().unwrap());
+
+    // everything is distributed, but nobody withdrew yet
+    suite.update_time(100);
+    suite.distribute_funds("distributor", None, None).unwrap();
+    let err = suite
+        .remove_distribution_flow("manager", asset.clone())
+        .unwrap_err();
+    assert_eq!(not_empty, err.downcast().unwrap());
+
+    suite.withdraw_funds("short", None, None).unwrap();
+    let err = suite
+        .remove_distribution_flow("manager", asset.clone())
+        .unwrap_err();
+    assert_eq!(not_empty, err.downcast().unwrap());
+    suite.withdraw_funds("long", None, None).unwrap();
+
+    // only manager and admin can remove the flow
+    let err = suite
+        .remove_distribution_flow("short", asset.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    suite.remove_distribution_flow("manager", asset).unwrap();
+
+    // the asset is gone from all reward related queries
+    assert_eq!(suite.query_rewards_power("long").unwrap(), vec![]);
+    assert_eq!(suite.withdrawable_rewards("long").unwrap(), vec![]);
+    assert!(suite
+        .query_annualized_rewards()
+        .unwrap()
+        .into_iter()
+        .all(|(_, rewards)| rewards.is_empty()));
+}
+
+#[test]
+fn dust_does_not_block_removal() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![("dust", 5_000), ("whale", 10_000_000)])
+        .with_native_balances(JUNO_DENOM, vec![("distributor", 200_000)])
+        .build();
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+    let rewards = vec![(100, Decimal::one()), (200, Decimal::one())];
+
+    suite
+        .create_distribution_flow("admin", "manager", asset.clone(), rewards.clone())
+        .unwrap();
+    suite.delegate("dust", 5_000, 100).unwrap();
+    suite.delegate("whale", 10_000_000, 100).unwrap();
+    suite
+        .distribute_funds("distributor", None, Some(juno(100_000)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards("dust").unwrap(), vec![juno(49)]);
+    suite.withdraw_funds("whale", None, None).unwrap();
+    assert_eq!(suite.query_balance("whale", JUNO_DENOM).unwrap(), 99_950);
+
+    // the dust staker never withdrawing does not keep the flow around
+    suite
+        .remove_distribution_flow("manager", asset.clone())
+        .unwrap();
+
+    // a new flow for the same asset does not use the adjustments of the removed one
+    suite
+        .create_distribution_flow("admin", "manager", asset, rewards)
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards("dust").unwrap(), vec![juno(0)]);
+    assert_eq!(suite.withdrawable_rewards("whale").unwrap(), vec![juno(0)]);
+    // the leftovers of the removed flow are distributed again
+    suite
+        .distribute_funds("distributor", None, Some(juno(100_000)))
+        .unwrap();
+    assert_eq!(suite.withdrawable_rewards("dust").unwrap(), vec![juno(50)]);
+    assert_eq!(
+        suite.withdrawable_rewards("whale").unwrap(),
+        vec![juno(100_000)]
+    );
+}
+
+#[test]
+fn removed_flow_frees_slot() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .build();
+    let rewards = vec![(100, Decimal::one()), (200, Decimal::one())];
+
+    for denom in ["a", "b", "c", "d", "e", "f"] {
+        suite
+            .create_distribution_flow(
+                "admin",
+                "manager",
+                AssetInfo::Native(denom.to_string()),
+                rewards.clone(),
+            )
+            .unwrap();
+    }
+    let err = suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native("g".to_string()),
+            rewards.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::TooManyDistributions(6),
+        err.downcast().unwrap()
+    );
+
+    suite
+        .remove_distribution_flow("admin", AssetInfo::Native("c".to_string()))
+        .unwrap();
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native("g".to_string()),
+            rewards,
+        )
+        .unwrap();
+}
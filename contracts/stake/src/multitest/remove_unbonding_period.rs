@@ -0,0 +1,73 @@
+use cosmwasm_std::{Decimal, Uint128};
+use cw_controllers::AdminError;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{SuiteBuilder, JUNO_DENOM};
+use crate::ContractError;
+
+const UNBONDING_PERIODS: &[u64; 3] = &[100, 200, 300];
+
+#[test]
+fn remove_unused_unbonding_period() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![("short", 10_000), ("medium", 10_000)])
+        .build();
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            asset.clone(),
+            vec![
+                (100, Decimal::one()),
+                (200, Decimal::percent(150)),
+                (300, Decimal::percent(200)),
+            ],
+        )
+        .unwrap();
+    suite.delegate("short", 5_000, 100).unwrap();
+    suite.delegate("medium", 5_000, 200).unwrap();
+
+    let err = suite.remove_unbonding_period("short", 300).unwrap_err();
+    assert_eq!(
+        ContractError::Admin(AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+    let err = suite.remove_unbonding_period("admin", 400).unwrap_err();
+    assert_eq!(
+        ContractError::NoUnbondingPeriodFound(400),
+        err.downcast().unwrap()
+    );
+    let err = suite.remove_unbonding_period("admin", 200).unwrap_err();
+    assert_eq!(
+        ContractError::UnbondingPeriodInUse(200),
+        err.downcast().unwrap()
+    );
+
+    suite.remove_unbonding_period("admin", 300).unwrap();
+
+    let periods = suite.query_staked_periods().unwrap();
+    assert_eq!(
+        periods
+            .iter()
+            .map(|info| (info.unbonding_period, info.total_staked))
+            .collect::<Vec<_>>(),
+        vec![(100, Uint128::new(5_000)), (200, Uint128::new(5_000))]
+    );
+    let history = suite.query_multiplier_history(asset).unwrap();
+    assert_eq!(
+        history.last().unwrap().rewards,
+        vec![(100, Decimal::one()), (200, Decimal::percent(150))]
+    );
+
+    // the removed period cannot be used anymore
+    let err = suite.delegate("short", 5_000, 300).unwrap_err();
+    assert_eq!(
+        ContractError::NoUnbondingPeriodFound(300),
+        err.downcast().unwrap()
+    );
+    suite.delegate("short", 5_000, 100).unwrap();
+}
@@ -0,0 +1,143 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const USER: &str = "user";
+const RECIPIENT: &str = "recipient";
+
+#[test]
+fn rescue_mistakenly_sent_native_token() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_native_balances("uatom", vec![(USER, 1_000)])
+        .build();
+
+    // user sends tokens without any distribution flow to the contract
+    let stake_contract = Addr::unchecked(suite.stake_contract());
+    suite
+        .app
+        .send_tokens(
+            Addr::unchecked(USER),
+            stake_contract,
+            &[coin(1_000, "uatom")],
+        )
+        .unwrap();
+
+    let err = suite
+        .rescue_tokens(
+            USER,
+            AssetInfo::Native("uatom".to_string()),
+            RECIPIENT,
+            1_000,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::Admin(cw_controllers::AdminError::NotAdmin {}),
+        err.downcast().unwrap()
+    );
+
+    let err = suite
+        .rescue_tokens(
+            ADMIN,
+            AssetInfo::Native("uatom".to_string()),
+            RECIPIENT,
+            1_001,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::RescueAmountTooHigh {
+            available: Uint128::new(1_000)
+        },
+        err.downcast().unwrap()
+    );
+
+    suite
+        .rescue_tokens(
+            ADMIN,
+            AssetInfo::Native("uatom".to_string()),
+            RECIPIENT,
+            1_000,
+        )
+        .unwrap();
+    assert_eq!(suite.query_balance(RECIPIENT, "uatom").unwrap(), 1_000);
+    assert_eq!(
+        suite
+            .query_balance(&suite.stake_contract(), "uatom")
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn rescue_cannot_touch_obligations() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_initial_balances(vec![(USER, 10_500)])
+        .with_native_balances(JUNO_DENOM, vec![(ADMIN, 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(USER, 10_000, None).unwrap();
+    suite.unbond(USER, 2_000, None).unwrap();
+
+    // bonded and unbonding tokens cannot be rescued, only the directly transferred ones
+    let stake_contract = suite.stake_contract();
+    suite.transfer(USER, &stake_contract, 500u128).unwrap();
+    let err = suite
+        .rescue_tokens(
+            ADMIN,
+            AssetInfo::Token(suite.token_contract()),
+            RECIPIENT,
+            501,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::RescueAmountTooHigh {
+            available: Uint128::new(500)
+        },
+        err.downcast().unwrap()
+    );
+    suite
+        .rescue_tokens(
+            ADMIN,
+            AssetInfo::Token(suite.token_contract()),
+            RECIPIENT,
+            500,
+        )
+        .unwrap();
+    assert_eq!(
+        suite.query_balance_vesting_contract(RECIPIENT).unwrap(),
+        500
+    );
+    assert_eq!(suite.query_balance_staking_contract().unwrap(), 10_000);
+
+    // rewards of a distribution flow are owed to the stakers
+    suite
+        .distribute_funds(ADMIN, None, Some(juno(1_000)))
+        .unwrap();
+    let err = suite
+        .rescue_tokens(
+            ADMIN,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            RECIPIENT,
+            1,
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::RescueAmountTooHigh {
+            available: Uint128::zero()
+        },
+        err.downcast().unwrap()
+    );
+}
@@ -0,0 +1,63 @@
+use cosmwasm_std::{Addr, Decimal};
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM, SEVEN_DAYS};
+
+const USER: &str = "user";
+const COLD_WALLET: &str = "cold_wallet";
+const OTHER_WALLET: &str = "other_wallet";
+
+#[test]
+fn withdrawals_go_to_stored_receiver() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![SEVEN_DAYS])
+        .with_initial_balances(vec![(USER, 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("funder", 3_000)])
+        .build();
+    suite
+        .create_distribution_flow(
+            "admin",
+            "admin",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(USER, 10_000, SEVEN_DAYS).unwrap();
+
+    assert_eq!(suite.query_reward_receiver(USER).unwrap(), None);
+    suite.set_reward_receiver(USER, Some(COLD_WALLET)).unwrap();
+    assert_eq!(
+        suite.query_reward_receiver(USER).unwrap(),
+        Some(Addr::unchecked(COLD_WALLET))
+    );
+
+    // withdrawals without a receiver go to the stored one
+    suite
+        .distribute_funds("funder", None, Some(juno(1_000)))
+        .unwrap();
+    suite.withdraw_funds(USER, None, None).unwrap();
+    assert_eq!(suite.query_balance(COLD_WALLET, JUNO_DENOM).unwrap(), 1_000);
+    assert_eq!(suite.query_balance(USER, JUNO_DENOM).unwrap(), 0);
+
+    // an explicit receiver still takes precedence
+    suite
+        .distribute_funds("funder", None, Some(juno(1_000)))
+        .unwrap();
+    suite.withdraw_funds(USER, None, OTHER_WALLET).unwrap();
+    assert_eq!(
+        suite.query_balance(OTHER_WALLET, JUNO_DENOM).unwrap(),
+        1_000
+    );
+    assert_eq!(suite.query_balance(COLD_WALLET, JUNO_DENOM).unwrap(), 1_000);
+
+    // after clearing it, rewards go to the user again
+    suite.set_reward_receiver(USER, None).unwrap();
+    assert_eq!(suite.query_reward_receiver(USER).unwrap(), None);
+    suite
+        .distribute_funds("funder", None, Some(juno(1_000)))
+        .unwrap();
+    suite.withdraw_funds(USER, None, None).unwrap();
+    assert_eq!(suite.query_balance(USER, JUNO_DENOM).unwrap(), 1_000);
+    assert_eq!(suite.query_balance(COLD_WALLET, JUNO_DENOM).unwrap(), 1_000);
+}
@@ -0,0 +1,109 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::AssetInfo;
+
+use super::suite::{juno, SuiteBuilder, JUNO_DENOM};
+use crate::state::ScheduledMultipliers;
+use crate::ContractError;
+
+const UNBONDING_PERIODS: &[u64; 2] = &[100, 200];
+
+#[test]
+fn scheduled_multipliers_apply_after_effective_time() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(UNBONDING_PERIODS.to_vec())
+        .with_initial_balances(vec![("short", 10_000), ("long", 10_000)])
+        .with_native_balances(JUNO_DENOM, vec![("distributor", 4_000)])
+        .build();
+    let asset = AssetInfo::Native(JUNO_DENOM.to_string());
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            asset.clone(),
+            vec![(100, Decimal::one()), (200, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate("short", 10_000, 100).unwrap();
+    suite.delegate("long", 10_000, 200).unwrap();
+
+    let now = suite.app.block_info().time.seconds();
+    let tripled = vec![(100, Decimal::one()), (200, Decimal::percent(300))];
+
+    // only the manager can schedule, and only for the future
+    let err = suite
+        .schedule_multiplier_update("admin", asset.clone(), tripled.clone(), now + 1000)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = suite
+        .schedule_multiplier_update("manager", asset.clone(), tripled.clone(), now)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::EffectiveTimeNotInFuture {},
+        err.downcast().unwrap()
+    );
+
+    suite
+        .schedule_multiplier_update("manager", asset.clone(), tripled.clone(), now + 1000)
+        .unwrap();
+    assert_eq!(
+        suite.query_scheduled_multipliers(asset.clone()).unwrap(),
+        Some(ScheduledMultipliers {
+            rewards: tripled.clone(),
+            effective_at: now + 1000,
+        })
+    );
+
+    // before the effective time, the old multipliers apply
+    suite
+        .distribute_funds("distributor", None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("short").unwrap(),
+        vec![juno(500)]
+    );
+    assert_eq!(suite.withdrawable_rewards("long").unwrap(), vec![juno(500)]);
+    assert!(suite
+        .query_scheduled_multipliers(asset.clone())
+        .unwrap()
+        .is_some());
+
+    // rewards released until the change takes effect are still distributed with the old ones
+    suite.update_time(1000);
+    suite
+        .distribute_funds("distributor", None, Some(juno(1_000)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("short").unwrap(),
+        vec![juno(1_000)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards("long").unwrap(),
+        vec![juno(1_000)]
+    );
+    assert_eq!(
+        suite.query_scheduled_multipliers(asset.clone()).unwrap(),
+        None
+    );
+    assert_eq!(
+        suite.query_multiplier_history(asset.clone()).unwrap()[1].rewards,
+        tripled
+    );
+
+    // afterwards, the new ones apply once the stakers are updated
+    suite
+        .apply_distribution_update("anyone", asset, None)
+        .unwrap();
+    suite
+        .distribute_funds("distributor", None, Some(juno(2_000)))
+        .unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards("short").unwrap(),
+        vec![juno(1_500)]
+    );
+    assert_eq!(
+        suite.withdrawable_rewards("long").unwrap(),
+        vec![juno(2_500)]
+    );
+}
@@ -780,3 +780,50 @@ fn delegate_unbond_under_min_bond() {
         1_500u128
     );
 }
+
+#[test]
+fn total_rewards_power_excluding_address() {
+    let unbonding_period = 1000u64;
+    let mut suite = SuiteBuilder::new()
+        .with_unbonding_periods(vec![unbonding_period])
+        .with_admin("admin")
+        .with_initial_balances(vec![("alice", 10_000), ("bob", 30_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            "admin",
+            AssetInfo::Native("juno".to_string()),
+            vec![(unbonding_period, Decimal::one())],
+        )
+        .unwrap();
+
+    suite
+        .delegate("alice", 10_000u128, unbonding_period)
+        .unwrap();
+    suite.delegate("bob", 30_000u128, unbonding_period).unwrap();
+
+    let total = suite.query_total_rewards_power().unwrap()[0].1;
+    let alice = suite.query_rewards_power("alice").unwrap()[0].1;
+    let bob = suite.query_rewards_power("bob").unwrap()[0].1;
+    assert_eq!((total, alice, bob), (40, 10, 30));
+
+    assert_eq!(
+        suite.query_total_rewards_power_excluding("alice").unwrap(),
+        juno_power(total - alice)
+    );
+    assert_eq!(
+        suite.query_total_rewards_power_excluding("alice").unwrap(),
+        juno_power(bob)
+    );
+    assert_eq!(
+        suite.query_total_rewards_power_excluding("bob").unwrap(),
+        juno_power(alice)
+    );
+    // an address without stake excludes nothing
+    assert_eq!(
+        suite.query_total_rewards_power_excluding("carol").unwrap(),
+        juno_power(total)
+    );
+}
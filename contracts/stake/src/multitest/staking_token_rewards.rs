@@ -0,0 +1,328 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use wyndex::asset::{AssetInfo, AssetInfoExt, AssetInfoValidated};
+
+use super::suite::{SuiteBuilder, SEVEN_DAYS};
+use crate::msg::{AssetReconciliationResponse, RewardTokenBalanceResponse};
+use crate::ContractError;
+
+const ADMIN: &str = "admin";
+const FUNDER: &str = "funder";
+const MEMBER: &str = "member";
+
+#[test]
+fn principal_and_rewards_are_tracked_separately() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_initial_balances(vec![(MEMBER, 100_000), (FUNDER, 10_000)])
+        .build();
+    let token = AssetInfoValidated::Token(Addr::unchecked(suite.token_contract()));
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Token(suite.token_contract()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+
+    suite.delegate(MEMBER, 100_000, None).unwrap();
+    assert_eq!(
+        suite.query_reward_token_balance().unwrap(),
+        RewardTokenBalanceResponse {
+            principal: Uint128::new(100_000),
+            rewards: Uint128::zero(),
+        }
+    );
+
+    // fund the staking token flow with the staking token itself
+    suite
+        .execute_fund_distribution_with_cw20(FUNDER, token.with_balance(10_000u128))
+        .unwrap();
+    assert_eq!(
+        suite.query_reward_token_balance().unwrap(),
+        RewardTokenBalanceResponse {
+            principal: Uint128::new(100_000),
+            rewards: Uint128::new(10_000),
+        }
+    );
+
+    // only the funded rewards are distributed, not the bonded principal
+    suite.update_time(100);
+    suite.distribute_funds(ADMIN, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(MEMBER).unwrap(),
+        vec![token.with_balance(10_000u128)]
+    );
+    assert_eq!(
+        suite.undistributed_funds().unwrap(),
+        vec![token.with_balance(0u128)]
+    );
+
+    // unbonding tokens are still principal and are not distributed either
+    suite.unbond(MEMBER, 50_000, None).unwrap();
+    suite.distribute_funds(ADMIN, None, None).unwrap();
+    assert_eq!(
+        suite.withdrawable_rewards(MEMBER).unwrap(),
+        vec![token.with_balance(10_000u128)]
+    );
+    assert_eq!(
+        suite.query_reward_token_balance().unwrap(),
+        RewardTokenBalanceResponse {
+            principal: Uint128::new(100_000),
+            rewards: Uint128::new(10_000),
+        }
+    );
+
+    // compounding moves the rewards into the bonded principal
+    suite.compound_rewards(MEMBER, None).unwrap();
+    assert_eq!(suite.query_staked(MEMBER, None).unwrap(), 60_000);
+    assert_eq!(
+        suite.withdrawable_rewards(MEMBER).unwrap(),
+        vec![token.with_balance(0u128)]
+    );
+    assert_eq!(
+        suite.query_reward_token_balance().unwrap(),
+        RewardTokenBalanceResponse {
+            principal: Uint128::new(110_000),
+            rewards: Uint128::zero(),
+        }
+    );
+    assert_eq!(suite.query_balance_staking_contract().unwrap(), 110_000);
+
+    // nothing left to compound
+    let err = suite.compound_rewards(MEMBER, None).unwrap_err();
+    assert_eq!(
+        ContractError::NoRewardsToCompound {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn asset_reconciliation_separates_principal() {
+    let manager = "manager";
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_unbonding_periods(vec![100])
+        .with_initial_balances(vec![(MEMBER, 100_000), (FUNDER, 10_000)])
+        .with_native_balances("juno", vec![(manager, 1_000)])
+        .build();
+    let token_info = AssetInfo::Token(suite.token_contract());
+    let juno_info = AssetInfo::Native("juno".to_string());
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            token_info.clone(),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            manager,
+            juno_info.clone(),
+            vec![(100, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(MEMBER, 100_000, 100).unwrap();
+
+    let token = AssetInfoValidated::Token(Addr::unchecked(suite.token_contract()));
+    suite
+        .execute_fund_distribution_with_cw20(FUNDER, token.with_balance(10_000u128))
+        .unwrap();
+    suite
+        .execute_fund_distribution_curve(manager, "juno", 1_000, 100)
+        .unwrap();
+
+    let check = |reconciliation: &AssetReconciliationResponse| {
+        assert_eq!(
+            reconciliation.surplus,
+            reconciliation.contract_balance
+                - reconciliation.owed_claims_if_staking_token
+                - reconciliation.owed_withdrawable
+                - reconciliation.owed_undistributed
+        );
+    };
+
+    // all funded rewards are still locked by the curves
+    let reconciliation = suite
+        .query_asset_reconciliation(token_info.clone())
+        .unwrap();
+    check(&reconciliation);
+    assert_eq!(
+        reconciliation,
+        AssetReconciliationResponse {
+            contract_balance: Uint128::new(110_000),
+            owed_undistributed: Uint128::new(10_000),
+            owed_withdrawable: Uint128::zero(),
+            owed_claims_if_staking_token: Uint128::new(100_000),
+            surplus: Uint128::zero(),
+        }
+    );
+    let reconciliation = suite.query_asset_reconciliation(juno_info.clone()).unwrap();
+    check(&reconciliation);
+    assert_eq!(reconciliation.contract_balance.u128(), 1_000);
+    assert_eq!(reconciliation.owed_claims_if_staking_token, Uint128::zero());
+    assert_eq!(reconciliation.surplus, Uint128::zero());
+
+    // released rewards are surplus until they are distributed
+    suite.update_time(40);
+    let reconciliation = suite
+        .query_asset_reconciliation(token_info.clone())
+        .unwrap();
+    check(&reconciliation);
+    assert_eq!(reconciliation.owed_undistributed.u128(), 6_000);
+    assert_eq!(reconciliation.surplus.u128(), 4_000);
+
+    suite.distribute_funds(ADMIN, None, None).unwrap();
+    // unbonding tokens are still owed to the staker
+    suite.unbond(MEMBER, 50_000, 100).unwrap();
+    let reconciliation = suite.query_asset_reconciliation(token_info).unwrap();
+    check(&reconciliation);
+    assert_eq!(reconciliation.owed_claims_if_staking_token.u128(), 100_000);
+    assert_eq!(reconciliation.owed_withdrawable.u128(), 4_000);
+    assert_eq!(reconciliation.surplus, Uint128::zero());
+    let reconciliation = suite.query_asset_reconciliation(juno_info).unwrap();
+    check(&reconciliation);
+    assert_eq!(reconciliation.owed_withdrawable.u128(), 400);
+    assert_eq!(reconciliation.owed_undistributed.u128(), 600);
+    assert_eq!(reconciliation.surplus, Uint128::zero());
+}
+
+#[test]
+fn auto_compound_bonds_rewards_of_opted_in_users() {
+    const OTHER: &str = "other";
+    const KEEPER: &str = "keeper";
+    let long = 2 * SEVEN_DAYS;
+
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_unbonding_periods(vec![SEVEN_DAYS, long])
+        .with_initial_balances(vec![(MEMBER, 50_000), (OTHER, 50_000), (FUNDER, 20_000)])
+        .build();
+    let token = AssetInfoValidated::Token(Addr::unchecked(suite.token_contract()));
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Token(suite.token_contract()),
+            vec![(SEVEN_DAYS, Decimal::one()), (long, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(MEMBER, 50_000, SEVEN_DAYS).unwrap();
+    suite.delegate(OTHER, 50_000, SEVEN_DAYS).unwrap();
+
+    // only existing unbonding periods can be chosen
+    let err = suite.set_auto_compound(MEMBER, true, 1).unwrap_err();
+    assert_eq!(
+        ContractError::NoUnbondingPeriodFound(1),
+        err.downcast().unwrap()
+    );
+    suite.set_auto_compound(MEMBER, true, long).unwrap();
+
+    suite
+        .execute_fund_distribution_with_cw20(FUNDER, token.with_balance(10_000u128))
+        .unwrap();
+    suite.update_time(100);
+    suite.distribute_funds(ADMIN, None, None).unwrap();
+
+    // the keeper only compounds for users that opted in
+    suite.run_auto_compound(KEEPER, &[MEMBER, OTHER]).unwrap();
+    assert_eq!(suite.query_staked(MEMBER, SEVEN_DAYS).unwrap(), 50_000);
+    assert_eq!(suite.query_staked(MEMBER, long).unwrap(), 5_000);
+    assert_eq!(
+        suite.withdrawable_rewards(MEMBER).unwrap(),
+        vec![token.with_balance(0u128)]
+    );
+    assert_eq!(suite.query_staked(OTHER, long).unwrap(), 0);
+    assert_eq!(
+        suite.withdrawable_rewards(OTHER).unwrap(),
+        vec![token.with_balance(5_000u128)]
+    );
+    assert_eq!(
+        suite.query_reward_token_balance().unwrap(),
+        RewardTokenBalanceResponse {
+            principal: Uint128::new(105_000),
+            rewards: Uint128::new(5_000),
+        }
+    );
+
+    // running again with nothing to compound is a no-op
+    suite.run_auto_compound(KEEPER, &[MEMBER]).unwrap();
+    assert_eq!(suite.query_staked(MEMBER, long).unwrap(), 5_000);
+
+    // after opting out, rewards are left alone
+    suite.set_auto_compound(MEMBER, false, long).unwrap();
+    suite
+        .execute_fund_distribution_with_cw20(FUNDER, token.with_balance(10_000u128))
+        .unwrap();
+    suite.update_time(100);
+    suite.distribute_funds(ADMIN, None, None).unwrap();
+    let withdrawable = suite.withdrawable_rewards(MEMBER).unwrap();
+    assert!(!withdrawable[0].amount.is_zero());
+    suite.run_auto_compound(KEEPER, &[MEMBER]).unwrap();
+    assert_eq!(suite.query_staked(MEMBER, long).unwrap(), 5_000);
+    assert_eq!(suite.withdrawable_rewards(MEMBER).unwrap(), withdrawable);
+}
+
+#[test]
+fn withdraw_and_restake_bonds_staked_token_rewards() {
+    let manager = "manager";
+    let mut suite = SuiteBuilder::new()
+        .with_admin(ADMIN)
+        .with_initial_balances(vec![(MEMBER, 100_000), (FUNDER, 10_000)])
+        .with_native_balances("juno", vec![(manager, 1_000)])
+        .build();
+    let token = AssetInfoValidated::Token(Addr::unchecked(suite.token_contract()));
+
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            ADMIN,
+            AssetInfo::Token(suite.token_contract()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite
+        .create_distribution_flow(
+            ADMIN,
+            manager,
+            AssetInfo::Native("juno".to_string()),
+            vec![(SEVEN_DAYS, Decimal::one())],
+        )
+        .unwrap();
+    suite.delegate(MEMBER, 100_000, None).unwrap();
+    let power_before = suite.query_rewards_power(MEMBER).unwrap()[0].1;
+
+    suite
+        .execute_fund_distribution_with_cw20(FUNDER, token.with_balance(10_000u128))
+        .unwrap();
+    suite.update_time(100);
+    suite.distribute_funds(ADMIN, None, None).unwrap();
+
+    // the rewards are bonded without ever reaching the member's wallet
+    suite.withdraw_and_restake(MEMBER, None).unwrap();
+    assert_eq!(suite.query_staked(MEMBER, None).unwrap(), 110_000);
+    assert_eq!(
+        suite
+            .query_cw20_balance(MEMBER, suite.token_contract())
+            .unwrap(),
+        0
+    );
+    assert!(suite.query_rewards_power(MEMBER).unwrap()[0].1 > power_before);
+
+    // rewards in other assets cannot be restaked
+    suite
+        .execute_fund_distribution_curve(manager, "juno", 1_000, 100)
+        .unwrap();
+    suite.update_time(100);
+    suite.distribute_funds(ADMIN, None, None).unwrap();
+    let err = suite.withdraw_and_restake(MEMBER, None).unwrap_err();
+    assert_eq!(
+        ContractError::CannotRestakeAsset(AssetInfoValidated::Native("juno".to_string())),
+        err.downcast().unwrap()
+    );
+}
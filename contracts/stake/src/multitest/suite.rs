@@ -6,18 +6,24 @@ use cosmwasm_std::{to_binary, Addr, Coin, Decimal, Empty, StdResult, Uint128};
 use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
 use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
 use cw_controllers::{Claim, ClaimsResponse};
-use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use cw_multi_test::{next_block, App, AppResponse, Contract, ContractWrapper, Executor};
 use wyndex::{
     asset::{AssetInfo, AssetInfoExt, AssetInfoValidated, AssetValidated},
     stake::{InstantiateMsg, UnbondingPeriod},
 };
 
 use crate::msg::{
-    AllStakedResponse, AnnualizedReward, AnnualizedRewardsResponse, BondingInfoResponse,
-    BondingPeriodInfo, DelegatedResponse, DistributedRewardsResponse, ExecuteMsg, QueryMsg,
-    RewardsPowerResponse, StakedResponse, TotalStakedResponse, UnbondAllResponse,
-    UndistributedRewardsResponse, WithdrawableRewardsResponse,
+    AllStakedResponse, AnnualizedReward, AnnualizedRewardsResponse, AssetReconciliationResponse,
+    BondingInfoResponse, BondingPeriodInfo, DelegatedResponse, DetailedWithdrawableRewards,
+    DistributedRewardsResponse, DistributionFunding, EarnedSinceWithdrawResponse, ExecuteMsg,
+    FlowAccountingResponse, FlowSchedule, FundingSource, LastInteractionResponse,
+    NextRewardChangeResponse, PowerDecayConfig, QueryMsg, RateAtResponse, RebondCooldownResponse,
+    RewardReceiverResponse, RewardTokenBalanceResponse, RewardsPowerResponse, StakedResponse,
+    StakerCountResponse, TotalStakedResponse, TotalToBeDistributedResponse, UnbondAllResponse,
+    UndistributedRewardsResponse, UserSummaryResponse, WithdrawableRewardsDetailedResponse,
+    WithdrawableRewardsResponse,
 };
+use crate::state::{InteractionBlock, MultiplierChange, RoundingMode, ScheduledMultipliers};
 use wyndex::stake::{FundingInfo, ReceiveMsg};
 
 pub const SEVEN_DAYS: u64 = 604800;
@@ -201,6 +207,7 @@ impl SuiteBuilder {
         Suite {
             app,
             token_id,
+            stake_id,
             stake_contract,
             token_contract,
         }
@@ -210,6 +217,7 @@ impl SuiteBuilder {
 pub struct Suite {
     pub app: App,
     token_id: u64,
+    stake_id: u64,
     stake_contract: Addr,
     token_contract: Addr,
 }
@@ -230,6 +238,11 @@ impl Suite {
         self.app.set_block(block);
     }
 
+    // advance to the next block
+    pub fn next_block(&mut self) {
+        self.app.update_block(next_block);
+    }
+
     /// Create a new token contract and return the address
     pub fn instantiate_token(
         &mut self,
@@ -268,6 +281,33 @@ impl Suite {
             .unwrap()
     }
 
+    /// Create another staking contract for the same token and return the address
+    pub fn instantiate_stake_contract(
+        &mut self,
+        admin: &str,
+        unbonding_periods: Vec<UnbondingPeriod>,
+    ) -> Addr {
+        self.app
+            .instantiate_contract(
+                self.stake_id,
+                Addr::unchecked("admin"),
+                &InstantiateMsg {
+                    cw20_contract: self.token_contract.to_string(),
+                    tokens_per_power: Uint128::new(1000),
+                    min_bond: Uint128::new(5000),
+                    unbonding_periods,
+                    admin: Some(admin.to_owned()),
+                    unbonder: None,
+                    max_distributions: 6,
+                    converter: None,
+                },
+                &[],
+                "stake",
+                None,
+            )
+            .unwrap()
+    }
+
     fn unbonding_period_or_default(&self, unbonding_period: impl Into<Option<u64>>) -> u64 {
         // Use default SEVEN_DAYS unbonding period if none provided
         if let Some(up) = unbonding_period.into() {
@@ -284,6 +324,18 @@ impl Suite {
         manager: &str,
         asset: AssetInfo,
         rewards: Vec<(UnbondingPeriod, Decimal)>,
+    ) -> AnyResult<AppResponse> {
+        self.create_distribution_flow_with_claimant_share(sender, manager, asset, rewards, None)
+    }
+
+    // create a new distribution flow that keeps rewarding unbonding tokens
+    pub fn create_distribution_flow_with_claimant_share(
+        &mut self,
+        sender: &str,
+        manager: &str,
+        asset: AssetInfo,
+        rewards: Vec<(UnbondingPeriod, Decimal)>,
+        claimant_reward_share: impl Into<Option<Decimal>>,
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(sender),
@@ -292,6 +344,7 @@ impl Suite {
                 manager: manager.to_string(),
                 asset,
                 rewards,
+                claimant_reward_share: claimant_reward_share.into(),
             },
             &[],
         )
@@ -314,6 +367,19 @@ impl Suite {
         amount: u128,
         unbonding_period: impl Into<Option<u64>>,
         delegate_as: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.delegate_with_rights(sender, amount, unbonding_period, delegate_as, None, None)
+    }
+
+    // call to staking contract by sender
+    pub fn delegate_with_rights(
+        &mut self,
+        sender: &str,
+        amount: u128,
+        unbonding_period: impl Into<Option<u64>>,
+        delegate_as: Option<&str>,
+        unbond_controller: Option<&str>,
+        withdraw_to: Option<&str>,
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(sender),
@@ -324,6 +390,8 @@ impl Suite {
                 msg: to_binary(&ReceiveMsg::Delegate {
                     unbonding_period: self.unbonding_period_or_default(unbonding_period),
                     delegate_as: delegate_as.map(|s| s.to_string()),
+                    unbond_controller: unbond_controller.map(|s| s.to_string()),
+                    withdraw_to: withdraw_to.map(|s| s.to_string()),
                 })?,
             },
             &[],
@@ -358,6 +426,34 @@ impl Suite {
         )
     }
 
+    // call to staking contract by sender
+    pub fn delegate_shared(
+        &mut self,
+        sender: &str,
+        amount: u128,
+        unbonding_period: impl Into<Option<u64>>,
+        shares: &[(&str, Decimal)],
+    ) -> AnyResult<AppResponse> {
+        let shares = shares
+            .iter()
+            .map(|(addr, share)| (addr.to_string(), *share))
+            .collect();
+
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.token_contract.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.stake_contract.to_string(),
+                amount: amount.into(),
+                msg: to_binary(&ReceiveMsg::DelegateShared {
+                    shares,
+                    unbonding_period: self.unbonding_period_or_default(unbonding_period),
+                })?,
+            },
+            &[],
+        )
+    }
+
     // call to stake contract by sender
     pub fn rebond(
         &mut self,
@@ -365,6 +461,18 @@ impl Suite {
         amount: u128,
         bond_from: impl Into<Option<u64>>,
         bond_to: impl Into<Option<u64>>,
+    ) -> AnyResult<AppResponse> {
+        self.rebond_for(sender, None, amount, bond_from, bond_to)
+    }
+
+    // rebond the stake of `owner`, called by its unbond controller
+    pub fn rebond_for(
+        &mut self,
+        sender: &str,
+        owner: Option<&str>,
+        amount: u128,
+        bond_from: impl Into<Option<u64>>,
+        bond_to: impl Into<Option<u64>>,
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(sender),
@@ -373,6 +481,27 @@ impl Suite {
                 tokens: amount.into(),
                 bond_from: self.unbonding_period_or_default(bond_from),
                 bond_to: self.unbonding_period_or_default(bond_to),
+                owner: owner.map(|s| s.to_string()),
+            },
+            &[],
+        )
+    }
+
+    pub fn rebond_split(
+        &mut self,
+        sender: &str,
+        bond_from: u64,
+        targets: &[(u64, u128)],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.stake_contract.clone(),
+            &ExecuteMsg::RebondSplit {
+                bond_from,
+                targets: targets
+                    .iter()
+                    .map(|&(period, amount)| (period, amount.into()))
+                    .collect(),
             },
             &[],
         )
@@ -383,6 +512,34 @@ impl Suite {
         sender: &str,
         amount: u128,
         unbonding_period: impl Into<Option<u64>>,
+    ) -> AnyResult<AppResponse> {
+        self.unbond_for(sender, None, amount, unbonding_period)
+    }
+
+    pub fn cancel_unbonding(
+        &mut self,
+        sender: &str,
+        amount: u128,
+        unbonding_period: impl Into<Option<u64>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.stake_contract.clone(),
+            &ExecuteMsg::CancelUnbonding {
+                unbonding_period: self.unbonding_period_or_default(unbonding_period),
+                amount: amount.into(),
+            },
+            &[],
+        )
+    }
+
+    // unbond the stake of `owner`, called by its unbond controller
+    pub fn unbond_for(
+        &mut self,
+        sender: &str,
+        owner: Option<&str>,
+        amount: u128,
+        unbonding_period: impl Into<Option<u64>>,
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(sender),
@@ -390,11 +547,21 @@ impl Suite {
             &ExecuteMsg::Unbond {
                 tokens: amount.into(),
                 unbonding_period: self.unbonding_period_or_default(unbonding_period),
+                owner: owner.map(|s| s.to_string()),
             },
             &[],
         )
     }
 
+    pub fn exit_all(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.stake_contract.clone(),
+            &ExecuteMsg::ExitAll {},
+            &[],
+        )
+    }
+
     pub fn quick_unbond(&mut self, sender: &str, stakers: &[&str]) -> AnyResult<AppResponse> {
         let stakers = stakers.iter().map(|s| s.to_string()).collect();
         self.app.execute_contract(
@@ -405,6 +572,25 @@ impl Suite {
         )
     }
 
+    pub fn rescue_tokens(
+        &mut self,
+        sender: &str,
+        asset: AssetInfo,
+        recipient: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.stake_contract.clone(),
+            &ExecuteMsg::RescueTokens {
+                asset,
+                recipient: recipient.to_owned(),
+                amount: amount.into(),
+            },
+            &[],
+        )
+    }
+
     pub fn claim(&mut self, sender: &str) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(sender),
@@ -414,6 +600,45 @@ impl Suite {
         )
     }
 
+    pub fn set_claim_receiver(
+        &mut self,
+        sender: &str,
+        receiver: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetClaimReceiver {
+                receiver: receiver.map(str::to_owned),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_reward_receiver(
+        &mut self,
+        sender: &str,
+        receiver: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetRewardReceiver {
+                receiver: receiver.map(str::to_owned),
+            },
+            &[],
+        )
+    }
+
+    pub fn claim_and_withdraw_all(&mut self, sender: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.stake_contract.clone(),
+            &ExecuteMsg::ClaimAndWithdrawAll {},
+            &[],
+        )
+    }
+
     // call to vesting contract
     pub fn transfer(
         &mut self,
@@ -456,6 +681,20 @@ impl Suite {
         )
     }
 
+    /// Distributes rewards with the native `funds` attached to the message, without a separate transfer
+    pub fn distribute_with_funds(
+        &mut self,
+        executor: &str,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::DistributeRewards { sender: None },
+            funds,
+        )
+    }
+
     pub fn execute_fund_distribution<'s>(
         &mut self,
         executor: &str,
@@ -475,6 +714,7 @@ impl Suite {
                     distribution_duration: 100,
                     amount: funds.amount,
                 },
+                on_behalf_of: None,
             },
             &[Coin {
                 denom: funds.info.to_string(),
@@ -501,6 +741,7 @@ impl Suite {
                     distribution_duration,
                     amount: Uint128::from(amount),
                 },
+                on_behalf_of: None,
             },
             &[Coin {
                 denom: denom.into(),
@@ -510,6 +751,29 @@ impl Suite {
     }
 
     // call to staking contract by sender
+    pub fn execute_fund_distribution_multi(
+        &mut self,
+        executor: &str,
+        fundings: Vec<(AssetInfo, FundingInfo)>,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::FundDistributionMulti {
+                fundings: fundings
+                    .into_iter()
+                    .map(|(asset, funding_info)| DistributionFunding {
+                        asset,
+                        funding_info,
+                    })
+                    .collect(),
+                on_behalf_of: None,
+            },
+            funds,
+        )
+    }
+
     pub fn execute_fund_distribution_with_cw20(
         &mut self,
         executor: &str,
@@ -545,7 +809,37 @@ impl Suite {
             &Cw20ExecuteMsg::Send {
                 contract: self.stake_contract.to_string(),
                 amount: funds.amount,
-                msg: to_binary(&ReceiveMsg::Fund { funding_info })?,
+                msg: to_binary(&ReceiveMsg::Fund {
+                    funding_info,
+                    on_behalf_of: None,
+                })?,
+            },
+            &[],
+        )
+    }
+
+    /// Funds a cw20 distribution flow, attributing the funds to `on_behalf_of`
+    pub fn execute_fund_distribution_with_cw20_on_behalf_of(
+        &mut self,
+        executor: &str,
+        funds: AssetValidated,
+        funding_info: FundingInfo,
+        on_behalf_of: &str,
+    ) -> AnyResult<AppResponse> {
+        let token = match funds.info {
+            AssetInfoValidated::Token(contract_addr) => contract_addr,
+            _ => bail!("Only tokens are supported for cw20 distribution"),
+        };
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            token,
+            &Cw20ExecuteMsg::Send {
+                contract: self.stake_contract.to_string(),
+                amount: funds.amount,
+                msg: to_binary(&ReceiveMsg::Fund {
+                    funding_info,
+                    on_behalf_of: Some(on_behalf_of.to_owned()),
+                })?,
             },
             &[],
         )
@@ -586,67 +880,691 @@ impl Suite {
         )
     }
 
-    #[allow(dead_code)]
-    pub fn delegate_withdrawal(
+    pub fn withdraw_funds_batch(
         &mut self,
         executor: &str,
-        delegated: &str,
+        owners: &[&str],
     ) -> AnyResult<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(executor),
             self.stake_contract.clone(),
-            &ExecuteMsg::DelegateWithdrawal {
-                delegated: delegated.to_owned(),
+            &ExecuteMsg::WithdrawRewardsBatch {
+                owners: owners.iter().map(|owner| owner.to_string()).collect(),
             },
             &[],
         )
     }
 
-    pub fn withdrawable_rewards(&self, owner: &str) -> StdResult<Vec<AssetValidated>> {
-        let resp: WithdrawableRewardsResponse = self.app.wrap().query_wasm_smart(
+    pub fn compound_rewards(
+        &mut self,
+        executor: &str,
+        unbonding_period: impl Into<Option<u64>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
             self.stake_contract.clone(),
-            &QueryMsg::WithdrawableRewards {
-                owner: owner.to_owned(),
+            &ExecuteMsg::CompoundRewards {
+                unbonding_period: self.unbonding_period_or_default(unbonding_period),
             },
-        )?;
-        Ok(resp.rewards)
+            &[],
+        )
     }
 
-    pub fn distributed_funds(&self) -> StdResult<Vec<AssetValidated>> {
-        let resp: DistributedRewardsResponse = self.app.wrap().query_wasm_smart(
+    pub fn withdraw_and_restake(
+        &mut self,
+        executor: &str,
+        unbonding_period: impl Into<Option<u64>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
             self.stake_contract.clone(),
-            &QueryMsg::DistributedRewards {},
-        )?;
-        Ok(resp.distributed)
+            &ExecuteMsg::WithdrawAndRestake {
+                unbonding_period: self.unbonding_period_or_default(unbonding_period),
+            },
+            &[],
+        )
     }
 
-    pub fn withdrawable_funds(&self) -> StdResult<Vec<AssetValidated>> {
-        let resp: DistributedRewardsResponse = self.app.wrap().query_wasm_smart(
+    pub fn set_auto_compound(
+        &mut self,
+        executor: &str,
+        enabled: bool,
+        unbonding_period: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
             self.stake_contract.clone(),
-            &QueryMsg::DistributedRewards {},
-        )?;
-        Ok(resp.withdrawable)
+            &ExecuteMsg::SetAutoCompound {
+                enabled,
+                unbonding_period,
+            },
+            &[],
+        )
     }
 
-    pub fn undistributed_funds(&self) -> StdResult<Vec<AssetValidated>> {
-        let resp: UndistributedRewardsResponse = self.app.wrap().query_wasm_smart(
+    pub fn run_auto_compound(&mut self, executor: &str, users: &[&str]) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
             self.stake_contract.clone(),
-            &QueryMsg::UndistributedRewards {},
-        )?;
-        Ok(resp.rewards)
+            &ExecuteMsg::RunAutoCompound {
+                users: users.iter().map(|user| user.to_string()).collect(),
+            },
+            &[],
+        )
     }
 
-    #[allow(dead_code)]
-    pub fn delegated(&self, owner: &str) -> StdResult<Addr> {
-        let resp: DelegatedResponse = self.app.wrap().query_wasm_smart(
-            self.stake_contract.clone(),
-            &QueryMsg::Delegated {
-                owner: owner.to_owned(),
+    pub fn update_power_decay(
+        &mut self,
+        executor: &str,
+        power_decay: Option<(u64, u64)>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::UpdatePowerDecay {
+                power_decay: power_decay.map(|(inactivity_window, decay_duration)| {
+                    PowerDecayConfig {
+                        inactivity_window,
+                        decay_duration,
+                    }
+                }),
+            },
+            &[],
+        )
+    }
+
+    pub fn apply_decay(&mut self, executor: &str, stakers: &[&str]) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::ApplyDecay {
+                stakers: stakers.iter().map(|s| s.to_string()).collect(),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_min_bond(&mut self, executor: &str, value: u128) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetMinBond {
+                value: value.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn apply_min_bond(
+        &mut self,
+        executor: &str,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::ApplyMinBond {
+                limit: limit.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_power_rounding(
+        &mut self,
+        executor: &str,
+        mode: RoundingMode,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetPowerRounding { mode },
+            &[],
+        )
+    }
+
+    pub fn remove_unbonding_period(
+        &mut self,
+        executor: &str,
+        unbonding_period: UnbondingPeriod,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::RemoveUnbondingPeriod { unbonding_period },
+            &[],
+        )
+    }
+
+    pub fn add_unbonding_period(
+        &mut self,
+        executor: &str,
+        unbonding_period: UnbondingPeriod,
+        rewards: Vec<(AssetInfo, Decimal)>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::AddUnbondingPeriod {
+                unbonding_period,
+                rewards,
+            },
+            &[],
+        )
+    }
+
+    pub fn instant_unbond(
+        &mut self,
+        sender: &str,
+        amount: u128,
+        unbonding_period: UnbondingPeriod,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(sender),
+            self.stake_contract.clone(),
+            &ExecuteMsg::InstantUnbond {
+                tokens: amount.into(),
+                unbonding_period,
+            },
+            &[],
+        )
+    }
+
+    pub fn set_instant_unbond(
+        &mut self,
+        executor: &str,
+        fee: Option<Decimal>,
+        treasury: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetInstantUnbond {
+                fee,
+                treasury: treasury.map(|addr| addr.to_owned()),
+            },
+            &[],
+        )
+    }
+
+    pub fn recall_undistributed(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::RecallUndistributed {
+                asset,
+                amount: amount.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_min_distribute_interval(
+        &mut self,
+        executor: &str,
+        interval: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetMinDistributeInterval { interval },
+            &[],
+        )
+    }
+
+    pub fn set_max_reward_multiplier(
+        &mut self,
+        executor: &str,
+        max: Decimal,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetMaxRewardMultiplier { max },
+            &[],
+        )
+    }
+
+    pub fn set_max_distributions(
+        &mut self,
+        executor: &str,
+        new_max: u32,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetMaxDistributions { new_max },
+            &[],
+        )
+    }
+
+    pub fn set_funding_source(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+        source: Option<FundingSource>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetFundingSource { asset, source },
+            &[],
+        )
+    }
+
+    pub fn set_min_power_to_earn(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+        min_power: u128,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetMinPowerToEarn {
+                asset,
+                min_power: min_power.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_distribution_paused(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+        paused: bool,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetDistributionPaused { asset, paused },
+            &[],
+        )
+    }
+
+    pub fn pause_distribution(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::PauseDistribution { asset },
+            &[],
+        )
+    }
+
+    pub fn resume_distribution(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::ResumeDistribution { asset },
+            &[],
+        )
+    }
+
+    pub fn update_distribution_multipliers(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+        rewards: Vec<(UnbondingPeriod, Decimal)>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::UpdateDistributionMultipliers { asset, rewards },
+            &[],
+        )
+    }
+
+    pub fn apply_distribution_update(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+        limit: impl Into<Option<u32>>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::ApplyDistributionUpdate {
+                asset,
+                limit: limit.into(),
+            },
+            &[],
+        )
+    }
+
+    pub fn remove_distribution_flow(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::RemoveDistributionFlow { asset },
+            &[],
+        )
+    }
+
+    pub fn schedule_multiplier_update(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+        rewards: Vec<(UnbondingPeriod, Decimal)>,
+        effective_at: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::ScheduleMultiplierUpdate {
+                asset,
+                rewards,
+                effective_at,
+            },
+            &[],
+        )
+    }
+
+    pub fn accept_funding_source(
+        &mut self,
+        executor: &str,
+        asset: AssetInfo,
+        amount: u128,
+        epoch: u64,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::AcceptFundingSource {
+                asset,
+                amount: amount.into(),
+                epoch,
+            },
+            &[],
+        )
+    }
+
+    pub fn increase_allowance(
+        &mut self,
+        owner: &str,
+        spender: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(owner),
+            self.token_contract.clone(),
+            &Cw20ExecuteMsg::IncreaseAllowance {
+                spender: spender.to_string(),
+                amount: amount.into(),
+                expires: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn prepay_funding(&mut self, executor: &str, funds: &[Coin]) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::PrepayFunding {},
+            funds,
+        )
+    }
+
+    pub fn pull_funding(&mut self, executor: &str, asset: AssetInfo) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::PullFunding { asset },
+            &[],
+        )
+    }
+
+    pub fn set_migration_target(
+        &mut self,
+        executor: &str,
+        target: Option<&Addr>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetMigrationTarget {
+                target: target.map(Addr::to_string),
+            },
+            &[],
+        )
+    }
+
+    pub fn migrate_position(&mut self, executor: &str, target: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::MigratePosition {
+                target_contract: target.to_string(),
+            },
+            &[],
+        )
+    }
+
+    #[allow(dead_code)]
+    pub fn delegate_withdrawal(
+        &mut self,
+        executor: &str,
+        delegated: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::DelegateWithdrawal {
+                delegated: delegated.to_owned(),
+            },
+            &[],
+        )
+    }
+
+    pub fn set_delegation_rights(
+        &mut self,
+        executor: &str,
+        unbond_controller: Option<&str>,
+        withdraw_to: Option<&str>,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(executor),
+            self.stake_contract.clone(),
+            &ExecuteMsg::SetDelegationRights {
+                unbond_controller: unbond_controller.map(str::to_owned),
+                withdraw_to: withdraw_to.map(str::to_owned),
+            },
+            &[],
+        )
+    }
+
+    pub fn withdrawable_rewards(&self, owner: &str) -> StdResult<Vec<AssetValidated>> {
+        let resp: WithdrawableRewardsResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::WithdrawableRewards {
+                owner: owner.to_owned(),
+            },
+        )?;
+        Ok(resp.rewards)
+    }
+
+    pub fn withdrawable_rewards_detailed(
+        &self,
+        owner: &str,
+    ) -> StdResult<Vec<DetailedWithdrawableRewards>> {
+        let resp: WithdrawableRewardsDetailedResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::WithdrawableRewardsDetailed {
+                owner: owner.to_owned(),
+            },
+        )?;
+        Ok(resp.rewards)
+    }
+
+    pub fn withdrawable_rewards_at(
+        &self,
+        owner: &str,
+        at_time: u64,
+    ) -> StdResult<Vec<AssetValidated>> {
+        let resp: WithdrawableRewardsResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::WithdrawableAt {
+                owner: owner.to_owned(),
+                at_time,
+            },
+        )?;
+        Ok(resp.rewards)
+    }
+
+    pub fn distributed_funds(&self) -> StdResult<Vec<AssetValidated>> {
+        let resp: DistributedRewardsResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::DistributedRewards {},
+        )?;
+        Ok(resp.distributed)
+    }
+
+    pub fn withdrawable_funds(&self) -> StdResult<Vec<AssetValidated>> {
+        let resp: DistributedRewardsResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::DistributedRewards {},
+        )?;
+        Ok(resp.withdrawable)
+    }
+
+    pub fn undistributed_funds(&self) -> StdResult<Vec<AssetValidated>> {
+        let resp: UndistributedRewardsResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::UndistributedRewards {},
+        )?;
+        Ok(resp.rewards)
+    }
+
+    pub fn query_flow_accounting(&self, asset: AssetInfo) -> StdResult<FlowAccountingResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::FlowAccounting { asset },
+        )
+    }
+
+    pub fn query_lifetime_funded(&self) -> StdResult<Vec<AssetValidated>> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.stake_contract.clone(), &QueryMsg::LifetimeFunded {})
+    }
+
+    pub fn query_flows_schedule(&self) -> StdResult<Vec<FlowSchedule>> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.stake_contract.clone(), &QueryMsg::FlowsSchedule {})
+    }
+
+    pub fn query_asset_reconciliation(
+        &self,
+        asset: AssetInfo,
+    ) -> StdResult<AssetReconciliationResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::AssetReconciliation { asset },
+        )
+    }
+
+    pub fn query_user_multipliers(
+        &self,
+        address: &str,
+    ) -> StdResult<Vec<(AssetInfoValidated, UnbondingPeriod, Decimal)>> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::UserMultipliers {
+                address: address.to_owned(),
+            },
+        )
+    }
+
+    pub fn query_multiplier_history(&self, asset: AssetInfo) -> StdResult<Vec<MultiplierChange>> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::MultiplierHistory { asset },
+        )
+    }
+
+    pub fn query_scheduled_multipliers(
+        &self,
+        asset: AssetInfo,
+    ) -> StdResult<Option<ScheduledMultipliers>> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::ScheduledMultipliers { asset },
+        )
+    }
+
+    pub fn query_earned_since_withdraw(
+        &self,
+        owner: &str,
+    ) -> StdResult<EarnedSinceWithdrawResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::EarnedSinceWithdraw {
+                owner: owner.to_owned(),
+            },
+        )
+    }
+
+    pub fn query_distribution_preview(
+        &self,
+        asset: AssetInfo,
+        amount: u128,
+    ) -> StdResult<Vec<(UnbondingPeriod, Uint128)>> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::DistributionPreview {
+                asset,
+                amount: amount.into(),
+            },
+        )
+    }
+
+    pub fn pending_distribution(&self) -> StdResult<Vec<AssetValidated>> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::PendingDistribution {},
+        )
+    }
+
+    #[allow(dead_code)]
+    pub fn delegated(&self, owner: &str) -> StdResult<Addr> {
+        let resp: DelegatedResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::Delegated {
+                owner: owner.to_owned(),
             },
         )?;
         Ok(resp.delegated)
     }
 
+    pub fn query_reward_receiver(&self, owner: &str) -> StdResult<Option<Addr>> {
+        let resp: RewardReceiverResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::RewardReceiver {
+                owner: owner.to_owned(),
+            },
+        )?;
+        Ok(resp.receiver)
+    }
+
     /// returns address' balance of native token
     pub fn query_balance(&self, address: &str, denom: &str) -> StdResult<u128> {
         let resp = self.app.wrap().query_balance(address, denom)?;
@@ -709,8 +1627,17 @@ impl Suite {
     }
 
     pub fn query_all_staked(&self, address: &str) -> StdResult<AllStakedResponse> {
+        self.query_all_staked_in(&self.stake_contract, address)
+    }
+
+    /// Queries all stakes of `address` in the given staking contract
+    pub fn query_all_staked_in(
+        &self,
+        stake_contract: &Addr,
+        address: &str,
+    ) -> StdResult<AllStakedResponse> {
         let all_staked: AllStakedResponse = self.app.wrap().query_wasm_smart(
-            self.stake_contract.clone(),
+            stake_contract.clone(),
             &QueryMsg::AllStaked {
                 address: address.to_owned(),
             },
@@ -726,6 +1653,14 @@ impl Suite {
         Ok(total_staked.total_staked.u128())
     }
 
+    pub fn query_staker_count(&self) -> StdResult<u64> {
+        let resp: StakerCountResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(self.stake_contract.clone(), &QueryMsg::StakerCount {})?;
+        Ok(resp.count)
+    }
+
     pub fn query_claims(&self, address: &str) -> StdResult<Vec<Claim>> {
         let claims: ClaimsResponse = self.app.wrap().query_wasm_smart(
             self.stake_contract.clone(),
@@ -746,6 +1681,17 @@ impl Suite {
         Ok(apr.rewards)
     }
 
+    pub fn query_annualized_rewards_at(
+        &self,
+        timestamp: u64,
+    ) -> StdResult<Vec<(UnbondingPeriod, Vec<AnnualizedReward>)>> {
+        let apr: AnnualizedRewardsResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::AnnualizedRewardsAt { timestamp },
+        )?;
+        Ok(apr.rewards)
+    }
+
     pub fn query_rewards_power(&self, address: &str) -> StdResult<Vec<(AssetInfoValidated, u128)>> {
         let rewards: RewardsPowerResponse = self.app.wrap().query_wasm_smart(
             self.stake_contract.clone(),
@@ -762,6 +1708,15 @@ impl Suite {
             .collect())
     }
 
+    pub fn query_user_earning_assets(&self, address: &str) -> StdResult<Vec<AssetInfoValidated>> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::UserEarningAssets {
+                address: address.to_owned(),
+            },
+        )
+    }
+
     pub fn query_total_rewards_power(&self) -> StdResult<Vec<(AssetInfoValidated, u128)>> {
         let rewards: RewardsPowerResponse = self
             .app
@@ -776,6 +1731,25 @@ impl Suite {
             .collect())
     }
 
+    pub fn query_total_rewards_power_excluding(
+        &self,
+        address: &str,
+    ) -> StdResult<Vec<(AssetInfoValidated, u128)>> {
+        let rewards: RewardsPowerResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::TotalRewardsPowerExcluding {
+                address: address.to_owned(),
+            },
+        )?;
+
+        Ok(rewards
+            .rewards
+            .into_iter()
+            .map(|(a, p)| (a, p.u128()))
+            .filter(|(_, p)| *p > 0)
+            .collect())
+    }
+
     pub fn query_unbond_all(&self) -> StdResult<bool> {
         let resp: UnbondAllResponse = self
             .app
@@ -784,4 +1758,75 @@ impl Suite {
 
         Ok(resp.unbond_all)
     }
+
+    pub fn query_rebond_cooldown(
+        &self,
+        address: &str,
+        unbonding_period: impl Into<Option<u64>>,
+    ) -> StdResult<u64> {
+        let resp: RebondCooldownResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::RebondCooldown {
+                address: address.to_owned(),
+                unbonding_period: self.unbonding_period_or_default(unbonding_period),
+            },
+        )?;
+
+        Ok(resp.remaining)
+    }
+
+    pub fn query_last_interaction(&self, address: &str) -> StdResult<Option<InteractionBlock>> {
+        let resp: LastInteractionResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::LastInteraction {
+                address: address.to_owned(),
+            },
+        )?;
+
+        Ok(resp.last_interaction)
+    }
+
+    pub fn query_user_summary(&self, address: &str) -> StdResult<UserSummaryResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::UserSummary {
+                address: address.to_owned(),
+            },
+        )
+    }
+
+    pub fn query_next_reward_change(&self, owner: &str) -> StdResult<Option<u64>> {
+        let resp: NextRewardChangeResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::NextRewardChange {
+                owner: owner.to_owned(),
+            },
+        )?;
+        Ok(resp.timestamp)
+    }
+
+    pub fn query_rate_at(&self, asset: AssetInfo, at_time: u64) -> StdResult<Decimal> {
+        let resp: RateAtResponse = self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::RateAt { asset, at_time },
+        )?;
+        Ok(resp.rate)
+    }
+
+    pub fn query_reward_token_balance(&self) -> StdResult<RewardTokenBalanceResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::RewardTokenBalance {},
+        )
+    }
+
+    pub fn query_total_to_be_distributed(
+        &self,
+        asset: AssetInfo,
+    ) -> StdResult<TotalToBeDistributedResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.stake_contract.clone(),
+            &QueryMsg::TotalToBeDistributed { asset },
+        )
+    }
 }
@@ -0,0 +1,48 @@
+use cosmwasm_std::Decimal;
+use wyndex::asset::{AssetInfo, AssetInfoValidated};
+
+use super::suite::{SuiteBuilder, JUNO_DENOM};
+
+#[test]
+fn only_flows_with_power_are_listed() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100, 200])
+        .with_initial_balances(vec![("user", 10_000)])
+        .build();
+
+    // the short period earns nothing from the juno flow
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::zero()), (200, Decimal::one())],
+        )
+        .unwrap();
+    suite
+        .create_distribution_flow(
+            "admin",
+            "manager",
+            AssetInfo::Native("luna".to_string()),
+            vec![(100, Decimal::one()), (200, Decimal::one())],
+        )
+        .unwrap();
+
+    assert_eq!(suite.query_user_earning_assets("user").unwrap(), vec![]);
+
+    suite.delegate("user", 10_000, 100).unwrap();
+    assert_eq!(
+        suite.query_user_earning_assets("user").unwrap(),
+        vec![AssetInfoValidated::Native("luna".to_string())]
+    );
+
+    suite.rebond("user", 5_000, 100, 200).unwrap();
+    assert_eq!(
+        suite.query_user_earning_assets("user").unwrap(),
+        vec![
+            AssetInfoValidated::Native(JUNO_DENOM.to_string()),
+            AssetInfoValidated::Native("luna".to_string()),
+        ]
+    );
+}
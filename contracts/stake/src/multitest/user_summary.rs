@@ -0,0 +1,72 @@
+use cosmwasm_std::{Decimal, Uint128};
+use wyndex::asset::AssetInfo;
+
+use super::suite::{SuiteBuilder, JUNO_DENOM};
+
+const MANAGER: &str = "manager";
+const USER: &str = "user";
+
+#[test]
+fn summary_matches_individual_queries() {
+    let mut suite = SuiteBuilder::new()
+        .with_admin("admin")
+        .with_unbonding_periods(vec![100, 200])
+        .with_initial_balances(vec![(USER, 100_000)])
+        .with_native_balances(JUNO_DENOM, vec![(MANAGER, 1_000)])
+        .build();
+
+    suite
+        .create_distribution_flow(
+            "admin",
+            MANAGER,
+            AssetInfo::Native(JUNO_DENOM.to_string()),
+            vec![(100, Decimal::one()), (200, Decimal::percent(200))],
+        )
+        .unwrap();
+
+    // nothing yet
+    let summary = suite.query_user_summary(USER).unwrap();
+    assert_eq!(summary.stakes, vec![]);
+    assert_eq!(summary.total_staked, Uint128::zero());
+    assert_eq!(summary.pending_claims, vec![]);
+
+    suite.delegate(USER, 60_000, 100).unwrap();
+    suite.delegate(USER, 40_000, 200).unwrap();
+    suite
+        .execute_fund_distribution_curve(MANAGER, JUNO_DENOM, 1_000, 1_000)
+        .unwrap();
+
+    // one claim matures, the other one is still unbonding
+    suite.unbond(USER, 10_000, 100).unwrap();
+    suite.update_time(150);
+    suite.unbond(USER, 5_000, 200).unwrap();
+    suite.distribute_funds(MANAGER, None, None).unwrap();
+
+    let summary = suite.query_user_summary(USER).unwrap();
+    assert_eq!(summary.stakes, suite.query_all_staked(USER).unwrap().stakes);
+    assert_eq!(summary.total_staked.u128(), 85_000);
+    assert_eq!(
+        summary
+            .rewards_power
+            .into_iter()
+            .map(|(asset, power)| (asset, power.u128()))
+            .collect::<Vec<_>>(),
+        suite.query_rewards_power(USER).unwrap()
+    );
+    assert_eq!(
+        summary.withdrawable_rewards,
+        suite.withdrawable_rewards(USER).unwrap()
+    );
+    assert!(!summary.withdrawable_rewards[0].amount.is_zero());
+
+    let claims = suite.query_claims(USER).unwrap();
+    assert_eq!(claims.len(), 2);
+    assert_eq!(summary.claimable, claims[0].amount);
+    assert_eq!(summary.pending_claims, vec![claims[1].clone()]);
+
+    assert_eq!(
+        summary.next_reward_change,
+        suite.query_next_reward_change(USER).unwrap()
+    );
+    assert!(summary.next_reward_change.is_some());
+}
@@ -2,7 +2,10 @@ use cosmwasm_schema::cw_serde;
 use serde::{Deserialize, Serialize};
 use wynd_curve_utils::Curve;
 
-use crate::{utils::calc_power, ContractError};
+use crate::{
+    utils::{calc_power, calc_power_with_min_bond},
+    ContractError,
+};
 use cosmwasm_std::{Addr, Decimal, Env, OverflowError, StdResult, Storage, Timestamp, Uint128};
 use cw_controllers::{Admin, Claims};
 use cw_storage_plus::{Item, Map};
@@ -29,6 +32,76 @@ pub struct Config {
     /// Allows converting staked LP tokens to LP tokens of another pool.
     /// E.g. LP tokens of the USDC-JUNO pool can be converted to LP tokens of the USDC-wyJUNO pool
     pub converter: Option<ConverterConfig>,
+    /// The minimum number of seconds between two distributions.
+    /// [`crate::msg::ExecuteMsg::DistributeRewards`] does nothing if called sooner.
+    #[serde(default)]
+    pub min_distribute_interval: u64,
+    /// The highest rewards multiplier a distribution flow can use for any unbonding period
+    #[serde(default = "default_max_reward_multiplier")]
+    pub max_reward_multiplier: Decimal,
+    /// How the rewards power of a stake is rounded.
+    /// Stakers switch to it on their next interaction, see [`POWER_ROUNDING`].
+    #[serde(default)]
+    pub power_rounding: RoundingMode,
+    /// Configuration for [`crate::msg::ExecuteMsg::InstantUnbond`], disabled if `None`
+    #[serde(default)]
+    pub instant_unbond: Option<InstantUnbondConfig>,
+    /// A change of `min_bond` that is still being applied to the stakers
+    #[serde(default)]
+    pub min_bond_update: Option<MinBondUpdate>,
+}
+
+impl Config {
+    /// Returns the `min_bond` currently applied to the stakes of `staker`.
+    /// While a change is applied, the stakers it did not reach yet keep the previous one.
+    pub fn min_bond_of(&self, staker: &Addr) -> Uint128 {
+        match &self.min_bond_update {
+            Some(update)
+                if update
+                    .last_updated
+                    .as_ref()
+                    .map_or(true, |last| staker > last) =>
+            {
+                update.old_min_bond
+            }
+            _ => self.min_bond,
+        }
+    }
+}
+
+/// A change of [`Config::min_bond`] that is applied to the stakers in batches,
+/// see [`crate::msg::ExecuteMsg::ApplyMinBond`].
+#[cw_serde]
+pub struct MinBondUpdate {
+    /// The `min_bond` of the stakers the update did not reach yet
+    pub old_min_bond: Uint128,
+    /// The last staker (in ascending order) the update was applied to
+    pub last_updated: Option<Addr>,
+}
+
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum RoundingMode {
+    /// Round down to the next whole power
+    #[default]
+    Floor,
+    /// Round to the nearest whole power, halves are rounded up
+    Nearest,
+}
+
+/// The [`Config::max_reward_multiplier`] a contract starts with, i.e. 1000%
+pub const DEFAULT_MAX_REWARD_MULTIPLIER: u64 = 10;
+
+pub(crate) fn default_max_reward_multiplier() -> Decimal {
+    Decimal::from_ratio(DEFAULT_MAX_REWARD_MULTIPLIER, 1u64)
+}
+
+#[cw_serde]
+pub struct InstantUnbondConfig {
+    /// Share of the instantly unbonded tokens that is kept as a penalty
+    pub fee: Decimal,
+    /// Address that receives the penalty. It is burned if not set.
+    pub treasury: Option<Addr>,
 }
 
 #[cw_serde]
@@ -116,6 +189,15 @@ impl BondingInfo {
         locked_stake
     }
 
+    /// Returns the time at which the last of the currently locked tokens unlocks, if any
+    pub fn locked_until(&self, env: &Env) -> Option<Timestamp> {
+        // locked_tokens are sorted by expiry, so the last one unlocks last
+        self.locked_tokens
+            .last()
+            .map(|(t, _)| *t)
+            .filter(|t| t > &env.block.time)
+    }
+
     /// Return all locked tokens at a given block time that is all
     /// locked_tokens with a Timestamp > the block time passed in env as a param
     pub fn total_unlocked(&self, env: &Env) -> Uint128 {
@@ -162,6 +244,11 @@ pub const TOTAL_STAKED: Item<TokenInfo> = Item::new("total_staked");
 
 pub const STAKE: Map<(&Addr, UnbondingPeriod), BondingInfo> = Map::new("stake");
 
+/// Number of addresses with stake in at least one unbonding period.
+/// Only stake changes since the count was introduced are tracked, so addresses that already had
+/// stake before are not included.
+pub const STAKER_COUNT: Item<u64> = Item::new("staker_count");
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct TotalStake {
     /// Total stake
@@ -170,6 +257,17 @@ pub struct TotalStake {
     /// This is used when calculating the total staking power because we don't
     /// want to count stakes below min_bond into the total.
     pub powered_stake: Uint128,
+    /// Part of `powered_stake` that does not count towards the rewards power anymore,
+    /// because of the power decay of inactive stakers (see [`POWER_DECAY`]).
+    #[serde(default)]
+    pub decayed_stake: Uint128,
+}
+
+impl TotalStake {
+    /// Returns the stake that counts towards the total rewards power
+    pub fn effective_stake(&self) -> Uint128 {
+        self.powered_stake.saturating_sub(self.decayed_stake)
+    }
 }
 /// Total stake minus any stake that is below min_bond by unbonding period.
 /// This is used when calculating the total staking power because we don't
@@ -218,6 +316,60 @@ pub struct Distribution {
     pub manager: Addr,
     /// Rewards multiplier by unbonding period for this distribution
     pub reward_multipliers: Vec<(UnbondingPeriod, Decimal)>,
+    /// Fraction of the former rewards power that tokens keep while waiting in the claim queue
+    #[serde(default)]
+    pub claimant_reward_share: Decimal,
+    /// Minimum rewards power a staker needs in this distribution to earn from it.
+    /// The share of stakers below it goes to the others.
+    #[serde(default)]
+    pub min_power_to_earn: Uint128,
+    /// Total rewards power of the stakers below `min_power_to_earn`
+    #[serde(default)]
+    pub excluded_power: Uint128,
+    /// Whether funding and distributing this flow is halted.
+    /// Rewards that were already distributed can still be withdrawn.
+    #[serde(default)]
+    pub paused: bool,
+    /// Time at which reward accrual was frozen by pausing the flow.
+    /// The reward curve is delayed by the paused time once the flow is resumed.
+    #[serde(default)]
+    pub paused_at: Option<u64>,
+    /// Total rewards power stakers gain from rounding their power to nearest instead of down.
+    /// This is added on top of the rewards power of the (rounded down) total stake.
+    #[serde(default)]
+    pub rounding_surplus: Uint128,
+    /// Change of this distribution that did not reach all stakers yet.
+    /// The distribution does not distribute rewards until it did.
+    #[serde(default)]
+    pub update: Option<DistributionUpdate>,
+    /// Number of flows for the same asset that were removed before this one was created.
+    /// Withdraw adjustments of an earlier generation belong to a removed flow and are ignored.
+    #[serde(default)]
+    pub generation: u64,
+}
+
+/// A change of a distribution's rewards multipliers or `min_power_to_earn` that is applied to
+/// the stakers in batches, see [`crate::msg::ExecuteMsg::ApplyDistributionUpdate`].
+#[cw_serde]
+pub struct DistributionUpdate {
+    /// The multipliers of the stakers the update did not reach yet
+    pub old_rewards: Vec<(UnbondingPeriod, Decimal)>,
+    /// The `min_power_to_earn` of the stakers the update did not reach yet
+    #[serde(default)]
+    pub old_min_power_to_earn: Uint128,
+    /// The last staker (in ascending order) the update was applied to
+    pub last_updated: Option<Addr>,
+}
+
+/// Rewards power of a single staker within a distribution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RewardsPower {
+    pub power: Uint128,
+    /// The part of `power` that comes from rounding to nearest instead of down
+    pub rounding_surplus: Uint128,
+    /// The part of `power` that earns from the distribution,
+    /// i.e. zero if it is below `min_power_to_earn`
+    pub earning: Uint128,
 }
 
 impl Distribution {
@@ -232,6 +384,49 @@ impl Distribution {
             .map_err(|_| ContractError::NoUnbondingPeriodFound(unbonding_period))
     }
 
+    /// Returns the withdraw adjustment of `staker` in this flow.
+    /// Adjustments left over from a removed flow of the same asset are ignored,
+    /// they are overwritten the next time the adjustment is saved.
+    pub fn adjustment_of(
+        &self,
+        storage: &dyn Storage,
+        staker: &Addr,
+        asset: &AssetInfoValidated,
+    ) -> StdResult<WithdrawAdjustment> {
+        Ok(WITHDRAW_ADJUSTMENT
+            .may_load(storage, (staker, asset))?
+            .filter(|adjustment| adjustment.generation == self.generation)
+            .unwrap_or(WithdrawAdjustment {
+                generation: self.generation,
+                ..Default::default()
+            }))
+    }
+
+    /// Returns the running update if it did not reach `staker` yet.
+    fn pending_update_of(&self, staker: &Addr) -> Option<&DistributionUpdate> {
+        self.update.as_ref().filter(|update| {
+            update
+                .last_updated
+                .as_ref()
+                .map_or(true, |last| staker > last)
+        })
+    }
+
+    /// Returns the rewards multipliers currently applied to `staker`.
+    /// While an update is running, the stakers it did not reach yet keep the previous ones.
+    pub fn multipliers_of(&self, staker: &Addr) -> &[(UnbondingPeriod, Decimal)] {
+        self.pending_update_of(staker)
+            .map_or(&self.reward_multipliers, |update| &update.old_rewards)
+    }
+
+    /// Returns the `min_power_to_earn` currently applied to `staker`, like [`Self::multipliers_of`].
+    pub fn min_power_to_earn_of(&self, staker: &Addr) -> Uint128 {
+        self.pending_update_of(staker)
+            .map_or(self.min_power_to_earn, |update| {
+                update.old_min_power_to_earn
+            })
+    }
+
     pub fn total_rewards_power_of_period(
         &self,
         storage: &dyn Storage,
@@ -241,13 +436,45 @@ impl Distribution {
         let totals = TOTAL_PER_PERIOD.load(storage).unwrap_or_default();
         let total = totals
             .binary_search_by_key(&period, |(period, _)| *period)
-            .map(|idx| totals[idx].1.powered_stake) // map to powered stake
+            .map(|idx| totals[idx].1.effective_stake()) // map to powered stake
             .map_err(|_| ContractError::NoUnbondingPeriodFound(period))?;
-        Ok(calc_power(cfg, total, self.rewards_multiplier(period)?))
+        Ok(calc_power(
+            cfg,
+            total,
+            self.rewards_multiplier(period)?,
+            RoundingMode::Floor,
+        ))
     }
 
     /// Returns the total rewards power within this distribution.
+    /// The power of stakers below `min_power_to_earn` is not included.
     pub fn total_rewards_power(&self, storage: &dyn Storage, cfg: &Config) -> Uint128 {
+        (self
+            .rewards_power_per_period(storage, cfg)
+            .into_iter()
+            .map(|(_, power)| power)
+            .sum::<Uint128>()
+            + self.rounding_surplus)
+            .saturating_sub(self.excluded_power)
+    }
+
+    /// Returns the rewards power of `staker` that earns from this distribution.
+    pub fn calc_earning_power(
+        &self,
+        storage: &dyn Storage,
+        cfg: &Config,
+        staker: &Addr,
+    ) -> StdResult<Uint128> {
+        Ok(self.calc_rewards_power(storage, cfg, staker)?.earning)
+    }
+
+    /// Returns the rewards power of all stakers (and pending claims) per unbonding period,
+    /// rounded down. [`Distribution::rounding_surplus`] is not included.
+    pub fn rewards_power_per_period(
+        &self,
+        storage: &dyn Storage,
+        cfg: &Config,
+    ) -> Vec<(UnbondingPeriod, Uint128)> {
         let totals = TOTAL_PER_PERIOD.load(storage).unwrap_or_default();
         self.reward_multipliers
             .iter()
@@ -259,10 +486,24 @@ impl Distribution {
                         unbonding_period, unbonding_period2,
                         "Unbonding period mismatch"
                     );
-                    calc_power(cfg, total_stake.powered_stake, multiplier)
+                    let pending = if self.claimant_reward_share.is_zero() {
+                        Uint128::zero()
+                    } else {
+                        TOTAL_PENDING_PER_PERIOD
+                            .may_load(storage, unbonding_period)
+                            .unwrap_or_default()
+                            .unwrap_or_default()
+                    };
+                    let power = calc_power(
+                        cfg,
+                        total_stake.effective_stake(),
+                        multiplier,
+                        RoundingMode::Floor,
+                    ) + self.claimant_power(cfg, pending, multiplier);
+                    (unbonding_period, power)
                 },
             )
-            .sum::<Uint128>()
+            .collect()
     }
 
     pub fn calc_rewards_power(
@@ -270,16 +511,94 @@ impl Distribution {
         storage: &dyn Storage,
         cfg: &Config,
         staker: &Addr,
-    ) -> StdResult<Uint128> {
+    ) -> StdResult<RewardsPower> {
+        let rounding = load_power_rounding(storage, staker)?;
+        let sum = |powers: Vec<(UnbondingPeriod, Uint128)>| -> Uint128 {
+            powers.into_iter().map(|(_, power)| power).sum()
+        };
+        let power = sum(self.rewards_power_per_period_rounded(storage, cfg, staker, rounding)?);
+        let rounded_down = match rounding {
+            RoundingMode::Floor => power,
+            RoundingMode::Nearest => sum(self.rewards_power_per_period_rounded(
+                storage,
+                cfg,
+                staker,
+                RoundingMode::Floor,
+            )?),
+        };
+        let earning = if power < self.min_power_to_earn_of(staker) {
+            Uint128::zero()
+        } else {
+            power
+        };
+        Ok(RewardsPower {
+            power,
+            rounding_surplus: power - rounded_down,
+            earning,
+        })
+    }
+
+    /// Returns the rewards power of the staker per unbonding period.
+    /// Tokens waiting to be claimed count towards the unbonding period they were unbonded from.
+    pub fn calc_rewards_power_per_period(
+        &self,
+        storage: &dyn Storage,
+        cfg: &Config,
+        staker: &Addr,
+    ) -> StdResult<Vec<(UnbondingPeriod, Uint128)>> {
+        let rounding = load_power_rounding(storage, staker)?;
+        self.rewards_power_per_period_rounded(storage, cfg, staker, rounding)
+    }
+
+    fn rewards_power_per_period_rounded(
+        &self,
+        storage: &dyn Storage,
+        cfg: &Config,
+        staker: &Addr,
+        rounding: RoundingMode,
+    ) -> StdResult<Vec<(UnbondingPeriod, Uint128)>> {
         // get rewards for all unbonding periods
-        let mut power = Uint128::zero();
-        for &(unbonding_period, multiplier) in self.reward_multipliers.iter() {
-            let bonding_info = STAKE
-                .may_load(storage, (staker, unbonding_period))?
+        let power_factor = load_power_factor(storage, staker)?;
+        let multipliers = self.multipliers_of(staker);
+        let mut powers = multipliers
+            .iter()
+            .map(|&(unbonding_period, multiplier)| {
+                let bonding_info = STAKE
+                    .may_load(storage, (staker, unbonding_period))?
+                    .unwrap_or_default();
+                let power = calc_power_with_min_bond(
+                    cfg,
+                    cfg.min_bond_of(staker),
+                    bonding_info.total_stake(),
+                    multiplier * power_factor,
+                    rounding,
+                );
+                Ok((unbonding_period, power))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // add the reduced power of tokens waiting to be claimed
+        if !self.claimant_reward_share.is_zero() {
+            let pending_claims = PENDING_CLAIMS
+                .may_load(storage, staker)?
                 .unwrap_or_default();
-            power += calc_power(cfg, bonding_info.total_stake(), multiplier);
+            for claim in pending_claims {
+                if let Ok(idx) =
+                    multipliers.binary_search_by_key(&claim.unbonding_period, |(period, _)| *period)
+                {
+                    let multiplier = multipliers[idx].1;
+                    powers[idx].1 += self.claimant_power(cfg, claim.amount, multiplier);
+                }
+            }
         }
-        Ok(power)
+        Ok(powers)
+    }
+
+    /// Returns the rewards power of `amount` tokens waiting to be claimed,
+    /// given the multiplier of the unbonding period they were unbonded from.
+    /// In contrast to staked tokens, `min_bond` does not apply here.
+    fn claimant_power(&self, cfg: &Config, amount: Uint128, multiplier: Decimal) -> Uint128 {
+        amount * (multiplier * self.claimant_reward_share) / cfg.tokens_per_power
     }
 }
 
@@ -290,21 +609,235 @@ pub struct WithdrawAdjustment {
     pub shares_correction: i128,
     /// How much funds addresses already withdrawn.
     pub withdrawn_rewards: Uint128,
+    /// The [`Distribution::generation`] of the flow this adjustment belongs to
+    #[serde(default)]
+    pub generation: u64,
 }
 
 /// Rewards distribution data
 pub const DISTRIBUTION: Map<&AssetInfoValidated, Distribution> = Map::new("distribution");
+
+/// Unbonding period the staked token rewards of each address that opted in to auto-compounding
+/// are bonded to
+pub const AUTO_COMPOUND: Map<&Addr, UnbondingPeriod> = Map::new("auto_compound");
+
+/// Block time (in seconds) of each address' last rewards withdrawal
+pub const LAST_WITHDRAW: Map<&Addr, u64> = Map::new("last_withdraw");
+/// Total rewards each address had earned per asset at its last rewards withdrawal,
+/// i.e. everything it withdrew until then
+pub const EARNED_AT_LAST_WITHDRAW: Map<(&Addr, &AssetInfoValidated), Uint128> =
+    Map::new("earned_at_last_withdraw");
+
+#[cw_serde]
+pub struct MultiplierChange {
+    /// Block time in seconds at which the multipliers took effect
+    pub changed_at: u64,
+    /// Rewards multiplier by unbonding period from that time on
+    pub rewards: Vec<(UnbondingPeriod, Decimal)>,
+}
+
+/// Append-only log of the reward multipliers of each distribution flow, oldest first.
+/// Flows created before this log existed start with their first multiplier update.
+pub const MULTIPLIER_HISTORY: Map<&AssetInfoValidated, Vec<MultiplierChange>> =
+    Map::new("multiplier_history");
+
+/// Appends the given multipliers to the multiplier history of `asset`
+pub fn record_multiplier_change(
+    storage: &mut dyn Storage,
+    asset: &AssetInfoValidated,
+    changed_at: u64,
+    rewards: Vec<(UnbondingPeriod, Decimal)>,
+) -> StdResult<()> {
+    MULTIPLIER_HISTORY.update(storage, asset, |history| -> StdResult<_> {
+        let mut history = history.unwrap_or_default();
+        history.push(MultiplierChange {
+            changed_at,
+            rewards,
+        });
+        Ok(history)
+    })?;
+    Ok(())
+}
+
+#[cw_serde]
+pub struct ScheduledMultipliers {
+    /// Rewards multiplier by unbonding period once the change is applied
+    pub rewards: Vec<(UnbondingPeriod, Decimal)>,
+    /// Block time in seconds from which on the change is applied
+    pub effective_at: u64,
+}
+
+/// Pre-announced multiplier changes of each distribution flow.
+/// They are applied by the first reward distribution at or after `effective_at`.
+pub const SCHEDULED_MULTIPLIERS: Map<&AssetInfoValidated, ScheduledMultipliers> =
+    Map::new("scheduled_multipliers");
+
 /// Information how to exactly adjust rewards while withdrawal.
 /// This is per user, so it applies to all distributions.
 pub const WITHDRAW_ADJUSTMENT: Map<(&Addr, &AssetInfoValidated), WithdrawAdjustment> =
     Map::new("withdraw_adjustment");
 
+/// Number of distribution flows that were removed per asset.
+/// Used as the [`Distribution::generation`] of a new flow for the same asset.
+pub const REMOVED_FLOWS: Map<&AssetInfoValidated, u64> = Map::new("removed_flows");
+
+/// A claim that still accrues rewards for distributions with a non-zero
+/// [`Distribution::claimant_reward_share`].
+#[cw_serde]
+pub struct PendingClaim {
+    /// The unbonding period the tokens were unbonded from
+    pub unbonding_period: UnbondingPeriod,
+    pub amount: Uint128,
+    /// When the claim matures
+    pub release_at: Timestamp,
+    /// The unbond controller holding the claim, if it is not the staker itself
+    #[serde(default)]
+    pub claimant: Option<Addr>,
+}
+
+/// Claims of each user that are still accruing rewards.
+/// They are removed once they matured, at the latest when the tokens are claimed.
+pub const PENDING_CLAIMS: Map<&Addr, Vec<PendingClaim>> = Map::new("pending_claims");
+/// Stakers with entries in [`PENDING_CLAIMS`] by the block time (in seconds) their claims mature,
+/// so matured claims can stop accruing rewards without waiting for the staker
+pub const PENDING_CLAIM_MATURITY: Map<(u64, &Addr), ()> = Map::new("pending_claim_maturity");
+/// Total amount of tokens in [`PENDING_CLAIMS`] per unbonding period
+pub const TOTAL_PENDING_PER_PERIOD: Map<UnbondingPeriod, Uint128> =
+    Map::new("total_pending_per_period");
+
+/// Amount of rewards provided by each funder per reward asset.
+pub const FUNDERS: Map<(&AssetInfoValidated, &Addr), Uint128> = Map::new("funders");
+/// The rewards of each funder per reward asset that are still locked, by time.
+/// Recalled funds go back to the funders in proportion to this.
+pub const FUNDER_SCHEDULES: Map<(&AssetInfoValidated, &Addr), Curve> = Map::new("funder_schedules");
+
+/// Cumulative amounts that went into and out of a distribution flow
+#[cw_serde]
+#[derive(Default)]
+pub struct FlowTotals {
+    /// Rewards funded or sent along with [`crate::msg::ExecuteMsg::DistributeRewards`]
+    pub funded: Uint128,
+    /// Rewards withdrawn or compounded by the stakers
+    pub withdrawn: Uint128,
+}
+
+/// Cumulative funded and withdrawn rewards per reward asset
+pub const FLOW_TOTALS: Map<&AssetInfoValidated, FlowTotals> = Map::new("flow_totals");
+
+/// Source an exhausted distribution flow is re-funded from by [`crate::msg::ExecuteMsg::PullFunding`]
+#[cw_serde]
+pub struct FundingSourceInfo {
+    pub address: Addr,
+    /// Amount pulled from the source per epoch
+    pub amount: Uint128,
+    /// Duration in seconds over which each pulled amount is distributed
+    pub epoch: u64,
+    /// Native tokens the source deposited in advance, not part of the reward balance yet
+    pub prepaid: Uint128,
+    /// Whether the source accepted the current terms, cw20 tokens are only pulled after that
+    #[serde(default)]
+    pub accepted: bool,
+}
+
+/// Configured funding source per reward asset
+pub const FUNDING_SOURCES: Map<&AssetInfoValidated, FundingSourceInfo> =
+    Map::new("funding_sources");
+
 /// User delegated for funds withdrawal
 pub const DELEGATED: Map<&Addr, Addr> = Map::new("delegated");
 
+/// Address receiving the claimed tokens of a user. Users without an entry receive them themselves.
+pub const CLAIM_RECEIVER: Map<&Addr, Addr> = Map::new("claim_receiver");
+
+/// Address receiving the withdrawn rewards of a user if no receiver is given on withdrawal.
+/// Users without an entry receive them at the withdrawing address.
+pub const REWARD_RECEIVER: Map<&Addr, Addr> = Map::new("reward_receiver");
+
+/// Rights over a staker's bond that were given away on delegation
+#[cw_serde]
+#[derive(Default)]
+pub struct DelegationRights {
+    /// The only address allowed to unbond or rebond the stake
+    pub unbond_controller: Option<Addr>,
+    /// The address receiving all rewards of the staker
+    pub withdraw_to: Option<Addr>,
+}
+
+/// Delegation rights per staker. Stakers without an entry have full control over their bond.
+pub const DELEGATION_RIGHTS: Map<&Addr, DelegationRights> = Map::new("delegation_rights");
+
+/// Decay of the rewards power of stakers that did not interact with the contract for a while
+#[cw_serde]
+pub struct PowerDecay {
+    /// Seconds without interaction after which the rewards power starts to decay
+    pub inactivity_window: u64,
+    /// Seconds it takes for the rewards power to decay linearly from full to zero
+    pub decay_duration: u64,
+    /// Time at which the decay was enabled. Stakers count as active at this time.
+    pub enabled_at: u64,
+}
+
+impl PowerDecay {
+    /// Returns the fraction of their rewards power a staker keeps at `now`, given their last interaction
+    pub fn power_factor(&self, last_interaction: Option<u64>, now: u64) -> Decimal {
+        let decay_start = last_interaction
+            .unwrap_or_default()
+            .max(self.enabled_at)
+            .saturating_add(self.inactivity_window);
+        let elapsed = now.saturating_sub(decay_start);
+        if elapsed >= self.decay_duration {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(self.decay_duration - elapsed, self.decay_duration)
+        }
+    }
+}
+
+/// Power decay configuration. Decay is disabled if this is not set.
+pub const POWER_DECAY: Item<PowerDecay> = Item::new("power_decay");
+
+#[cw_serde]
+pub struct InteractionBlock {
+    pub height: u64,
+    /// Block time in seconds
+    pub time: u64,
+}
+
+/// Block of the last bond, rebond, unbond or withdrawal of each staker
+pub const LAST_INTERACTION: Map<&Addr, InteractionBlock> = Map::new("last_interaction");
+/// Fraction of the rewards power currently applied to each staker.
+/// Stakers without an entry have their full rewards power.
+pub const POWER_FACTOR: Map<&Addr, Decimal> = Map::new("power_factor");
+
+/// Loads the fraction of the rewards power currently applied to the given staker
+pub fn load_power_factor(storage: &dyn Storage, staker: &Addr) -> StdResult<Decimal> {
+    Ok(POWER_FACTOR
+        .may_load(storage, staker)?
+        .unwrap_or_else(Decimal::one))
+}
+
+/// Rounding of the rewards power each staker had at their last interaction.
+/// Changes of [`Config::power_rounding`] only apply to a staker once they interact again,
+/// so that rewards power already accounted for stays the same.
+/// Stakers without an entry have their power rounded down.
+pub const POWER_ROUNDING: Map<&Addr, RoundingMode> = Map::new("power_rounding");
+
+/// Loads the rounding currently applied to the rewards power of the given staker
+pub fn load_power_rounding(storage: &dyn Storage, staker: &Addr) -> StdResult<RoundingMode> {
+    Ok(POWER_ROUNDING
+        .may_load(storage, staker)?
+        .unwrap_or_default())
+}
+
+/// Staking contract stakers can move their positions to without unbonding
+pub const MIGRATION_TARGET: Item<Addr> = Item::new("migration_target");
+
 /// Flag to allow fast unbonding in emergency cases.
 pub const UNBOND_ALL: Item<bool> = Item::new("unbond_all");
 
+/// The block time of the last [`crate::msg::ExecuteMsg::DistributeRewards`] that distributed rewards
+pub const LAST_DISTRIBUTION: Item<u64> = Item::new("last_distribution");
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,7 +3,7 @@ use cw20::Cw20ExecuteMsg;
 
 use wynd_curve_utils::{Curve, PiecewiseLinear, SaturatingLinear};
 
-use crate::state::Config;
+use crate::state::{Config, RoundingMode};
 
 pub fn create_undelegate_msg(
     recipient: Addr,
@@ -21,11 +21,35 @@ pub fn create_undelegate_msg(
     }))
 }
 
-pub fn calc_power(cfg: &Config, stake: Uint128, multiplier: Decimal) -> Uint128 {
-    if stake < cfg.min_bond {
+/// Returns the rewards power of `stake` with the given `multiplier`, rounded according to `rounding`.
+/// Stakes below `min_bond` have no power.
+pub fn calc_power(
+    cfg: &Config,
+    stake: Uint128,
+    multiplier: Decimal,
+    rounding: RoundingMode,
+) -> Uint128 {
+    calc_power_with_min_bond(cfg, cfg.min_bond, stake, multiplier, rounding)
+}
+
+/// Like [`calc_power`], but with the given `min_bond` instead of the configured one.
+pub fn calc_power_with_min_bond(
+    cfg: &Config,
+    min_bond: Uint128,
+    stake: Uint128,
+    multiplier: Decimal,
+    rounding: RoundingMode,
+) -> Uint128 {
+    if stake < min_bond {
         Uint128::zero()
     } else {
-        stake * multiplier / cfg.tokens_per_power
+        let weighted = stake * multiplier;
+        match rounding {
+            RoundingMode::Floor => weighted / cfg.tokens_per_power,
+            RoundingMode::Nearest => {
+                (weighted + cfg.tokens_per_power / Uint128::new(2)) / cfg.tokens_per_power
+            }
+        }
     }
 }
 
@@ -36,6 +60,42 @@ pub trait CurveExt {
     /// Returns the last `x` value of the curve, if any.
     /// This will be `None` for infinite and empty curves.
     fn end(&self) -> Option<u64>;
+
+    /// Returns the first `x` value after `x` at which the slope of the curve changes, if any.
+    fn next_change(&self, x: u64) -> Option<u64>;
+
+    /// Returns how many units the curve decreases by per unit of `x`, right after `x`.
+    /// Increasing parts of the curve are treated as not decreasing at all.
+    fn decrease_rate(&self, x: u64) -> Decimal;
+}
+
+/// Returns a curve that is `value` up to `x` and afterwards follows `curve`, scaled down by the
+/// same ratio as its value at `x` is lowered to `value`.
+/// `value` must not be higher than the value of `curve` at `x`.
+pub fn scale_down_from(curve: &Curve, x: u64, value: Uint128) -> Curve {
+    let current = curve.value(x);
+    if value.is_zero() || current.is_zero() {
+        return Curve::constant(0);
+    }
+    let steps = match curve {
+        Curve::Constant { y } => return Curve::constant(y.multiply_ratio(value, current).u128()),
+        Curve::SaturatingLinear(sl) => vec![(sl.min_x, sl.min_y), (sl.max_x, sl.max_y)],
+        Curve::PiecewiseLinear(pl) => pl.steps.clone(),
+    };
+    let steps = std::iter::once((x, value))
+        .chain(
+            steps
+                .into_iter()
+                .filter(|(step, _)| *step > x)
+                .map(|(step, y)| (step, y.multiply_ratio(value, current))),
+        )
+        .collect();
+    Curve::PiecewiseLinear(PiecewiseLinear { steps })
+}
+
+/// Returns how much the line from `(x1, y1)` to `(x2, y2)` decreases per unit of `x`.
+fn segment_decrease_rate(x1: u64, y1: Uint128, x2: u64, y2: Uint128) -> Decimal {
+    Decimal::from_ratio(y1.saturating_sub(y2), x2 - x1)
 }
 
 impl CurveExt for Curve {
@@ -54,6 +114,22 @@ impl CurveExt for Curve {
             Curve::PiecewiseLinear(pl) => pl.end(),
         }
     }
+
+    fn next_change(&self, x: u64) -> Option<u64> {
+        match self {
+            Curve::Constant { .. } => None,
+            Curve::SaturatingLinear(sl) => sl.next_change(x),
+            Curve::PiecewiseLinear(pl) => pl.next_change(x),
+        }
+    }
+
+    fn decrease_rate(&self, x: u64) -> Decimal {
+        match self {
+            Curve::Constant { .. } => Decimal::zero(),
+            Curve::SaturatingLinear(sl) => sl.decrease_rate(x),
+            Curve::PiecewiseLinear(pl) => pl.decrease_rate(x),
+        }
+    }
 }
 
 impl CurveExt for SaturatingLinear {
@@ -67,6 +143,19 @@ impl CurveExt for SaturatingLinear {
     fn end(&self) -> Option<u64> {
         Some(self.max_x)
     }
+
+    fn next_change(&self, x: u64) -> Option<u64> {
+        [self.min_x, self.max_x]
+            .into_iter()
+            .find(|&change| change > x)
+    }
+
+    fn decrease_rate(&self, x: u64) -> Decimal {
+        if x < self.min_x || x >= self.max_x {
+            return Decimal::zero();
+        }
+        segment_decrease_rate(self.min_x, self.min_y, self.max_x, self.max_y)
+    }
 }
 
 impl CurveExt for PiecewiseLinear {
@@ -80,4 +169,99 @@ impl CurveExt for PiecewiseLinear {
     fn end(&self) -> Option<u64> {
         self.steps.last().map(|(x, _)| *x)
     }
+
+    fn next_change(&self, x: u64) -> Option<u64> {
+        self.steps
+            .iter()
+            .map(|(step, _)| *step)
+            .find(|&step| step > x)
+    }
+
+    fn decrease_rate(&self, x: u64) -> Decimal {
+        self.steps
+            .windows(2)
+            .find(|w| w[0].0 <= x && x < w[1].0)
+            .map_or_else(Decimal::zero, |w| {
+                segment_decrease_rate(w[0].0, w[0].1, w[1].0, w[1].1)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            cw20_contract: Addr::unchecked("token"),
+            instantiator: Addr::unchecked("instantiator"),
+            tokens_per_power: Uint128::new(1000),
+            min_bond: Uint128::new(5000),
+            unbonding_periods: vec![100],
+            max_distributions: 6,
+            unbonder: None,
+            converter: None,
+            min_distribute_interval: 0,
+            max_reward_multiplier: Decimal::one(),
+            power_rounding: RoundingMode::Floor,
+            instant_unbond: None,
+            min_bond_update: None,
+        }
+    }
+
+    #[test]
+    fn calc_power_rounds_down() {
+        let cfg = config();
+        let mode = RoundingMode::Floor;
+        assert_eq!(
+            calc_power(&cfg, Uint128::new(5400), Decimal::one(), mode),
+            Uint128::new(5)
+        );
+        assert_eq!(
+            calc_power(&cfg, Uint128::new(5600), Decimal::one(), mode),
+            Uint128::new(5)
+        );
+        assert_eq!(
+            calc_power(&cfg, Uint128::new(9000), Decimal::percent(50), mode),
+            Uint128::new(4)
+        );
+    }
+
+    #[test]
+    fn calc_power_rounds_to_nearest() {
+        let cfg = config();
+        let mode = RoundingMode::Nearest;
+        assert_eq!(
+            calc_power(&cfg, Uint128::new(5400), Decimal::one(), mode),
+            Uint128::new(5)
+        );
+        assert_eq!(
+            calc_power(&cfg, Uint128::new(5500), Decimal::one(), mode),
+            Uint128::new(6)
+        );
+        assert_eq!(
+            calc_power(&cfg, Uint128::new(5600), Decimal::one(), mode),
+            Uint128::new(6)
+        );
+        assert_eq!(
+            calc_power(&cfg, Uint128::new(9000), Decimal::percent(50), mode),
+            Uint128::new(5)
+        );
+    }
+
+    #[test]
+    fn calc_power_is_zero_below_min_bond() {
+        for mode in [RoundingMode::Floor, RoundingMode::Nearest] {
+            let cfg = config();
+            assert_eq!(
+                calc_power(&cfg, Uint128::new(4999), Decimal::one(), mode),
+                Uint128::zero()
+            );
+            // even if rounding to nearest would give some power
+            assert_eq!(
+                calc_power(&cfg, Uint128::new(4999), Decimal::percent(200), mode),
+                Uint128::zero()
+            );
+        }
+    }
 }
@@ -296,6 +296,10 @@ pub struct DistributionFlow {
     pub rewards: Vec<(UnbondingPeriod, Decimal)>,
     /// The number of seconds over which funded distributions are stretched.
     pub reward_duration: u64,
+    /// The address that can manage the distribution flow on the new staking contract.
+    /// Defaults to the factory.
+    #[serde(default)]
+    pub manager: Option<String>,
 }
 
 /// Like [`StakeConfig`] but with all fields being optional.
@@ -351,6 +355,25 @@ pub enum QueryMsg {
     /// Used by the `gauge-adapter` contract
     #[returns(bool)]
     ValidateStakingAddress { address: String },
+    /// Returns the code IDs the factory currently uses to instantiate new pairs,
+    /// their LP tokens and their staking contracts.
+    #[returns(CodeIdsResponse)]
+    CodeIds {},
+    /// Returns the number of registered pairs, broken down by pair type and by whether they
+    /// were created permissionlessly, using a [`StatsResponse`] structure.
+    #[returns(StatsResponse)]
+    Stats {},
+    /// Returns the pairs created after the given block time (in seconds), ordered by creation time.
+    /// Pairs registered before creation times were recorded are not included.
+    #[returns(PairsCreatedAfterResponse)]
+    PairsCreatedAfter {
+        /// Only pairs created strictly after this block time are returned
+        timestamp: u64,
+        /// The address of the pair to start reading after
+        start_after: Option<String>,
+        /// The number of pairs to read and return
+        limit: Option<u32>,
+    },
 }
 
 /// A custom struct for each query response that returns general contract settings/configs.
@@ -372,6 +395,30 @@ pub struct ConfigResponse {
     pub trading_starts: Option<u64>,
 }
 
+/// A custom struct that contains the code IDs used by the factory.
+#[cw_serde]
+pub struct CodeIdsResponse {
+    /// Pair contract code identifier for each pair type
+    pub pair_code_ids: Vec<(PairType, u64)>,
+    /// CW20 token contract code identifier
+    pub token_code_id: u64,
+    /// LP token staking contract code identifier
+    pub staking_code_id: u64,
+}
+
+/// A custom struct that contains statistics about the pairs registered in the factory.
+#[cw_serde]
+pub struct StatsResponse {
+    /// Total number of registered pairs
+    pub total_pairs: u64,
+    /// Number of registered pairs for each pair type
+    pub pairs_by_type: Vec<(PairType, u64)>,
+    /// Number of registered pairs created by anyone with a deposit
+    pub permissionless_pairs: u64,
+    /// Number of registered pairs created by the owner
+    pub permissioned_pairs: u64,
+}
+
 /// A custom struct for each query response that returns an array of objects of type [`PairInfo`].
 #[cw_serde]
 pub struct PairsResponse {
@@ -379,6 +426,13 @@ pub struct PairsResponse {
     pub pairs: Vec<PairInfo>,
 }
 
+/// A custom struct for the [`QueryMsg::PairsCreatedAfter`] query response.
+#[cw_serde]
+pub struct PairsCreatedAfterResponse {
+    /// The pairs together with the block time they were created at, ordered by creation time
+    pub pairs: Vec<(u64, PairInfo)>,
+}
+
 /// A custom struct for each query response that returns an object of type [`FeeInfoResponse`].
 #[cw_serde]
 pub struct FeeInfoResponse {
@@ -31,6 +31,12 @@ pub use utils::*;
 /// Decimal precision for TWAP results
 pub const TWAP_PRECISION: u8 = 6;
 
+/// Maximum number of offer sizes a single [`QueryMsg::SlippageCurve`] query can sample
+pub const MAX_SLIPPAGE_CURVE_POINTS: usize = 50;
+
+/// Maximum number of simulations a single [`QueryMsg::SimulateBatch`] query can request
+pub const MAX_BATCH_SIMULATIONS: usize = 50;
+
 /// This structure stores the main parameters for an Wyndex pair
 #[cw_serde]
 pub struct PairInfo {
@@ -181,8 +187,17 @@ pub enum ExecuteMsg {
         slippage_tolerance: Option<Decimal>,
         /// The receiver of LP tokens
         receiver: Option<String>,
+        /// Unix timestamp in seconds after which the provision is rejected
+        #[serde(default)]
+        deadline: Option<u64>,
+        /// If set to `true`, the provision is rejected unless it deposits every asset in the pool.
+        /// Defaults to `false`, which allows imbalanced deposits of only some of the assets.
+        #[serde(default)]
+        require_all_assets: Option<bool>,
     },
-    /// Swap performs a swap in the pool
+    /// Swap performs a swap in the pool.
+    /// Cw20 tokens flagged as fee-on-transfer in a stableswap pair are pulled from the sender
+    /// using an allowance, all other cw20 tokens have to be sent using [`Cw20HookMsg::Swap`].
     Swap {
         offer_asset: Asset,
         ask_asset_info: Option<AssetInfo>,
@@ -194,6 +209,9 @@ pub enum ExecuteMsg {
         /// The commission for the referral.
         /// This is capped by the configured max commission
         referral_commission: Option<Decimal>,
+        /// Unix timestamp in seconds after which the swap is rejected
+        #[serde(default)]
+        deadline: Option<u64>,
     },
     /// Update the pair configuration
     UpdateConfig { params: Binary },
@@ -213,6 +231,26 @@ pub enum ExecuteMsg {
     ClaimOwnership {},
     /// Freeze all but withdraw liquidity, can only be called if a circuit breaker is set through a MigrateMsg
     Freeze { frozen: bool },
+    /// Starts ramping the stableswap amplification from its current value to `next_amp`,
+    /// reaching it at `next_amp_time`. Only supported by stableswap pairs.
+    StartAmpRamp { next_amp: u64, next_amp_time: u64 },
+    /// Stops the current amplification ramp, pinning it at its current value.
+    /// Only supported by stableswap pairs.
+    StopAmpRamp {},
+    /// Sends the protocol fees held by the pair to `recipient`.
+    /// Can only be called by the factory's fee address. Only supported by stableswap pairs.
+    CollectProtocolFees { recipient: String },
+    /// Sends the sender's withdrawn assets that could not be transferred during a withdrawal
+    /// with `skip_frozen` set. Only supported by stableswap pairs.
+    ClaimUnsentRefunds {},
+    /// Burns `amount` of the sender's LP tokens using an allowance and sends back the
+    /// proportional share of the pool, without any fees and without querying rate providers.
+    /// Only possible while the pair is frozen. Only supported by stableswap pairs.
+    EmergencyWithdraw { amount: Uint128 },
+    /// Reconciles the assets held back from the pool for unsent refunds with the refunds that
+    /// are still owed and credits any excess to the liquidity providers.
+    /// Can only be called by the pair owner. Only supported by stableswap pairs.
+    SweepDust {},
 }
 
 /// This structure describes a CW20 hook message.
@@ -229,9 +267,20 @@ pub enum Cw20HookMsg {
         /// The commission for the referral.
         /// This is capped by and defaulting to the configured max commission
         referral_commission: Option<Decimal>,
+        /// Unix timestamp in seconds after which the swap is rejected
+        #[serde(default)]
+        deadline: Option<u64>,
     },
     /// Withdraw liquidity from the pool
-    WithdrawLiquidity { assets: Vec<Asset> },
+    WithdrawLiquidity {
+        assets: Vec<Asset>,
+        /// If set, cw20 assets that cannot be transferred (e.g. because the token contract is
+        /// paused) do not make the whole withdrawal fail. They are kept for the withdrawer
+        /// to claim later using [`ExecuteMsg::ClaimUnsentRefunds`].
+        /// Only supported by stableswap pairs.
+        #[serde(default)]
+        skip_frozen: bool,
+    },
 }
 
 #[cw_serde]
@@ -314,6 +363,149 @@ pub enum QueryMsg {
         /// (higher numbers gives more accuracy at higher gas cost)
         iterations: u8,
     },
+    /// Returns the assets received for withdrawing the given amount of LP tokens in a [`WithdrawSimulationResponse`] object.
+    /// This is only supported by the stableswap pair.
+    #[returns(WithdrawSimulationResponse)]
+    WithdrawSimulation {
+        amount: Uint128,
+        /// If set, also simulates withdrawing only this asset
+        ask_asset_info: Option<AssetInfo>,
+    },
+    /// Returns the protocol fees held by the pair that were not collected yet.
+    /// This is only supported by the stableswap pair.
+    #[returns(Vec<AssetValidated>)]
+    PendingProtocolFees {},
+    /// Returns the LP tokens held by the protocol's fee address and what they can currently be
+    /// redeemed for, in a [`ProtocolLpShareResponse`] object.
+    /// This is only supported by the stableswap pair.
+    #[returns(ProtocolLpShareResponse)]
+    ProtocolLpShare {},
+    /// Returns the first and second derivatives of the swap curve at the current balances,
+    /// i.e. the marginal rate of an infinitely small swap of `offer` into `ask`.
+    /// This is only supported by the stableswap pair.
+    #[returns(MarginalResponse)]
+    Marginal { offer: AssetInfo, ask: AssetInfo },
+    /// Returns whether swapping `offer` into `ask` at the current pool state returns at least
+    /// `min_receive`, together with the simulated return amount.
+    /// This is only supported by the stableswap pair.
+    #[returns(SwapMeetsMinResponse)]
+    SwapMeetsMin {
+        offer: Asset,
+        ask: AssetInfo,
+        min_receive: Uint128,
+    },
+    /// Returns each asset's share of the pool as a fraction, summing up to one.
+    /// Amounts are normalized to the same precision before comparing them.
+    /// This is only supported by the stableswap pair.
+    #[returns(Vec<(AssetInfoValidated, Decimal)>)]
+    Composition {},
+    /// Returns the amount of LP tokens that need to be burned to withdraw exactly the `desired` assets
+    /// using [`Cw20HookMsg::WithdrawLiquidity`] with `assets` set to `desired`.
+    /// Fails if the pool cannot pay out the `desired` assets.
+    /// This is only supported by the stableswap pair.
+    #[returns(LpForWithdrawalResponse)]
+    LpForWithdrawal { desired: Vec<Asset> },
+    /// Returns the amount of LP tokens minted for providing the given `assets` and the fraction
+    /// of the pool they will represent, in a [`DepositShareResponse`] object.
+    /// This is only supported by the stableswap pair.
+    #[returns(DepositShareResponse)]
+    DepositShare { assets: Vec<Asset> },
+    /// Simulates swapping each of the given `sizes` of `offer` into `ask` at the current pool state,
+    /// e.g. to draw the price impact curve of the pair.
+    /// At most [`MAX_SLIPPAGE_CURVE_POINTS`] sizes can be requested at once.
+    /// This is only supported by the stableswap pair.
+    #[returns(SlippageCurveResponse)]
+    SlippageCurve {
+        offer: AssetInfo,
+        ask: AssetInfo,
+        sizes: Vec<Uint128>,
+    },
+    /// Compares the spot price of `offer` in terms of `ask` with `external_price` and returns
+    /// the swap that moves the spot price to `external_price` in an [`ArbVsPriceResponse`] object.
+    /// This is only supported by the stableswap pair.
+    #[returns(ArbVsPriceResponse)]
+    ArbVsPrice {
+        offer: AssetInfo,
+        ask: AssetInfo,
+        /// The price of one `offer` in `ask` elsewhere
+        external_price: Decimal,
+    },
+    /// Simulates each of the given swaps at the current pool state, independently of each other,
+    /// and returns one [`BatchSimulationResult`] per request, in the same order.
+    /// At most [`MAX_BATCH_SIMULATIONS`] swaps can be requested at once.
+    /// This is only supported by the stableswap pair.
+    #[returns(Vec<BatchSimulationResult>)]
+    SimulateBatch {
+        requests: Vec<BatchSimulationRequest>,
+    },
+    /// Returns whether the reserves are consistent with the outstanding LP supply,
+    /// in a [`HealthResponse`] object. Meant for monitoring.
+    /// This is only supported by the stableswap pair.
+    #[returns(HealthResponse)]
+    Health {},
+    /// Compares what `lp_amount` LP tokens can currently be withdrawn for with simply holding
+    /// the basket they were backed by at the first deposit into the pool, valued at the given
+    /// external prices, in a [`ValueVsHoldResponse`] object.
+    /// Every pool asset needs a price, which is per smallest unit of the asset.
+    /// This is only supported by the stableswap pair.
+    #[returns(ValueVsHoldResponse)]
+    ValueVsHold {
+        lp_amount: Uint128,
+        external_prices: Vec<(AssetInfo, Decimal)>,
+    },
+    /// Returns for every pair of pool assets whether the spot price, scaled by the rates the
+    /// assets are pegged at, lies within `1 ± band`, in a [`PegStatusResponse`] object.
+    /// This is only supported by the stableswap pair.
+    #[returns(PegStatusResponse)]
+    PegStatus { band: Decimal },
+    /// Returns the reserves both as raw amounts and in the decimal form the swap math works with,
+    /// together with the current amp and invariant D, in a [`PoolDebugResponse`] object.
+    /// Meant for reproducing swap results off-chain.
+    /// This is only supported by the stableswap pair.
+    #[returns(PoolDebugResponse)]
+    PoolDebug {},
+    /// Returns the pool's assets together with the number of decimals stored for each of them,
+    /// which is what amounts are scaled by in the swap math.
+    /// This is only supported by the stableswap pair.
+    #[returns(Vec<AssetPrecision>)]
+    Assets {},
+    /// Returns the spot price of `offer` in terms of `ask` like [`QueryMsg::SpotPrice`], but after
+    /// swapping `amount` of `offer` into `ask`, in a [`SpotPriceResponse`] object.
+    /// The swap changes the reserves like an actual swap would, including the fees leaving the pool,
+    /// but without any referral commission or staker fee discount.
+    /// This is only supported by the stableswap pair.
+    #[returns(SpotPriceResponse)]
+    PostSwapSpot {
+        offer: AssetInfo,
+        ask: AssetInfo,
+        amount: Uint128,
+    },
+}
+
+/// This structure holds the parameters that are returned from a withdraw simulation response
+#[cw_serde]
+pub struct WithdrawSimulationResponse {
+    /// The assets received for a balanced withdraw
+    pub assets: Vec<AssetValidated>,
+    /// The asset received when withdrawing only the requested `ask_asset_info`,
+    /// after deducting the imbalance fee
+    pub single_asset: Option<AssetValidated>,
+}
+
+/// This structure holds the parameters that are returned from an [`QueryMsg::LpForWithdrawal`] query
+#[cw_serde]
+pub struct LpForWithdrawalResponse {
+    /// The amount of LP tokens burned to withdraw the desired assets, including the imbalance fee
+    pub lp_amount: Uint128,
+}
+
+/// This structure holds the parameters that are returned from a [`QueryMsg::DepositShare`] query
+#[cw_serde]
+pub struct DepositShareResponse {
+    /// The amount of LP tokens minted for the deposit
+    pub lp_amount: Uint128,
+    /// The fraction of the LP token supply `lp_amount` makes up after the deposit
+    pub share_of_pool: Decimal,
 }
 
 /// This struct is used to return a query result with the total amount of LP tokens and assets in a specific pool.
@@ -347,6 +539,10 @@ pub struct SimulationResponse {
     pub commission_amount: Uint128,
     /// The absolute amount of referral commission (denominated in `offer_asset_info`)
     pub referral_amount: Uint128,
+    /// The spread relative to what the offer is worth at the current spot price,
+    /// i.e. `spread_amount / (return_amount + commission_amount + spread_amount)`
+    #[serde(default)]
+    pub spread_percent: Decimal,
 }
 
 /// This structure holds the parameters that are returned from a reverse swap simulation response.
@@ -382,6 +578,14 @@ pub struct StablePoolParams {
     pub owner: Option<String>,
     /// Information on LSD, if supported (TODO: always require?)
     pub lsd: Option<LsdInfo>,
+    /// Symbol of the LP token. Defaults to "uLP".
+    pub lp_token_symbol: Option<String>,
+    /// Decimals of the LP token, at most 18. Defaults to [`LP_TOKEN_PRECISION`].
+    pub lp_token_decimals: Option<u8>,
+    /// Exchange rate providers for other assets of the pool that are pegged at a non-1:1 ratio.
+    /// They are queried for the target value the same way as the LSD hub.
+    #[serde(default)]
+    pub rate_providers: Vec<LsdInfo>,
 }
 
 #[cw_serde]
@@ -407,8 +611,79 @@ pub struct StablePoolConfig {
 /// This enum stores the options available to start and stop changing a stableswap pool's amplification.
 #[cw_serde]
 pub enum StablePoolUpdateParams {
-    StartChangingAmp { next_amp: u64, next_amp_time: u64 },
+    StartChangingAmp {
+        next_amp: u64,
+        next_amp_time: u64,
+    },
     StopChangingAmp {},
+    /// Sets or removes the routing of swap fees into a buy-and-burn target.
+    UpdateFeeBurn {
+        fee_burn: Option<FeeBurnParams>,
+    },
+    /// Sets whether protocol fees are held by the pair until they are collected
+    /// using [`ExecuteMsg::CollectProtocolFees`], instead of being sent out on every swap.
+    UpdateHoldProtocolFees {
+        hold: bool,
+    },
+    /// Sets or removes the maximum relative price move allowed within a single block.
+    /// Swaps that would move the price further away from the price at the start of the block are rejected.
+    UpdateMaxBlockPriceMove {
+        max_block_price_move: Option<Decimal>,
+    },
+    /// Flags or unflags a cw20 pool asset as taking a fee on transfer.
+    /// Deposits of flagged tokens are measured by the balance the pair actually received.
+    UpdateFeeOnTransfer {
+        token: String,
+        fee_on_transfer: bool,
+    },
+    /// Sets the swap fee discounts for swappers that stake the pool's LP token
+    /// in the pair's staking contract. An empty list disables the discounts.
+    UpdateStakerFeeDiscounts {
+        tiers: Vec<StakerFeeDiscount>,
+    },
+    /// Sets the number of seconds LP tokens minted on a provide cannot be withdrawn
+    /// by their receiver. Zero disables the lock.
+    UpdateProvideLock {
+        provide_lock: u64,
+    },
+    /// Sets the minimum reserve of each given asset that withdrawals cannot go below,
+    /// keeping the invariant well-conditioned. Assets that are not listed have no minimum.
+    UpdateMinReserves {
+        min_reserves: Vec<Asset>,
+    },
+    /// Restricts providing liquidity to the given addresses, or opens it to everyone (`None`).
+    /// Swaps are not affected.
+    UpdateLpWhitelist {
+        lp_whitelist: Option<Vec<String>>,
+    },
+    /// Sets or removes the incentive for deposits to rebalance the pool. Deposits that make the pool
+    /// more imbalanced receive fewer LP tokens, deposits that restore the balance receive a bonus
+    /// paid from what was withheld before. The incentive has to be greater than 0 and at most 1.
+    UpdateRebalanceIncentive {
+        rebalance_incentive: Option<Decimal>,
+    },
+}
+
+/// This structure describes how a part of the swap fees is used to buy and burn a token.
+#[cw_serde]
+pub struct FeeBurnParams {
+    /// The fraction of the LP part of the swap commission that is sent to `target`
+    pub fraction: Decimal,
+    /// Address of the contract buying and burning the pool's LP token or a governance token
+    pub target: String,
+    /// Optional message attached when sending the fee to `target`.
+    /// If not set, the fee is just transferred.
+    pub msg: Option<Binary>,
+}
+
+/// A swap fee discount for swappers that have at least `min_staked` LP tokens bonded
+/// in the pair's staking contract. The tier with the highest matching `min_staked` applies.
+#[cw_serde]
+pub struct StakerFeeDiscount {
+    /// The minimum amount of bonded LP tokens (summed over all unbonding periods)
+    pub min_staked: Uint128,
+    /// The fraction of the swap commission that is waived
+    pub discount: Decimal,
 }
 
 /// This structure holds the parameters that are returned from a reverse swap simulation response.
@@ -423,3 +698,139 @@ pub struct SpotPricePredictionResponse {
     /// Returns None, result is already below the spot price
     pub trade: Option<Uint128>,
 }
+
+#[cw_serde]
+pub struct SwapMeetsMinResponse {
+    /// Whether `return_amount` is at least the requested `min_receive`
+    pub meets_min: bool,
+    /// The amount of `ask` the swap would return, after fees
+    pub return_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct ArbVsPriceResponse {
+    /// The current spot price of `offer` in terms of `ask`, before fees
+    pub spot_price: Decimal,
+    /// The asset and amount to sell to the pool to bring its spot price to `external_price`.
+    /// This is `offer` if the pool pays more than `external_price` for it, `ask` if it pays less
+    /// and `None` if the prices match.
+    pub trade: Option<AssetValidated>,
+}
+
+#[cw_serde]
+pub struct SlippageCurveResponse {
+    /// One point per requested size, in the same order
+    pub points: Vec<SlippagePoint>,
+}
+
+#[cw_serde]
+pub struct SlippagePoint {
+    /// The amount of `offer` swapped
+    pub offer_amount: Uint128,
+    /// The amount of `ask` the swap would return, after fees
+    pub return_amount: Uint128,
+    /// The spread of the swap (denominated in `ask`)
+    pub spread_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct ProtocolLpShareResponse {
+    /// The factory's fee address, `None` if protocol fees are disabled
+    pub fee_address: Option<Addr>,
+    /// The amount of LP tokens held by `fee_address`
+    pub lp_amount: Uint128,
+    /// The assets `lp_amount` can currently be withdrawn for
+    pub underlying: Vec<AssetValidated>,
+}
+
+#[cw_serde]
+pub struct ValueVsHoldResponse {
+    /// The assets `lp_amount` can currently be withdrawn for
+    pub current_assets: Vec<AssetValidated>,
+    /// The value of `current_assets` at the external prices
+    pub current_value: Uint128,
+    /// The assets `lp_amount` was backed by at the first deposit into the pool
+    pub hold_assets: Vec<AssetValidated>,
+    /// The value of `hold_assets` at the external prices
+    pub hold_value: Uint128,
+}
+
+#[cw_serde]
+pub struct PoolDebugResponse {
+    /// The raw reserves, excluding pending protocol fees and unsent refunds
+    pub reserves: Vec<AssetValidated>,
+    /// The reserves divided by `10^precision` of their asset, as used by the swap math
+    pub decimal_reserves: Vec<DecimalAsset>,
+    /// The number of decimals of each asset, in the same order as `reserves`
+    pub precisions: Vec<u8>,
+    /// The greatest number of decimals of all assets, which the swap math calculates with
+    pub greatest_precision: u8,
+    /// The current amplification coefficient
+    pub amp: Decimal,
+    /// The current invariant D of the reserves
+    pub d: Uint128,
+}
+
+#[cw_serde]
+pub struct AssetPrecision {
+    pub info: AssetInfoValidated,
+    /// The number of decimals of the asset
+    pub precision: u8,
+}
+
+#[cw_serde]
+pub struct PegStatusResponse {
+    /// One entry per pair of pool assets
+    pub pairs: Vec<PegStatus>,
+}
+
+#[cw_serde]
+pub struct PegStatus {
+    pub offer: AssetInfoValidated,
+    pub ask: AssetInfoValidated,
+    /// The spot price of one `offer` in `ask`, scaled by the rates both assets are pegged at
+    pub price: Decimal,
+    /// Whether `price` lies within the requested band around `1.0`
+    pub in_band: bool,
+}
+
+#[cw_serde]
+pub struct BatchSimulationRequest {
+    pub offer: AssetInfo,
+    pub ask: AssetInfo,
+    /// The amount of `offer` to swap
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct BatchSimulationResult {
+    /// The amount of `ask` the swap would return, after fees
+    pub return_amount: Uint128,
+    /// The spread of the swap (denominated in `ask`)
+    pub spread_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct HealthResponse {
+    /// The current invariant D of the reserves, in LP token units
+    pub d: Uint128,
+    /// The total amount of LP tokens currently issued
+    pub total_share: Uint128,
+    /// The invariant per LP token. This only grows with fees, starting at one.
+    /// It is one if no LP tokens were issued yet.
+    pub virtual_price: Decimal,
+    /// The amount of LP tokens not backed by the reserves. Non-zero indicates an accounting bug.
+    pub drift: Uint128,
+    /// Whether the reserves fully back the LP supply
+    pub healthy: bool,
+}
+
+#[cw_serde]
+pub struct MarginalResponse {
+    /// Amount of `ask` received per unit of `offer` for an infinitely small swap (`-dy/dx`),
+    /// before fees
+    pub first_derivative: Decimal,
+    /// Curvature of the swap curve (`d²y/dx²`), i.e. how fast `first_derivative` drops
+    /// per unit of `offer` swapped
+    pub second_derivative: Decimal,
+}
@@ -1,5 +1,7 @@
 use crate::asset::MINIMUM_LIQUIDITY_AMOUNT;
-use cosmwasm_std::{CheckedMultiplyRatioError, ConversionOverflowError, OverflowError, StdError};
+use cosmwasm_std::{
+    CheckedMultiplyRatioError, ConversionOverflowError, Decimal, OverflowError, StdError, Uint128,
+};
 use thiserror::Error;
 
 /// This enum describes pair contract errors
@@ -65,8 +67,8 @@ pub enum ContractError {
     #[error("A pool with a dynamic target rate can only have 2 assets, one native token and one cw20 token")]
     InvalidAssetsForTargetRate {},
 
-    #[error("Amp coefficient must be greater than 0 and less than or equal to {max_amp}")]
-    IncorrectAmp { max_amp: u64 },
+    #[error("Amp coefficient must be between {min_amp} and {max_amp}")]
+    IncorrectAmp { min_amp: u64, max_amp: u64 },
 
     #[error(
         "The difference between the old and new amp value must not exceed {max_amp_change} times"
@@ -81,6 +83,9 @@ pub enum ContractError {
     #[error("You need to provide init params")]
     InitParamsNotFound {},
 
+    #[error("LP token decimals must not exceed {max}")]
+    InvalidLpTokenDecimals { max: u8 },
+
     #[error("It is not possible to provide liquidity with one token for an empty pool")]
     InvalidProvideLPsWithSingleToken {},
 
@@ -106,9 +111,50 @@ pub enum ContractError {
     )]
     TooManyAssets { max: usize, provided: usize },
 
+    #[error("No {0} provided, but the deposit is required to include all pool assets")]
+    MissingAsset(String),
+
+    #[error("Fee burn fraction must be less than or equal to 1")]
+    InvalidFeeBurnFraction {},
+
+    #[error("Staker fee discounts must be at most 1 and have distinct minimum stakes")]
+    InvalidStakerFeeDiscount {},
+
+    #[error("{locked} LP tokens are locked until {unlock_at}")]
+    LpLocked { locked: Uint128, unlock_at: u64 },
+
+    #[error("Withdrawal would leave less than the minimum reserve of {0} in the pool")]
+    BelowMinReserve(String),
+
+    #[error("{0} is not allowed to provide liquidity")]
+    NotWhitelistedProvider(String),
+
+    #[error("Rebalance incentive must be greater than 0 and at most 1")]
+    InvalidRebalanceIncentive {},
+
+    #[error("Max block price move must be greater than 0")]
+    InvalidMaxBlockPriceMove {},
+
+    #[error("Swap would move the price by more than {max} within a single block")]
+    BlockPriceMoveTooLarge { max: Decimal },
+
     #[error("Contract has been frozen")]
     ContractFrozen {},
 
+    #[error("Emergency withdrawals are only possible while the contract is frozen")]
+    NotFrozen {},
+
+    #[error("Deadline {deadline} has passed")]
+    Expired { deadline: u64 },
+
+    #[error("No unsent refunds to claim")]
+    NoUnsentRefunds {},
+
+    #[error(
+        "Fee-on-transfer token {0} has to be swapped using an allowance instead of a cw20 send"
+    )]
+    FeeOnTransferSend(String),
+
     #[error("Spot price parameters incorrect - max_trade must be bigger then 0")]
     SpotPriceInvalidMaxTrade {},
 
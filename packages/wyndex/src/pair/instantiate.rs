@@ -17,6 +17,8 @@ use super::{ContractError, PairInfo, StakeConfig};
 const TMP_STAKING_CONFIG: Item<StakeConfig> = Item::new("tmp_staking_config");
 
 pub const LP_TOKEN_PRECISION: u8 = 6;
+/// The default symbol of the LP token.
+const LP_TOKEN_SYMBOL: &str = "uLP";
 /// A `reply` call code ID used for token instantiation sub-message.
 const INSTANTIATE_TOKEN_REPLY_ID: u64 = 1;
 /// A `reply` call code ID used for staking contract instantiation sub-message.
@@ -30,6 +32,8 @@ pub fn create_lp_token(
     token_code_id: u64,
     asset_infos: &[AssetInfoValidated],
     factory_addr: &Addr,
+    symbol: Option<String>,
+    decimals: Option<u8>,
 ) -> StdResult<SubMsg> {
     let token_name = format_lp_token_name(asset_infos, querier)?;
 
@@ -42,8 +46,8 @@ pub fn create_lp_token(
             code_id: token_code_id,
             msg: to_binary(&TokenInstantiateMsg {
                 name: token_name,
-                symbol: "uLP".to_string(),
-                decimals: LP_TOKEN_PRECISION,
+                symbol: symbol.unwrap_or_else(|| LP_TOKEN_SYMBOL.to_string()),
+                decimals: decimals.unwrap_or(LP_TOKEN_PRECISION),
                 initial_balances: vec![],
                 mint: Some(MinterResponse {
                     minter: env.contract.address.to_string(),
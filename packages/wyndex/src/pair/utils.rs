@@ -5,8 +5,8 @@ use super::error::ContractError;
 use crate::asset::{Asset, AssetInfo, AssetInfoValidated, AssetValidated};
 
 use cosmwasm_std::{
-    from_slice, wasm_execute, Addr, Api, CosmosMsg, Decimal, Fraction, QuerierWrapper, StdError,
-    StdResult, Uint128,
+    from_slice, wasm_execute, Addr, Api, BlockInfo, CosmosMsg, Decimal, Fraction, QuerierWrapper,
+    StdError, StdResult, Uint128,
 };
 use cw20::Cw20ExecuteMsg;
 use itertools::Itertools;
@@ -31,6 +31,36 @@ pub fn migration_check(
     }
 }
 
+/// Returns an error if the given deadline (unix timestamp in seconds) lies before the current block.
+pub fn assert_deadline(block: &BlockInfo, deadline: Option<u64>) -> Result<(), ContractError> {
+    match deadline {
+        Some(deadline) if block.time.seconds() > deadline => {
+            Err(ContractError::Expired { deadline })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns an error if `require_all_assets` is set and `assets` does not include
+/// a non-zero deposit of every asset in the pool.
+pub fn assert_all_assets_provided(
+    pool_assets: &[AssetInfoValidated],
+    assets: &[AssetValidated],
+    require_all_assets: Option<bool>,
+) -> Result<(), ContractError> {
+    if !require_all_assets.unwrap_or_default() {
+        return Ok(());
+    }
+    match pool_assets.iter().find(|pool_asset| {
+        !assets
+            .iter()
+            .any(|a| &a.info == *pool_asset && !a.amount.is_zero())
+    }) {
+        Some(missing) => Err(ContractError::MissingAsset(missing.to_string())),
+        None => Ok(()),
+    }
+}
+
 /// Helper function to check if the given asset infos are valid.
 pub fn check_asset_infos(
     api: &dyn Api,
@@ -128,6 +158,27 @@ pub fn assert_max_spread(
     Ok(())
 }
 
+/// Returns the spread relative to the amount the offer would return at the spot price,
+/// which is the sum of all three amounts.
+///
+/// * **return_amount** amount of assets to receive from the swap, after fees.
+///
+/// * **spread_amount** spread used in the swap.
+///
+/// * **commission_amount** fees charged by the swap.
+pub fn spread_percent(
+    return_amount: Uint128,
+    spread_amount: Uint128,
+    commission_amount: Uint128,
+) -> Decimal {
+    let spot_return = return_amount + commission_amount + spread_amount;
+    if spot_return.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(spread_amount, spot_return)
+    }
+}
+
 /// Mint LP tokens for a beneficiary
 ///
 /// * **recipient** LP token recipient.
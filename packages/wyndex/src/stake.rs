@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 
 /// Unbonding period in seconds
 pub type UnbondingPeriod = u64;
@@ -39,6 +39,16 @@ pub enum ReceiveMsg {
         unbonding_period: u64,
         /// If set, the staked assets will be assigned to the given address instead of the sender
         delegate_as: Option<String>,
+        /// If set, only this address can unbond or rebond the staked assets and it receives the claims.
+        /// The staker itself loses these rights.
+        /// Rights can only be changed while the staker has no bonded tokens, so all further
+        /// delegations to the same staker have to repeat them.
+        /// When delegating as someone else, the rights have to match the ones that address set
+        /// itself, e.g. with `SetDelegationRights`.
+        unbond_controller: Option<String>,
+        /// If set, all rewards of the staker are sent to this address when withdrawn.
+        /// This address is also allowed to trigger the withdrawal.
+        withdraw_to: Option<String>,
     },
     /// This will delegate a large sum on behalf of many different users.
     /// The total amount in delegate_to must be <= the amount of tokens sent.
@@ -48,8 +58,22 @@ pub enum ReceiveMsg {
         unbonding_period: u64,
         delegate_to: Vec<(String, Uint128)>,
     },
+    /// Splits the sent tokens into independent stakes of the given addresses,
+    /// each getting its share of the amount. The shares have to sum up to one and every
+    /// resulting stake has to be at least `min_bond`. Rounding leftovers go to the last address.
+    DelegateShared {
+        shares: Vec<(String, Decimal)>,
+        /// Unbonding period in seconds
+        unbonding_period: u64,
+    },
     /// Fund a distribution flow with cw20 tokens and update the Reward Config for that cw20 asset.
-    Fund { funding_info: FundingInfo },
+    Fund {
+        funding_info: FundingInfo,
+        /// The address the funds are attributed to. Defaults to the sender of the tokens.
+        /// Set this when funding on behalf of someone else (e.g. a treasury), so that any
+        /// reclaimed funds go back to them instead of the sending contract.
+        on_behalf_of: Option<String>,
+    },
 }
 
 #[cw_serde]
@@ -7,7 +7,7 @@ use wyndex::{
     factory::PartialStakeConfig,
     stake::ReceiveMsg,
 };
-use wyndex_stake::msg::{QueryMsg as StakeQueryMsg, StakedResponse};
+use wyndex_stake::msg::{DistributionDataResponse, QueryMsg as StakeQueryMsg, StakedResponse};
 use wyndex_stake::state::Config as WyndexStakeConfig;
 
 mod staking {
@@ -92,6 +92,8 @@ mod staking {
                 ReceiveMsg::Delegate {
                     unbonding_period: 1,
                     delegate_as: None,
+                    unbond_controller: None,
+                    withdraw_to: None,
                 },
             )
             .unwrap();
@@ -199,16 +201,19 @@ mod staking {
                         asset: ujuno_info.clone(),
                         rewards: vec![(1, Decimal::one())],
                         reward_duration: 100,
+                        manager: None,
                     },
                     DistributionFlow {
                         asset: uluna_info.clone(),
                         rewards: vec![(1, Decimal::one())],
                         reward_duration: 100,
+                        manager: None,
                     },
                     DistributionFlow {
                         asset: test_info,
                         rewards: vec![(1, Decimal::one())],
                         reward_duration: 100,
+                        manager: None,
                     },
                 ],
             )
@@ -230,4 +235,68 @@ mod staking {
             .distribute_funds(pair_info.staking_addr, user, &[coin(100, no_dist)])
             .unwrap_err();
     }
+
+    #[test]
+    fn create_pair_with_distribution_manager() {
+        let owner = "owner";
+        let manager = "manager";
+
+        let ujuno_info = AssetInfo::Native("ujuno".to_string());
+        let uluna_info = AssetInfo::Native("uluna".to_string());
+
+        let mut suite = SuiteBuilder::new()
+            .with_stake_config(DefaultStakeConfig {
+                staking_code_id: 0,
+                tokens_per_power: Uint128::new(1),
+                min_bond: Uint128::new(1),
+                unbonding_periods: vec![1],
+                max_distributions: 2,
+                converter: None,
+            })
+            .build();
+
+        suite
+            .create_pair_and_distributions(
+                owner,
+                wyndex::factory::PairType::Xyk {},
+                vec![ujuno_info.clone(), uluna_info.clone()],
+                None,
+                vec![
+                    DistributionFlow {
+                        asset: ujuno_info.clone(),
+                        rewards: vec![(1, Decimal::one())],
+                        reward_duration: 100,
+                        manager: Some(manager.to_string()),
+                    },
+                    DistributionFlow {
+                        asset: uluna_info.clone(),
+                        rewards: vec![(1, Decimal::one())],
+                        reward_duration: 100,
+                        manager: None,
+                    },
+                ],
+            )
+            .unwrap();
+
+        let pair_info = suite.query_pair(vec![ujuno_info, uluna_info]).unwrap();
+        let resp: DistributionDataResponse = suite
+            .app
+            .wrap()
+            .query_wasm_smart(pair_info.staking_addr, &StakeQueryMsg::DistributionData {})
+            .unwrap();
+
+        // the flows exist right after creating the pair, managed by the given manager or the factory
+        let managers = resp
+            .distributions
+            .into_iter()
+            .map(|(asset, distribution)| (asset.to_string(), distribution.manager))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            managers,
+            vec![
+                ("ujuno".to_string(), Addr::unchecked(manager)),
+                ("uluna".to_string(), suite.factory.clone()),
+            ]
+        );
+    }
 }